@@ -0,0 +1,38 @@
+// ============================================
+// 基准测试的公共部分
+// ============================================
+// 计时和按终端宽度对齐表格，tokenize 和 spatial 两个基准测试共用：
+//
+//   mod common;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_tutorial::unicode;
+
+/// 每种做法重复运行的次数
+pub const ROUNDS: usize = 20;
+
+/// 运行 ROUNDS 次，返回最快的一次用时和最后一次的结果
+pub fn measure<T>(f: impl Fn() -> T) -> (Duration, T) {
+    let mut best = Duration::MAX;
+    let mut result = None;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        result = Some(black_box(f()));
+        best = best.min(start.elapsed());
+    }
+    (best, result.expect("ROUNDS 大于 0"))
+}
+
+/// 按终端宽度在右边补空格，左对齐到 width 列；中文每个字占两列，不能直接用 {:<n}
+pub fn pad(text: &str, width: usize) -> String {
+    let fill = width.saturating_sub(unicode::width(text));
+    format!("{}{}", text, " ".repeat(fill))
+}
+
+/// 按终端宽度在左边补空格，右对齐到 width 列
+pub fn column(text: &str, width: usize) -> String {
+    let fill = width.saturating_sub(unicode::width(text));
+    format!("{}{}", " ".repeat(fill), text)
+}
@@ -0,0 +1,150 @@
+// ============================================
+// 空间索引基准测试
+// ============================================
+// 矩形数量从少到多，比较暴力法、均匀网格和四叉树，找出索引开始比暴力法
+// 快的数量（交叉点）：
+//
+//   cargo bench --bench spatial
+//
+// 矩形的密度保持不变：数量翻倍时平面的面积也翻倍。两项工作：
+// - 区域查询：索引先建好，只计查询的时间
+// - 所有相交的矩形对：从建索引开始计时，暴力法不需要建索引
+//
+// 每种做法重复运行，取最快的一次
+
+mod common;
+
+use std::time::Duration;
+
+use common::{ROUNDS, column, measure, pad};
+use rust_tutorial::geometry::{Point, Rectangle};
+use rust_tutorial::grid::Rng;
+use rust_tutorial::spatial::{BruteForce, QuadTree, SpatialIndex, UniformGrid};
+
+const QUERIES: usize = 200;
+const SIZES: [usize; 10] = [4, 8, 16, 32, 64, 128, 256, 512, 1024, 4096];
+/// 矩形边长最多 MAX_SIDE，网格的格子取同样的大小
+const MAX_SIDE: usize = 32;
+
+/// 表格第一列的宽度
+const NAME_WIDTH: usize = 12;
+/// 其余各列的宽度
+const COLUMN_WIDTH: usize = 15;
+
+/// 平均每个矩形占 (2·MAX_SIDE)² 的面积
+fn world(n: usize) -> usize {
+    2 * MAX_SIDE * (n as f64).sqrt().ceil() as usize
+}
+
+fn rectangles(n: usize, rng: &mut Rng) -> Vec<Rectangle> {
+    let world = world(n);
+    (0..n)
+        .map(|_| {
+            Rectangle::new(
+                rng.below(world) as i32,
+                rng.below(world) as i32,
+                1 + rng.below(MAX_SIDE) as u32,
+                1 + rng.below(MAX_SIDE) as u32,
+            )
+        })
+        .collect()
+}
+
+fn build(kind: usize, rectangles: &[Rectangle]) -> Box<dyn SpatialIndex> {
+    let mut index: Box<dyn SpatialIndex> = match kind {
+        0 => Box::new(BruteForce::new()),
+        1 => Box::new(UniformGrid::new(MAX_SIDE as u32)),
+        _ => Box::new(QuadTree::new()),
+    };
+    for &rectangle in rectangles {
+        index.insert(rectangle);
+    }
+    index
+}
+
+/// 打印一张表，返回每种索引第一次比暴力法快时的矩形数量；
+/// run 对第 kind 种做法计时，返回用时和结果的个数
+fn table(
+    title: &str,
+    run: impl Fn(usize, &[Rectangle]) -> (Duration, usize),
+) -> [Option<usize>; 2] {
+    println!("{}", title);
+    println!(
+        "{}{}{}{}",
+        pad("矩形数", NAME_WIDTH),
+        column("暴力法", COLUMN_WIDTH),
+        column("均匀网格", COLUMN_WIDTH),
+        column("四叉树", COLUMN_WIDTH)
+    );
+    let mut crossover = [None; 2];
+    let mut rng = Rng::new(24);
+    for n in SIZES {
+        let rectangles = rectangles(n, &mut rng);
+        let results: Vec<(Duration, usize)> = (0..3).map(|kind| run(kind, &rectangles)).collect();
+        assert!(
+            results.iter().all(|&(_, count)| count == results[0].1),
+            "三种做法的结果不一致"
+        );
+        let micros = |kind: usize| results[kind].0.as_secs_f64() * 1_000_000.0;
+        println!(
+            "{}{}{}{}",
+            pad(&n.to_string(), NAME_WIDTH),
+            column(&format!("{:.1}µs", micros(0)), COLUMN_WIDTH),
+            column(&format!("{:.1}µs", micros(1)), COLUMN_WIDTH),
+            column(&format!("{:.1}µs", micros(2)), COLUMN_WIDTH)
+        );
+        for (slot, kind) in crossover.iter_mut().zip(1..) {
+            if slot.is_none() && results[kind].0 < results[0].0 {
+                *slot = Some(n);
+            }
+        }
+    }
+    println!();
+    crossover
+}
+
+fn main() {
+    println!(
+        "矩形边长 1..={}，网格的格子边长 {}，每种做法运行 {} 次取最快\n",
+        MAX_SIDE, MAX_SIDE, ROUNDS
+    );
+
+    let queries = table(
+        &format!("区域查询（{} 次）", QUERIES),
+        |kind, rectangles| {
+            // 建索引和生成查询不计时
+            let index = build(kind, rectangles);
+            let world = world(rectangles.len());
+            let mut rng = Rng::new(7);
+            let regions: Vec<Rectangle> = (0..QUERIES)
+                .map(|_| {
+                    let origin = Point::new(rng.below(world) as i32, rng.below(world) as i32);
+                    Rectangle::square(origin, MAX_SIDE as u32)
+                })
+                .collect();
+            measure(|| {
+                regions
+                    .iter()
+                    .map(|region| index.query_region(region).len())
+                    .sum()
+            })
+        },
+    );
+    let pairs = table(
+        "所有相交的矩形对（含建索引）",
+        |kind, rectangles| measure(|| build(kind, rectangles).overlapping_pairs().len()),
+    );
+
+    for (title, crossover) in [("区域查询", queries), ("相交的矩形对", pairs)] {
+        let describe = |n: Option<usize>| match n {
+            Some(n) => format!("从 {} 个矩形起比暴力法快", n),
+            None => String::from("在测试的范围内都不比暴力法快"),
+        };
+        println!(
+            "{}: 均匀网格{}，四叉树{}",
+            title,
+            describe(crossover[0]),
+            describe(crossover[1])
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// ============================================
+// 分词器基准测试
+// ============================================
+// 用所有章节的源码作为输入，比较零拷贝分词器和按 split_whitespace
+// 切分后复制成 Vec<String> 的做法：
+//
+//   cargo bench --bench tokenize
+//
+// 每种做法重复运行，取最快的一次；同时统计每次运行分配内存的次数
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{ROUNDS, column, measure, pad};
+use rust_tutorial::chapters;
+use rust_tutorial::tokenizer::{self, Tokenizer};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// 运行一次 f，返回产生的记号数和这次分配内存的次数
+fn counting(f: impl Fn() -> usize) -> (usize, usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let count = f();
+    (count, ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+fn main() {
+    let corpus: String = chapters::all()
+        .iter()
+        .map(|chapter| chapter.source)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .repeat(8);
+    let text = black_box(corpus.as_str());
+    // 初始化 Unicode 类别表，不计入结果
+    Tokenizer::new("é").count();
+
+    let cases: [(&str, &dyn Fn() -> usize); 4] = [
+        ("split_whitespace → Vec<String>", &|| {
+            let words: Vec<String> = text.split_whitespace().map(String::from).collect();
+            words.len()
+        }),
+        ("split_whitespace（只借用）", &|| {
+            text.split_whitespace().count()
+        }),
+        ("Tokenizer（逐个产生）", &|| {
+            Tokenizer::new(text).filter(Result::is_ok).count()
+        }),
+        ("tokenize → Vec<Token>", &|| {
+            tokenizer::tokenize(text).map_or(0, |tokens| tokens.len())
+        }),
+    ];
+
+    let megabytes = text.len() as f64 / 1_000_000.0;
+    println!(
+        "输入: {:.1} MB，每种做法运行 {} 次取最快\n",
+        megabytes, ROUNDS
+    );
+    println!(
+        "{} {} {} {} {}",
+        pad("做法", 32),
+        column("记号", 10),
+        column("用时", 10),
+        column("MB/s", 10),
+        column("分配", 8)
+    );
+    for (name, f) in cases {
+        let (time, (count, allocations)) = measure(|| counting(f));
+        println!(
+            "{} {:>10} {:>8.2}ms {:>10.0} {:>8}",
+            pad(name, 32),
+            count,
+            time.as_secs_f64() * 1000.0,
+            megabytes / time.as_secs_f64(),
+            allocations
+        );
+    }
+}
@@ -0,0 +1,39 @@
+// 违反所有权和借用规则的常见写法，每一段对应一个错误编号
+// rust-tutorial borrow borrowck/errors.rs
+
+// E0382: 移动之后再使用
+let s1 = String::from("hello");
+let s2 = s1;
+println!("{}, world!", s1);
+
+// E0499: 两个可变借用同时存在
+let mut s = String::from("hello");
+let r1 = &mut s;
+let r2 = &mut s;
+println!("{}, {}", r1, r2);
+
+// E0502: 不可变借用还在使用时可变借用
+let mut s = String::from("hello world");
+let word = first_word(&s);
+s.clear();
+println!("the first word is: {}", word);
+
+// E0505: 被借用时移出
+let s = String::from("hello");
+let r = &s;
+takes_ownership(s);
+println!("{}", r);
+
+// E0597: 引用比它借用的值活得更久
+let r;
+{
+    let x = 5;
+    r = &x;
+}
+println!("r: {}", r);
+
+// E0106: 返回引用，但没有可以借用的参数
+fn dangle() -> &String {
+    let s = String::from("hello");
+    &s
+}
@@ -0,0 +1,57 @@
+// 第 05 章 main 中的所有权和借用，按顺序执行，没有错误
+// rust-tutorial borrow borrowck/ownership.rs --trace
+
+{
+    let s = String::from("hello"); // s 在这里开始有效
+} // s 在这里离开作用域，内存被释放
+
+// 移动
+let s1 = String::from("hello");
+let s2 = s1; // s1 的所有权被移动到 s2
+println!("s2 = {}", s2);
+
+// Copy 类型被复制
+let x = 5;
+let y = x;
+println!("x = {}, y = {}", x, y);
+
+// 克隆
+let s1 = String::from("hello");
+let s2 = s1.clone();
+println!("s1 = {}, s2 = {}", s1, s2);
+
+// 所有权和函数
+let s = String::from("hello");
+takes_ownership(s);
+let x = 5;
+makes_copy(x);
+println!("x = {}", x);
+
+let s1 = gives_ownership();
+let s2 = String::from("hello");
+let s3 = takes_and_gives_back(s2);
+println!("s1 = {}, s3 = {}", s1, s3);
+
+// 引用和借用
+let s1 = String::from("hello");
+let len = calculate_length(&s1);
+println!("{} 的长度是 {}", s1, len);
+
+let mut s = String::from("hello");
+change(&mut s);
+println!("s = {}", s);
+
+// r1 和 r2 最后一次使用之后，就可以可变借用 s（非词法生命周期）
+let mut s = String::from("hello");
+let r1 = &s;
+let r2 = &s;
+println!("{}, {}", r1, r2);
+let r3 = &mut s;
+println!("{}", r3);
+
+// 切片
+let s = String::from("hello world");
+let hello = &s[0..5];
+let world = &s[6..11];
+let word = first_word(&s);
+println!("{hello}, {world}: {word}");
@@ -0,0 +1,100 @@
+// ============================================
+// 构建脚本：生成关键字索引
+// ============================================
+// 扫描 src/ 下的章节文件，把关键字、类型、宏和生命周期出现的位置
+// 以及第 16 章的关键字分类总结写成 $OUT_DIR/keyword_index.rs，
+// 由 src/index.rs 引入。章节文件改动后 cargo 会重新运行这个脚本。
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[allow(dead_code)]
+#[path = "src/outline.rs"]
+mod outline;
+#[allow(dead_code)]
+#[path = "src/scan.rs"]
+mod scan;
+
+/// src/ 下的章节文件：文件名以两位数字和下划线开头，例如 07_enums.rs
+fn chapter_files() -> Vec<(u8, PathBuf)> {
+    let mut files: Vec<(u8, PathBuf)> = fs::read_dir("src")
+        .expect("无法读取 src 目录")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let (number, rest) = name.split_once('_')?;
+            if number.len() != 2 || !rest.ends_with(".rs") {
+                return None;
+            }
+            Some((number.parse().ok()?, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn main() {
+    // 监视整个 src 目录，新增的章节文件也会触发重新生成
+    println!("cargo:rerun-if-changed=src");
+
+    let files = chapter_files();
+    let mut catalogue = Vec::new();
+    let mut sources = Vec::new();
+    for (number, path) in &files {
+        let source = fs::read_to_string(path).expect("无法读取章节文件");
+        if path.ends_with("16_keywords.rs") {
+            catalogue = scan::catalogue(&source);
+        }
+        sources.push((*number, source));
+    }
+
+    let keywords: Vec<&str> = catalogue.iter().map(|entry| entry.name.as_str()).collect();
+    let mut terms: BTreeMap<String, Vec<(u8, usize, String)>> = BTreeMap::new();
+    for (number, source) in &sources {
+        let labels = scan::section_labels(source);
+        for token in scan::tokens(source) {
+            if scan::is_indexed(&token.text, &keywords) {
+                let label = labels[token.line].clone();
+                let occurrences = terms.entry(token.text).or_default();
+                // 同一行出现多次只记一次
+                if occurrences.last().map(|o| (o.0, o.1)) != Some((*number, token.line)) {
+                    occurrences.push((*number, token.line, label));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str("// 由 build.rs 生成，不要手动修改\n\n");
+    out.push_str("pub static KEYWORDS: &[Keyword] = &[\n");
+    for entry in &catalogue {
+        writeln!(
+            out,
+            "    Keyword {{ name: {:?}, kind: KeywordKind::{:?}, category: {:?}, description: {:?} }},",
+            entry.name, entry.kind, entry.category, entry.description
+        )
+        .unwrap();
+    }
+    out.push_str("];\n\n");
+
+    out.push_str("pub static TERMS: &[(&str, &[Occurrence])] = &[\n");
+    for (term, occurrences) in &terms {
+        writeln!(out, "    ({:?}, &[", term).unwrap();
+        for (chapter, line, section) in occurrences {
+            writeln!(
+                out,
+                "        Occurrence {{ chapter: {}, line: {}, section: {:?} }},",
+                chapter, line, section
+            )
+            .unwrap();
+        }
+        out.push_str("    ]),\n");
+    }
+    out.push_str("];\n");
+
+    let dir = env::var("OUT_DIR").expect("cargo 应该设置 OUT_DIR");
+    fs::write(Path::new(&dir).join("keyword_index.rs"), out).expect("无法写入索引");
+}
@@ -3,7 +3,7 @@
 // ============================================
 // Rust 中的变量默认是不可变的（immutable），这是 Rust 安全性的重要特性之一
 
-fn main() {
+pub fn main() {
     // 1. 基本变量声明
     // 使用 let 关键字声明变量，默认不可变
     let x = 5;
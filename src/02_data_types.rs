@@ -3,7 +3,7 @@
 // ============================================
 // Rust 是静态类型语言，编译时必须知道所有变量的类型
 
-fn main() {
+pub fn main() {
     // ========== 标量类型 (Scalar Types) ==========
 
     // 1. 整数类型 (Integer Types)
@@ -3,8 +3,10 @@
 // ============================================
 // Rust 代码中的函数和变量名使用 snake_case 命名规范
 
-fn main() {
-    println!("这是 main 函数");
+use crate::arithmetic::{self, Op};
+
+pub fn main() {
+    println!("{}", t!("functions.main_fn"));
 
     // 调用函数
     another_function();
@@ -19,8 +21,11 @@ fn main() {
     println!("5 * 6 = {}", result2);
 
     // 提前返回
+    // 出错时返回 Err，而不是一个看起来正常的值（例如 0）
     let result3 = divide(10, 2);
-    println!("10 / 2 = {}", result3);
+    println!("10 / 2 = {:?}", result3);
+    println!("10 / 0 = {:?}", divide(10, 0));
+    println!("i32::MIN / -1 = {:?}", divide(i32::MIN, -1));
 
     // 函数指针
     let func: fn(i32, i32) -> i32 = add;
@@ -35,13 +40,13 @@ fn main() {
 
 // 1. 基本函数定义
 fn another_function() {
-    println!("这是另一个函数");
+    println!("{}", t!("functions.another_fn"));
 }
 
 // 2. 带参数的函数
 // 参数必须显式声明类型
 fn function_with_parameters(x: i32, y: i32) {
-    println!("参数值: x = {}, y = {}", x, y);
+    println!("{}", t!("functions.parameters", x, y));
 }
 
 // 3. 带返回值的函数
@@ -57,11 +62,12 @@ fn multiply(x: i32, y: i32) -> i32 {
 }
 
 // 5. 提前返回
-fn divide(x: i32, y: i32) -> i32 {
+// 除数为零时提前返回错误；i32::MIN / -1 的溢出由 arithmetic::divide 检查
+fn divide(x: i32, y: i32) -> Result<i32, arithmetic::Error<i32>> {
     if y == 0 {
-        return 0; // 提前返回
+        return Err(arithmetic::Error::DivisionByZero { op: Op::Divide, dividend: x }); // 提前返回
     }
-    x / y // 正常返回
+    arithmetic::divide(x, y) // 正常返回
 }
 
 // 6. 多个返回值（使用元组）
@@ -72,7 +78,7 @@ fn calculate(x: i32, y: i32) -> (i32, i32, i32) {
 // 7. 无返回值函数
 // 实际上返回单元类型 ()
 fn no_return() {
-    println!("这个函数没有返回值");
+    println!("{}", t!("functions.no_return"));
     // 隐式返回 ()
 }
 
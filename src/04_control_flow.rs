@@ -2,27 +2,27 @@
 // 04. 流程控制 (Control Flow)
 // ============================================
 
-fn main() {
+pub fn main() {
     // ========== if 表达式 ==========
 
     // 1. 基本的 if 语句
     let number = 3;
     if number < 5 {
-        println!("条件为真");
+        println!("{}", t!("control_flow.condition_true"));
     } else {
-        println!("条件为假");
+        println!("{}", t!("control_flow.condition_false"));
     }
 
     // 2. if-else if-else
     let number = 6;
     if number % 4 == 0 {
-        println!("数字可以被 4 整除");
+        println!("{}", t!("control_flow.divisible_by_4"));
     } else if number % 3 == 0 {
-        println!("数字可以被 3 整除");
+        println!("{}", t!("control_flow.divisible_by_3"));
     } else if number % 2 == 0 {
-        println!("数字可以被 2 整除");
+        println!("{}", t!("control_flow.divisible_by_2"));
     } else {
-        println!("数字不能被 4、3 或 2 整除");
+        println!("{}", t!("control_flow.not_divisible"));
     }
 
     // 3. if 作为表达式使用
@@ -54,7 +54,7 @@ fn main() {
     // 6. for 循环遍历集合
     let a = [10, 20, 30, 40, 50];
     for element in a.iter() {
-        println!("值为: {}", element);
+        println!("{}", t!("control_flow.element", element));
     }
 
     // 7. for 循环使用范围
@@ -73,46 +73,46 @@ fn main() {
     // 8. match 基本用法
     let number = 3;
     match number {
-        1 => println!("一"),
-        2 => println!("二"),
-        3 => println!("三"),
-        _ => println!("其他"), // 默认分支
+        1 => println!("{}", t!("control_flow.one")),
+        2 => println!("{}", t!("control_flow.two")),
+        3 => println!("{}", t!("control_flow.three")),
+        _ => println!("{}", t!("control_flow.other")), // 默认分支
     }
 
     // 9. match 返回值
     let number = 2;
     let description = match number {
-        1 => "一",
-        2 => "二",
-        3 => "三",
-        _ => "其他",
+        1 => t!("control_flow.one"),
+        2 => t!("control_flow.two"),
+        3 => t!("control_flow.three"),
+        _ => t!("control_flow.other"),
     };
-    println!("描述: {}", description);
+    println!("{}", t!("control_flow.description", description));
 
     // 10. match 多个值
     let number = 5;
     match number {
-        1 | 2 | 3 => println!("小"),
-        4 | 5 | 6 => println!("中"),
-        7 | 8 | 9 => println!("大"),
-        _ => println!("其他"),
+        1 | 2 | 3 => println!("{}", t!("control_flow.small")),
+        4 | 5 | 6 => println!("{}", t!("control_flow.medium")),
+        7 | 8 | 9 => println!("{}", t!("control_flow.large")),
+        _ => println!("{}", t!("control_flow.other")),
     }
 
     // 11. match 范围匹配
     let number = 42;
     match number {
-        1..=10 => println!("1 到 10"),
-        11..=20 => println!("11 到 20"),
-        21..=50 => println!("21 到 50"),
-        _ => println!("其他"),
+        1..=10 => println!("{}", t!("control_flow.range_1_10")),
+        11..=20 => println!("{}", t!("control_flow.range_11_20")),
+        21..=50 => println!("{}", t!("control_flow.range_21_50")),
+        _ => println!("{}", t!("control_flow.other")),
     }
 
     // 12. match 解构
     let point = (0, 5);
     match point {
-        (0, 0) => println!("原点"),
-        (0, y) => println!("在 y 轴上，y = {}", y),
-        (x, 0) => println!("在 x 轴上，x = {}", x),
+        (0, 0) => println!("{}", t!("control_flow.origin")),
+        (0, y) => println!("{}", t!("control_flow.on_y_axis", y)),
+        (x, 0) => println!("{}", t!("control_flow.on_x_axis", x)),
         (x, y) => println!("({}, {})", x, y),
     }
 
@@ -122,12 +122,12 @@ fn main() {
     // 用于只关心一个匹配分支的情况
     let some_value = Some(3);
     if let Some(3) = some_value {
-        println!("值是 3");
+        println!("{}", t!("control_flow.value_is_3"));
     }
 
     // 等价于：
     match some_value {
-        Some(3) => println!("值是 3"),
+        Some(3) => println!("{}", t!("control_flow.value_is_3")),
         _ => (),
     }
 
@@ -147,14 +147,14 @@ fn main() {
 
     // 15. 循环标签（用于 break 和 continue）
     'outer: loop {
-        println!("进入外层循环");
+        println!("{}", t!("control_flow.enter_outer"));
         'inner: loop {
-            println!("进入内层循环");
+            println!("{}", t!("control_flow.enter_inner"));
             break 'outer; // 跳出外层循环
         }
-        println!("这行不会执行");
+        println!("{}", t!("control_flow.never_printed"));
     }
-    println!("退出外层循环");
+    println!("{}", t!("control_flow.exit_outer"));
 
     // ========== continue ==========
 
@@ -171,8 +171,8 @@ fn main() {
     // 17. match 守卫
     let num = Some(4);
     match num {
-        Some(x) if x < 5 => println!("小于 5: {}", x),
-        Some(x) => println!("大于等于 5: {}", x),
+        Some(x) if x < 5 => println!("{}", t!("control_flow.less_than_5", x)),
+        Some(x) => println!("{}", t!("control_flow.at_least_5", x)),
         None => (),
     }
 
@@ -188,13 +188,13 @@ fn main() {
         Message::Hello {
             id: id_variable @ 3..=7,
         } => {
-            println!("找到范围内的 id: {}", id_variable)
+            println!("{}", t!("control_flow.id_in_range", id_variable))
         }
         Message::Hello { id: 10..=12 } => {
-            println!("找到另一个范围内的 id")
+            println!("{}", t!("control_flow.id_in_other_range"))
         }
         Message::Hello { id } => {
-            println!("找到其他 id: {}", id)
+            println!("{}", t!("control_flow.other_id", id))
         }
     }
 }
@@ -3,7 +3,7 @@
 // ============================================
 // 所有权是 Rust 最独特的特性，它让 Rust 无需垃圾回收就能保证内存安全
 
-fn main() {
+pub fn main() {
     // ========== 所有权规则 ==========
     // 1. Rust 中的每一个值都有一个被称为其 所有者（owner）的变量
     // 2. 值在任一时刻有且只有一个所有者
@@ -66,7 +66,7 @@ fn main() {
     // 7. 使用引用，不获取所有权
     let s1 = String::from("hello");
     let len = calculate_length(&s1); // 传递引用
-    println!("'{}' 的长度是 {}", s1, len); // s1 仍然有效
+    println!("{}", t!("ownership.length", s1, len)); // s1 仍然有效
 
     // 8. 可变引用
     let mut s = String::from("hello");
@@ -3,7 +3,7 @@
 // ============================================
 // 结构体让你可以创建自定义数据类型
 
-fn main() {
+pub fn main() {
     // ========== 定义和实例化结构体 ==========
 
     // 1. 基本结构体定义
@@ -58,18 +58,21 @@ fn main() {
         height: 50,
     };
 
-    println!("rect1 的面积: {}", rect1.area());
+    println!("{}", t!("structs.area", rect1.area()));
     println!(
-        "rect1 可以容纳 rect2: {}",
-        rect1.can_hold(&Rectangle {
-            width: 10,
-            height: 40
-        })
+        "{}",
+        t!(
+            "structs.can_hold",
+            rect1.can_hold(&Rectangle {
+                width: 10,
+                height: 40
+            })
+        )
     );
 
     // 8. 关联函数（类似静态方法）
     let sq = Rectangle::square(3);
-    println!("正方形: {:?}", sq);
+    println!("{}", t!("structs.square", format!("{:?}", sq)));
 
     // ========== 使用其他结构体的例子 ==========
 
@@ -79,15 +82,15 @@ fn main() {
     };
 
     person.introduce();
-    println!("{} 的年龄: {}", person.name, person.get_age());
+    println!("{}", t!("structs.age", person.name, person.get_age()));
 }
 
 // ========== 带方法的结构体示例 ==========
 
 #[derive(Debug)] // 允许使用 {:?} 打印
-struct Rectangle {
-    width: u32,
-    height: u32,
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
 }
 
 // impl 块用于定义方法
@@ -129,6 +132,25 @@ impl Rectangle {
     }
 }
 
+use crate::geometry;
+
+// 和 geometry::Rectangle 互相转换：这里的矩形没有位置，转过去时放在原点，
+// 转回来时丢掉位置
+impl From<Rectangle> for geometry::Rectangle {
+    fn from(rect: Rectangle) -> Self {
+        geometry::Rectangle::new(0, 0, rect.width, rect.height)
+    }
+}
+
+impl From<geometry::Rectangle> for Rectangle {
+    fn from(rect: geometry::Rectangle) -> Self {
+        Rectangle {
+            width: rect.width,
+            height: rect.height,
+        }
+    }
+}
+
 // ========== 另一个结构体示例 ==========
 
 struct Person {
@@ -139,7 +161,7 @@ struct Person {
 impl Person {
     // 方法示例
     fn introduce(&self) {
-        println!("我是 {}, {} 岁", self.name, self.age);
+        println!("{}", t!("structs.introduce", self.name, self.age));
     }
 
     fn get_age(&self) -> u32 {
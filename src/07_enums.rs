@@ -3,14 +3,10 @@
 // ============================================
 // 枚举允许你定义一个类型，它可以是几个不同的变体之一
 
-fn main() {
+pub fn main() {
     // ========== 基本枚举 ==========
 
-    // 1. 定义枚举
-    enum IpAddrKind {
-        V4,
-        V6,
-    }
+    // 1. 定义枚举（定义在模块顶层，见下方 IpAddrKind）
 
     // 2. 使用枚举值
     let four = IpAddrKind::V4;
@@ -39,14 +35,7 @@ fn main() {
     let home = IpAddr2::V4(127, 0, 0, 1);
     let loopback = IpAddr2::V6(String::from("::1"));
 
-    // 5. 枚举变体可以包含结构体
-    enum Message {
-        Quit,
-        Move { x: i32, y: i32 },
-        Write(String),
-        ChangeColor(i32, i32, i32),
-    }
-
+    // 5. 枚举变体可以包含结构体（定义在模块顶层，见下方 Message）
     let msg1 = Message::Quit;
     let msg2 = Message::Move { x: 10, y: 20 };
     let msg3 = Message::Write(String::from("hello"));
@@ -85,7 +74,8 @@ fn main() {
     // }
 
     let success: Result<i32, &str> = Ok(42);
-    let failure: Result<i32, &str> = Err("出错了");
+    let message = t!("enums.failure");
+    let failure: Result<i32, &str> = Err(&message);
 
     println!("success: {:?}, failure: {:?}", success, failure);
 
@@ -111,6 +101,20 @@ fn main() {
 
 // ========== 函数使用枚举 ==========
 
+// route 和 Message::call 都在 main 之外使用这两个枚举，
+// 所以它们必须定义在模块顶层，而不是 main 函数内部
+enum IpAddrKind {
+    V4,
+    V6,
+}
+
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(i32, i32, i32),
+}
+
 fn route(ip_kind: IpAddrKind) {
     match ip_kind {
         IpAddrKind::V4 => println!("IPv4"),
@@ -124,10 +128,10 @@ impl Message {
     fn call(&self) {
         // 方法实现
         match self {
-            Message::Quit => println!("退出"),
-            Message::Move { x, y } => println!("移动到 ({}, {})", x, y),
-            Message::Write(s) => println!("写入: {}", s),
-            Message::ChangeColor(r, g, b) => println!("改变颜色: RGB({}, {}, {})", r, g, b),
+            Message::Quit => println!("{}", t!("enums.quit")),
+            Message::Move { x, y } => println!("{}", t!("enums.move_to", x, y)),
+            Message::Write(s) => println!("{}", t!("enums.write", s)),
+            Message::ChangeColor(r, g, b) => println!("{}", t!("enums.change_color", r, g, b)),
         }
     }
 }
@@ -144,10 +148,10 @@ enum State {
 impl State {
     fn execute(&self) {
         match self {
-            State::Idle => println!("状态：空闲"),
-            State::Running => println!("状态：运行中"),
-            State::Paused => println!("状态：已暂停"),
-            State::Stopped => println!("状态：已停止"),
+            State::Idle => println!("{}", t!("enums.state_idle")),
+            State::Running => println!("{}", t!("enums.state_running")),
+            State::Paused => println!("{}", t!("enums.state_paused")),
+            State::Stopped => println!("{}", t!("enums.state_stopped")),
         }
     }
 }
@@ -164,16 +168,16 @@ enum Event {
 fn handle_event(event: Event) {
     match event {
         Event::Click { x, y } => {
-            println!("点击位置: ({}, {})", x, y);
+            println!("{}", t!("enums.click", x, y));
         }
         Event::KeyPress(key) => {
-            println!("按键: {}", key);
+            println!("{}", t!("enums.key_press", key));
         }
         Event::MouseMove { x, y } => {
-            println!("鼠标移动: ({}, {})", x, y);
+            println!("{}", t!("enums.mouse_move", x, y));
         }
         Event::Quit => {
-            println!("退出事件");
+            println!("{}", t!("enums.quit_event"));
         }
     }
 }
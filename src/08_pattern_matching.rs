@@ -3,25 +3,25 @@
 // ============================================
 // Rust 的模式匹配功能非常强大，主要用于 match 表达式和 if let
 
-fn main() {
+pub fn main() {
     // ========== match 表达式 ==========
 
     // 1. 基本 match 用法
     let number = 3;
     match number {
-        1 => println!("一"),
-        2 => println!("二"),
-        3 => println!("三"),
-        _ => println!("其他"), // 默认分支，必须覆盖所有可能
+        1 => println!("{}", t!("pattern_matching.one")),
+        2 => println!("{}", t!("pattern_matching.two")),
+        3 => println!("{}", t!("pattern_matching.three")),
+        _ => println!("{}", t!("pattern_matching.other")), // 默认分支，必须覆盖所有可能
     }
 
     // 2. match 返回值
     let number = 2;
     let text = match number {
-        1 => "一",
-        2 => "二",
-        3 => "三",
-        _ => "其他",
+        1 => t!("pattern_matching.one"),
+        2 => t!("pattern_matching.two"),
+        3 => t!("pattern_matching.three"),
+        _ => t!("pattern_matching.other"),
     };
     println!("{}", text);
 
@@ -38,9 +38,9 @@ fn main() {
     // 4. 匹配嵌套的 Option
     let some_value = Some(Some(42));
     match some_value {
-        Some(Some(v)) => println!("嵌套值: {}", v),
-        Some(None) => println!("内部是 None"),
-        None => println!("外部是 None"),
+        Some(Some(v)) => println!("{}", t!("pattern_matching.nested", v)),
+        Some(None) => println!("{}", t!("pattern_matching.inner_none")),
+        None => println!("{}", t!("pattern_matching.outer_none")),
     }
 
     // ========== 匹配 Result ==========
@@ -48,8 +48,8 @@ fn main() {
     // 5. 匹配 Result<T, E>
     let result: Result<i32, &str> = Ok(42);
     match result {
-        Ok(value) => println!("成功: {}", value),
-        Err(error) => println!("错误: {}", error),
+        Ok(value) => println!("{}", t!("pattern_matching.ok", value)),
+        Err(error) => println!("{}", t!("pattern_matching.err", error)),
     }
 
     // ========== 多个值匹配 ==========
@@ -57,10 +57,10 @@ fn main() {
     // 6. 使用 | 匹配多个值
     let number = 5;
     match number {
-        1 | 2 | 3 => println!("小"),
-        4 | 5 | 6 => println!("中"),
-        7 | 8 | 9 => println!("大"),
-        _ => println!("其他"),
+        1 | 2 | 3 => println!("{}", t!("pattern_matching.small")),
+        4 | 5 | 6 => println!("{}", t!("pattern_matching.medium")),
+        7 | 8 | 9 => println!("{}", t!("pattern_matching.large")),
+        _ => println!("{}", t!("pattern_matching.other")),
     }
 
     // ========== 范围匹配 ==========
@@ -68,10 +68,10 @@ fn main() {
     // 7. 使用 ..= 匹配范围
     let number = 42;
     match number {
-        1..=10 => println!("1 到 10"),
-        11..=20 => println!("11 到 20"),
-        21..=50 => println!("21 到 50"),
-        _ => println!("其他"),
+        1..=10 => println!("{}", t!("pattern_matching.range_1_10")),
+        11..=20 => println!("{}", t!("pattern_matching.range_11_20")),
+        21..=50 => println!("{}", t!("pattern_matching.range_21_50")),
+        _ => println!("{}", t!("pattern_matching.other")),
     }
 
     // ========== 解构 ==========
@@ -79,9 +79,9 @@ fn main() {
     // 8. 解构元组
     let point = (0, 5);
     match point {
-        (0, 0) => println!("原点"),
-        (0, y) => println!("在 y 轴上，y = {}", y),
-        (x, 0) => println!("在 x 轴上，x = {}", x),
+        (0, 0) => println!("{}", t!("pattern_matching.origin")),
+        (0, y) => println!("{}", t!("pattern_matching.on_y_axis", y)),
+        (x, 0) => println!("{}", t!("pattern_matching.on_x_axis", x)),
         (x, y) => println!("({}, {})", x, y),
     }
 
@@ -93,8 +93,8 @@ fn main() {
 
     let p = Point { x: 0, y: 7 };
     match p {
-        Point { x, y: 0 } => println!("在 x 轴上，x = {}", x),
-        Point { x: 0, y } => println!("在 y 轴上，y = {}", y),
+        Point { x, y: 0 } => println!("{}", t!("pattern_matching.on_x_axis", x)),
+        Point { x: 0, y } => println!("{}", t!("pattern_matching.on_y_axis", y)),
         Point { x, y } => println!("({}, {})", x, y),
     }
 
@@ -108,11 +108,11 @@ fn main() {
 
     let msg = Message::ChangeColor(0, 160, 255);
     match msg {
-        Message::Quit => println!("退出"),
-        Message::Move { x, y } => println!("移动到 ({}, {})", x, y),
-        Message::Write(text) => println!("写入: {}", text),
+        Message::Quit => println!("{}", t!("pattern_matching.quit")),
+        Message::Move { x, y } => println!("{}", t!("pattern_matching.move_to", x, y)),
+        Message::Write(text) => println!("{}", t!("pattern_matching.write", text)),
         Message::ChangeColor(r, g, b) => {
-            println!("改变颜色: RGB({}, {}, {})", r, g, b);
+            println!("{}", t!("pattern_matching.change_color", r, g, b));
         }
     }
 
@@ -121,15 +121,15 @@ fn main() {
     // 11. match 守卫
     let num = Some(4);
     match num {
-        Some(x) if x < 5 => println!("小于 5: {}", x),
-        Some(x) => println!("大于等于 5: {}", x),
+        Some(x) if x < 5 => println!("{}", t!("pattern_matching.less_than_5", x)),
+        Some(x) => println!("{}", t!("pattern_matching.at_least_5", x)),
         None => (),
     }
 
     // 12. 多个守卫条件
     let point = (4, 5);
     match point {
-        (x, y) if x == y => println!("在对角线上"),
+        (x, y) if x == y => println!("{}", t!("pattern_matching.on_diagonal")),
         (x, y) if x > y => println!("x > y"),
         (x, y) if x < y => println!("x < y"),
         _ => (),
@@ -147,13 +147,13 @@ fn main() {
         Message2::Hello {
             id: id_variable @ 3..=7,
         } => {
-            println!("找到范围内的 id: {}", id_variable)
+            println!("{}", t!("pattern_matching.id_in_range", id_variable))
         }
         Message2::Hello { id: 10..=12 } => {
-            println!("找到另一个范围内的 id")
+            println!("{}", t!("pattern_matching.id_in_other_range"))
         }
         Message2::Hello { id } => {
-            println!("找到其他 id: {}", id)
+            println!("{}", t!("pattern_matching.other_id", id))
         }
     }
 
@@ -163,21 +163,21 @@ fn main() {
     // 用于只关心一个匹配分支的情况
     let some_value = Some(3);
     if let Some(3) = some_value {
-        println!("值是 3");
+        println!("{}", t!("pattern_matching.value_is_3"));
     }
 
     // 等价于：
     match some_value {
-        Some(3) => println!("值是 3"),
+        Some(3) => println!("{}", t!("pattern_matching.value_is_3")),
         _ => (),
     }
 
     // 15. if let else
     let some_number = Some(7);
     if let Some(i) = some_number {
-        println!("匹配到: {}", i);
+        println!("{}", t!("pattern_matching.matched", i));
     } else {
-        println!("没有匹配");
+        println!("{}", t!("pattern_matching.no_match"));
     }
 
     // ========== while let ==========
@@ -219,13 +219,13 @@ fn main() {
     // 21. 匹配引用
     let reference = &4;
     match reference {
-        &val => println!("通过解构获得值: {}", val),
+        &val => println!("{}", t!("pattern_matching.destructured", val)),
     }
 
     // 22. 使用 ref 关键字
     let value = 5;
     match value {
-        ref r => println!("获得引用: {:p}", r),
+        ref r => println!("{}", t!("pattern_matching.reference", format!("{:p}", r))),
     }
 
     // ========== 可反驳和不可反驳模式 ==========
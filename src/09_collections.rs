@@ -5,7 +5,9 @@
 
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
-fn main() {
+use crate::interner::{Interner, Symbol};
+
+pub fn main() {
     // ========== Vector (Vec<T>) ==========
 
     // 1. 创建 Vector
@@ -20,12 +22,12 @@ fn main() {
 
     // 3. 访问元素
     let third = &v[2]; // 使用索引（可能 panic）
-    println!("第三个元素: {}", third);
+    println!("{}", t!("collections.third", third));
 
     let third = v.get(2); // 使用 get（返回 Option）
     match third {
-        Some(value) => println!("第三个元素: {}", value),
-        None => println!("没有第三个元素"),
+        Some(value) => println!("{}", t!("collections.third", value)),
+        None => println!("{}", t!("collections.no_third")),
     }
 
     // 4. 遍历 Vector
@@ -38,7 +40,7 @@ fn main() {
     for i in &mut v {
         *i += 1; // 解引用并修改
     }
-    println!("修改后: {:?}", v);
+    println!("{}", t!("collections.modified", format!("{:?}", v)));
 
     // 6. 使用枚举存储不同类型
     enum SpreadsheetCell {
@@ -85,7 +87,7 @@ fn main() {
     // 12. 字符串切片
     let hello = "Здравствуйте";
     let s = &hello[0..4]; // 必须是字符边界
-    println!("切片: {}", s);
+    println!("{}", t!("collections.slice", s));
 
     // 13. 遍历字符串
     for c in "नमस्ते".chars() {
@@ -109,8 +111,8 @@ fn main() {
     let team_name = String::from("Blue");
     let score = scores.get(&team_name);
     match score {
-        Some(s) => println!("Blue 队的分数: {}", s),
-        None => println!("没有找到"),
+        Some(s) => println!("{}", t!("collections.blue_score", s)),
+        None => println!("{}", t!("collections.not_found")),
     }
 
     // 17. 遍历 HashMap
@@ -135,12 +137,36 @@ fn main() {
     }
     println!("{:?}", map);
 
+    // 20. 用驻留的符号作为键
+    // 单词来自临时的字符串时，HashMap<String, _> 要为每个单词复制一份 String；
+    // Interner 每个不同的单词只存一份，键是 4 字节、实现了 Copy 的 Symbol
+    let long_text = vec![text; 100].join(" ");
+    let mut interner = Interner::new();
+    let mut counts: HashMap<Symbol, i32> = HashMap::new();
+    for word in long_text.split_whitespace() {
+        let count = counts.entry(interner.get_or_intern(word)).or_insert(0);
+        *count += 1;
+    }
+    for (symbol, word) in &interner {
+        println!("{:?} {}: {}", symbol, word, counts[&symbol]);
+    }
+
+    // 比较分配次数和字节数
+    let copies = long_text.split_whitespace().count();
+    let copied: usize = long_text.split_whitespace().map(str::len).sum();
+    let stats = interner.stats();
+    println!("{}", t!("collections.owned_keys", copies, copied));
+    println!(
+        "{}",
+        t!("collections.interned_keys", stats.strings, stats.allocations, stats.bytes)
+    );
+
     // ========== HashSet ==========
 
-    // 20. 创建 HashSet
+    // 21. 创建 HashSet
     let mut set = HashSet::new();
 
-    // 21. 插入值
+    // 22. 插入值
     set.insert(1);
     set.insert(2);
     set.insert(3);
@@ -148,56 +174,56 @@ fn main() {
 
     println!("set: {:?}", set);
 
-    // 22. 检查值是否存在
+    // 23. 检查值是否存在
     if set.contains(&2) {
-        println!("包含 2");
+        println!("{}", t!("collections.contains_2"));
     }
 
-    // 23. 集合操作
+    // 24. 集合操作
     let set1: HashSet<i32> = [1, 2, 3].iter().cloned().collect();
     let set2: HashSet<i32> = [3, 4, 5].iter().cloned().collect();
 
     // 并集
     let union: HashSet<_> = set1.union(&set2).collect();
-    println!("并集: {:?}", union);
+    println!("{}", t!("collections.union", format!("{:?}", union)));
 
     // 交集
     let intersection: HashSet<_> = set1.intersection(&set2).collect();
-    println!("交集: {:?}", intersection);
+    println!("{}", t!("collections.intersection", format!("{:?}", intersection)));
 
     // 差集
     let difference: HashSet<_> = set1.difference(&set2).collect();
-    println!("差集: {:?}", difference);
+    println!("{}", t!("collections.difference", format!("{:?}", difference)));
 
     // ========== VecDeque (双端队列) ==========
 
-    // 24. 创建 VecDeque
+    // 25. 创建 VecDeque
     let mut deque = VecDeque::new();
 
-    // 25. 从两端添加元素
+    // 26. 从两端添加元素
     deque.push_back(1);
     deque.push_back(2);
     deque.push_front(0);
 
     println!("deque: {:?}", deque);
 
-    // 26. 从两端移除元素
+    // 27. 从两端移除元素
     let front = deque.pop_front();
     let back = deque.pop_back();
     println!("front: {:?}, back: {:?}", front, back);
 
     // ========== BinaryHeap (优先队列) ==========
 
-    // 27. 创建 BinaryHeap（最大堆）
+    // 28. 创建 BinaryHeap（最大堆）
     let mut heap = BinaryHeap::new();
 
-    // 28. 插入元素
+    // 29. 插入元素
     heap.push(3);
     heap.push(1);
     heap.push(4);
     heap.push(2);
 
-    // 29. 获取最大元素
+    // 30. 获取最大元素
     while let Some(max) = heap.pop() {
         println!("{}", max); // 输出: 4, 3, 2, 1
     }
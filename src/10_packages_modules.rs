@@ -12,33 +12,33 @@ mod front_of_house {
     // 2. 嵌套模块
     pub mod hosting {
         pub fn add_to_waitlist() {
-            println!("添加到等待列表");
+            println!("{}", t!("packages_modules.add_to_waitlist"));
         }
 
         fn seat_at_table() {
-            println!("安排座位");
+            println!("{}", t!("packages_modules.seat_at_table"));
         }
     }
 
     mod serving {
         fn take_order() {
-            println!("接受订单");
+            println!("{}", t!("packages_modules.take_order"));
         }
 
         fn serve_order() {
-            println!("上菜");
+            println!("{}", t!("packages_modules.serve_order"));
         }
 
         fn take_payment() {
-            println!("收银");
+            println!("{}", t!("packages_modules.take_payment"));
         }
     }
 }
 
 // 3. 使用模块
 fn eat_at_restaurant() {
-    // 绝对路径
-    crate::front_of_house::hosting::add_to_waitlist();
+    // 绝对路径（本章作为 rust-tutorial 的 chapters::packages_modules 模块编译）
+    crate::chapters::packages_modules::front_of_house::hosting::add_to_waitlist();
 
     // 相对路径
     front_of_house::hosting::add_to_waitlist();
@@ -47,14 +47,15 @@ fn eat_at_restaurant() {
 // ========== 使用 use 关键字 ==========
 
 // 4. 使用 use 引入路径
-use crate::front_of_house::hosting;
+// 同一个名字只能引入一次，这里由下面第 8 条的 pub use 同时完成引入和重新导出
+// use self::front_of_house::hosting;
 
 fn eat_at_restaurant2() {
     hosting::add_to_waitlist();
 }
 
 // 5. 使用 use 引入函数（不推荐，除非是 trait 方法）
-// use crate::front_of_house::hosting::add_to_waitlist;
+// use self::front_of_house::hosting::add_to_waitlist;
 // fn eat_at_restaurant3() {
 //     add_to_waitlist();
 // }
@@ -72,7 +73,7 @@ use std::fmt::Result;
 use std::io::Result as IoResult;
 
 // 8. 使用 pub use 重新导出
-pub use crate::front_of_house::hosting;
+pub use self::front_of_house::hosting;
 
 // ========== 模块文件系统 ==========
 
@@ -114,7 +115,7 @@ mod back_of_house {
 fn eat_at_restaurant4() {
     let mut meal = back_of_house::Breakfast::summer("Rye");
     meal.toast = String::from("Wheat");
-    println!("我要 {} 吐司", meal.toast);
+    println!("{}", t!("packages_modules.toast", meal.toast));
 
     // meal.seasonal_fruit = String::from("blueberries"); // 错误！私有字段
 
@@ -135,7 +136,7 @@ mod back_of_house2 {
 }
 
 fn serve_order() {
-    println!("上菜");
+    println!("{}", t!("packages_modules.serve_order"));
 }
 
 // ========== 模块组织示例 ==========
@@ -143,23 +144,23 @@ fn serve_order() {
 // 12. 典型的模块结构
 mod network {
     pub fn connect() {
-        println!("网络连接");
+        println!("{}", t!("packages_modules.network_connect"));
     }
 
     pub mod server {
         pub fn serve() {
-            println!("服务器服务");
+            println!("{}", t!("packages_modules.server_serve"));
         }
     }
 }
 
 mod client {
     pub fn connect() {
-        println!("客户端连接");
+        println!("{}", t!("packages_modules.client_connect"));
     }
 }
 
-fn main() {
+pub fn main() {
     // 使用模块
     network::connect();
     network::server::serve();
@@ -3,11 +3,14 @@
 // ============================================
 // Rust 将错误分为两大类：可恢复错误和不可恢复错误
 
+use std::fmt;
 use std::fs::File;
 use std::io::{self, Read};
 use std::num;
 
-fn main() {
+use crate::arithmetic;
+
+pub fn main() {
     // ========== panic! 宏（不可恢复错误） ==========
 
     // 1. 使用 panic! 宏
@@ -26,13 +29,14 @@ fn main() {
     // }
 
     // 4. 处理 Result
-    let f = File::open("hello.txt");
-    let f = match f {
-        Ok(file) => file,
-        Err(error) => {
-            panic!("打开文件时出错: {:?}", error);
-        }
-    };
+    // 如果 hello.txt 不存在，下面的代码会 panic（第 5 条演示了如何按错误类型处理）
+    // let f = File::open("hello.txt");
+    // let f = match f {
+    //     Ok(file) => file,
+    //     Err(error) => {
+    //         panic!("打开文件时出错: {:?}", error);
+    //     }
+    // };
 
     // 5. 匹配不同的错误
     let f = File::open("hello.txt");
@@ -41,10 +45,10 @@ fn main() {
         Err(error) => match error.kind() {
             io::ErrorKind::NotFound => match File::create("hello.txt") {
                 Ok(fc) => fc,
-                Err(e) => panic!("创建文件时出错: {:?}", e),
+                Err(e) => panic!("{}", t!("error_handling.create_failed", format!("{:?}", e))),
             },
             other_error => {
-                panic!("打开文件时出错: {:?}", other_error);
+                panic!("{}", t!("error_handling.open_failed", format!("{:?}", other_error)));
             }
         },
     };
@@ -62,14 +66,14 @@ fn main() {
     // 8. 使用 ? 运算符传播错误
     // ? 运算符只能用于返回 Result 或 Option 的函数
     match read_username_from_file() {
-        Ok(username) => println!("用户名: {}", username),
-        Err(e) => println!("错误: {}", e),
+        Ok(username) => println!("{}", t!("error_handling.username", username)),
+        Err(e) => println!("{}", t!("error_handling.error", e)),
     }
 
     // 9. 使用 ? 运算符的简化版本
     match read_username_from_file_simple() {
-        Ok(username) => println!("用户名: {}", username),
-        Err(e) => println!("错误: {}", e),
+        Ok(username) => println!("{}", t!("error_handling.username", username)),
+        Err(e) => println!("{}", t!("error_handling.error", e)),
     }
 
     // ========== 自定义错误类型 ==========
@@ -101,8 +105,8 @@ fn main() {
     }
 
     match last_char_of_first_line("hello\nworld") {
-        Some(c) => println!("第一个字符: {}", c),
-        None => println!("没有找到"),
+        Some(c) => println!("{}", t!("error_handling.first_char", c)),
+        None => println!("{}", t!("error_handling.not_found")),
     }
 
     // ========== 错误处理最佳实践 ==========
@@ -120,13 +124,19 @@ fn main() {
 
     // 14. 链式调用和错误处理
     match divide(10, 2) {
-        Ok(result) => println!("结果: {}", result),
-        Err(e) => println!("错误: {}", e),
+        Ok(result) => println!("{}", t!("error_handling.result", result)),
+        Err(e) => println!("{}", t!("error_handling.error", e)),
     }
 
     match divide(10, 0) {
-        Ok(result) => println!("结果: {}", result),
-        Err(e) => println!("错误: {}", e),
+        Ok(result) => println!("{}", t!("error_handling.result", result)),
+        Err(e) => println!("{}", t!("error_handling.error", e)),
+    }
+
+    // i32::MIN / -1 的结果超出了 i32 的范围
+    match divide(i32::MIN, -1) {
+        Ok(result) => println!("{}", t!("error_handling.result", result)),
+        Err(e) => println!("{}", t!("error_handling.error", e)),
     }
 }
 
@@ -168,21 +178,39 @@ fn read_username_from_file_simpler() -> Result<String, io::Error> {
 #[derive(Debug)]
 enum DivisionError {
     DivisionByZero,
+    Overflow,
 }
 
-fn divide(a: i32, b: i32) -> Result<i32, DivisionError> {
-    if b == 0 {
-        Err(DivisionError::DivisionByZero)
-    } else {
-        Ok(a / b)
+// 实现 Display 才能使用 {} 打印错误
+impl fmt::Display for DivisionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivisionError::DivisionByZero => write!(f, "{}", t!("error_handling.division_by_zero")),
+            DivisionError::Overflow => write!(f, "{}", t!("error_handling.division_overflow")),
+        }
+    }
+}
+
+// 实现 From 之后，? 运算符会自动把 arithmetic::Error 转换成 DivisionError
+impl From<arithmetic::Error<i32>> for DivisionError {
+    fn from(error: arithmetic::Error<i32>) -> Self {
+        match error {
+            arithmetic::Error::DivisionByZero { .. } => DivisionError::DivisionByZero,
+            _ => DivisionError::Overflow,
+        }
     }
 }
 
+fn divide(a: i32, b: i32) -> Result<i32, DivisionError> {
+    let quotient = arithmetic::divide(a, b)?;
+    Ok(quotient)
+}
+
 // ========== 错误处理模式 ==========
 
 // 15. 使用 map 和 map_err 转换错误
 fn parse_number(s: &str) -> Result<i32, String> {
-    s.parse::<i32>().map_err(|e| format!("解析错误: {}", e))
+    s.parse::<i32>().map_err(|e| t!("error_handling.parse_error", e))
 }
 
 // 16. 使用 and_then 链式操作
@@ -3,17 +3,17 @@
 // ============================================
 // 泛型允许我们编写可以处理多种数据类型的代码
 
-fn main() {
+pub fn main() {
     // ========== 函数中的泛型 ==========
 
     // 1. 泛型函数
     let number_list = vec![34, 50, 25, 100, 65];
     let result = largest(&number_list);
-    println!("最大数字: {}", result);
+    println!("{}", t!("generics.largest_number", result));
 
     let char_list = vec!['y', 'm', 'a', 'q'];
     let result = largest(&char_list);
-    println!("最大字符: {}", result);
+    println!("{}", t!("generics.largest_char", result));
 
     // ========== 结构体中的泛型 ==========
 
@@ -63,7 +63,7 @@ fn main() {
 
     // 6. 为特定类型实现方法
     let p = Point { x: 5.0, y: 10.0 };
-    println!("距离原点: {}", p.distance_from_origin());
+    println!("{}", t!("generics.distance", p.distance_from_origin()));
 
     // ========== 性能 ==========
 
@@ -76,7 +76,7 @@ fn main() {
     // 8. 使用 Trait 约束泛型
     let number_list = vec![34, 50, 25, 100, 65];
     let result = largest_with_trait(&number_list);
-    println!("最大数字: {}", result);
+    println!("{}", t!("generics.largest_number", result));
 
     // ========== 多个 Trait 约束 ==========
 
@@ -97,8 +97,8 @@ fn main() {
     let string1 = String::from("long string is long");
     {
         let string2 = String::from("xyz");
-        let result = longest_with_an_announcement(&string1, &string2, "重要通知");
-        println!("最长的字符串: {}", result);
+        let result = longest_with_an_announcement(&string1, &string2, t!("generics.notice"));
+        println!("{}", t!("generics.longest", result));
     }
 }
 
@@ -133,9 +133,9 @@ fn largest_with_trait<T: PartialOrd + Copy>(list: &[T]) -> T {
 // ========== 泛型结构体 ==========
 
 #[derive(Debug)]
-struct Point<T> {
-    x: T,
-    y: T,
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
 }
 
 // 为泛型结构体实现方法
@@ -152,6 +152,24 @@ impl Point<f32> {
     }
 }
 
+use crate::geometry;
+
+// 也可以只为特定类型实现 Trait：只有 Point<i32> 能和 geometry::Point 互相转换
+impl From<Point<i32>> for geometry::Point {
+    fn from(point: Point<i32>) -> Self {
+        geometry::Point::new(point.x, point.y)
+    }
+}
+
+impl From<geometry::Point> for Point<i32> {
+    fn from(point: geometry::Point) -> Self {
+        Point {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
 // 多个泛型参数
 #[derive(Debug)]
 struct Point2<T, U> {
@@ -249,21 +267,21 @@ impl<T> Pair<T> {
 impl<T: std::fmt::Display + PartialOrd> Pair<T> {
     fn cmp_display(&self) {
         if self.x >= self.y {
-            println!("最大的成员是 x = {}", self.x);
+            println!("{}", t!("generics.largest_member_x", self.x));
         } else {
-            println!("最大的成员是 y = {}", self.y);
+            println!("{}", t!("generics.largest_member_y", self.y));
         }
     }
 }
 
 // ========== 泛型和生命周期一起使用 ==========
 
-use std::fmt::Display;
-
+// 注意：本文件自定义了一个名为 Display 的 trait，
+// 所以这里使用完整路径 std::fmt::Display 来指代标准库的 Display
 fn longest_with_an_announcement<'a, T>(x: &'a str, y: &'a str, ann: T) -> &'a str
 where
-    T: Display,
+    T: std::fmt::Display,
 {
-    println!("通知! {}", ann);
+    println!("{}", t!("generics.announcement", ann));
     if x.len() > y.len() { x } else { y }
 }
@@ -3,7 +3,7 @@
 // ============================================
 // Trait 定义了共享的行为，类似于其他语言中的接口
 
-fn main() {
+pub fn main() {
     // ========== 基本 Trait 使用 ==========
     
     // 1. 使用实现了 Trait 的类型
@@ -14,7 +14,9 @@ fn main() {
         retweet: false,
     };
     
-    println!("1 new tweet: {}", tweet.summarize());
+    // Tweet 同时实现了 Summary 和 Summary2，两者都有 summarize 方法，
+    // 直接调用 tweet.summarize() 会产生歧义（E0034），需要指明使用哪个 Trait
+    println!("1 new tweet: {}", Summary::summarize(&tweet));
     
     let article = NewsArticle {
         headline: String::from("Penguins win the Stanley Cup Championship!"),
@@ -23,7 +25,7 @@ fn main() {
         content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
     };
     
-    println!("New article available! {}", article.summarize());
+    println!("New article available! {}", Summary::summarize(&article));
     
     // ========== 默认实现 ==========
     
@@ -35,7 +37,7 @@ fn main() {
         retweet: false,
     };
     
-    println!("{}", tweet2.summarize());
+    println!("{}", Summary2::summarize(&tweet2));
     println!("{}", tweet2.summarize_author());
     
     // ========== Trait 作为参数 ==========
@@ -196,9 +198,9 @@ impl<T> Pair<T> {
 impl<T: Display + PartialOrd> Pair<T> {
     fn cmp_display(&self) {
         if self.x >= self.y {
-            println!("最大的成员是 x = {}", self.x);
+            println!("{}", t!("traits.largest_member_x", self.x));
         } else {
-            println!("最大的成员是 y = {}", self.y);
+            println!("{}", t!("traits.largest_member_y", self.y));
         }
     }
 }
@@ -208,9 +210,9 @@ impl<T: Display + PartialOrd> Pair<T> {
 use std::ops::Add;
 
 #[derive(Debug, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
 }
 
 // 实现 Add Trait 以支持 + 运算符
@@ -232,6 +234,24 @@ impl Display for Point {
     }
 }
 
+use crate::geometry;
+
+// 实现 From 以便和 geometry::Point 互相转换，之后就可以用 .into()
+impl From<Point> for geometry::Point {
+    fn from(point: Point) -> Self {
+        geometry::Point::new(point.x, point.y)
+    }
+}
+
+impl From<geometry::Point> for Point {
+    fn from(point: geometry::Point) -> Self {
+        Point {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
 // ========== 关联类型 ==========
 
 // 关联类型是 Trait 定义中的占位符类型
@@ -287,19 +307,19 @@ struct Human;
 
 impl Pilot for Human {
     fn fly(&self) {
-        println!("这是机长在说话。");
+        println!("{}", t!("traits.captain"));
     }
 }
 
 impl Wizard for Human {
     fn fly(&self) {
-        println!("起来！");
+        println!("{}", t!("traits.wizard"));
     }
 }
 
 impl Human {
     fn fly(&self) {
-        println!("*挥舞着手臂*");
+        println!("{}", t!("traits.waving"));
     }
 }
 
@@ -3,7 +3,7 @@
 // ============================================
 // 生命周期是 Rust 中确保引用有效的机制
 
-fn main() {
+pub fn main() {
     // ========== 生命周期基础 ==========
     
     // 1. 生命周期确保引用有效
@@ -11,7 +11,7 @@ fn main() {
     {
         let string2 = String::from("xyz");
         let result = longest(string1.as_str(), string2.as_str());
-        println!("最长的字符串是 {}", result);
+        println!("{}", t!("lifetimes.longest", result));
     }
     
     // ========== 生命周期注解语法 ==========
@@ -29,17 +29,17 @@ fn main() {
     let string2 = "xyz";
     
     let result = longest(string1.as_str(), string2);
-    println!("最长的字符串是 {}", result);
+    println!("{}", t!("lifetimes.longest", result));
     
     // ========== 结构体中的生命周期 ==========
     
     // 4. 结构体定义中的生命周期注解
     let novel = String::from("Call me Ishmael. Some years ago...");
-    let first_sentence = novel.split('.').next().expect("找不到 '.'");
+    let first_sentence = novel.split('.').next().expect(&t!("lifetimes.no_period"));
     let i = ImportantExcerpt {
         part: first_sentence,
     };
-    println!("重要摘录: {}", i.part);
+    println!("{}", t!("lifetimes.excerpt", i.part));
     
     // ========== 生命周期省略规则 ==========
     
@@ -53,7 +53,7 @@ fn main() {
     // 示例：这些函数不需要显式生命周期注解
     let s = String::from("hello");
     let len = first_word(&s);
-    println!("第一个单词: {}", len);
+    println!("{}", t!("lifetimes.first_word", len));
     
     // ========== 方法定义中的生命周期 ==========
     
@@ -61,8 +61,8 @@ fn main() {
     let i = ImportantExcerpt {
         part: "hello world",
     };
-    let announcement = "重要通知";
-    let result = i.announce_and_return_part(announcement);
+    let announcement = t!("lifetimes.notice");
+    let result = i.announce_and_return_part(&announcement);
     println!("{}", result);
     
     // ========== 静态生命周期 ==========
@@ -77,7 +77,7 @@ fn main() {
     // 8. 结合泛型类型参数、Trait Bound 和生命周期
     let string1 = String::from("long string is long");
     let string2 = String::from("xyz");
-    let ann = "重要通知";
+    let ann = t!("lifetimes.notice");
     let result = longest_with_an_announcement(string1.as_str(), string2.as_str(), ann);
     println!("{}", result);
     
@@ -141,7 +141,7 @@ impl<'a> ImportantExcerpt<'a> {
     
     // 显式生命周期注解
     fn announce_and_return_part(&self, announcement: &str) -> &str {
-        println!("注意! {}", announcement);
+        println!("{}", t!("lifetimes.attention", announcement));
         self.part
     }
 }
@@ -173,7 +173,7 @@ fn longest_with_an_announcement<'a, T>(x: &'a str, y: &'a str, ann: T) -> &'a st
 where
     T: Display,
 {
-    println!("通知! {}", ann);
+    println!("{}", t!("lifetimes.announcement", ann));
     if x.len() > y.len() {
         x
     } else {
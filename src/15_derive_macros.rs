@@ -3,7 +3,7 @@
 // ============================================
 // #[derive(...)] 是 Rust 的属性宏，用于自动实现某些 Trait
 
-fn main() {
+pub fn main() {
     // ========== Debug Trait ==========
 
     // 1. Debug 允许使用 {:?} 或 {:#?} 格式化输出
@@ -48,7 +48,7 @@ fn main() {
 
     // 在条件判断中使用
     if p1 == p2 {
-        println!("两个点相同");
+        println!("{}", t!("derive_macros.same_points"));
     }
 
     // ========== Eq Trait ==========
@@ -156,7 +156,7 @@ fn main() {
     }
 
     let config = Config::default();
-    println!("默认配置: {:?}", config);
+    println!("{}", t!("derive_macros.default_config", format!("{:?}", config)));
 
     // ========== 组合使用 ==========
 
@@ -212,7 +212,7 @@ fn main() {
 
     // 在测试中很有用
     assert_eq!(rect1, rect2);
-    println!("测试通过！两个矩形相等");
+    println!("{}", t!("derive_macros.rects_equal"));
 
     // ========== 手动实现 vs derive ==========
 
@@ -238,7 +238,7 @@ fn main() {
 
     let mp1 = ManualPoint { x: 1, y: 2 };
     let mp2 = ManualPoint { x: 1, y: 2 };
-    println!("手动实现: {:?}, 相等: {}", mp1, mp1 == mp2);
+    println!("{}", t!("derive_macros.manual", format!("{:?}", mp1), mp1 == mp2));
 
     // ========== derive 的限制 ==========
 
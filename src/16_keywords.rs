@@ -3,8 +3,8 @@
 // ============================================
 // Rust 关键字是语言保留的标识符，不能用作变量名、函数名等
 
-fn main() {
-    println!("Rust 关键字示例");
+pub fn main() {
+    println!("{}", t!("keywords.intro"));
 
     // ========== 声明和定义关键字示例 ==========
 
@@ -24,21 +24,21 @@ fn main() {
 
     // 11. if - 条件语句
     if x > 5 {
-        println!("x 大于 5");
+        println!("{}", t!("keywords.x_gt_5"));
     }
 
     // 12. else - else 分支
     if x > 5 {
-        println!("大于 5");
+        println!("{}", t!("keywords.gt_5"));
     } else {
-        println!("小于等于 5");
+        println!("{}", t!("keywords.le_5"));
     }
 
     // 13. match - 模式匹配
     match x {
-        1 => println!("一"),
-        2 => println!("二"),
-        _ => println!("其他"),
+        1 => println!("{}", t!("keywords.one")),
+        2 => println!("{}", t!("keywords.two")),
+        _ => println!("{}", t!("keywords.other")),
     }
 
     // 14. loop - 无限循环
@@ -49,7 +49,7 @@ fn main() {
             break counter * 2; // 使用 break 退出并返回值
         }
     };
-    println!("loop 结果: {}", result);
+    println!("{}", t!("keywords.loop_result", result));
 
     // 15. while - while 循环
     let mut i = 0;
@@ -81,28 +81,28 @@ fn main() {
     // 20. move - 强制闭包获取所有权
     let s = String::from("hello");
     let closure = move || {
-        println!("move 闭包: {}", s); // s 被移动到闭包中
+        println!("{}", t!("keywords.move_closure", s)); // s 被移动到闭包中
     };
     closure();
 
     // 21. ref - 在模式匹配中获取引用
     let value = 5;
     match value {
-        ref r => println!("ref 引用: {:p}", r),
+        ref r => println!("{}", t!("keywords.ref_reference", format!("{:p}", r))),
     }
 
     // 22. mut - 使变量或引用可变
     let mut x = 5;
     let r = &mut x; // 可变引用
     *r += 1;
-    println!("mut 可变引用: {}", x);
+    println!("{}", t!("keywords.mut_reference", x));
 
     // ========== 类型相关关键字示例 ==========
 
     // 23. as - 类型转换
     let integer = 65;
     let character = integer as u8 as char;
-    println!("as 类型转换: {} -> {}", integer, character);
+    println!("{}", t!("keywords.as_cast", integer, character));
 
     // ========== 实际使用示例 ==========
 
@@ -111,8 +111,8 @@ fn main() {
 
     // 模式匹配
     match point.x {
-        1 => println!("x 是 1"),
-        _ => println!("x 不是 1"),
+        1 => println!("{}", t!("keywords.x_is_1")),
+        _ => println!("{}", t!("keywords.x_is_not_1")),
     }
 
     // 循环
@@ -132,7 +132,7 @@ fn main() {
 
 // 1. fn - 定义函数
 fn my_function() {
-    println!("这是一个函数");
+    println!("{}", t!("keywords.a_function"));
 }
 
 // 5. struct - 定义结构体
@@ -165,7 +165,7 @@ type Kilometers = i32;
 // 10. mod - 定义模块
 mod my_module {
     pub fn function() {
-        println!("模块函数");
+        println!("{}", t!("keywords.module_fn"));
     }
 }
 
@@ -202,7 +202,8 @@ use std::collections::HashMap;
 
 // 28. crate - 当前 crate 的根模块
 fn use_crate() {
-    crate::my_module::function();
+    // 本章作为 rust-tutorial 的 chapters::keywords 模块编译
+    crate::chapters::keywords::my_module::function();
 }
 
 // 29. super - 父模块（在子模块中使用）
@@ -211,7 +212,7 @@ fn use_crate() {
 // 30. self - 当前模块或方法接收者
 impl Person {
     fn introduce(&self) {
-        println!("我是 {}", self.name);
+        println!("{}", t!("keywords.introduce", self.name));
     }
 }
 
@@ -297,7 +298,8 @@ fn linux_only_function() {}
 // ========== 其他关键字 ==========
 
 // 41. extern - 声明外部函数或变量
-extern "C" {
+// Rust 2024 中 extern 块必须标记为 unsafe
+unsafe extern "C" {
     fn abs(input: i32) -> i32;
 }
 
@@ -0,0 +1,475 @@
+// ============================================
+// 账户存储 (Accounts)
+// ============================================
+// 第 06 章的 User 有 username、email、sign_in_count 和 active，并用
+// ..user1 演示了结构体更新语法；第 15 章的 User 多了一个 id。这里把两者
+// 合在一起，做成一个内存中的账户仓库：
+//
+// - 创建、修改、停用和删除账户；id 从 1 开始递增，删除后不会复用，
+//   最大到 u32::MAX - 1
+// - 用户名和邮箱都必须唯一，比较时不区分大小写（Alice 和 alice 是同一个人），
+//   但保存和显示时保留原来的大小写
+// - 用户名只能包含英文字母、数字、_、- 和 .，邮箱要符合 本地部分@域名 的格式；
+//   两者都只有 ASCII 字符，不区分大小写就是简单的 ASCII 大小写转换
+// - 每次登录 sign_in_count 加一；停用的账户不能登录
+// - 修改用闭包返回新的 User，调用者可以像第 06 章那样写
+//   User { email: ..., ..user }；检查不通过时账户保持原样
+//
+// 账户可以保存到文本文件，每行一个账户，字段用空格分隔：
+//
+//     # 以 # 开头的行是注释，空行会被忽略
+//     next_id 4                                  <- 下一个新账户的 id
+//     1 alice alice@example.com 3 active         <- id 用户名 邮箱 登录次数 状态
+//     3 Bob bob@example.com 0 inactive
+//
+// 读取时会重新检查所有的约束，手工编辑出错的文件会报告出错的行号。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct User {
+    pub id: u32,
+    pub username: String,
+    pub email: String,
+    pub sign_in_count: u64,
+    pub active: bool,
+}
+
+/// 账户操作失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountError {
+    InvalidUsername {
+        username: String,
+        reason: &'static str,
+    },
+    InvalidEmail {
+        email: String,
+        reason: &'static str,
+    },
+    /// 用户名已经被另一个账户使用（不区分大小写）
+    UsernameTaken(String),
+    EmailTaken(String),
+    NotFound(u32),
+    /// 停用的账户不能登录
+    Inactive(u32),
+    /// update 的闭包改了 id
+    IdChanged {
+        id: u32,
+        new_id: u32,
+    },
+    /// id 已经用到 u32::MAX，不能再创建账户
+    IdsExhausted,
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccountError::InvalidUsername { username, reason } => {
+                write!(f, "用户名 {:?} 无效: {}", username, reason)
+            }
+            AccountError::InvalidEmail { email, reason } => {
+                write!(f, "邮箱 {:?} 无效: {}", email, reason)
+            }
+            AccountError::UsernameTaken(username) => write!(f, "用户名 {} 已经被使用", username),
+            AccountError::EmailTaken(email) => write!(f, "邮箱 {} 已经被使用", email),
+            AccountError::NotFound(id) => write!(f, "找不到 id 为 {} 的账户", id),
+            AccountError::Inactive(id) => write!(f, "账户 {} 已停用，不能登录", id),
+            AccountError::IdChanged { id, new_id } => {
+                write!(f, "不能修改账户的 id（{} 改成了 {}）", id, new_id)
+            }
+            AccountError::IdsExhausted => write!(f, "账户 id 已经用完，不能再创建账户"),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {}
+
+/// 账户文件中的格式错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 出错的行号（从 1 开始）
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 行: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ========== 校验 ==========
+
+const MAX_USERNAME: usize = 32;
+const MAX_LOCAL_PART: usize = 64;
+const MAX_EMAIL: usize = 254;
+const MAX_LABEL: usize = 63;
+
+/// 检查用户名，返回不合格的原因
+///
+/// 只接受 ASCII：Unicode 的大小写规则里 "STRASSE" 和 "straße"、开尔文符号 K 和 k
+/// 是同一个词，逐个字符转小写却分不出来，会让两个“一样”的用户名同时存在
+pub fn check_username(username: &str) -> Result<(), &'static str> {
+    let mut chars = username.chars();
+    let Some(first) = chars.next() else {
+        return Err("不能为空");
+    };
+    if !first.is_ascii_alphanumeric() {
+        return Err("必须以英文字母或数字开头");
+    }
+    if !chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+        return Err("只能包含英文字母、数字、_、- 和 .");
+    }
+    if username.len() > MAX_USERNAME {
+        return Err("不能超过 32 个字符");
+    }
+    Ok(())
+}
+
+/// 检查邮箱格式：本地部分@域名，返回不合格的原因。
+/// 只接受常见的写法，不支持带引号的本地部分和 IP 地址形式的域名
+pub fn check_email(email: &str) -> Result<(), &'static str> {
+    if email.len() > MAX_EMAIL {
+        return Err("不能超过 254 个字节");
+    }
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return Err("缺少 @");
+    };
+    if local.is_empty() {
+        return Err("@ 前面不能为空");
+    }
+    if local.len() > MAX_LOCAL_PART {
+        return Err("@ 前面不能超过 64 个字节");
+    }
+    let allowed = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-.".contains(c);
+    if !local.chars().all(allowed) {
+        return Err("@ 前面含有不允许的字符");
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err("@ 前面的点不能在开头、结尾或连续出现");
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    if domain.is_empty() {
+        return Err("@ 后面不能为空");
+    }
+    if labels.len() < 2 {
+        return Err("域名至少要有两段，例如 example.com");
+    }
+    for label in &labels {
+        if label.is_empty() {
+            return Err("域名中的点不能在开头、结尾或连续出现");
+        }
+        if label.len() > MAX_LABEL {
+            return Err("域名的每一段不能超过 63 个字节");
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err("域名只能包含字母、数字和 -");
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err("域名的每一段不能以 - 开头或结尾");
+        }
+    }
+    if labels[labels.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+        return Err("顶级域名不能全是数字");
+    }
+    Ok(())
+}
+
+/// 比较用户名和邮箱时用的键；两者都已经检查过只有 ASCII 字符
+fn fold(s: &str) -> String {
+    s.to_ascii_lowercase()
+}
+
+// ========== 账户仓库 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accounts {
+    /// 按 id 排序，遍历和保存的顺序固定
+    users: BTreeMap<u32, User>,
+    /// 小写的用户名 -> id
+    usernames: HashMap<String, u32>,
+    /// 小写的邮箱 -> id
+    emails: HashMap<String, u32>,
+    next_id: u32,
+}
+
+impl Default for Accounts {
+    fn default() -> Accounts {
+        Accounts::new()
+    }
+}
+
+impl Accounts {
+    pub fn new() -> Accounts {
+        Accounts {
+            users: BTreeMap::new(),
+            usernames: HashMap::new(),
+            emails: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// 创建一个启用的、还没有登录过的账户
+    pub fn create(&mut self, username: &str, email: &str) -> Result<&User, AccountError> {
+        // next_id 本身也要能用：最后一个可用的 id 是 u32::MAX - 1
+        let next_id = self
+            .next_id
+            .checked_add(1)
+            .ok_or(AccountError::IdsExhausted)?;
+        let user = User {
+            id: self.next_id,
+            username: username.to_string(),
+            email: email.to_string(),
+            sign_in_count: 0,
+            active: true,
+        };
+        self.check(&user)?;
+        self.next_id = next_id;
+        Ok(self.insert(user))
+    }
+
+    pub fn get(&self, id: u32) -> Option<&User> {
+        self.users.get(&id)
+    }
+
+    /// 按用户名查找，不区分大小写
+    pub fn find_by_username(&self, username: &str) -> Option<&User> {
+        let id = self.usernames.get(&fold(username))?;
+        self.users.get(id)
+    }
+
+    /// 按邮箱查找，不区分大小写
+    pub fn find_by_email(&self, email: &str) -> Option<&User> {
+        let id = self.emails.get(&fold(email))?;
+        self.users.get(id)
+    }
+
+    /// 用 change 返回的 User 替换账户，例如
+    /// `accounts.update(id, |user| User { email: new_email, ..user })`。
+    /// id 不能改；新的用户名和邮箱要通过检查，否则账户保持原样
+    pub fn update(
+        &mut self,
+        id: u32,
+        change: impl FnOnce(User) -> User,
+    ) -> Result<&User, AccountError> {
+        let old = self.users.get(&id).ok_or(AccountError::NotFound(id))?;
+        let new = change(old.clone());
+        if new.id != id {
+            return Err(AccountError::IdChanged { id, new_id: new.id });
+        }
+        self.check(&new)?;
+        self.remove(id);
+        Ok(self.insert(new))
+    }
+
+    /// 停用账户：保留数据，但不能再登录
+    pub fn deactivate(&mut self, id: u32) -> Result<&User, AccountError> {
+        self.update(id, |user| User {
+            active: false,
+            ..user
+        })
+    }
+
+    /// 删除账户并返回它；用户名和邮箱可以被新账户使用，id 不会复用
+    pub fn delete(&mut self, id: u32) -> Result<User, AccountError> {
+        self.remove(id).ok_or(AccountError::NotFound(id))
+    }
+
+    /// 记录一次登录，返回更新后的账户
+    pub fn record_sign_in(&mut self, id: u32) -> Result<&User, AccountError> {
+        let user = self.users.get_mut(&id).ok_or(AccountError::NotFound(id))?;
+        if !user.active {
+            return Err(AccountError::Inactive(id));
+        }
+        user.sign_in_count = user.sign_in_count.saturating_add(1);
+        Ok(user)
+    }
+
+    /// 按 id 的顺序遍历所有账户
+    pub fn iter(&self) -> impl Iterator<Item = &User> {
+        self.users.values()
+    }
+
+    /// 启用（active 为 true）或停用的账户，按 id 排序
+    pub fn by_active(&self, active: bool) -> impl Iterator<Item = &User> {
+        self.iter().filter(move |user| user.active == active)
+    }
+
+    /// user 能不能放进仓库：格式正确，用户名和邮箱没有被别的账户使用
+    fn check(&self, user: &User) -> Result<(), AccountError> {
+        check_username(&user.username).map_err(|reason| AccountError::InvalidUsername {
+            username: user.username.clone(),
+            reason,
+        })?;
+        check_email(&user.email).map_err(|reason| AccountError::InvalidEmail {
+            email: user.email.clone(),
+            reason,
+        })?;
+        let taken_by_other = |index: &HashMap<String, u32>, key: &str| {
+            index.get(&fold(key)).is_some_and(|&owner| owner != user.id)
+        };
+        if taken_by_other(&self.usernames, &user.username) {
+            return Err(AccountError::UsernameTaken(user.username.clone()));
+        }
+        if taken_by_other(&self.emails, &user.email) {
+            return Err(AccountError::EmailTaken(user.email.clone()));
+        }
+        Ok(())
+    }
+
+    /// 放入已经检查过的账户
+    fn insert(&mut self, user: User) -> &User {
+        let id = user.id;
+        self.usernames.insert(fold(&user.username), id);
+        self.emails.insert(fold(&user.email), id);
+        self.users.insert(id, user);
+        &self.users[&id]
+    }
+
+    fn remove(&mut self, id: u32) -> Option<User> {
+        let user = self.users.remove(&id)?;
+        self.usernames.remove(&fold(&user.username));
+        self.emails.remove(&fold(&user.email));
+        Some(user)
+    }
+}
+
+// ========== 保存和读取 ==========
+
+impl Accounts {
+    /// 文件格式见模块开头的说明
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# rust-tutorial 账户\n");
+        text.push_str(&format!("next_id {}\n", self.next_id));
+        for user in self.iter() {
+            let state = if user.active { "active" } else { "inactive" };
+            text.push_str(&format!(
+                "{} {} {} {} {}\n",
+                user.id, user.username, user.email, user.sign_in_count, state
+            ));
+        }
+        text
+    }
+
+    /// 解析 to_text 的输出，并重新检查所有约束
+    pub fn parse(text: &str) -> Result<Accounts, ParseError> {
+        let mut accounts = Accounts::new();
+        let mut next_id: Option<(usize, u32)> = None;
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| ParseError { line, message };
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+
+            // 1. next_id N
+            if fields[0] == "next_id" {
+                let [_, value] = fields[..] else {
+                    return Err(error(String::from("next_id 后面应该只有一个数字")));
+                };
+                if next_id.is_some() {
+                    return Err(error(String::from("重复的 next_id")));
+                }
+                let value = value
+                    .parse()
+                    .map_err(|_| error(format!("next_id 不是有效的数字: {}", value)))?;
+                next_id = Some((line, value));
+                continue;
+            }
+
+            // 2. id 用户名 邮箱 登录次数 状态
+            let [id, username, email, count, state] = fields[..] else {
+                return Err(error(format!(
+                    "应该有 5 个字段（id 用户名 邮箱 登录次数 状态），实际有 {} 个",
+                    fields.len()
+                )));
+            };
+            let id: u32 = id
+                .parse()
+                .map_err(|_| error(format!("id 不是有效的数字: {}", id)))?;
+            let sign_in_count = count
+                .parse()
+                .map_err(|_| error(format!("登录次数不是有效的数字: {}", count)))?;
+            let active = match state {
+                "active" => true,
+                "inactive" => false,
+                other => {
+                    return Err(error(format!(
+                        "未知的状态 {}，只能是 active 或 inactive",
+                        other
+                    )));
+                }
+            };
+            // u32::MAX 留给 next_id 表示 id 已经用完
+            if id == u32::MAX {
+                return Err(error(format!("id 不能是 {}（u32::MAX）", id)));
+            }
+            if accounts.users.contains_key(&id) {
+                return Err(error(format!("重复的 id {}", id)));
+            }
+            let user = User {
+                id,
+                username: username.to_string(),
+                email: email.to_string(),
+                sign_in_count,
+                active,
+            };
+            accounts.check(&user).map_err(|e| error(e.to_string()))?;
+            accounts.insert(user);
+        }
+
+        // 没有 next_id 时接着最大的 id 往下编
+        let largest = accounts.users.keys().next_back().copied();
+        accounts.next_id = match (next_id, largest) {
+            (Some((line, value)), Some(largest)) if value <= largest => {
+                return Err(ParseError {
+                    line,
+                    message: format!("next_id {} 不大于已有的 id {}", value, largest),
+                });
+            }
+            (Some((_, value)), _) => value,
+            // 上面拒绝了 u32::MAX，加一不会溢出
+            (None, largest) => largest.map_or(1, |id| id + 1),
+        };
+        Ok(accounts)
+    }
+
+    /// 读取账户文件；文件不存在时返回空的仓库，格式错误时返回 InvalidData
+    pub fn load(path: &Path) -> io::Result<Accounts> {
+        match fs::read_to_string(path) {
+            Ok(text) => Accounts::parse(&text).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} {}", path.display(), e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Accounts::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 先写到临时文件再改名，写到一半失败时原来的文件保持完整
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, self.to_text())?;
+        fs::rename(&temporary, path)
+    }
+}
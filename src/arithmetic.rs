@@ -0,0 +1,370 @@
+// ============================================
+// 整数算术 (Arithmetic)
+// ============================================
+// 第 03 章的 divide 演示提前返回，第 11 章的 divide 演示自定义错误类型。
+// 两者都要面对同样的问题：除数为零时不能得到结果，i32::MIN / -1 的结果
+// 超出了 i32 的范围（debug 构建会 panic）。这个模块为所有整数类型提供统一的算术函数：
+//
+// - add、subtract、multiply、divide、remainder、pow 返回 Result，
+//   错误说明是哪种情况：除数为零、结果大于最大值（上溢）、小于最小值（下溢）、指数为负
+// - saturating 和 wrapping 中的同名函数在溢出时分别取边界值、按位数回绕，
+//   只有除数为零和指数为负仍然是错误
+//
+// 函数对 Int trait 泛型，Int 为 12 种整数类型实现，直接转发给标准库的
+// checked_*、saturating_*、wrapping_* 方法。章节代码也使用这个模块，
+// 单独编译章节时它会被一起复制（见 toolchain.rs），所以这里只依赖标准库。
+
+use std::fmt;
+
+// ========== 整数类型 ==========
+
+/// 整数类型共有的运算，由标准库中同名的方法实现
+pub trait Int: Copy + PartialOrd + fmt::Debug + fmt::Display {
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+    /// 类型名，例如 "i32"
+    const NAME: &'static str;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_div(self, rhs: Self) -> Self;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn wrapping_div(self, rhs: Self) -> Self;
+    fn wrapping_rem(self, rhs: Self) -> Self;
+    /// 非负数转换成 u128，负数返回 None
+    fn to_u128(self) -> Option<u128>;
+}
+
+macro_rules! impl_int {
+    ($($t:ident),*) => {
+        $(impl Int for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+            const MIN: $t = $t::MIN;
+            const MAX: $t = $t::MAX;
+            const NAME: &'static str = stringify!($t);
+
+            fn checked_add(self, rhs: $t) -> Option<$t> {
+                $t::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: $t) -> Option<$t> {
+                $t::checked_sub(self, rhs)
+            }
+            fn checked_mul(self, rhs: $t) -> Option<$t> {
+                $t::checked_mul(self, rhs)
+            }
+            fn checked_div(self, rhs: $t) -> Option<$t> {
+                $t::checked_div(self, rhs)
+            }
+            fn checked_rem(self, rhs: $t) -> Option<$t> {
+                $t::checked_rem(self, rhs)
+            }
+            fn checked_pow(self, exp: u32) -> Option<$t> {
+                $t::checked_pow(self, exp)
+            }
+            fn saturating_add(self, rhs: $t) -> $t {
+                $t::saturating_add(self, rhs)
+            }
+            fn saturating_sub(self, rhs: $t) -> $t {
+                $t::saturating_sub(self, rhs)
+            }
+            fn saturating_mul(self, rhs: $t) -> $t {
+                $t::saturating_mul(self, rhs)
+            }
+            fn saturating_div(self, rhs: $t) -> $t {
+                $t::saturating_div(self, rhs)
+            }
+            fn wrapping_add(self, rhs: $t) -> $t {
+                $t::wrapping_add(self, rhs)
+            }
+            fn wrapping_sub(self, rhs: $t) -> $t {
+                $t::wrapping_sub(self, rhs)
+            }
+            fn wrapping_mul(self, rhs: $t) -> $t {
+                $t::wrapping_mul(self, rhs)
+            }
+            fn wrapping_div(self, rhs: $t) -> $t {
+                $t::wrapping_div(self, rhs)
+            }
+            fn wrapping_rem(self, rhs: $t) -> $t {
+                $t::wrapping_rem(self, rhs)
+            }
+            fn to_u128(self) -> Option<u128> {
+                u128::try_from(self).ok()
+            }
+        })*
+    };
+}
+impl_int!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+// ========== 错误 ==========
+
+/// 算术运算
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Pow,
+}
+
+impl Op {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Subtract => "-",
+            Op::Multiply => "*",
+            Op::Divide => "/",
+            Op::Remainder => "%",
+            Op::Pow => "pow",
+        }
+    }
+}
+
+/// 算术运算失败的原因，带有参与运算的值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<T> {
+    /// 除数（或求余的除数）为零
+    DivisionByZero { op: Op, dividend: T },
+    /// 结果大于类型的最大值
+    Overflow { op: Op, lhs: T, rhs: T },
+    /// 结果小于类型的最小值
+    Underflow { op: Op, lhs: T, rhs: T },
+    /// pow 的指数为负数；整数的负数次幂不是整数
+    NegativeExponent { base: T, exponent: T },
+}
+
+impl<T: Int> Error<T> {
+    /// 溢出方向上的边界值，饱和运算用它作为结果
+    fn bound(&self) -> Option<T> {
+        match self {
+            Error::Overflow { .. } => Some(T::MAX),
+            Error::Underflow { .. } => Some(T::MIN),
+            _ => None,
+        }
+    }
+}
+
+/// 显示成表达式，例如 "2147483647 + 1" 或 "2.pow(-1)"
+fn expression<T: Int>(op: Op, lhs: T, rhs: T) -> String {
+    match op {
+        Op::Pow => format!("{}.pow({})", lhs, rhs),
+        _ => format!("{} {} {}", lhs, op.symbol(), rhs),
+    }
+}
+
+impl<T: Int> fmt::Display for Error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::DivisionByZero { op, dividend } => {
+                write!(f, "除数为零: {}", expression(op, dividend, T::ZERO))
+            }
+            Error::Overflow { op, lhs, rhs } => write!(
+                f,
+                "结果大于 {} 的最大值 {}: {}",
+                T::NAME,
+                T::MAX,
+                expression(op, lhs, rhs)
+            ),
+            Error::Underflow { op, lhs, rhs } => write!(
+                f,
+                "结果小于 {} 的最小值 {}: {}",
+                T::NAME,
+                T::MIN,
+                expression(op, lhs, rhs)
+            ),
+            Error::NegativeExponent { base, exponent } => {
+                write!(f, "指数不能为负数: {}", expression(Op::Pow, base, exponent))
+            }
+        }
+    }
+}
+
+impl<T: Int> std::error::Error for Error<T> {}
+
+/// 按结果的符号区分上溢和下溢
+fn out_of_range<T: Int>(op: Op, lhs: T, rhs: T, negative: bool) -> Error<T> {
+    if negative {
+        Error::Underflow { op, lhs, rhs }
+    } else {
+        Error::Overflow { op, lhs, rhs }
+    }
+}
+
+fn is_negative<T: Int>(n: T) -> bool {
+    n < T::ZERO
+}
+
+fn is_odd<T: Int>(n: T) -> bool {
+    n.wrapping_rem(T::ONE.wrapping_add(T::ONE)) != T::ZERO
+}
+
+/// 除数为零时返回错误
+fn check_divisor<T: Int>(op: Op, dividend: T, divisor: T) -> Result<(), Error<T>> {
+    if divisor == T::ZERO {
+        Err(Error::DivisionByZero { op, dividend })
+    } else {
+        Ok(())
+    }
+}
+
+// ========== 检查溢出的运算 ==========
+
+pub fn add<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+    a.checked_add(b)
+        .ok_or_else(|| out_of_range(Op::Add, a, b, is_negative(b)))
+}
+
+pub fn subtract<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+    a.checked_sub(b)
+        .ok_or_else(|| out_of_range(Op::Subtract, a, b, !is_negative(b)))
+}
+
+pub fn multiply<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+    a.checked_mul(b)
+        .ok_or_else(|| out_of_range(Op::Multiply, a, b, is_negative(a) != is_negative(b)))
+}
+
+/// 除法向零取整；唯一会溢出的是有符号类型的 MIN / -1
+pub fn divide<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+    check_divisor(Op::Divide, a, b)?;
+    a.checked_div(b).ok_or(Error::Overflow {
+        op: Op::Divide,
+        lhs: a,
+        rhs: b,
+    })
+}
+
+/// 余数的符号和被除数相同；MIN % -1 的数学结果是 0，
+/// 但和标准库一样报告为溢出（计算它需要先算出溢出的商）
+pub fn remainder<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+    check_divisor(Op::Remainder, a, b)?;
+    a.checked_rem(b).ok_or(Error::Overflow {
+        op: Op::Remainder,
+        lhs: a,
+        rhs: b,
+    })
+}
+
+/// `base` 的 `exponent` 次幂；指数和底数类型相同，负数指数是错误
+pub fn pow<T: Int>(base: T, exponent: T) -> Result<T, Error<T>> {
+    let Some(exp) = exponent.to_u128() else {
+        return Err(Error::NegativeExponent { base, exponent });
+    };
+    let result = match u32::try_from(exp) {
+        Ok(exp) => base.checked_pow(exp),
+        // 指数超过 u32 时，只有 0、1、-1 的幂不溢出
+        Err(_) if base == T::ZERO || base == T::ONE => Some(base),
+        Err(_) if is_negative(base) && base == T::ZERO.wrapping_sub(T::ONE) => {
+            Some(if is_odd(exponent) { base } else { T::ONE })
+        }
+        Err(_) => None,
+    };
+    result.ok_or_else(|| {
+        out_of_range(
+            Op::Pow,
+            base,
+            exponent,
+            is_negative(base) && is_odd(exponent),
+        )
+    })
+}
+
+// ========== 饱和运算 ==========
+
+/// 溢出时取类型的最小值或最大值
+pub mod saturating {
+    use super::{Error, Int, Op};
+
+    pub fn add<T: Int>(a: T, b: T) -> T {
+        a.saturating_add(b)
+    }
+
+    pub fn subtract<T: Int>(a: T, b: T) -> T {
+        a.saturating_sub(b)
+    }
+
+    pub fn multiply<T: Int>(a: T, b: T) -> T {
+        a.saturating_mul(b)
+    }
+
+    /// MIN / -1 得到 MAX
+    pub fn divide<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+        super::check_divisor(Op::Divide, a, b)?;
+        Ok(a.saturating_div(b))
+    }
+
+    /// MIN % -1 得到精确的结果 0
+    pub fn remainder<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+        super::wrapping::remainder(a, b)
+    }
+
+    pub fn pow<T: Int>(base: T, exponent: T) -> Result<T, Error<T>> {
+        match super::pow(base, exponent) {
+            Err(error) => error.bound().ok_or(error),
+            result => result,
+        }
+    }
+}
+
+// ========== 回绕运算 ==========
+
+/// 溢出时按类型的位数回绕（截断到低位），和 release 构建中的 + - * 一样
+pub mod wrapping {
+    use super::{Error, Int, Op};
+
+    pub fn add<T: Int>(a: T, b: T) -> T {
+        a.wrapping_add(b)
+    }
+
+    pub fn subtract<T: Int>(a: T, b: T) -> T {
+        a.wrapping_sub(b)
+    }
+
+    pub fn multiply<T: Int>(a: T, b: T) -> T {
+        a.wrapping_mul(b)
+    }
+
+    /// MIN / -1 回绕成 MIN
+    pub fn divide<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+        super::check_divisor(Op::Divide, a, b)?;
+        Ok(a.wrapping_div(b))
+    }
+
+    /// MIN % -1 得到 0
+    pub fn remainder<T: Int>(a: T, b: T) -> Result<T, Error<T>> {
+        super::check_divisor(Op::Remainder, a, b)?;
+        Ok(a.wrapping_rem(b))
+    }
+
+    /// 按二进制位逐次平方，指数可以超过 u32
+    pub fn pow<T: Int>(base: T, exponent: T) -> Result<T, Error<T>> {
+        let Some(mut exp) = exponent.to_u128() else {
+            return Err(Error::NegativeExponent { base, exponent });
+        };
+        let (mut result, mut base) = (T::ONE, base);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.wrapping_mul(base);
+            }
+            base = base.wrapping_mul(base);
+            exp >>= 1;
+        }
+        Ok(result)
+    }
+}
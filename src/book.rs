@@ -0,0 +1,527 @@
+// ============================================
+// 书籍生成 (Book)
+// ============================================
+// 把章节源码按照它们的固定结构（见 outline.rs）转换成一本书：
+//
+//     // 07. 枚举 (Enums)              -> # 07. 枚举 (Enums)
+//     // ========== 基本枚举 ========== -> ## 基本枚举
+//     // 1. 定义枚举                   -> ### 1. 定义枚举
+//     // 紧跟在标题后的注释             -> 正文
+//     其余代码                          -> 代码块
+//
+// 只有位于行首的标题和注释参与排版（main 函数体按去掉一层缩进计算），
+// 嵌套在 mod、impl 中的注释留在代码块里。看起来像代码的注释
+// （例如被注释掉的编译错误示例）也留在代码块里。
+//
+// 生成的目录可以直接交给 mdBook 构建，同时附带一个不依赖任何工具的
+// 单文件 HTML。可选地把每个小节运行时的输出嵌入到它的代码块下方。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::casts;
+use crate::chapters::Chapter;
+use crate::i18n;
+use crate::outline;
+use crate::toolchain::{self, Failure};
+
+/// 一章对应的页面
+#[derive(Clone)]
+pub struct Page {
+    pub chapter: &'static Chapter,
+    /// 横幅下方的简介
+    pub intro: Vec<String>,
+    pub blocks: Vec<Block>,
+}
+
+/// 页面中的一块内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// 小节标题（level 2）或编号标题（level 3）；`line` 是源码中的行号（从 0 开始）
+    Heading {
+        level: usize,
+        text: String,
+        line: usize,
+    },
+    /// 由注释组成的正文，每个元素是一行
+    Prose(Vec<String>),
+    /// 一段代码，保留原有的相对缩进
+    Code(Vec<String>),
+    /// 运行这一节代码得到的输出
+    Output(String),
+}
+
+impl Page {
+    /// 页面文件名，例如 "07_enums.md"
+    pub fn file_name(&self) -> String {
+        format!("{:02}_{}.md", self.chapter.number, self.chapter.slug)
+    }
+
+    /// 页面标题，例如 "07. 枚举 (Enums)"
+    pub fn title(&self) -> String {
+        format!("{:02}. {}", self.chapter.number, self.chapter.title())
+    }
+}
+
+// ========== 解析章节 ==========
+
+/// 章节 main 函数的首行和末行（都是行号），没有 main 时返回 None
+fn main_lines(lines: &[&str]) -> Option<(usize, usize)> {
+    let open = lines.iter().position(|line| *line == "pub fn main() {")?;
+    let close = open + lines[open..].iter().position(|line| *line == "}")?;
+    Some((open, close))
+}
+
+/// 被注释掉的代码：含有分号、以花括号结尾、带缩进，
+/// 或者以关键字开头并且带有括号、等号之类的符号（"impl 块用于定义方法" 是正文）
+fn looks_like_code(comment: &str) -> bool {
+    const KEYWORDS: [&str; 7] = ["fn ", "let ", "use ", "mod ", "impl ", "extern ", "async "];
+    comment.contains(';')
+        || comment.starts_with('}')
+        || comment.ends_with(['{', '}'])
+        || comment.starts_with(' ')
+        || (KEYWORDS.iter().any(|keyword| comment.starts_with(keyword))
+            && comment.contains(['(', '=', ':', '{']))
+}
+
+/// 把一章的源码解析成页面
+pub fn page(chapter: &'static Chapter) -> Page {
+    let lines: Vec<&str> = chapter.source.lines().collect();
+    let header = lines
+        .iter()
+        .position(|line| !line.trim_start().starts_with("//"))
+        .unwrap_or(lines.len());
+
+    // 横幅是第一个 "// ====" 到第二个 "// ====" 之间的部分，之后是简介
+    let intro = lines[..header]
+        .iter()
+        .map(|line| line.trim_start_matches("//").trim())
+        .skip_while(|line| !line.starts_with('='))
+        .skip(1)
+        .skip_while(|line| !line.starts_with('='))
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+
+    let main = main_lines(&lines);
+    let mut builder = Builder::default();
+    let mut index = header;
+
+    while index < lines.len() {
+        if main.is_some_and(|(open, close)| index == open || index == close) {
+            builder.flush();
+            index += 1;
+            continue;
+        }
+
+        // main 函数体中的代码去掉一层缩进
+        let in_main = main.is_some_and(|(open, close)| open < index && index < close);
+        let text = if in_main {
+            lines[index].strip_prefix("    ").unwrap_or(lines[index])
+        } else {
+            lines[index]
+        };
+
+        // 只有行首的标题和注释参与排版
+        let top = !text.starts_with(' ');
+        if text.trim().is_empty() {
+            builder.blank();
+        } else if let Some(title) = outline::group_title(text).filter(|_| top) {
+            builder.heading(2, title.to_string(), index);
+        } else if let Some((number, title)) = outline::numbered(text).filter(|_| top) {
+            builder.heading(3, format!("{}. {}", number, title), index);
+        } else if let Some(comment) = text.strip_prefix("//").filter(|_| builder.prose_allowed) {
+            let comment = comment.strip_prefix(' ').unwrap_or(comment);
+            if looks_like_code(comment) {
+                builder.code(text);
+            } else {
+                builder.prose(comment);
+            }
+        } else {
+            builder.code(text);
+        }
+        index += 1;
+    }
+    builder.flush();
+
+    Page {
+        chapter,
+        intro,
+        blocks: builder.blocks,
+    }
+}
+
+/// 逐行构建页面内容
+#[derive(Default)]
+struct Builder {
+    blocks: Vec<Block>,
+    prose: Vec<String>,
+    code: Vec<String>,
+    /// 标题或空行之后、代码之前的行首注释才算正文
+    prose_allowed: bool,
+}
+
+impl Builder {
+    fn flush(&mut self) {
+        if !self.prose.is_empty() {
+            self.blocks
+                .push(Block::Prose(std::mem::take(&mut self.prose)));
+        }
+        while self.code.last().is_some_and(|line| line.trim().is_empty()) {
+            self.code.pop();
+        }
+        if !self.code.is_empty() {
+            self.blocks
+                .push(Block::Code(std::mem::take(&mut self.code)));
+        }
+        self.prose_allowed = true;
+    }
+
+    fn heading(&mut self, level: usize, text: String, line: usize) {
+        self.flush();
+        self.blocks.push(Block::Heading { level, text, line });
+    }
+
+    fn blank(&mut self) {
+        if !self.prose.is_empty() {
+            self.flush();
+        } else if self.code.is_empty() {
+            self.prose_allowed = true;
+        } else {
+            self.code.push(String::new());
+            self.prose_allowed = true;
+        }
+    }
+
+    fn prose(&mut self, line: &str) {
+        // 正文打断了代码：先结束前面的代码块
+        if !self.code.is_empty() {
+            self.flush();
+        }
+        self.prose.push(line.to_string());
+    }
+
+    fn code(&mut self, line: &str) {
+        if !self.prose.is_empty() {
+            self.flush();
+        }
+        self.code.push(line.to_string());
+        self.prose_allowed = false;
+    }
+}
+
+// ========== 小节输出 ==========
+
+// 插入到每个标题前的标记行的前缀，后面跟着标题的行号
+const MARKER: &str = "\u{1}book-section:";
+
+/// 运行一章，返回 main 函数中每个标题（按行号）之后、下一个标题之前打印的输出
+///
+/// 做法是在 main 函数体中每个小节和编号标题之前插入一行打印标记的代码，
+/// 用 toolchain 单独编译运行，再按标记切分输出。
+pub fn section_output(chapter: &Chapter) -> io::Result<BTreeMap<usize, String>> {
+    let lines: Vec<&str> = chapter.source.lines().collect();
+    let Some((open, close)) = main_lines(&lines) else {
+        return Ok(BTreeMap::new());
+    };
+
+    let mut source = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let heading = line.strip_prefix("    ").is_some_and(|text| {
+            !text.starts_with(' ')
+                && (outline::group_title(text).is_some() || outline::numbered(text).is_some())
+        });
+        if open < index && index < close && heading {
+            source.push_str(&format!(
+                "    println!(\"{{}}{{}}\", {:?}, {});\n",
+                MARKER, index
+            ));
+        }
+        source.push_str(line);
+        source.push('\n');
+    }
+
+    let dir = toolchain::scratch_dir("book")?;
+    let file = dir.join(chapter.file_name());
+    let result =
+        fs::write(&file, source).and_then(|()| toolchain::run_chapter(chapter.slug, &file));
+    fs::remove_dir_all(&dir)?;
+    let output = match result? {
+        Ok(output) => output,
+        Err(Failure::Compile(message) | Failure::Run(message)) => {
+            return Err(io::Error::other(format!(
+                "无法运行 {}:\n{}",
+                chapter.file_name(),
+                message
+            )));
+        }
+    };
+
+    let mut sections: BTreeMap<usize, String> = BTreeMap::new();
+    let mut current = None;
+    for line in output.lines() {
+        if let Some(index) = line.strip_prefix(MARKER) {
+            current = index.parse().ok();
+        } else if let Some(index) = current {
+            let text = sections.entry(index).or_default();
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    Ok(sections)
+}
+
+/// 把 [`section_output`] 的结果插入页面：每个标题的输出放在下一个标题之前
+pub fn embed_output(page: &mut Page, outputs: &BTreeMap<usize, String>) {
+    let mut blocks = Vec::with_capacity(page.blocks.len());
+    let mut pending: Option<&String> = None;
+
+    for block in page.blocks.drain(..) {
+        if let Block::Heading { line, .. } = &block {
+            if let Some(output) = pending.take() {
+                blocks.push(Block::Output(output.clone()));
+            }
+            pending = outputs.get(line);
+        }
+        blocks.push(block);
+    }
+    if let Some(output) = pending {
+        blocks.push(Block::Output(output.clone()));
+    }
+    page.blocks = blocks;
+}
+
+// ========== Markdown ==========
+
+/// 把正文行拼接起来；以 "- " 开头的行组成列表，前面需要一个空行
+fn prose_lines(lines: &[String]) -> Vec<String> {
+    let mut result = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let starts_list =
+            line.starts_with("- ") && index > 0 && !lines[index - 1].starts_with("- ");
+        if starts_list {
+            result.push(String::new());
+        }
+        result.push(line.clone());
+    }
+    result
+}
+
+/// 页面的 Markdown
+///
+/// 代码中的 t! 调用后面会附上当前语言的消息模板，见 i18n::annotate。
+pub fn markdown(page: &Page) -> String {
+    let mut out = format!("# {}\n\n", page.title());
+    if !page.intro.is_empty() {
+        out.push_str(&page.intro.join("\n"));
+        out.push_str("\n\n");
+    }
+
+    for block in &page.blocks {
+        match block {
+            Block::Heading { level, text, .. } => {
+                out.push_str(&format!("{} {}\n\n", "#".repeat(*level), text));
+            }
+            Block::Prose(lines) => {
+                let lines = prose_lines(lines);
+                for (index, line) in lines.iter().enumerate() {
+                    out.push_str(line);
+                    // 行尾两个空格表示换行，保留注释原有的分行
+                    let next = lines.get(index + 1);
+                    if !line.is_empty() && next.is_some_and(|next| !next.is_empty()) {
+                        out.push_str("  ");
+                    }
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            Block::Code(lines) => {
+                out.push_str("```rust\n");
+                for line in lines {
+                    out.push_str(&i18n::annotate(i18n::lang(), line));
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            Block::Output(output) => {
+                out.push_str("输出:\n\n```text\n");
+                out.push_str(output);
+                out.push_str("```\n\n");
+            }
+        }
+    }
+    out
+}
+
+/// 附录页面的文件名
+pub const CASTS_FILE: &str = "casts.md";
+
+/// mdBook 的目录文件 SUMMARY.md；附录放在分隔线之后，不编号
+pub fn summary(pages: &[Page]) -> String {
+    let mut out = String::from("# Summary\n\n");
+    for page in pages {
+        out.push_str(&format!("- [{}]({})\n", page.title(), page.file_name()));
+    }
+    out.push_str(&format!("\n---\n\n[附录：as 类型转换]({})\n", CASTS_FILE));
+    out
+}
+
+// ========== HTML ==========
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "\
+body { max-width: 52em; margin: 2em auto; padding: 0 1em; line-height: 1.6;
+       font-family: system-ui, sans-serif; color: #222; }
+nav ol { columns: 2; }
+pre { background: #f6f8fa; padding: 0.8em 1em; overflow-x: auto; border-radius: 4px; }
+pre.output { background: #fffbe6; border-left: 4px solid #e0c050; }
+h1 { border-bottom: 2px solid #ddd; margin-top: 2.5em; }
+a { color: #0550ae; }";
+
+/// 所有页面合成的单文件 HTML，不依赖任何外部资源
+pub fn html(pages: &[Page]) -> String {
+    let lang = i18n::lang();
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Rust 教程</title>\n<style>\n{}\n</style>\n</head>\n<body>\n",
+        lang, STYLE
+    );
+
+    out.push_str("<nav>\n<h2>目录</h2>\n<ol>\n");
+    for page in pages {
+        out.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            page.chapter.slug,
+            escape(&page.title())
+        ));
+    }
+    out.push_str("</ol>\n</nav>\n");
+
+    for page in pages {
+        out.push_str(&format!(
+            "\n<section id=\"{}\">\n<h1>{}</h1>\n",
+            page.chapter.slug,
+            escape(&page.title())
+        ));
+        if !page.intro.is_empty() {
+            let intro: Vec<String> = page.intro.iter().map(|line| escape(line)).collect();
+            out.push_str(&format!("<p>{}</p>\n", intro.join("<br>\n")));
+        }
+
+        for block in &page.blocks {
+            match block {
+                Block::Heading { level, text, line } => {
+                    out.push_str(&format!(
+                        "<h{0} id=\"{1}-{2}\">{3}</h{0}>\n",
+                        level,
+                        page.chapter.slug,
+                        line + 1,
+                        escape(text)
+                    ));
+                }
+                Block::Prose(lines) => html_prose(&mut out, lines),
+                Block::Code(lines) => {
+                    out.push_str("<pre><code class=\"language-rust\">");
+                    for line in lines {
+                        out.push_str(&escape(&i18n::annotate(lang, line)));
+                        out.push('\n');
+                    }
+                    out.push_str("</code></pre>\n");
+                }
+                Block::Output(output) => {
+                    out.push_str("<pre class=\"output\">");
+                    out.push_str(&escape(output));
+                    out.push_str("</pre>\n");
+                }
+            }
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// 正文：普通行组成段落，以 "- " 开头的行组成列表
+fn html_prose(out: &mut String, lines: &[String]) {
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut items: Vec<String> = Vec::new();
+
+    let flush = |out: &mut String, paragraph: &mut Vec<String>, items: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", paragraph.join("<br>\n")));
+            paragraph.clear();
+        }
+        if !items.is_empty() {
+            out.push_str("<ul>\n");
+            for item in items.drain(..) {
+                out.push_str(&format!("<li>{}</li>\n", item));
+            }
+            out.push_str("</ul>\n");
+        }
+    };
+
+    for line in lines {
+        match line.strip_prefix("- ") {
+            Some(item) => {
+                if !paragraph.is_empty() {
+                    flush(out, &mut paragraph, &mut items);
+                }
+                items.push(escape(item));
+            }
+            None => {
+                if !items.is_empty() {
+                    flush(out, &mut paragraph, &mut items);
+                }
+                paragraph.push(escape(line));
+            }
+        }
+    }
+    flush(out, &mut paragraph, &mut items);
+}
+
+// ========== 写入文件 ==========
+
+/// 在 `dir` 中写入 mdBook 项目（book.toml、src/SUMMARY.md、每章一页和附录）
+/// 以及单文件的 rust-tutorial.html，返回写入的文件
+pub fn write(dir: &Path, pages: &[Page]) -> io::Result<Vec<PathBuf>> {
+    let src = dir.join("src");
+    fs::create_dir_all(&src)?;
+
+    let mut files = vec![
+        (
+            dir.join("book.toml"),
+            format!(
+                "[book]\ntitle = \"Rust 教程\"\nlanguage = \"{}\"\nsrc = \"src\"\n",
+                i18n::lang()
+            ),
+        ),
+        (src.join("SUMMARY.md"), summary(pages)),
+    ];
+    for page in pages {
+        files.push((src.join(page.file_name()), markdown(page)));
+    }
+    files.push((src.join(CASTS_FILE), casts::markdown()));
+    files.push((dir.join("rust-tutorial.html"), html(pages)));
+
+    for (path, content) in &files {
+        fs::write(path, content)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
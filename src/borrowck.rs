@@ -0,0 +1,2184 @@
+// ============================================
+// 所有权和借用检查模拟器 (Borrow Checker)
+// ============================================
+// 第 05 章列出了所有权规则：每个值有且只有一个所有者，所有者离开作用域时值被丢弃；
+// 同一时刻要么只有一个可变引用，要么只有任意多个不可变引用；引用必须总是有效的。
+// 这个模块按顺序执行一段只有直线代码的 Rust 脚本（没有 if 和循环，用到时报告
+// “模拟器不支持的语法”，不带 rustc 的错误编号），跟踪每个变量的状态和每一次借用，
+// 用 rustc 的错误编号报告违反规则的地方：
+//
+// - E0382  使用已经移动的值（use / borrow of moved value）
+// - E0499  同时存在两个可变借用
+// - E0502  可变借用和不可变借用同时存在
+// - E0505  值被借用时移出
+// - E0597  被借用的值先于引用离开作用域（does not live long enough）
+// - 以及 E0381、E0384、E0425、E0503、E0506、E0507、E0594、E0596、E0061、E0106
+//
+// 和 rustc 一样采用非词法生命周期（NLL）：借用从创建开始，到引用最后一次被使用为止，
+// 而不是到作用域结束。所以第 05 章中 r1、r2 最后一次使用之后，就可以再创建 &mut s。
+//
+// 支持的语法：
+//
+//     let s = String::from("hello");   let mut v = vec![1, 2];   let x = 5;
+//     let s2 = s;   let s3 = s2.clone();   let r = &s3;   let m = &mut v;   &s[0..5]
+//     let r;   r = &x;   x += 1;   *m = 3;   { ... }
+//     takes_ownership(s2);   change(&mut s);   s.push_str("!");   println!("{}", r);
+//     fn first_word(s: &str) -> &str { ... }   函数体会被跳过，只检查签名和调用
+//
+// 第 05 章的函数（takes_ownership、makes_copy、gives_ownership、takes_and_gives_back、
+// calculate_length、change）以及 first_word 和 drop 已经预先声明。
+// 返回引用的函数按生命周期省略规则处理：返回值借用的是唯一的那个引用参数。
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::unicode;
+
+// ========== 错误 ==========
+
+/// 源码中的一段（行、列从 1 开始，长度按字符计）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+    /// 代码可能是正确的 Rust，只是模拟器不支持（例如 if 和循环）；
+    /// 这种错误没有对应的 rustc 错误，不能当成代码的问题
+    pub unsupported: bool,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.unsupported {
+            write!(
+                f,
+                "第 {} 行: 模拟器不支持的语法: {}",
+                self.line, self.message
+            )
+        } else {
+            write!(f, "第 {} 行: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 错误中标出的一段代码；主标签用 ^ 标出，其他的用 -
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub primary: bool,
+    pub text: String,
+}
+
+/// 违反所有权或借用规则的地方，措辞与 rustc 一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub code: &'static str,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub note: Option<String>,
+    /// 出错的语句是第几步（从 0 开始）
+    pub step: usize,
+}
+
+impl Violation {
+    fn new(code: &'static str, message: String) -> Violation {
+        Violation {
+            code,
+            message,
+            labels: Vec::new(),
+            note: None,
+            step: 0,
+        }
+    }
+
+    fn primary(mut self, span: Span, text: String) -> Violation {
+        self.labels.push(Label {
+            span,
+            primary: true,
+            text,
+        });
+        self
+    }
+
+    fn secondary(mut self, span: Option<Span>, text: String) -> Violation {
+        if let Some(span) = span {
+            self.labels.push(Label {
+                span,
+                primary: false,
+                text,
+            });
+        }
+        self
+    }
+
+    fn note(mut self, note: String) -> Violation {
+        self.note = Some(note);
+        self
+    }
+
+    /// 主标签所在的行
+    pub fn line(&self) -> usize {
+        self.labels
+            .iter()
+            .find(|label| label.primary)
+            .map_or(0, |label| label.span.line)
+    }
+
+    /// 像 rustc 一样列出涉及的代码行，并在下面标出每个标签
+    pub fn render(&self, source: &str, file_name: &str) -> String {
+        let mut out = format!("{}\n", self);
+        let mut lines: Vec<usize> = self.labels.iter().map(|label| label.span.line).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        let width = lines.last().map_or(1, |line| line.to_string().len());
+        let pad = " ".repeat(width);
+        if let Some(primary) = self.labels.iter().find(|label| label.primary) {
+            out.push_str(&format!(
+                "{}--> {}:{}:{}\n",
+                pad, file_name, primary.span.line, primary.span.column
+            ));
+        }
+        out.push_str(&format!("{} |\n", pad));
+        let mut previous = None;
+        for line in lines {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                out.push_str("...\n");
+            }
+            previous = Some(line);
+            let code = source.lines().nth(line - 1).unwrap_or("");
+            out.push_str(&format!("{:>width$} | {}\n", line, code, width = width));
+            let mut labels: Vec<&Label> = self
+                .labels
+                .iter()
+                .filter(|label| label.span.line == line)
+                .collect();
+            labels.sort_by_key(|label| label.span.column);
+            for label in labels {
+                let before: String = code.chars().take(label.span.column - 1).collect();
+                let mark = if label.primary { "^" } else { "-" };
+                out.push_str(&format!(
+                    "{} | {}{} {}\n",
+                    pad,
+                    " ".repeat(unicode::width(&before)),
+                    mark.repeat(label.span.len.max(1)),
+                    label.text
+                ));
+            }
+        }
+        if let Some(note) = &self.note {
+            out.push_str(&format!("{} |\n{} = note: {}\n", pad, pad, note));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error[{}]: {}", self.code, self.message)
+    }
+}
+
+// ========== 词法分析 ==========
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    /// 数字字面量的类型，例如 "i32"、"f64"、"u8"
+    Number(&'static str),
+    Str(String),
+    Char,
+    Lifetime,
+    Punct(&'static str),
+    Eof,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    span: Span,
+    /// 在源码中的字节范围
+    start: usize,
+    end: usize,
+}
+
+// 较长的符号放在前面，优先匹配
+const PUNCTS: [&str; 40] = [
+    "..=", "::", "->", "=>", "..", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=",
+    "%=", "+", "-", "*", "/", "%", "=", "<", ">", "!", "(", ")", "{", "}", "[", "]", ",", ";", ":",
+    ".", "&", "#", "?", "|", "@",
+];
+
+const NUMBER_TYPES: [&str; 14] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64",
+];
+
+fn lex(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    while let Some(&(start, c)) = chars.peek() {
+        let span_start = (line, column);
+        // 取出满足条件的一串字符
+        let mut take = |pred: &mut dyn FnMut(char) -> bool| {
+            let mut text = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if !pred(c) {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+                if c == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+            }
+            text
+        };
+
+        let rest = &source[start..];
+        let tok = if c.is_whitespace() {
+            take(&mut |c| c.is_whitespace());
+            continue;
+        } else if rest.starts_with("//") {
+            take(&mut |c| c != '\n');
+            continue;
+        } else if c.is_ascii_digit() {
+            let mut text = take(&mut |c| c.is_ascii_alphanumeric() || c == '_');
+            let bytes = source.as_bytes();
+            let end = start + text.len();
+            let fraction = bytes.get(end) == Some(&b'.')
+                && bytes.get(end + 1).is_some_and(|b| b.is_ascii_digit());
+            if fraction {
+                let mut first = true;
+                text.push_str(&take(&mut |c| {
+                    let ok = (first && c == '.') || c.is_ascii_alphanumeric() || c == '_';
+                    first = false;
+                    ok
+                }));
+            }
+            let suffix = NUMBER_TYPES.iter().find(|ty| text.ends_with(*ty));
+            Tok::Number(match suffix {
+                Some(ty) => ty,
+                None if fraction => "f64",
+                None => "i32",
+            })
+        } else if c.is_alphabetic() || c == '_' {
+            Tok::Ident(take(&mut |c| c.is_alphanumeric() || c == '_'))
+        } else if c == '"' {
+            let mut escaped = false;
+            let mut closed = 0;
+            let text = take(&mut |c| {
+                let keep = closed < 2;
+                if !escaped && c == '"' {
+                    closed += 1;
+                }
+                escaped = !escaped && c == '\\';
+                keep
+            });
+            if closed < 2 {
+                return Err(ParseError {
+                    line: span_start.0,
+                    message: String::from("unterminated double quote string"),
+                    unsupported: false,
+                });
+            }
+            Tok::Str(text[1..text.len() - 1].to_string())
+        } else if c == '\'' {
+            // 'a' 和 '\n' 是字符，'a 是生命周期
+            let is_char = rest.chars().nth(2) == Some('\'')
+                || (rest[1..].starts_with('\\') && rest.chars().nth(3) == Some('\''));
+            if is_char {
+                let n = if rest.chars().nth(2) == Some('\'') {
+                    3
+                } else {
+                    4
+                };
+                let mut count = 0;
+                take(&mut |_| {
+                    count += 1;
+                    count <= n
+                });
+                Tok::Char
+            } else {
+                let mut first = true;
+                take(&mut |c| {
+                    let ok = first || c.is_alphanumeric() || c == '_';
+                    first = false;
+                    ok
+                });
+                Tok::Lifetime
+            }
+        } else if let Some(punct) = PUNCTS.iter().find(|p| rest.starts_with(*p)) {
+            let mut count = 0;
+            take(&mut |_| {
+                count += 1;
+                count <= punct.len()
+            });
+            Tok::Punct(punct)
+        } else {
+            return Err(ParseError {
+                line,
+                message: format!("unknown start of token: {}", c),
+                unsupported: false,
+            });
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            tok,
+            span: Span {
+                line: span_start.0,
+                column: span_start.1,
+                len: source[start..end].chars().count(),
+            },
+            start,
+            end,
+        });
+    }
+    tokens.push(Token {
+        tok: Tok::Eof,
+        span: Span {
+            line,
+            column,
+            len: 0,
+        },
+        start: source.len(),
+        end: source.len(),
+    });
+    Ok(tokens)
+}
+
+// ========== 语法树 ==========
+
+/// 代码中出现的一个变量名，以及它解析到的绑定
+#[derive(Debug, Clone)]
+struct Name {
+    text: String,
+    binding: Option<usize>,
+    span: Span,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    /// 字面量等不涉及变量的值，只记录类型
+    Value(String),
+    Place(Name),
+    /// *r
+    Deref(Name),
+    /// &x、&mut x、&x[a..b]，span 从 & 开始
+    Borrow {
+        name: Name,
+        mutable: bool,
+        slice: bool,
+        span: Span,
+    },
+    /// &<表达式>，借用一个临时值
+    BorrowTemp(Box<Expr>, bool),
+    /// x[i]
+    Index(Name),
+    Method {
+        receiver: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+    },
+    Call {
+        name: String,
+        span: Span,
+        args: Vec<Expr>,
+    },
+    Macro {
+        name: String,
+        args: Vec<Expr>,
+    },
+    /// String::from(...)、Vec::new()、Some(...) 等构造出新值的调用
+    Construct {
+        ty: String,
+        args: Vec<Expr>,
+    },
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Tuple(Vec<Expr>),
+    Array(Vec<Expr>),
+}
+
+/// 函数签名；参数和返回值只记录类型
+#[derive(Debug, Clone)]
+struct Signature {
+    params: Vec<String>,
+    ret: Option<String>,
+    /// 返回类型中写了生命周期（例如 &'a str），不需要省略规则
+    ret_lifetime: bool,
+    ret_span: Span,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Let {
+        name: Name,
+        ty: Option<String>,
+        init: Option<Expr>,
+    },
+    /// x = e、x += e、*r = e
+    Assign {
+        target: Name,
+        deref: bool,
+        compound: bool,
+        value: Expr,
+    },
+    Expr(Expr),
+    Open,
+    Close,
+    Fn(Signature),
+}
+
+#[derive(Debug, Clone)]
+struct Statement {
+    kind: Kind,
+    span: Span,
+    /// 语句的源码，多行时合成一行
+    text: String,
+}
+
+/// 一个变量的声明
+#[derive(Debug, Clone)]
+struct Decl {
+    name: String,
+    mutable: bool,
+    depth: usize,
+    span: Span,
+}
+
+// ========== 语法分析 ==========
+
+/// 预先声明的函数
+const PRELUDE: &str = "
+fn takes_ownership(some_string: String) {}
+fn makes_copy(some_integer: i32) {}
+fn gives_ownership() -> String {}
+fn takes_and_gives_back(a_string: String) -> String {}
+fn calculate_length(s: &String) -> usize {}
+fn change(some_string: &mut String) {}
+fn first_word(s: &str) -> &str {}
+fn drop<T>(_x: T) {}
+";
+
+const BINARY: [&str; 13] = [
+    "+", "-", "*", "/", "%", "==", "!=", "<", ">", "<=", ">=", "&&", "||",
+];
+
+/// 出现在表达式位置时说明用到了模拟器不支持的语法，而不是未定义的变量
+const UNSUPPORTED_KEYWORDS: [&str; 22] = [
+    "if", "else", "match", "while", "loop", "for", "in", "break", "continue", "return", "struct",
+    "enum", "impl", "trait", "mod", "use", "const", "static", "type", "unsafe", "async", "move",
+];
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    i: usize,
+    /// 每一层作用域中声明的变量（Decl 的下标）
+    scopes: Vec<Vec<usize>>,
+    decls: Vec<Decl>,
+    /// 每个变量被使用的位置：(第几条语句, 位置)
+    uses: Vec<Vec<(usize, Span)>>,
+    functions: HashMap<String, Signature>,
+    statements: Vec<Statement>,
+}
+
+type Parsed<T> = Result<T, ParseError>;
+
+impl Parser<'_> {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.i].tok
+    }
+
+    fn peek_at(&self, n: usize) -> &Tok {
+        &self.tokens[(self.i + n).min(self.tokens.len() - 1)].tok
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.i].span
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.tokens[self.i].clone();
+        if self.i < self.tokens.len() - 1 {
+            self.i += 1;
+        }
+        token
+    }
+
+    fn is(&self, punct: &str) -> bool {
+        *self.peek() == Tok::Punct(punct_str(punct))
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = self.is(punct);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            line: self.span().line,
+            message,
+            unsupported: false,
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Parsed<()> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`, found {}", punct, self.describe())))
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.peek() {
+            Tok::Eof => String::from("end of input"),
+            _ => {
+                let token = &self.tokens[self.i];
+                format!("`{}`", &self.source[token.start..token.end])
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Parsed<(String, Span)> {
+        match self.peek().clone() {
+            Tok::Ident(name) => Ok((name, self.bump().span)),
+            _ => Err(self.error(format!("expected identifier, found {}", self.describe()))),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(name) if name == keyword)
+    }
+
+    /// 按名字查找最内层的绑定，并记下这次使用
+    fn resolve(&mut self, text: String, span: Span) -> Name {
+        let binding = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .copied()
+            .find(|&id| self.decls[id].name == text);
+        if let Some(id) = binding {
+            self.uses[id].push((self.statements.len(), span));
+        }
+        Name {
+            text,
+            binding,
+            span,
+        }
+    }
+
+    fn declare(&mut self, text: &str, mutable: bool, span: Span) -> Name {
+        let id = self.decls.len();
+        self.decls.push(Decl {
+            name: text.to_string(),
+            mutable,
+            depth: self.scopes.len() - 1,
+            span,
+        });
+        self.uses.push(Vec::new());
+        self.scopes.last_mut().unwrap().push(id);
+        Name {
+            text: text.to_string(),
+            binding: Some(id),
+            span,
+        }
+    }
+
+    fn program(&mut self) -> Parsed<()> {
+        while *self.peek() != Tok::Eof {
+            if self.eat(";") {
+                continue;
+            }
+            let first = self.i;
+            let kind = self.statement()?;
+            self.eat(";");
+            let (start, end) = (self.tokens[first].start, self.tokens[self.i - 1].end);
+            let text: Vec<&str> = self.source[start..end].split_whitespace().collect();
+            self.statements.push(Statement {
+                kind,
+                span: self.tokens[first].span,
+                text: text.join(" "),
+            });
+        }
+        if self.scopes.len() > 1 {
+            return Err(self.error(String::from("this file contains an unclosed delimiter `{`")));
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self) -> Parsed<Kind> {
+        if self.eat("{") {
+            self.scopes.push(Vec::new());
+            return Ok(Kind::Open);
+        }
+        if self.is("}") {
+            if self.scopes.len() == 1 {
+                return Err(self.error(String::from("unexpected closing delimiter: `}`")));
+            }
+            self.bump();
+            self.scopes.pop();
+            return Ok(Kind::Close);
+        }
+        if self.is_keyword("pub") && matches!(self.peek_at(1), Tok::Ident(name) if name == "fn") {
+            self.bump();
+        }
+        if self.is_keyword("fn") {
+            return self.function();
+        }
+        if self.is_keyword("let") {
+            self.bump();
+            let mutable = self.is_keyword("mut");
+            if mutable {
+                self.bump();
+            }
+            let (text, span) = self.ident()?;
+            let ty = if self.eat(":") {
+                Some(self.ty(&["=", ";"])?.0)
+            } else {
+                None
+            };
+            // 先解析初始值，`let s = s;` 中右边的 s 是之前的绑定
+            let init = if self.eat("=") {
+                Some(self.expr()?)
+            } else {
+                None
+            };
+            let name = self.declare(&text, mutable, span);
+            return Ok(Kind::Let { name, ty, init });
+        }
+
+        // 赋值：x = e、x += e、*r = e
+        let deref = self.is("*");
+        let offset = usize::from(deref);
+        if let (Tok::Ident(text), Tok::Punct(op)) = (self.peek_at(offset), self.peek_at(offset + 1))
+            && ["=", "+=", "-=", "*=", "/=", "%="].contains(op)
+        {
+            let (text, compound) = (text.clone(), *op != "=");
+            self.i += offset;
+            let span = self.bump().span;
+            self.bump();
+            let value = self.expr()?;
+            // 给变量赋值不算使用，解引用和复合赋值要读取它
+            let target = if deref || compound {
+                self.resolve(text, span)
+            } else {
+                let mut name = self.resolve(text, span);
+                if let Some(id) = name.binding {
+                    self.uses[id].pop();
+                } else {
+                    name.binding = None;
+                }
+                name
+            };
+            return Ok(Kind::Assign {
+                target,
+                deref,
+                compound,
+                value,
+            });
+        }
+        Ok(Kind::Expr(self.expr()?))
+    }
+
+    /// fn 名字<泛型>(参数: 类型, ...) -> 类型 { 函数体 }，函数体被跳过
+    fn function(&mut self) -> Parsed<Kind> {
+        self.bump();
+        let (name, _) = self.ident()?;
+        if self.eat("<") {
+            self.skip_until(">")?;
+        }
+        self.expect("(")?;
+        let mut params = Vec::new();
+        while !self.eat(")") {
+            if self.is_keyword("mut") {
+                self.bump();
+            }
+            self.ident()?;
+            self.expect(":")?;
+            params.push(self.ty(&[",", ")"])?.0);
+            if !self.is(")") {
+                self.expect(",")?;
+            }
+        }
+        let (ret, ret_lifetime, ret_span) = if self.eat("->") {
+            let start = self.span();
+            let (ty, lifetime) = self.ty(&["{", ";"])?;
+            let len = ty.chars().count() + if lifetime { 3 } else { 0 };
+            (Some(ty), lifetime, Span { len, ..start })
+        } else {
+            (None, false, self.span())
+        };
+        if self.eat("{") {
+            self.skip_until("}")?;
+        }
+        let signature = Signature {
+            params,
+            ret,
+            ret_lifetime,
+            ret_span,
+        };
+        self.functions.insert(name, signature.clone());
+        Ok(Kind::Fn(signature))
+    }
+
+    /// 跳过成对的括号，直到与开头配对的 close
+    fn skip_until(&mut self, close: &str) -> Parsed<()> {
+        let open = match close {
+            "}" => "{",
+            ")" => "(",
+            "]" => "[",
+            _ => "<",
+        };
+        let mut depth = 1;
+        while depth > 0 {
+            if *self.peek() == Tok::Eof {
+                return Err(self.error(format!("expected `{}`, found end of input", close)));
+            }
+            if self.is(open) {
+                depth += 1;
+            } else if self.is(close) {
+                depth -= 1;
+            }
+            self.bump();
+        }
+        Ok(())
+    }
+
+    /// 读取一个类型，直到最外层遇到 stops 中的符号；生命周期被去掉，
+    /// 返回类型和其中有没有写生命周期
+    fn ty(&mut self, stops: &[&str]) -> Parsed<(String, bool)> {
+        let mut text = String::new();
+        let mut depth = 0;
+        let mut lifetime = false;
+        let mut word = false;
+        loop {
+            let token = &self.tokens[self.i];
+            match &token.tok {
+                Tok::Eof => break,
+                Tok::Punct(p) if depth == 0 && stops.contains(p) => break,
+                Tok::Punct(p) => {
+                    match *p {
+                        "<" | "(" | "[" => depth += 1,
+                        ">" | ")" | "]" => depth -= 1,
+                        _ => {}
+                    }
+                    text.push_str(p);
+                    if matches!(*p, "," | ";") {
+                        text.push(' ');
+                    }
+                    word = false;
+                }
+                Tok::Lifetime => lifetime = true,
+                _ => {
+                    if word {
+                        text.push(' ');
+                    }
+                    text.push_str(&self.source[token.start..token.end]);
+                    word = true;
+                }
+            }
+            self.bump();
+        }
+        if text.is_empty() {
+            return Err(self.error(format!("expected type, found {}", self.describe())));
+        }
+        Ok((text, lifetime))
+    }
+
+    fn expr(&mut self) -> Parsed<Expr> {
+        let mut left = self.unary()?;
+        while let Tok::Punct(op) = *self.peek()
+            && BINARY.contains(&op)
+        {
+            self.bump();
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Parsed<Expr> {
+        let start = self.span();
+        if self.eat("&") {
+            let mutable = self.is_keyword("mut");
+            if mutable {
+                self.bump();
+            }
+            let end = self.i;
+            let inner = self.unary()?;
+            let last = &self.tokens[self.i - 1].span;
+            let span = Span {
+                len: if last.line == start.line {
+                    last.column + last.len - start.column
+                } else {
+                    1
+                },
+                ..start
+            };
+            return Ok(match inner {
+                Expr::Place(name) if self.i == end + 1 => Expr::Borrow {
+                    name,
+                    mutable,
+                    slice: false,
+                    span,
+                },
+                Expr::Index(name) => Expr::Borrow {
+                    name,
+                    mutable,
+                    slice: true,
+                    span,
+                },
+                inner => Expr::BorrowTemp(Box::new(inner), mutable),
+            });
+        }
+        if self.eat("*") {
+            let (text, span) = self.ident()?;
+            return Ok(Expr::Deref(self.resolve(text, span)));
+        }
+        if self.eat("-") || self.eat("!") {
+            return self.unary();
+        }
+        let primary = self.primary()?;
+        self.postfix(primary)
+    }
+
+    fn postfix(&mut self, mut expr: Expr) -> Parsed<Expr> {
+        loop {
+            if self.is("[") {
+                self.bump();
+                self.skip_until("]")?;
+                expr = match expr {
+                    Expr::Place(name) => Expr::Index(name),
+                    other => other,
+                };
+            } else if self.is(".") {
+                self.bump();
+                let method = match self.peek().clone() {
+                    Tok::Ident(name) => name,
+                    Tok::Number(_) => String::from("0"),
+                    _ => {
+                        return Err(
+                            self.error(format!("expected identifier, found {}", self.describe()))
+                        );
+                    }
+                };
+                self.bump();
+                if self.is("::") {
+                    self.bump();
+                    self.expect("<")?;
+                    self.skip_until(">")?;
+                }
+                // 字段访问 t.0、p.x 当作不带参数的方法处理
+                let args = if self.eat("(") {
+                    self.args(")")?
+                } else {
+                    Vec::new()
+                };
+                expr = Expr::Method {
+                    receiver: Box::new(expr),
+                    method,
+                    args,
+                };
+            } else if self.eat("?") {
+                continue;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn args(&mut self, close: &str) -> Parsed<Vec<Expr>> {
+        let mut args = Vec::new();
+        while !self.eat(close) {
+            args.push(self.expr()?);
+            if !self.is(close) {
+                self.expect(",")?;
+            }
+        }
+        Ok(args)
+    }
+
+    fn primary(&mut self) -> Parsed<Expr> {
+        let token = self.bump();
+        match token.tok {
+            Tok::Number(ty) => Ok(Expr::Value(ty.to_string())),
+            Tok::Str(_) => Ok(Expr::Value(String::from("&str"))),
+            Tok::Char => Ok(Expr::Value(String::from("char"))),
+            Tok::Punct("(") => {
+                let items = self.args(")")?;
+                Ok(match items.len() {
+                    0 => Expr::Value(String::from("()")),
+                    1 if self.source[..self.tokens[self.i - 2].end].ends_with(',') => {
+                        Expr::Tuple(items)
+                    }
+                    1 => items.into_iter().next().unwrap(),
+                    _ => Expr::Tuple(items),
+                })
+            }
+            Tok::Punct("[") => {
+                let first = self.expr()?;
+                if self.eat(";") {
+                    self.expr()?;
+                    self.expect("]")?;
+                    return Ok(Expr::Array(vec![first]));
+                }
+                let mut items = vec![first];
+                if !self.is("]") {
+                    self.expect(",")?;
+                }
+                items.extend(self.args("]")?);
+                Ok(Expr::Array(items))
+            }
+            // 关键字不是变量名，不能报告成 E0425
+            Tok::Ident(name) if UNSUPPORTED_KEYWORDS.contains(&name.as_str()) => {
+                self.i -= 1;
+                Err(ParseError {
+                    unsupported: true,
+                    ..self.error(format!(
+                        "`{}`（只支持直线代码，没有 if、match、循环和类型定义）",
+                        name
+                    ))
+                })
+            }
+            Tok::Ident(name) if name == "true" || name == "false" => {
+                Ok(Expr::Value(String::from("bool")))
+            }
+            Tok::Ident(name) if self.is("!") => {
+                self.bump();
+                let close = if self.eat("[") {
+                    "]"
+                } else {
+                    self.expect("(")?;
+                    ")"
+                };
+                let mut args = Vec::new();
+                // 格式字符串中的 {name} 也是对变量的借用
+                if let Tok::Str(format) = self.peek().clone() {
+                    let at = self.span();
+                    args.extend(self.inline_args(&format, at));
+                }
+                args.extend(self.args(close)?);
+                Ok(Expr::Macro { name, args })
+            }
+            Tok::Ident(name) if self.is("::") => {
+                let mut ty = name;
+                while self.eat("::") {
+                    if self.eat("<") {
+                        self.skip_until(">")?;
+                        continue;
+                    }
+                    let (segment, _) = self.ident()?;
+                    if segment.starts_with(char::is_uppercase) {
+                        ty = segment;
+                    }
+                }
+                if self.eat("(") {
+                    let args = self.args(")")?;
+                    let ty = if ty == "Vec" {
+                        String::from("Vec<_>")
+                    } else {
+                        ty
+                    };
+                    Ok(Expr::Construct { ty, args })
+                } else {
+                    Ok(Expr::Value(ty))
+                }
+            }
+            Tok::Ident(name) if self.is("(") => {
+                self.bump();
+                let args = self.args(")")?;
+                if name.starts_with(char::is_uppercase) {
+                    Ok(Expr::Construct { ty: name, args })
+                } else {
+                    Ok(Expr::Call {
+                        name,
+                        span: token.span,
+                        args,
+                    })
+                }
+            }
+            // None 之类的单元变体
+            Tok::Ident(name) if name.starts_with(char::is_uppercase) => {
+                Ok(Expr::Value(if name == "None" {
+                    String::from("Option<_>")
+                } else {
+                    name
+                }))
+            }
+            Tok::Ident(name) => Ok(Expr::Place(self.resolve(name, token.span))),
+            _ => {
+                self.i -= 1;
+                Err(self.error(format!("expected expression, found {}", self.describe())))
+            }
+        }
+    }
+
+    /// "{r1}, {r2:?}" 中的变量名
+    fn inline_args(&mut self, format: &str, at: Span) -> Vec<Expr> {
+        let mut args = Vec::new();
+        let mut chars = format.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c != '{' {
+                continue;
+            }
+            if chars.peek().map(|&(_, c)| c) == Some('{') {
+                chars.next();
+                continue;
+            }
+            let rest = &format[i + 1..];
+            let end = rest.find([':', '}']).unwrap_or(rest.len());
+            let name = &rest[..end];
+            if name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                let column = at.column + 1 + format[..=i].chars().count();
+                let span = Span {
+                    column,
+                    len: name.chars().count(),
+                    ..at
+                };
+                args.push(Expr::Place(self.resolve(name.to_string(), span)));
+            }
+        }
+        args
+    }
+}
+
+fn punct_str(punct: &str) -> &'static str {
+    PUNCTS.iter().find(|p| **p == punct).copied().unwrap_or("")
+}
+
+// ========== 类型 ==========
+
+/// 实现了 Copy 的类型：数字、bool、char、不可变引用，以及由它们组成的元组和数组
+fn is_copy(ty: &str) -> bool {
+    if NUMBER_TYPES.contains(&ty) || ["bool", "char", "()", "_"].contains(&ty) {
+        return true;
+    }
+    if let Some(inner) = ty.strip_prefix('&') {
+        return !inner.starts_with("mut ");
+    }
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return is_copy(inner);
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        return split_top(inner, ',').iter().all(|t| is_copy(t.trim()));
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return is_copy(split_top(inner, ';')[0].trim());
+    }
+    false
+}
+
+/// 按最外层的分隔符切分，括号里面的不切
+fn split_top(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// 去掉一层引用：&String → String，&mut Vec<i32> → Vec<i32>
+fn pointee(ty: &str) -> &str {
+    ty.strip_prefix("&mut ")
+        .or_else(|| ty.strip_prefix('&'))
+        .unwrap_or(ty)
+}
+
+/// 元素类型：Vec<T>、[T; N]、[T] → T
+fn element(ty: &str) -> String {
+    let ty = pointee(ty);
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        return inner.to_string();
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return split_top(inner, ';')[0].trim().to_string();
+    }
+    String::from("_")
+}
+
+/// &s[a..b] 的类型
+fn slice_of(ty: &str) -> String {
+    match pointee(ty) {
+        "String" | "str" | "&str" => String::from("&str"),
+        _ => format!("&[{}]", element(ty)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Receiver {
+    Shared,
+    Mutable,
+    Owned,
+}
+
+/// 方法如何使用接收者，以及返回的类型；返回引用的方法借用接收者
+fn method(name: &str, ty: &str) -> (Receiver, String, bool) {
+    let ty = pointee(ty);
+    let (receiver, ret, borrows) = match name {
+        "push" | "push_str" | "clear" | "insert" | "truncate" | "sort" | "reverse" | "extend"
+        | "retain" | "dedup" | "append" => (Receiver::Mutable, "()", false),
+        "pop" | "remove" => (Receiver::Mutable, "_", false),
+        "clone" => (Receiver::Shared, ty, false),
+        "to_string" | "to_owned" | "to_uppercase" | "to_lowercase" | "repeat" | "replace" => {
+            (Receiver::Shared, "String", false)
+        }
+        "len" | "capacity" | "count" => (Receiver::Shared, "usize", false),
+        "is_empty" | "contains" | "starts_with" | "ends_with" => (Receiver::Shared, "bool", false),
+        "as_str" | "trim" => (Receiver::Shared, "&str", true),
+        "as_bytes" => (Receiver::Shared, "&[u8]", true),
+        name if name.starts_with("into_") || name == "unwrap" => (Receiver::Owned, "_", false),
+        _ => (Receiver::Shared, "_", false),
+    };
+    (receiver, ret.to_string(), borrows)
+}
+
+// ========== 程序 ==========
+
+/// 解析好的脚本
+#[derive(Debug, Clone)]
+pub struct Program {
+    statements: Vec<Statement>,
+    decls: Vec<Decl>,
+    uses: Vec<Vec<(usize, Span)>>,
+    functions: HashMap<String, Signature>,
+}
+
+impl Program {
+    pub fn parse(source: &str) -> Result<Program, ParseError> {
+        let prelude = parse(PRELUDE, HashMap::new())?;
+        parse(source, prelude.functions)
+    }
+
+    /// 语句的数量（每条语句是一步）
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// 按顺序执行每条语句，记录违反的规则和每一步之后的状态
+    pub fn run(&self) -> Run {
+        let mut machine = Machine {
+            program: self,
+            vars: vec![
+                Var {
+                    state: State::Uninit,
+                    ty: String::from("_"),
+                    in_scope: false,
+                    holds: Vec::new(),
+                };
+                self.decls.len()
+            ],
+            loans: Vec::new(),
+            step: 0,
+            depth: 0,
+            violations: Vec::new(),
+        };
+        let mut steps = Vec::new();
+        for (step, statement) in self.statements.iter().enumerate() {
+            machine.step = step;
+            let before = machine.violations.len();
+            let dropped = machine.execute(&statement.kind, statement.span);
+            // 没有被任何变量持有的借用是临时的，语句结束时失效
+            for loan in &mut machine.loans {
+                if loan.holders.is_empty() {
+                    loan.active = false;
+                }
+            }
+            steps.push(Step {
+                line: statement.span.line,
+                text: statement.text.clone(),
+                rows: machine.rows(),
+                dropped,
+                violations: (before..machine.violations.len()).collect(),
+                declaration: matches!(statement.kind, Kind::Fn(_)),
+            });
+        }
+        Run {
+            violations: machine.violations,
+            steps,
+        }
+    }
+}
+
+fn parse(source: &str, functions: HashMap<String, Signature>) -> Result<Program, ParseError> {
+    let mut parser = Parser {
+        source,
+        tokens: lex(source)?,
+        i: 0,
+        scopes: vec![Vec::new()],
+        decls: Vec::new(),
+        uses: Vec::new(),
+        functions,
+        statements: Vec::new(),
+    };
+    parser.program()?;
+    Ok(Program {
+        statements: parser.statements,
+        decls: parser.decls,
+        uses: parser.uses,
+        functions: parser.functions,
+    })
+}
+
+// ========== 执行 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Uninit,
+    Valid,
+    Moved { span: Span, to: String },
+}
+
+#[derive(Debug, Clone)]
+struct Var {
+    state: State,
+    ty: String,
+    in_scope: bool,
+    /// 这个引用持有的借用
+    holds: Vec<usize>,
+}
+
+/// 一次借用：借用了哪个变量，在哪里借用，被哪些引用变量持有
+#[derive(Debug, Clone)]
+struct Loan {
+    target: usize,
+    mutable: bool,
+    span: Span,
+    holders: Vec<usize>,
+    active: bool,
+}
+
+/// 表达式的值：类型和它携带的借用
+struct Val {
+    ty: String,
+    loans: Vec<usize>,
+}
+
+impl Val {
+    fn of(ty: impl Into<String>) -> Val {
+        Val {
+            ty: ty.into(),
+            loans: Vec::new(),
+        }
+    }
+}
+
+/// 使用变量的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    /// 复制或读取 Copy 的值
+    Read,
+    Move,
+    Shared,
+    Mutable,
+}
+
+struct Machine<'p> {
+    program: &'p Program,
+    vars: Vec<Var>,
+    loans: Vec<Loan>,
+    step: usize,
+    depth: usize,
+    violations: Vec<Violation>,
+}
+
+impl Machine<'_> {
+    fn report(&mut self, mut violation: Violation) {
+        violation.step = self.step;
+        self.violations.push(violation);
+    }
+
+    fn decl(&self, id: usize) -> &Decl {
+        &self.program.decls[id]
+    }
+
+    /// 变量在第 step 步或之后的第一次使用
+    fn next_use(&self, id: usize, step: usize) -> Option<Span> {
+        self.program.uses[id]
+            .iter()
+            .find(|(s, _)| *s >= step)
+            .map(|&(_, span)| span)
+    }
+
+    /// 借用在当前语句中是否还活着：临时借用在语句结束前都活着，
+    /// 被变量持有的借用活到那个变量最后一次使用（NLL）
+    fn is_live(&self, loan: &Loan) -> bool {
+        loan.active
+            && (loan.holders.is_empty()
+                || loan
+                    .holders
+                    .iter()
+                    .any(|&h| self.vars[h].in_scope && self.next_use(h, self.step).is_some()))
+    }
+
+    /// 借用之后第一次被使用的位置
+    fn later_use(&self, loan: &Loan) -> Option<Span> {
+        loan.holders
+            .iter()
+            .filter_map(|&h| self.next_use(h, self.step))
+            .min_by_key(|span| (span.line, span.column))
+    }
+
+    fn live_loans(&self, target: usize) -> Vec<usize> {
+        (0..self.loans.len())
+            .filter(|&i| self.loans[i].target == target && self.is_live(&self.loans[i]))
+            .collect()
+    }
+
+    fn resolve(&mut self, name: &Name) -> Option<usize> {
+        if name.binding.is_none() {
+            self.report(
+                Violation::new(
+                    "E0425",
+                    format!("cannot find value `{}` in this scope", name.text),
+                )
+                .primary(name.span, String::from("not found in this scope")),
+            );
+        }
+        name.binding
+    }
+
+    /// 检查变量能不能以 access 的方式使用：初始化了、没有被移走、没有冲突的借用
+    fn access(&mut self, id: usize, name: &Name, access: Access) -> bool {
+        let var = &self.vars[id];
+        match &var.state {
+            State::Uninit => {
+                let decl = self.decl(id).span;
+                self.report(
+                    Violation::new(
+                        "E0381",
+                        format!("used binding `{}` isn't initialized", name.text),
+                    )
+                    .secondary(
+                        Some(decl),
+                        String::from("binding declared here but left uninitialized"),
+                    )
+                    .primary(
+                        name.span,
+                        format!("`{}` used here but it isn't initialized", name.text),
+                    ),
+                );
+                return false;
+            }
+            State::Moved { span, .. } => {
+                let (what, how) = match access {
+                    Access::Read | Access::Move => ("use", "used"),
+                    Access::Shared | Access::Mutable => ("borrow", "borrowed"),
+                };
+                let violation = Violation::new(
+                    "E0382",
+                    format!("{} of moved value: `{}`", what, name.text),
+                )
+                .secondary(Some(*span), String::from("value moved here"))
+                .primary(name.span, format!("value {} here after move", how))
+                .note(format!(
+                    "move occurs because `{}` has type `{}`, which does not implement the `Copy` trait",
+                    name.text, var.ty
+                ));
+                self.report(violation);
+                return false;
+            }
+            State::Valid => {}
+        }
+
+        if access == Access::Mutable && !self.decl(id).mutable {
+            self.report(
+                Violation::new(
+                    "E0596",
+                    format!(
+                        "cannot borrow `{}` as mutable, as it is not declared as mutable",
+                        name.text
+                    ),
+                )
+                .primary(name.span, String::from("cannot borrow as mutable"))
+                .note(format!(
+                    "consider changing this to be mutable: `mut {}`",
+                    name.text
+                )),
+            );
+        }
+
+        let conflict = self.live_loans(id).into_iter().find(|&i| match access {
+            Access::Read | Access::Shared => self.loans[i].mutable,
+            Access::Move | Access::Mutable => true,
+        });
+        let Some(conflict) = conflict else {
+            return true;
+        };
+        let loan = self.loans[conflict].clone();
+        let later = self.later_use(&loan);
+        let n = &name.text;
+        let (kind, mine) = if loan.mutable {
+            ("mutable", "immutable")
+        } else {
+            ("immutable", "mutable")
+        };
+        let violation = match access {
+            Access::Read => Violation::new(
+                "E0503",
+                format!("cannot use `{}` because it was mutably borrowed", n),
+            )
+            .secondary(Some(loan.span), format!("`{}` is borrowed here", n))
+            .primary(name.span, format!("use of borrowed `{}`", n))
+            .secondary(later, String::from("borrow later used here")),
+            Access::Move => Violation::new(
+                "E0505",
+                format!("cannot move out of `{}` because it is borrowed", n),
+            )
+            .secondary(Some(loan.span), format!("borrow of `{}` occurs here", n))
+            .primary(name.span, format!("move out of `{}` occurs here", n))
+            .secondary(later, String::from("borrow later used here")),
+            Access::Mutable if loan.mutable => Violation::new(
+                "E0499",
+                format!("cannot borrow `{}` as mutable more than once at a time", n),
+            )
+            .secondary(
+                Some(loan.span),
+                String::from("first mutable borrow occurs here"),
+            )
+            .primary(name.span, String::from("second mutable borrow occurs here"))
+            .secondary(later, String::from("first borrow later used here")),
+            Access::Shared | Access::Mutable => Violation::new(
+                "E0502",
+                format!(
+                    "cannot borrow `{}` as {} because it is also borrowed as {}",
+                    n, mine, kind
+                ),
+            )
+            .secondary(Some(loan.span), format!("{} borrow occurs here", kind))
+            .primary(name.span, format!("{} borrow occurs here", mine))
+            .secondary(later, format!("{} borrow later used here", kind)),
+        };
+        self.report(violation);
+        false
+    }
+
+    /// 按值使用一个变量：Copy 的值被复制，其他的值被移动到 dest
+    fn take(&mut self, name: &Name, dest: &str) -> Val {
+        let Some(id) = self.resolve(name) else {
+            return Val::of("_");
+        };
+        let ty = self.vars[id].ty.clone();
+        let copy = is_copy(&ty);
+        let access = if copy { Access::Read } else { Access::Move };
+        // 引用本身不会和别的借用冲突，使用引用只是让它持有的借用继续活着
+        let is_ref = ty.starts_with('&');
+        let ok = if is_ref {
+            self.check_moved(id, name, access)
+        } else {
+            self.access(id, name, access)
+        };
+        let loans = self.vars[id].holds.clone();
+        if ok && !copy {
+            self.vars[id].state = State::Moved {
+                span: name.span,
+                to: dest.to_string(),
+            };
+        }
+        Val { ty, loans }
+    }
+
+    /// 只检查初始化和移动，不检查借用冲突
+    fn check_moved(&mut self, id: usize, name: &Name, access: Access) -> bool {
+        if self.vars[id].state == State::Valid {
+            return true;
+        }
+        self.access(id, name, access)
+    }
+
+    /// 创建一个借用，检查冲突
+    fn borrow(&mut self, name: &Name, mutable: bool, span: Span) -> Option<usize> {
+        let id = self.resolve(name)?;
+        let access = if mutable {
+            Access::Mutable
+        } else {
+            Access::Shared
+        };
+        // 错误标在整个 &x 上
+        let at = Name {
+            span,
+            ..name.clone()
+        };
+        self.access(id, &at, access);
+        self.loans.push(Loan {
+            target: id,
+            mutable,
+            span,
+            holders: Vec::new(),
+            active: true,
+        });
+        Some(self.loans.len() - 1)
+    }
+
+    /// 只读地使用变量（println!、比较）：引用直接使用，其他的值临时借用一下
+    fn inspect(&mut self, expr: &Expr) -> Val {
+        match expr {
+            Expr::Place(name) => {
+                let Some(id) = self.resolve(name) else {
+                    return Val::of("_");
+                };
+                let ty = self.vars[id].ty.clone();
+                if ty.starts_with('&') {
+                    self.check_moved(id, name, Access::Read);
+                } else {
+                    self.borrow(name, false, name.span);
+                }
+                Val::of(ty)
+            }
+            _ => self.eval(expr, "临时值"),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr, dest: &str) -> Val {
+        match expr {
+            Expr::Value(ty) => Val::of(ty.clone()),
+            Expr::Place(name) => self.take(name, dest),
+            Expr::Deref(name) => {
+                let Some(id) = self.resolve(name) else {
+                    return Val::of("_");
+                };
+                self.check_moved(id, name, Access::Read);
+                let ty = self.vars[id].ty.clone();
+                let target = pointee(&ty).to_string();
+                if ty.starts_with('&') && !is_copy(&target) {
+                    let kind = if ty.starts_with("&mut ") {
+                        "mutable"
+                    } else {
+                        "shared"
+                    };
+                    self.report(
+                        Violation::new(
+                            "E0507",
+                            format!("cannot move out of `*{}` which is behind a {} reference", name.text, kind),
+                        )
+                        .primary(
+                            name.span,
+                            format!(
+                                "move occurs because `*{}` has type `{}`, which does not implement the `Copy` trait",
+                                name.text, target
+                            ),
+                        ),
+                    );
+                }
+                Val::of(target)
+            }
+            Expr::Borrow {
+                name,
+                mutable,
+                slice,
+                span,
+            } => {
+                let loan = self.borrow(name, *mutable, *span);
+                let target = name.binding.map_or("_", |id| self.vars[id].ty.as_str());
+                let ty = match (slice, mutable) {
+                    (true, _) => slice_of(target),
+                    (false, true) => format!("&mut {}", target),
+                    (false, false) => format!("&{}", target),
+                };
+                Val {
+                    ty,
+                    loans: loan.into_iter().collect(),
+                }
+            }
+            Expr::BorrowTemp(inner, mutable) => {
+                let val = self.eval(inner, dest);
+                let prefix = if *mutable { "&mut " } else { "&" };
+                Val {
+                    ty: format!("{}{}", prefix, val.ty),
+                    loans: val.loans,
+                }
+            }
+            Expr::Index(name) => {
+                let val = self.inspect(&Expr::Place(name.clone()));
+                Val::of(element(&val.ty))
+            }
+            Expr::Method {
+                receiver,
+                method,
+                args,
+            } => {
+                for arg in args {
+                    self.eval(arg, &format!("{}()", method));
+                }
+                match &**receiver {
+                    Expr::Place(name) => self.call_method(name, method),
+                    receiver => {
+                        let val = self.eval(receiver, dest);
+                        let (_, ty, borrows) = self::method(method, &val.ty);
+                        let loans = if borrows { val.loans } else { Vec::new() };
+                        Val { ty, loans }
+                    }
+                }
+            }
+            Expr::Call { name, span, args } => self.call(name, *span, args),
+            Expr::Macro { name, args } => {
+                if name == "vec" {
+                    let items: Vec<Val> = args.iter().map(|arg| self.eval(arg, dest)).collect();
+                    let element = items.first().map_or("_", |val| val.ty.as_str());
+                    return Val::of(format!("Vec<{}>", element));
+                }
+                for arg in args {
+                    self.inspect(arg);
+                }
+                Val::of(if name == "format" || name == "t" {
+                    "String"
+                } else {
+                    "()"
+                })
+            }
+            Expr::Construct { ty, args } => {
+                let vals: Vec<Val> = args
+                    .iter()
+                    .map(|arg| self.eval(arg, &format!("{}()", ty)))
+                    .collect();
+                let ty = match ty.as_str() {
+                    "Some" => format!("Option<{}>", vals.first().map_or("_", |v| v.ty.as_str())),
+                    "Ok" | "Err" => String::from("Result<_, _>"),
+                    _ => ty.clone(),
+                };
+                Val::of(ty)
+            }
+            Expr::Binary(op, left, right) => {
+                if ["+", "-", "*", "/", "%"].contains(op) {
+                    // String + &str 移动左边的 String
+                    let left = self.eval(left, dest);
+                    self.eval(right, dest);
+                    Val::of(left.ty)
+                } else {
+                    self.inspect(left);
+                    self.inspect(right);
+                    Val::of("bool")
+                }
+            }
+            Expr::Tuple(items) => {
+                let vals: Vec<Val> = items.iter().map(|item| self.eval(item, dest)).collect();
+                let types: Vec<&str> = vals.iter().map(|val| val.ty.as_str()).collect();
+                Val {
+                    ty: format!("({})", types.join(", ")),
+                    loans: vals.iter().flat_map(|val| val.loans.clone()).collect(),
+                }
+            }
+            Expr::Array(items) => {
+                let vals: Vec<Val> = items.iter().map(|item| self.eval(item, dest)).collect();
+                let element = vals.first().map_or("_", |val| val.ty.as_str());
+                Val::of(format!("[{}; {}]", element, items.len()))
+            }
+        }
+    }
+
+    fn call_method(&mut self, name: &Name, method: &str) -> Val {
+        let Some(id) = self.resolve(name) else {
+            return Val::of("_");
+        };
+        let ty = self.vars[id].ty.clone();
+        let (receiver, ret, borrows) = self::method(method, &ty);
+        if let Some(inner) = ty.strip_prefix('&') {
+            // 通过引用调用方法：&mut T 可以调用 &mut self 的方法，&T 不行
+            self.check_moved(id, name, Access::Read);
+            if receiver == Receiver::Mutable && !inner.starts_with("mut ") {
+                self.report(
+                    Violation::new(
+                        "E0596",
+                        format!(
+                            "cannot borrow `*{}` as mutable, as it is behind a `&` reference",
+                            name.text
+                        ),
+                    )
+                    .primary(
+                        name.span,
+                        format!(
+                            "`{}` is a `&` reference, so the data it refers to cannot be borrowed as mutable",
+                            name.text
+                        ),
+                    ),
+                );
+            }
+            let loans = if borrows {
+                self.vars[id].holds.clone()
+            } else {
+                Vec::new()
+            };
+            return Val { ty: ret, loans };
+        }
+        match receiver {
+            Receiver::Owned => {
+                self.take(name, &format!("{}.{}()", name.text, method));
+                Val::of(ret)
+            }
+            Receiver::Shared | Receiver::Mutable => {
+                let loan = self.borrow(name, receiver == Receiver::Mutable, name.span);
+                let loans = if borrows {
+                    loan.into_iter().collect()
+                } else {
+                    Vec::new()
+                };
+                Val { ty: ret, loans }
+            }
+        }
+    }
+
+    fn call(&mut self, name: &str, span: Span, args: &[Expr]) -> Val {
+        let Some(signature) = self.program.functions.get(name) else {
+            for arg in args {
+                self.eval(arg, "临时值");
+            }
+            self.report(
+                Violation::new(
+                    "E0425",
+                    format!("cannot find function `{}` in this scope", name),
+                )
+                .primary(span, String::from("not found in this scope")),
+            );
+            return Val::of("_");
+        };
+        if signature.params.len() != args.len() {
+            let plural = |n: usize| if n == 1 { "" } else { "s" };
+            self.report(
+                Violation::new(
+                    "E0061",
+                    format!(
+                        "this function takes {} argument{} but {} argument{} {} supplied",
+                        signature.params.len(),
+                        plural(signature.params.len()),
+                        args.len(),
+                        plural(args.len()),
+                        if args.len() == 1 { "was" } else { "were" }
+                    ),
+                )
+                .primary(span, String::from("called here")),
+            );
+        }
+        let dest = format!("{}()", name);
+        let vals: Vec<Val> = args.iter().map(|arg| self.eval(arg, &dest)).collect();
+        match &signature.ret {
+            None => Val::of("()"),
+            // 生命周期省略：返回的引用借用的是引用参数
+            Some(ret) if ret.starts_with('&') => Val {
+                ty: ret.clone(),
+                loans: vals
+                    .iter()
+                    .filter(|val| val.ty.starts_with('&'))
+                    .flat_map(|val| val.loans.clone())
+                    .collect(),
+            },
+            Some(ret) => Val::of(ret.clone()),
+        }
+    }
+
+    /// 让变量持有这些借用
+    fn hold(&mut self, id: usize, loans: Vec<usize>) {
+        for &loan in &loans {
+            if !self.loans[loan].holders.contains(&id) {
+                self.loans[loan].holders.push(id);
+            }
+        }
+        self.vars[id].holds = loans;
+    }
+
+    /// 执行一条语句，返回离开作用域时被释放的变量
+    fn execute(&mut self, kind: &Kind, span: Span) -> Vec<String> {
+        match kind {
+            Kind::Let { name, ty, init, .. } => {
+                let id = name.binding.unwrap();
+                let val = init.as_ref().map(|init| self.eval(init, &name.text));
+                let var = &mut self.vars[id];
+                var.in_scope = true;
+                var.ty = ty
+                    .clone()
+                    .or_else(|| val.as_ref().map(|val| val.ty.clone()))
+                    .unwrap_or_else(|| String::from("_"));
+                if let Some(val) = val {
+                    var.state = State::Valid;
+                    self.hold(id, val.loans);
+                }
+            }
+            Kind::Assign {
+                target,
+                deref,
+                compound,
+                value,
+            } => self.assign(target, *deref, *compound, value),
+            Kind::Expr(expr) => {
+                self.eval(expr, "临时值");
+            }
+            Kind::Open => self.depth += 1,
+            Kind::Close => return self.close(span),
+            Kind::Fn(signature) => self.check_signature(signature),
+        }
+        Vec::new()
+    }
+
+    fn assign(&mut self, target: &Name, deref: bool, compound: bool, value: &Expr) {
+        let val = self.eval(value, &target.text);
+        let Some(id) = self.resolve(target) else {
+            return;
+        };
+        if deref {
+            let ty = self.vars[id].ty.clone();
+            if ty.starts_with('&') && !ty.starts_with("&mut ") {
+                self.report(
+                    Violation::new(
+                        "E0594",
+                        format!(
+                            "cannot assign to `*{}`, which is behind a `&` reference",
+                            target.text
+                        ),
+                    )
+                    .primary(
+                        target.span,
+                        format!(
+                            "`{}` is a `&` reference, so the data it refers to cannot be written",
+                            target.text
+                        ),
+                    ),
+                );
+            }
+            return;
+        }
+        if compound && !self.check_moved(id, target, Access::Read) {
+            return;
+        }
+        let decl = self.decl(id).clone();
+        if self.vars[id].state != State::Uninit && !decl.mutable {
+            self.report(
+                Violation::new(
+                    "E0384",
+                    format!(
+                        "cannot assign twice to immutable variable `{}`",
+                        target.text
+                    ),
+                )
+                .secondary(
+                    Some(decl.span),
+                    format!("first assignment to `{}`", target.text),
+                )
+                .primary(
+                    target.span,
+                    String::from("cannot assign twice to immutable variable"),
+                )
+                .note(format!(
+                    "consider making this binding mutable: `mut {}`",
+                    target.text
+                )),
+            );
+        }
+        if let Some(&i) = self.live_loans(id).first() {
+            let loan = self.loans[i].clone();
+            let later = self.later_use(&loan);
+            self.report(
+                Violation::new(
+                    "E0506",
+                    format!("cannot assign to `{}` because it is borrowed", target.text),
+                )
+                .secondary(
+                    Some(loan.span),
+                    format!("`{}` is borrowed here", target.text),
+                )
+                .primary(
+                    target.span,
+                    format!(
+                        "`{}` is assigned to here but it was already borrowed",
+                        target.text
+                    ),
+                )
+                .secondary(later, String::from("borrow later used here")),
+            );
+        }
+        if compound {
+            return;
+        }
+        // 引用指向新的值，不再持有原来的借用
+        for loan in std::mem::take(&mut self.vars[id].holds) {
+            self.loans[loan].holders.retain(|&h| h != id);
+        }
+        let var = &mut self.vars[id];
+        var.state = State::Valid;
+        if var.ty == "_" {
+            var.ty = val.ty;
+        }
+        self.hold(id, val.loans);
+    }
+
+    /// 离开作用域：检查还在被外面的引用使用的借用（E0597），然后释放变量
+    fn close(&mut self, span: Span) -> Vec<String> {
+        let depth = self.depth;
+        let dying: Vec<usize> = (0..self.vars.len())
+            .filter(|&id| self.vars[id].in_scope && self.decl(id).depth == depth)
+            .collect();
+        let mut dropped = Vec::new();
+        for &id in &dying {
+            let name = self.decl(id).name.clone();
+            for i in 0..self.loans.len() {
+                let loan = &self.loans[i];
+                if loan.target != id || !loan.active {
+                    continue;
+                }
+                let later = loan
+                    .holders
+                    .iter()
+                    .filter(|&&h| self.decl(h).depth < depth)
+                    .filter_map(|&h| self.next_use(h, self.step + 1))
+                    .min_by_key(|span| (span.line, span.column));
+                if let Some(later) = later {
+                    let borrow = loan.span;
+                    self.report(
+                        Violation::new("E0597", format!("`{}` does not live long enough", name))
+                            .secondary(
+                                Some(self.decl(id).span),
+                                format!("binding `{}` declared here", name),
+                            )
+                            .primary(
+                                borrow,
+                                String::from("borrowed value does not live long enough"),
+                            )
+                            .secondary(
+                                Some(span),
+                                format!("`{}` dropped here while still borrowed", name),
+                            )
+                            .secondary(Some(later), String::from("borrow later used here")),
+                    );
+                }
+                self.loans[i].active = false;
+            }
+            let var = &mut self.vars[id];
+            var.in_scope = false;
+            if var.state == State::Valid && !is_copy(&var.ty) {
+                dropped.push(name);
+            }
+        }
+        self.depth -= 1;
+        dropped
+    }
+
+    /// 返回引用的函数必须能按省略规则确定借用的是哪个参数（E0106）
+    fn check_signature(&mut self, signature: &Signature) {
+        let Some(ret) = &signature.ret else {
+            return;
+        };
+        if !ret.starts_with('&') || signature.ret_lifetime {
+            return;
+        }
+        let refs = signature
+            .params
+            .iter()
+            .filter(|p| p.starts_with('&'))
+            .count();
+        if refs == 1 {
+            return;
+        }
+        let note = if refs == 0 {
+            String::from(
+                "this function's return type contains a borrowed value, but there is no value for it to be borrowed from",
+            )
+        } else {
+            format!(
+                "this function's return type contains a borrowed value, but the signature does not say which one of the {} parameters it is borrowed from",
+                refs
+            )
+        };
+        self.report(
+            Violation::new("E0106", String::from("missing lifetime specifier"))
+                .primary(
+                    Span {
+                        len: 1,
+                        ..signature.ret_span
+                    },
+                    String::from("expected named lifetime parameter"),
+                )
+                .note(note),
+        );
+    }
+
+    /// 当前的状态表：每个还在作用域中的变量一行
+    fn rows(&self) -> Vec<Row> {
+        let live: Vec<usize> = (0..self.vars.len())
+            .filter(|&id| self.vars[id].in_scope)
+            .collect();
+        live.iter()
+            .filter_map(|&id| {
+                let decl = self.decl(id);
+                let var = &self.vars[id];
+                let mut state = match &var.state {
+                    State::Uninit => String::from("未初始化"),
+                    State::Moved { to, .. } => format!("已移动到 {}", to),
+                    State::Valid => self.describe(id),
+                };
+                let shadowed = live
+                    .iter()
+                    .any(|&other| other > id && self.decl(other).name == decl.name);
+                // 被遮蔽的变量不能再用名字访问，只在还被借用时列出
+                if shadowed {
+                    if !state.contains('被') {
+                        return None;
+                    }
+                    state.push_str("（已被遮蔽）");
+                }
+                Some(Row {
+                    name: decl.name.clone(),
+                    depth: decl.depth,
+                    ty: var.ty.clone(),
+                    state,
+                })
+            })
+            .collect()
+    }
+
+    /// 有效的值：引用写出它借用了谁，被借用的值写出被谁借用
+    fn describe(&self, id: usize) -> String {
+        let var = &self.vars[id];
+        let after = self.step + 1;
+        if var.ty.starts_with('&') && !var.holds.is_empty() {
+            let targets: Vec<String> = var
+                .holds
+                .iter()
+                .map(|&i| {
+                    let loan = &self.loans[i];
+                    let kind = if loan.mutable {
+                        "可变借用"
+                    } else {
+                        "借用"
+                    };
+                    format!("{} {}", kind, self.decl(loan.target).name)
+                })
+                .collect();
+            let mut text = targets.join("、");
+            if self.next_use(id, after).is_none() {
+                text.push_str("，之后不再使用");
+            }
+            return text;
+        }
+        let mut text = String::from("有效");
+        for mutable in [false, true] {
+            let holders: Vec<&str> = self
+                .loans
+                .iter()
+                .filter(|loan| loan.active && loan.target == id && loan.mutable == mutable)
+                .flat_map(|loan| &loan.holders)
+                .filter(|&&h| self.vars[h].in_scope && self.next_use(h, after).is_some())
+                .map(|&h| self.decl(h).name.as_str())
+                .collect();
+            if !holders.is_empty() {
+                let kind = if mutable { "可变借用" } else { "借用" };
+                text.push_str(&format!("，被 {} {}", holders.join("、"), kind));
+            }
+        }
+        text
+    }
+}
+
+// ========== 执行结果 ==========
+
+/// 状态表中的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub name: String,
+    /// 声明所在的作用域深度
+    pub depth: usize,
+    pub ty: String,
+    pub state: String,
+}
+
+/// 执行一条语句之后的状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub line: usize,
+    pub text: String,
+    pub rows: Vec<Row>,
+    /// 离开作用域时释放的变量
+    pub dropped: Vec<String>,
+    /// 这一步报告的错误在 Run::violations 中的下标
+    pub violations: Vec<usize>,
+    /// fn 声明，不改变状态
+    pub declaration: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub violations: Vec<Violation>,
+    pub steps: Vec<Step>,
+}
+
+impl Run {
+    pub fn codes(&self) -> Vec<&'static str> {
+        self.violations.iter().map(|v| v.code).collect()
+    }
+
+    /// 跟踪模式：每条语句之后打印状态表
+    ///
+    /// ```text
+    /// 第 3 行  let r1 = &s;
+    ///   s   String   有效，被 r1 借用
+    ///   r1  &String  借用 s
+    /// ```
+    pub fn trace(&self) -> String {
+        let mut out = String::new();
+        for step in &self.steps {
+            if step.declaration && step.violations.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("第 {} 行  {}\n", step.line, step.text));
+            for name in &step.dropped {
+                out.push_str(&format!("  释放 {}\n", name));
+            }
+            let names: Vec<String> = step
+                .rows
+                .iter()
+                .map(|row| format!("{}{}", "  ".repeat(row.depth), row.name))
+                .collect();
+            let name_width = names.iter().map(|n| unicode::width(n)).max().unwrap_or(0);
+            let ty_width = step
+                .rows
+                .iter()
+                .map(|row| unicode::width(&row.ty))
+                .max()
+                .unwrap_or(0);
+            for (name, row) in names.iter().zip(&step.rows) {
+                out.push_str(&format!(
+                    "  {}{}  {}{}  {}\n",
+                    name,
+                    " ".repeat(name_width - unicode::width(name)),
+                    row.ty,
+                    " ".repeat(ty_width - unicode::width(&row.ty)),
+                    row.state
+                ));
+            }
+            for &i in &step.violations {
+                out.push_str(&format!("  {}\n", self.violations[i]));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,647 @@
+// ============================================
+// 表达式计算器 (Calculator)
+// ============================================
+// 第 03 章定义了 add、multiply、divide，并把 add 存进 `fn(i32, i32) -> i32`
+// 类型的变量再调用。计算器把这个想法用到底：
+//
+// - 二元运算符 + - * / % ^ 各自对应一个函数指针（arithmetic 模块中的检查溢出的运算），
+//   按优先级爬升（precedence climbing）解析：^ 右结合且优先级最高，
+//   一元负号比 ^ 低、比 * / % 高，所以 -2 ^ 2 == -4
+// - abs(x)、max(a, b, ...) 这样的函数调用按名字在注册表中查找，
+//   注册表里可以放普通函数指针，也可以放捕获了环境的闭包
+// - `let 名字 = 表达式` 定义变量，之后的表达式可以使用
+//
+// 数值是 i64 整数，除法向零取整；溢出、除数为零等错误带有出错的位置。
+// 解析、求值都是递归的，所以语法树最多 MAX_DEPTH 层：括号、函数调用、负号和
+// 运算符链（1 + 1 + … 每个运算符算一层）超过这个深度时报告解析错误，而不是栈溢出。
+//
+//     calculator::eval("3 + 4 * 2")            -> Ok(11)
+//     calculator::eval("max(2, 7) ^ 2 % 10")   -> Ok(9)
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::arithmetic;
+
+// ========== 错误 ==========
+
+/// 解析或计算失败的原因，`column` 是出错处的字符位置（从 1 开始）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 不认识的字符
+    UnexpectedChar(char),
+    /// 出现在不该出现的位置的符号；`expected` 说明这里需要什么
+    Unexpected {
+        found: String,
+        expected: &'static str,
+    },
+    /// 数字超出 i64 的范围
+    NumberTooLarge(String),
+    /// 表达式嵌套超过 MAX_DEPTH 层
+    TooDeep,
+    UnknownVariable(String),
+    UnknownFunction(String),
+    /// 函数的参数个数不对
+    Arity {
+        name: String,
+        expected: Arity,
+        found: usize,
+    },
+    /// 运算溢出、除数为零或负数指数
+    Arithmetic(arithmetic::Error<i64>),
+    /// 注册的函数报告的错误
+    Function {
+        name: String,
+        message: String,
+    },
+}
+
+impl Error {
+    fn new(column: usize, kind: ErrorKind) -> Error {
+        Error { column, kind }
+    }
+
+    /// 显示出错的输入，并在出错的位置下方画一个 ^
+    pub fn render(&self, source: &str) -> String {
+        let indent: String = source
+            .chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        format!("{}\n{}^ {}\n", source.trim_end(), indent, self)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::UnexpectedChar(c) => write!(f, "无法识别的字符 {:?}", c),
+            ErrorKind::Unexpected { found, expected } => {
+                write!(f, "这里需要{}，却遇到了 {}", expected, found)
+            }
+            ErrorKind::NumberTooLarge(text) => write!(f, "数字 {} 超出了 i64 的范围", text),
+            ErrorKind::TooDeep => write!(f, "表达式嵌套太深，最多 {} 层", MAX_DEPTH),
+            ErrorKind::UnknownVariable(name) => write!(f, "没有定义变量 {}", name),
+            ErrorKind::UnknownFunction(name) => write!(f, "没有名为 {} 的函数", name),
+            ErrorKind::Arity {
+                name,
+                expected: Arity::Exactly(n),
+                found,
+            } => write!(f, "{} 需要 {} 个参数，传入了 {} 个", name, n, found),
+            ErrorKind::Arity {
+                name,
+                expected: Arity::AtLeastOne,
+                found,
+            } => write!(f, "{} 至少需要 1 个参数，传入了 {} 个", name, found),
+            ErrorKind::Arithmetic(error) => write!(f, "{}", error),
+            ErrorKind::Function { name, message } => write!(f, "{}: {}", name, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ========== 词法分析 ==========
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Ident(String),
+    /// + - * / % ^
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+    Assign,
+    End,
+}
+
+impl Token {
+    /// 用在错误信息中的写法
+    fn describe(&self) -> String {
+        match self {
+            Token::Number(n) => format!("数字 {}", n),
+            Token::Ident(name) => format!("名字 {}", name),
+            Token::Op(c) => format!("`{}`", c),
+            Token::LParen => String::from("`(`"),
+            Token::RParen => String::from("`)`"),
+            Token::Comma => String::from("`,`"),
+            Token::Assign => String::from("`=`"),
+            Token::End => String::from("输入结尾"),
+        }
+    }
+}
+
+/// 把输入切分成 (列号, 符号) 的列表，最后一项总是 End
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let digits = text.replace('_', "");
+            let n = digits
+                .parse()
+                .map_err(|_| Error::new(column, ErrorKind::NumberTooLarge(text)))?;
+            tokens.push((column, Token::Number(n)));
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((column, Token::Ident(chars[start..i].iter().collect())));
+            continue;
+        }
+        let token = match c {
+            '+' | '-' | '*' | '/' | '%' | '^' => Token::Op(c),
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '=' => Token::Assign,
+            _ => return Err(Error::new(column, ErrorKind::UnexpectedChar(c))),
+        };
+        tokens.push((column, token));
+        i += 1;
+    }
+    tokens.push((chars.len() + 1, Token::End));
+    Ok(tokens)
+}
+
+// ========== 语法分析 ==========
+
+/// 二元运算符由一个检查溢出的函数实现，和第 03 章的 `fn(i32, i32) -> i32` 一样是函数指针
+type BinaryFn = fn(i64, i64) -> Result<i64, arithmetic::Error<i64>>;
+
+/// 运算符表：符号、优先级、是否右结合、实现
+const OPERATORS: [(char, u8, bool, BinaryFn); 6] = [
+    ('+', 1, false, arithmetic::add),
+    ('-', 1, false, arithmetic::subtract),
+    ('*', 2, false, arithmetic::multiply),
+    ('/', 2, false, arithmetic::divide),
+    ('%', 2, false, arithmetic::remainder),
+    ('^', 4, true, arithmetic::pow),
+];
+
+/// 一元负号的优先级：介于 * / % 和 ^ 之间
+const UNARY: u8 = 3;
+
+/// 语法树最多的层数；求值和释放语法树都是递归的，太深会让栈溢出
+pub const MAX_DEPTH: usize = 256;
+
+fn operator(c: char) -> (u8, bool, BinaryFn) {
+    let (_, precedence, right, f) = OPERATORS
+        .into_iter()
+        .find(|(symbol, ..)| *symbol == c)
+        .expect("词法分析只产生表中的运算符");
+    (precedence, right, f)
+}
+
+/// 表达式语法树，每个节点记录它在输入中的列号
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(i64),
+    Variable(usize, String),
+    Neg(usize, Box<Expr>),
+    Binary(usize, char, Box<Expr>, Box<Expr>),
+    Call(usize, String, Vec<Expr>),
+}
+
+/// 一行输入：表达式，或者 `let 名字 = 表达式`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Let(String, Expr),
+    Expr(Expr),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    /// 当前位置在语法树中的深度（上界）
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn column(&self) -> usize {
+        self.tokens[self.pos].0
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let token = self.tokens[self.pos].clone();
+        if token.1 != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &'static str) -> Error {
+        Error::new(
+            self.column(),
+            ErrorKind::Unexpected {
+                found: self.peek().describe(),
+                expected,
+            },
+        )
+    }
+
+    /// 往下一层；超过 MAX_DEPTH 时报错
+    fn nest(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Error::new(self.column(), ErrorKind::TooDeep));
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), Error> {
+        if *self.peek() == token {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, Error> {
+        let is_let = *self.peek() == Token::Ident(String::from("let"));
+        let statement = if is_let {
+            self.next();
+            let name = match self.peek() {
+                Token::Ident(name) if name != "let" => name.clone(),
+                _ => return Err(self.unexpected("变量名")),
+            };
+            self.next();
+            self.expect(Token::Assign, " `=`")?;
+            Statement::Let(name, self.expression(0)?)
+        } else {
+            Statement::Expr(self.expression(0)?)
+        };
+        if *self.peek() != Token::End {
+            return Err(self.unexpected("运算符或输入结尾"));
+        }
+        Ok(statement)
+    }
+
+    /// 优先级爬升：解析一个操作数，然后只吸收优先级不低于 `min` 的运算符
+    fn expression(&mut self, min: u8) -> Result<Expr, Error> {
+        let outer = self.depth;
+        self.nest()?;
+        let mut lhs = self.unary()?;
+        while let Token::Op(c) = *self.peek() {
+            let (precedence, right, _) = operator(c);
+            if precedence < min {
+                break;
+            }
+            let column = self.next().0;
+            // 每个运算符都让语法树多一层，1 + 1 + … 这样的长链也会变深
+            self.nest()?;
+            // 左结合时右边只能吸收更高优先级的运算符，右结合时同级的也可以
+            let next = if right { precedence } else { precedence + 1 };
+            let rhs = self.expression(next)?;
+            lhs = Expr::Binary(column, c, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth = outer;
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if *self.peek() == Token::Op('-') {
+            let column = self.next().0;
+            let operand = self.expression(UNARY)?;
+            return Ok(Expr::Neg(column, Box::new(operand)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, Error> {
+        let column = self.column();
+        match self.peek().clone() {
+            Token::Number(n) => {
+                self.next();
+                Ok(Expr::Number(n))
+            }
+            Token::Ident(name) if name != "let" => {
+                self.next();
+                if *self.peek() != Token::LParen {
+                    return Ok(Expr::Variable(column, name));
+                }
+                self.next();
+                let mut args = Vec::new();
+                if *self.peek() != Token::RParen {
+                    loop {
+                        args.push(self.expression(0)?);
+                        if *self.peek() != Token::Comma {
+                            break;
+                        }
+                        self.next();
+                    }
+                }
+                self.expect(Token::RParen, " `,` 或 `)`")?;
+                Ok(Expr::Call(column, name, args))
+            }
+            Token::LParen => {
+                self.next();
+                let expr = self.expression(0)?;
+                self.expect(Token::RParen, " `)`")?;
+                Ok(expr)
+            }
+            _ => Err(self.unexpected("数字、变量、函数调用或 `(`")),
+        }
+    }
+}
+
+/// 解析一行输入
+pub fn parse(source: &str) -> Result<Statement, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        depth: 0,
+    };
+    parser.statement()
+}
+
+// ========== 函数注册表 ==========
+
+/// 函数接受的参数个数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    /// 至少一个
+    AtLeastOne,
+}
+
+impl Arity {
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeastOne => count >= 1,
+        }
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Arity::Exactly(n) => write!(f, "{}", n),
+            Arity::AtLeastOne => write!(f, "至少 1"),
+        }
+    }
+}
+
+/// 函数的返回值；Err 中是给用户看的说明
+pub type FnResult = Result<i64, String>;
+
+/// 装箱的闭包；和函数指针签名相同，但可以捕获环境
+pub type BoxedFn = Box<dyn Fn(&[i64]) -> FnResult>;
+
+/// 注册表中的函数：普通函数指针，或者捕获了环境的闭包
+pub enum Callable {
+    Pointer(fn(&[i64]) -> FnResult),
+    Closure(BoxedFn),
+}
+
+impl Callable {
+    fn call(&self, args: &[i64]) -> FnResult {
+        match self {
+            Callable::Pointer(f) => f(args),
+            Callable::Closure(f) => f(args),
+        }
+    }
+}
+
+struct Function {
+    arity: Arity,
+    callable: Callable,
+}
+
+fn abs(args: &[i64]) -> FnResult {
+    args[0]
+        .checked_abs()
+        .ok_or_else(|| format!("{} 的绝对值超出了 i64 的范围", args[0]))
+}
+
+fn min(args: &[i64]) -> FnResult {
+    Ok(args.iter().copied().min().unwrap_or_default())
+}
+
+fn max(args: &[i64]) -> FnResult {
+    Ok(args.iter().copied().max().unwrap_or_default())
+}
+
+/// 最大公约数，结果总是非负数
+fn gcd(args: &[i64]) -> FnResult {
+    let (mut a, mut b) = (args[0].unsigned_abs(), args[1].unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i64::try_from(a).map_err(|_| String::from("结果超出了 i64 的范围"))
+}
+
+/// 整数平方根（向下取整）
+fn sqrt(args: &[i64]) -> FnResult {
+    if args[0] < 0 {
+        return Err(format!("负数 {} 没有实数平方根", args[0]));
+    }
+    Ok(args[0].isqrt())
+}
+
+// ========== 计算器 ==========
+
+/// 带有变量和函数注册表的计算器
+pub struct Calculator {
+    variables: BTreeMap<String, i64>,
+    functions: BTreeMap<String, Function>,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Calculator::new()
+    }
+}
+
+impl Calculator {
+    /// 注册了内置函数 abs、min、max、gcd、sqrt 的计算器
+    pub fn new() -> Calculator {
+        let mut calculator = Calculator {
+            variables: BTreeMap::new(),
+            functions: BTreeMap::new(),
+        };
+        calculator.define("abs", Arity::Exactly(1), abs);
+        calculator.define("min", Arity::AtLeastOne, min);
+        calculator.define("max", Arity::AtLeastOne, max);
+        calculator.define("gcd", Arity::Exactly(2), gcd);
+        calculator.define("sqrt", Arity::Exactly(1), sqrt);
+        calculator
+    }
+
+    /// 注册（或替换）一个普通函数
+    pub fn define(&mut self, name: &str, arity: Arity, f: fn(&[i64]) -> FnResult) {
+        let callable = Callable::Pointer(f);
+        self.functions
+            .insert(name.to_string(), Function { arity, callable });
+    }
+
+    /// 注册（或替换）一个闭包
+    pub fn define_closure(
+        &mut self,
+        name: &str,
+        arity: Arity,
+        f: impl Fn(&[i64]) -> FnResult + 'static,
+    ) {
+        let callable = Callable::Closure(Box::new(f));
+        self.functions
+            .insert(name.to_string(), Function { arity, callable });
+    }
+
+    /// 已定义的变量，按名称排序
+    pub fn variables(&self) -> &BTreeMap<String, i64> {
+        &self.variables
+    }
+
+    /// 已注册的函数名和参数个数，按名称排序
+    pub fn functions(&self) -> Vec<(&str, Arity)> {
+        self.functions
+            .iter()
+            .map(|(name, function)| (name.as_str(), function.arity))
+            .collect()
+    }
+
+    /// 计算一行输入；`let` 语句返回赋给变量的值
+    pub fn eval(&mut self, source: &str) -> Result<i64, Error> {
+        match parse(source)? {
+            Statement::Let(name, expr) => {
+                let value = self.evaluate(&expr)?;
+                self.variables.insert(name, value);
+                Ok(value)
+            }
+            Statement::Expr(expr) => self.evaluate(&expr),
+        }
+    }
+
+    /// 计算语法树
+    pub fn evaluate(&self, expr: &Expr) -> Result<i64, Error> {
+        match expr {
+            Expr::Number(n) => Ok(*n),
+            Expr::Variable(column, name) => self
+                .variables
+                .get(name)
+                .copied()
+                .ok_or_else(|| Error::new(*column, ErrorKind::UnknownVariable(name.clone()))),
+            Expr::Neg(column, operand) => {
+                let value = self.evaluate(operand)?;
+                arithmetic::subtract(0, value)
+                    .map_err(|error| Error::new(*column, ErrorKind::Arithmetic(error)))
+            }
+            Expr::Binary(column, op, lhs, rhs) => {
+                let (_, _, f) = operator(*op);
+                let (lhs, rhs) = (self.evaluate(lhs)?, self.evaluate(rhs)?);
+                f(lhs, rhs).map_err(|error| Error::new(*column, ErrorKind::Arithmetic(error)))
+            }
+            Expr::Call(column, name, args) => {
+                let error = |kind| Error::new(*column, kind);
+                let function = self
+                    .functions
+                    .get(name)
+                    .ok_or_else(|| error(ErrorKind::UnknownFunction(name.clone())))?;
+                if !function.arity.accepts(args.len()) {
+                    return Err(error(ErrorKind::Arity {
+                        name: name.clone(),
+                        expected: function.arity,
+                        found: args.len(),
+                    }));
+                }
+                let args = args
+                    .iter()
+                    .map(|arg| self.evaluate(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                function.callable.call(&args).map_err(|message| {
+                    error(ErrorKind::Function {
+                        name: name.clone(),
+                        message,
+                    })
+                })
+            }
+        }
+    }
+}
+
+/// 用一个新的计算器计算表达式
+pub fn eval(source: &str) -> Result<i64, Error> {
+    Calculator::new().eval(source)
+}
+
+// ========== 交互模式 ==========
+
+const REPL_HELP: &str = "\
+输入表达式计算结果，例如 3 + 4 * 2、(1 + 2) ^ 3、-7 / 2、max(1, 5, 3)
+  let x = 表达式    定义变量
+  :vars             列出变量
+  :funcs            列出函数
+  :quit             退出
+
+运算符按优先级从高到低: ^（右结合）、一元 -、* / %、+ -";
+
+/// 交互式计算器：每行一个表达式或 let 语句
+pub fn repl(input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    let mut calculator = Calculator::new();
+    writeln!(out, "计算器，输入 :help 查看帮助，:quit 退出")?;
+
+    loop {
+        write!(out, "calc> ")?;
+        out.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(());
+        }
+
+        match line.trim() {
+            "" => continue,
+            ":quit" | ":q" => return Ok(()),
+            ":help" => writeln!(out, "{}", REPL_HELP)?,
+            ":vars" => {
+                if calculator.variables().is_empty() {
+                    writeln!(out, "（没有变量）")?;
+                }
+                for (name, value) in calculator.variables() {
+                    writeln!(out, "{} = {}", name, value)?;
+                }
+            }
+            ":funcs" => {
+                for (name, arity) in calculator.functions() {
+                    writeln!(out, "{}（{} 个参数）", name, arity)?;
+                }
+            }
+            command if command.starts_with(':') => {
+                writeln!(out, "未知的命令: {}（输入 :help 查看帮助）", command)?;
+            }
+            source => match calculator.eval(source) {
+                Ok(value) => writeln!(out, "{}", value)?,
+                Err(error) => write!(out, "{}", error.render(source))?,
+            },
+        }
+    }
+}
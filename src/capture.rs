@@ -0,0 +1,329 @@
+// ============================================
+// 闭包捕获方式 (Closure Capture)
+// ============================================
+// 第 03 章的 closure_example 只在注释里说明闭包的三种捕获方式：
+// FnOnce 获取所有权，FnMut 可变借用，Fn 不可变借用。这个模块用带日志的值把它演示出来：
+//
+// - Tracked<T> 包装一个值，创建、借用、可变借用、克隆、释放时都往 Log 里记一条事件。
+//   Rust 中的移动只是按位复制，无法拦截，所以移动要在发生的地方调用 moved() 记录
+// - call_fn、call_mut、call_once 分别要求参数实现 Fn、FnMut、FnOnce，调用前记录一条事件。
+//   场景能用哪个函数调用闭包由编译器检查，所以事件中的 trait 就是闭包实现了的 trait
+// - 每个场景是一个普通函数，运行后得到事件时间线：从中可以看出闭包调用时借用了什么，
+//   以及捕获的值是在调用时、闭包被释放时，还是在作用域结束时释放的
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+// ========== 事件和日志 ==========
+
+/// 闭包实现的 trait，从宽到严
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosureTrait {
+    FnOnce,
+    FnMut,
+    Fn,
+}
+
+impl fmt::Display for ClosureTrait {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ClosureTrait::FnOnce => "FnOnce",
+            ClosureTrait::FnMut => "FnMut",
+            ClosureTrait::Fn => "Fn",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Create(String),
+    Borrow(String),
+    BorrowMut(String),
+    Move(String),
+    Clone {
+        from: String,
+        to: String,
+    },
+    Drop(String),
+    /// 通过 call_fn、call_mut 或 call_once 调用闭包
+    Call {
+        closure: String,
+        via: ClosureTrait,
+    },
+    /// 场景自己加的说明
+    Note(String),
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Create(name) => write!(f, "创建 {}", name),
+            Event::Borrow(name) => write!(f, "借用 &{}", name),
+            Event::BorrowMut(name) => write!(f, "可变借用 &mut {}", name),
+            Event::Move(name) => write!(f, "移动 {}", name),
+            Event::Clone { from, to } => write!(f, "克隆 {} → {}", from, to),
+            Event::Drop(name) => write!(f, "释放 {}", name),
+            Event::Call { closure, via } => write!(f, "调用 {}（{}）", closure, via),
+            Event::Note(text) => write!(f, "-- {}", text),
+        }
+    }
+}
+
+/// 事件日志；克隆得到的是同一份日志
+#[derive(Debug, Clone, Default)]
+pub struct Log(Rc<RefCell<Vec<Event>>>);
+
+impl Log {
+    pub fn push(&self, event: Event) {
+        self.0.borrow_mut().push(event);
+    }
+
+    pub fn note(&self, text: impl Into<String>) {
+        self.push(Event::Note(text.into()));
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.0.borrow().clone()
+    }
+}
+
+// ========== 带日志的值 ==========
+
+/// 记录自己生命周期中每一步的值
+pub struct Tracked<T> {
+    name: String,
+    value: T,
+    log: Log,
+}
+
+impl<T> Tracked<T> {
+    pub fn new(log: &Log, name: &str, value: T) -> Self {
+        log.push(Event::Create(name.to_string()));
+        Tracked {
+            name: name.to_string(),
+            value,
+            log: log.clone(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get(&self) -> &T {
+        self.log.push(Event::Borrow(self.name.clone()));
+        &self.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.log.push(Event::BorrowMut(self.name.clone()));
+        &mut self.value
+    }
+
+    /// 记录一次移动，原样返回自己
+    pub fn moved(self) -> Self {
+        self.log.push(Event::Move(self.name.clone()));
+        self
+    }
+}
+
+/// 克隆出的值在名字后面加 '
+impl<T: Clone> Clone for Tracked<T> {
+    fn clone(&self) -> Self {
+        let name = format!("{}'", self.name);
+        self.log.push(Event::Clone {
+            from: self.name.clone(),
+            to: name.clone(),
+        });
+        Tracked {
+            name,
+            value: self.value.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.log.push(Event::Drop(self.name.clone()));
+    }
+}
+
+// ========== 调用闭包 ==========
+
+/// 通过 & 调用，要求闭包实现 Fn
+pub fn call_fn<R>(log: &Log, name: &str, f: &impl Fn() -> R) -> R {
+    log.push(Event::Call {
+        closure: name.to_string(),
+        via: ClosureTrait::Fn,
+    });
+    f()
+}
+
+/// 通过 &mut 调用，要求闭包实现 FnMut
+pub fn call_mut<R>(log: &Log, name: &str, f: &mut impl FnMut() -> R) -> R {
+    log.push(Event::Call {
+        closure: name.to_string(),
+        via: ClosureTrait::FnMut,
+    });
+    f()
+}
+
+/// 按值调用，闭包被消耗，之后不能再调用
+pub fn call_once<R>(log: &Log, name: &str, f: impl FnOnce() -> R) -> R {
+    log.push(Event::Call {
+        closure: name.to_string(),
+        via: ClosureTrait::FnOnce,
+    });
+    f()
+}
+
+// ========== 场景 ==========
+
+pub struct Scenario {
+    /// 命令行中使用的名字
+    pub name: &'static str,
+    pub title: &'static str,
+    /// 闭包实现的最严格的 trait
+    pub implements: ClosureTrait,
+    run: fn(&Log),
+}
+
+impl Scenario {
+    /// 用一份新日志运行场景，返回全部事件
+    pub fn timeline(&self) -> Vec<Event> {
+        let log = Log::default();
+        (self.run)(&log);
+        log.events()
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!("[{}] {}（{}）\n", self.name, self.title, self.implements);
+        for (i, event) in self.timeline().iter().enumerate() {
+            out += &format!("{:>3}. {}\n", i + 1, event);
+        }
+        out
+    }
+}
+
+pub fn scenarios() -> [Scenario; 6] {
+    [
+        Scenario {
+            name: "borrow",
+            title: "只读取捕获的变量，闭包不可变借用它",
+            implements: ClosureTrait::Fn,
+            run: borrow,
+        },
+        Scenario {
+            name: "mutate",
+            title: "修改捕获的变量，闭包可变借用它",
+            implements: ClosureTrait::FnMut,
+            run: mutate,
+        },
+        Scenario {
+            name: "consume",
+            title: "闭包体把捕获的值移走，不写 move 也按值捕获",
+            implements: ClosureTrait::FnOnce,
+            run: consume,
+        },
+        Scenario {
+            name: "move",
+            title: "move 闭包拥有捕获的值，值随闭包一起释放",
+            implements: ClosureTrait::Fn,
+            run: move_into,
+        },
+        Scenario {
+            name: "clone",
+            title: "先克隆再移入闭包，原值仍然可用",
+            implements: ClosureTrait::Fn,
+            run: clone_into,
+        },
+        Scenario {
+            name: "return",
+            title: "函数返回的闭包拥有状态，比创建它的函数活得久",
+            implements: ClosureTrait::FnMut,
+            run: returned,
+        },
+    ]
+}
+
+pub fn find(name: &str) -> Option<Scenario> {
+    scenarios().into_iter().find(|s| s.name == name)
+}
+
+fn borrow(log: &Log) {
+    let list = Tracked::new(log, "list", vec![1, 2, 3]);
+    let only_borrows = || list.get().len();
+    call_fn(log, "only_borrows", &only_borrows);
+    call_fn(log, "only_borrows", &only_borrows);
+    // 闭包只借用了 list，调用之后 list 仍然可以使用
+    log.note(format!("list 仍有 {} 个元素", list.get().len()));
+    log.note("作用域结束");
+}
+
+fn mutate(log: &Log) {
+    let mut counter = Tracked::new(log, "counter", 0);
+    let mut increment = || *counter.get_mut() += 1;
+    call_mut(log, "increment", &mut increment);
+    call_mut(log, "increment", &mut increment);
+    // increment 最后一次使用之后，可变借用就结束了
+    log.note(format!("counter = {}", counter.get()));
+    log.note("作用域结束");
+}
+
+fn consume(log: &Log) {
+    let s = Tracked::new(log, "s", String::from("hello"));
+    let takes_ownership = || {
+        let length = s.get().len();
+        drop(s);
+        length
+    };
+    call_once(log, "takes_ownership", takes_ownership);
+    // s 在调用时已经释放，takes_ownership 也不能再调用
+    log.note("调用结束");
+}
+
+fn move_into(log: &Log) {
+    let s = Tracked::new(log, "s", String::from("hello"));
+    let greet = {
+        let s = s.moved();
+        move || s.get().len()
+    };
+    call_fn(log, "greet", &greet);
+    call_fn(log, "greet", &greet);
+    log.note("drop(greet)");
+    drop(greet);
+    log.note("作用域结束");
+}
+
+fn clone_into(log: &Log) {
+    let data = Tracked::new(log, "data", vec![1, 2, 3]);
+    let sum = {
+        let data = data.clone().moved();
+        move || data.get().iter().sum::<i32>()
+    };
+    call_fn(log, "sum", &sum);
+    log.note(format!("原来的 data 仍有 {} 个元素", data.get().len()));
+    log.note("drop(sum)");
+    drop(sum);
+    log.note("作用域结束");
+}
+
+fn make_counter(log: &Log) -> impl FnMut() -> i32 + use<> {
+    let mut count = Tracked::new(log, "count", 0);
+    log.note("make_counter 返回");
+    move || {
+        *count.get_mut() += 1;
+        *count.get()
+    }
+}
+
+fn returned(log: &Log) {
+    let mut next = make_counter(log);
+    call_mut(log, "next", &mut next);
+    call_mut(log, "next", &mut next);
+    log.note("drop(next)");
+    drop(next);
+    log.note("作用域结束");
+}
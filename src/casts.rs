@@ -0,0 +1,649 @@
+// ============================================
+// as 类型转换表 (Casts)
+// ============================================
+// 第 02 章和第 16 章都用 `integer as u8 as char` 演示 as，但没有说明
+// 值放不下时会发生什么。这个模块对每一对基本类型（12 种整数、f32、f64、
+// char、bool），用边界值（MIN、MAX、-1、NaN、±inf、0x110000 等）算出 as 的结果，
+// 并和标准库的 From / TryFrom 对比：
+//
+// - 整数之间：截断到目标类型的位数，按补码重新解释（300i32 as u8 == 44，-1i32 as u32 == u32::MAX）
+// - 浮点数转整数：向零取整并饱和到目标范围，NaN 变成 0
+// - 整数转浮点数、f64 转 f32：取最接近的值，超出范围得到 inf
+// - char 可以转成任意整数（截断），但只有 u8 能用 as 转成 char
+// - bool 可以转成整数，不能转成浮点数；任何类型都不能用 as 转成 bool
+//
+// From 只在转换无损时实现，TryFrom 在值放不下时返回 Err，而 as 从不失败。
+// 所有结果都由真实的 as、from、try_from 表达式算出，表格可以输出到终端，
+// 也可以生成 Markdown 作为书的附录。
+
+use std::fmt;
+
+use crate::unicode;
+
+// ========== 类型 ==========
+
+/// 参与 as 转换的基本类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Type {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+    Char,
+    Bool,
+}
+
+impl Type {
+    pub const ALL: [Type; 16] = [
+        Type::I8,
+        Type::I16,
+        Type::I32,
+        Type::I64,
+        Type::I128,
+        Type::Isize,
+        Type::U8,
+        Type::U16,
+        Type::U32,
+        Type::U64,
+        Type::U128,
+        Type::Usize,
+        Type::F32,
+        Type::F64,
+        Type::Char,
+        Type::Bool,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Type::I8 => "i8",
+            Type::I16 => "i16",
+            Type::I32 => "i32",
+            Type::I64 => "i64",
+            Type::I128 => "i128",
+            Type::Isize => "isize",
+            Type::U8 => "u8",
+            Type::U16 => "u16",
+            Type::U32 => "u32",
+            Type::U64 => "u64",
+            Type::U128 => "u128",
+            Type::Usize => "usize",
+            Type::F32 => "f32",
+            Type::F64 => "f64",
+            Type::Char => "char",
+            Type::Bool => "bool",
+        }
+    }
+
+    /// 按名称查找，例如 "u8"
+    pub fn parse(name: &str) -> Option<Type> {
+        Type::ALL.into_iter().find(|ty| ty.name() == name)
+    }
+
+    pub fn is_integer(self) -> bool {
+        !matches!(self, Type::F32 | Type::F64 | Type::Char | Type::Bool)
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Type::F32 | Type::F64)
+    }
+
+    /// 整数和浮点数
+    pub fn is_numeric(self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// 这个类型的边界值：最小值、最大值、-1、0，浮点数的 NaN 和 ±inf，
+    /// 以及超出 char 范围的 0x110000
+    pub fn samples(self) -> Vec<Value> {
+        fn values<T: Native>(values: &[T]) -> Vec<Value> {
+            values.iter().map(|value| value.store()).collect()
+        }
+        match self {
+            Type::I8 => values(&[i8::MIN, -1, 0, i8::MAX]),
+            Type::I16 => values(&[i16::MIN, -1, 0, i16::MAX]),
+            Type::I32 => values(&[i32::MIN, -1, 0, i32::MAX]),
+            Type::I64 => values(&[i64::MIN, -1, 0, i64::MAX]),
+            Type::I128 => values(&[i128::MIN, -1, 0, i128::MAX]),
+            Type::Isize => values(&[isize::MIN, -1, 0, isize::MAX]),
+            // 65 就是第 02 章 `integer as u8 as char` 中的 'A'
+            Type::U8 => values(&[0, 65, u8::MAX]),
+            Type::U16 => values(&[0, u16::MAX]),
+            Type::U32 => values(&[0, 0x110000, u32::MAX]),
+            Type::U64 => values(&[0, 0x110000, u64::MAX]),
+            Type::U128 => values(&[0, 0x110000, u128::MAX]),
+            Type::Usize => values(&[0, 0x110000, usize::MAX]),
+            Type::F32 => values(&[
+                f32::MIN,
+                -1.9,
+                f32::MAX,
+                f32::NEG_INFINITY,
+                f32::INFINITY,
+                f32::NAN,
+            ]),
+            Type::F64 => values(&[
+                f64::MIN,
+                -1.9,
+                f64::MAX,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NAN,
+            ]),
+            // U+00FF 是 u8 能放下的最后一个字符，😻 需要 17 位，char::MAX 是 U+10FFFF
+            Type::Char => values(&['\0', 'A', 'ÿ', 'Ā', '😻', char::MAX]),
+            Type::Bool => values(&[false, true]),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// ========== 值 ==========
+
+/// 某个基本类型的值；整数扩展到 128 位保存，f32 无损地保存为 f64
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Signed(Type, i128),
+    Unsigned(Type, u128),
+    Float(Type, f64),
+    Char(char),
+    Bool(bool),
+}
+
+impl Value {
+    pub fn ty(self) -> Type {
+        match self {
+            Value::Signed(ty, _) | Value::Unsigned(ty, _) | Value::Float(ty, _) => ty,
+            Value::Char(_) => Type::Char,
+            Value::Bool(_) => Type::Bool,
+        }
+    }
+
+    /// 两个值是否相同；和 == 不同，NaN 与 NaN 相同
+    pub fn same(self, other: Value) -> bool {
+        match (self, other) {
+            (Value::Float(a_ty, a), Value::Float(b_ty, b)) => {
+                a_ty == b_ty && (a == b || a.is_nan() && b.is_nan())
+            }
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Signed(_, n) => write!(f, "{}", n),
+            Value::Unsigned(_, n) => write!(f, "{}", n),
+            // 按原类型的 Debug 格式显示，f32 才不会多出 f64 的尾数
+            Value::Float(Type::F32, x) => write!(f, "{:?}", x as f32),
+            Value::Float(_, x) => write!(f, "{:?}", x),
+            Value::Char(c) => write!(f, "{:?}", c),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// 原生类型和 Value 之间的转换
+trait Native: Copy {
+    /// 取出原生值；`value` 必须正好是这个类型
+    fn load(value: Value) -> Self;
+    fn store(self) -> Value;
+}
+
+macro_rules! impl_native {
+    ($variant:ident, $wide:ty: $($t:ty => $ty:ident),*) => {
+        $(impl Native for $t {
+            fn load(value: Value) -> $t {
+                match value {
+                    Value::$variant(Type::$ty, n) => n as $t,
+                    _ => unreachable!("{:?} 不是 {}", value, stringify!($t)),
+                }
+            }
+
+            fn store(self) -> Value {
+                Value::$variant(Type::$ty, self as $wide)
+            }
+        })*
+    };
+}
+impl_native!(Signed, i128: i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize);
+impl_native!(Unsigned, u128: u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize);
+impl_native!(Float, f64: f32 => F32, f64 => F64);
+
+impl Native for char {
+    fn load(value: Value) -> char {
+        match value {
+            Value::Char(c) => c,
+            _ => unreachable!("{:?} 不是 char", value),
+        }
+    }
+
+    fn store(self) -> Value {
+        Value::Char(self)
+    }
+}
+
+impl Native for bool {
+    fn load(value: Value) -> bool {
+        match value {
+            Value::Bool(b) => b,
+            _ => unreachable!("{:?} 不是 bool", value),
+        }
+    }
+
+    fn store(self) -> Value {
+        Value::Bool(self)
+    }
+}
+
+/// 对列出的每种类型执行同一段代码，代码中用 `T` 表示对应的原生类型，
+/// 其余类型执行 `$other`
+macro_rules! dispatch {
+    ($ty:expr, $t:ident => $body:expr, [$($variant:ident: $native:ty),*], $other:expr) => {
+        match $ty {
+            $(Type::$variant => {
+                type $t = $native;
+                $body
+            })*
+            _ => $other,
+        }
+    };
+}
+
+/// 12 种整数类型
+macro_rules! integer {
+    ($ty:expr, $t:ident => $body:expr, _ => $other:expr) => {
+        dispatch!($ty, $t => $body, [
+            I8: i8, I16: i16, I32: i32, I64: i64, I128: i128, Isize: isize,
+            U8: u8, U16: u16, U32: u32, U64: u64, U128: u128, Usize: usize
+        ], $other)
+    };
+}
+
+/// 整数和浮点数
+macro_rules! numeric {
+    ($ty:expr, $t:ident => $body:expr, _ => $other:expr) => {
+        dispatch!($ty, $t => $body, [
+            I8: i8, I16: i16, I32: i32, I64: i64, I128: i128, Isize: isize,
+            U8: u8, U16: u16, U32: u32, U64: u64, U128: u128, Usize: usize,
+            F32: f32, F64: f64
+        ], $other)
+    };
+}
+
+// ========== as ==========
+
+/// 编译器拒绝的 as 转换
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCast {
+    pub from: Type,
+    pub to: Type,
+}
+
+impl InvalidCast {
+    /// rustc 的错误代码
+    pub fn code(&self) -> &'static str {
+        match self.to {
+            Type::Bool => "E0054",
+            Type::Char => "E0604",
+            _ => "E0606",
+        }
+    }
+
+    pub fn help(&self) -> Option<&'static str> {
+        match (self.from, self.to) {
+            (from, Type::Bool) if from.is_integer() => Some("compare with zero instead: `x != 0`"),
+            (Type::U32, Type::Char) => Some("try `char::from_u32` instead"),
+            (from, Type::Char) if from.is_integer() => {
+                Some("try `char::from_u32` instead (via a `u32`)")
+            }
+            (Type::Char | Type::Bool, to) if to.is_float() => Some("cast through an integer first"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InvalidCast {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error[{}]: ", self.code())?;
+        match self.to {
+            Type::Bool => write!(f, "cannot cast `{}` as `bool`", self.from),
+            Type::Char => write!(f, "only `u8` can be cast as `char`, not `{}`", self.from),
+            _ => write!(f, "casting `{}` as `{}` is invalid", self.from, self.to),
+        }
+    }
+}
+
+impl std::error::Error for InvalidCast {}
+
+/// 计算 `value as to`
+pub fn cast(value: Value, to: Type) -> Result<Value, InvalidCast> {
+    let from = value.ty();
+    let result = match (from, to) {
+        (from, to) if from.is_numeric() && to.is_numeric() => numeric!(from, S => numeric!(
+            to,
+            T => (S::load(value) as T).store(),
+            _ => unreachable!()
+        ), _ => unreachable!()),
+        (Type::Char, to) if to.is_integer() => {
+            integer!(to, T => (char::load(value) as T).store(), _ => unreachable!())
+        }
+        (Type::Bool, to) if to.is_integer() => {
+            integer!(to, T => (bool::load(value) as T).store(), _ => unreachable!())
+        }
+        (Type::U8, Type::Char) => (u8::load(value) as char).store(),
+        // 转换成自身总是允许的
+        (Type::Char, Type::Char) | (Type::Bool, Type::Bool) => value,
+        _ => return Err(InvalidCast { from, to }),
+    };
+    Ok(result)
+}
+
+// ========== From / TryFrom ==========
+
+/// 标准库中把一种类型转换成另一种类型的 trait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trait {
+    /// 无损转换，总是成功
+    From,
+    /// 可能失败的转换，值放不下时返回 Err
+    TryFrom,
+}
+
+impl fmt::Display for Trait {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Trait::From => write!(f, "From"),
+            Trait::TryFrom => write!(f, "TryFrom"),
+        }
+    }
+}
+
+/// 用 From 或 TryFrom 转换的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Conversion {
+    From(Value),
+    /// None 表示 try_from 返回 Err
+    TryFrom(Option<Value>),
+    /// 标准库没有这个方向的 From 或 TryFrom
+    Missing,
+}
+
+impl Conversion {
+    pub fn provided_by(&self) -> Option<Trait> {
+        match self {
+            Conversion::From(_) => Some(Trait::From),
+            Conversion::TryFrom(_) => Some(Trait::TryFrom),
+            Conversion::Missing => None,
+        }
+    }
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Conversion::From(value) => write!(f, "{}", value),
+            Conversion::TryFrom(Some(value)) => write!(f, "Ok({})", value),
+            Conversion::TryFrom(None) => write!(f, "Err"),
+            Conversion::Missing => write!(f, "—"),
+        }
+    }
+}
+
+/// 标准库中的 `impl From<S> for T`（不含 `impl<T> From<T> for T`）。
+/// 每一项都会展开成真实的 `T::from` 调用，列错了就无法编译。
+macro_rules! std_from {
+    ($value:expr, $to:expr, $($s:ident: $st:ty => [$($t:ident: $tt:ty),*];)*) => {
+        match ($value.ty(), $to) {
+            $($((Type::$s, Type::$t) => Some(<$tt>::from(<$st>::load($value)).store()),)*)*
+            _ => None,
+        }
+    };
+}
+
+/// 用 From 或 TryFrom 把 `value` 转换成 `to`
+// 整数之间的 try_from 对所有类型对一起展开，其中无损的那些已经在前面用 from 处理
+#[allow(clippy::unnecessary_fallible_conversions)]
+pub fn convert(value: Value, to: Type) -> Conversion {
+    let from = value.ty();
+    if from == to {
+        return Conversion::From(value);
+    }
+    let lossless = std_from!(value, to,
+        I8: i8 => [I16: i16, I32: i32, I64: i64, I128: i128, Isize: isize, F32: f32, F64: f64];
+        I16: i16 => [I32: i32, I64: i64, I128: i128, Isize: isize, F32: f32, F64: f64];
+        I32: i32 => [I64: i64, I128: i128, F64: f64];
+        I64: i64 => [I128: i128];
+        U8: u8 => [
+            I16: i16, I32: i32, I64: i64, I128: i128, Isize: isize,
+            U16: u16, U32: u32, U64: u64, U128: u128, Usize: usize,
+            F32: f32, F64: f64, Char: char
+        ];
+        U16: u16 => [
+            I32: i32, I64: i64, I128: i128, U32: u32, U64: u64, U128: u128, Usize: usize,
+            F32: f32, F64: f64
+        ];
+        U32: u32 => [I64: i64, I128: i128, U64: u64, U128: u128, F64: f64];
+        U64: u64 => [I128: i128, U128: u128];
+        F32: f32 => [F64: f64];
+        Char: char => [U32: u32, U64: u64, U128: u128];
+        Bool: bool => [
+            I8: i8, I16: i16, I32: i32, I64: i64, I128: i128, Isize: isize,
+            U8: u8, U16: u16, U32: u32, U64: u64, U128: u128, Usize: usize,
+            F32: f32, F64: f64
+        ];
+    );
+    if let Some(value) = lossless {
+        return Conversion::From(value);
+    }
+
+    let fallible = match (from, to) {
+        // 任意两种整数之间都有 TryFrom
+        (from, to) if from.is_integer() && to.is_integer() => integer!(from, S => integer!(
+            to,
+            T => T::try_from(S::load(value)).ok().map(Native::store),
+            _ => unreachable!()
+        ), _ => unreachable!()),
+        (Type::U32, Type::Char) => char::try_from(u32::load(value)).ok().map(Native::store),
+        (Type::Char, Type::U8) => u8::try_from(char::load(value)).ok().map(Native::store),
+        (Type::Char, Type::U16) => u16::try_from(char::load(value)).ok().map(Native::store),
+        _ => return Conversion::Missing,
+    };
+    Conversion::TryFrom(fallible)
+}
+
+// ========== 转换表 ==========
+
+/// 一个边界值转换成目标类型的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub input: Value,
+    pub cast: Result<Value, InvalidCast>,
+    pub conversion: Conversion,
+}
+
+impl Cell {
+    /// 表格中显示的内容：as 合法时显示 as 的结果，TryFrom 会失败时加上 ✗；
+    /// as 不合法时显示 From / TryFrom 的结果，两者都没有时显示 —
+    pub fn text(&self) -> String {
+        match (self.cast, self.conversion) {
+            (Ok(value), Conversion::TryFrom(None)) => format!("{} ✗", value),
+            (Ok(value), _) => value.to_string(),
+            (Err(_), Conversion::From(value) | Conversion::TryFrom(Some(value))) => {
+                value.to_string()
+            }
+            (Err(_), Conversion::TryFrom(None)) => String::from("✗"),
+            (Err(_), Conversion::Missing) => String::from("—"),
+        }
+    }
+}
+
+/// 源类型转换成某个目标类型的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub to: Type,
+    pub cells: Vec<Cell>,
+}
+
+impl Row {
+    /// as 是否合法：合法时为 "as"，否则为 rustc 的错误代码
+    pub fn cast_column(&self) -> &'static str {
+        match self.cells[0].cast {
+            Ok(_) => "as",
+            Err(error) => error.code(),
+        }
+    }
+
+    /// 标准库提供的转换 trait，没有时为 "—"
+    pub fn trait_column(&self) -> &'static str {
+        match self.cells[0].conversion.provided_by() {
+            Some(Trait::From) => "From",
+            Some(Trait::TryFrom) => "TryFrom",
+            None => "—",
+        }
+    }
+}
+
+/// 一种源类型的所有边界值转换成所有类型的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub from: Type,
+    pub samples: Vec<Value>,
+    pub rows: Vec<Row>,
+}
+
+pub fn table(from: Type) -> Table {
+    let samples = from.samples();
+    let rows = Type::ALL
+        .into_iter()
+        .map(|to| Row {
+            to,
+            cells: samples
+                .iter()
+                .map(|&input| Cell {
+                    input,
+                    cast: cast(input, to),
+                    conversion: convert(input, to),
+                })
+                .collect(),
+        })
+        .collect();
+    Table {
+        from,
+        samples,
+        rows,
+    }
+}
+
+/// 所有源类型的转换表
+pub fn tables() -> Vec<Table> {
+    Type::ALL.into_iter().map(table).collect()
+}
+
+/// 表格的图例，每项一行
+pub const LEGEND: [&str; 3] = [
+    "as 列: as 合法时为 as，否则是 rustc 的错误代码",
+    "trait 列: 标准库提供的 From（无损，总是成功）或 TryFrom（可能失败），— 表示都没有",
+    "单元格: as 合法时是 as 的结果，✗ 表示 TryFrom 会返回 Err；as 不合法时是 From / TryFrom 的结果",
+];
+
+impl Table {
+    fn lines(&self) -> Vec<Vec<String>> {
+        let mut header = vec![format!("{} →", self.from), String::new(), String::new()];
+        header.extend(self.samples.iter().map(Value::to_string));
+        let mut lines = vec![header];
+        for row in &self.rows {
+            let mut line = vec![
+                row.to.to_string(),
+                row.cast_column().to_string(),
+                row.trait_column().to_string(),
+            ];
+            line.extend(row.cells.iter().map(Cell::text));
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// 终端中按列对齐的表格
+    pub fn render(&self) -> String {
+        let lines = self.lines();
+        let mut widths = vec![0; lines[0].len()];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(unicode::width(cell));
+            }
+        }
+
+        let mut out = String::new();
+        for line in &lines {
+            let mut text = String::new();
+            for (cell, width) in line.iter().zip(&widths) {
+                text.push_str(cell);
+                text.push_str(&" ".repeat(width - unicode::width(cell) + 2));
+            }
+            out.push_str(text.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Markdown 表格，数值列右对齐
+    pub fn markdown(&self) -> String {
+        let lines = self.lines();
+        let mut out = String::new();
+        for (index, line) in lines.iter().enumerate() {
+            let cells: Vec<String> = if index == 0 {
+                let mut cells = vec![
+                    String::from("目标"),
+                    String::from("as"),
+                    String::from("trait"),
+                ];
+                cells.extend(line[3..].iter().map(|cell| format!("`{}`", cell)));
+                cells
+            } else {
+                let mut cells = vec![format!("`{}`", line[0])];
+                cells.extend(line[1..].iter().cloned());
+                cells
+            };
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if index == 0 {
+                let mut rule = vec![":--"; 3];
+                rule.extend(vec!["--:"; line.len() - 3]);
+                out.push_str(&format!("|{}|\n", rule.join("|")));
+            }
+        }
+        out
+    }
+}
+
+/// 书的附录页面：所有源类型的转换表
+pub fn markdown() -> String {
+    let mut out = String::from("# 附录：as 类型转换\n\n");
+    out.push_str(
+        "第 02 章和第 16 章用 `integer as u8 as char` 演示了 as。\
+         下面的表格列出每种基本类型的边界值用 as 转换成其他类型的结果，\
+         并和标准库的 `From` / `TryFrom` 对比。\n\n",
+    );
+    for line in LEGEND {
+        out.push_str(&format!("- {}\n", line));
+    }
+    for table in tables() {
+        out.push_str(&format!("\n## 从 `{}` 转换\n\n", table.from));
+        out.push_str(&table.markdown());
+    }
+    out
+}
@@ -0,0 +1,106 @@
+// ============================================
+// 章节注册表 (Chapters)
+// ============================================
+// 把 src/01_variables.rs … src/16_keywords.rs 作为模块编译进 crate，
+// 并记录每一章的编号、名称、源码和 main 函数，供 rust-tutorial 命令行调度
+
+// 章节代码是教学示例，故意保留了未使用的变量、函数和不推荐的写法
+#![allow(unused, clippy::all)]
+
+/// 一个教程章节
+pub struct Chapter {
+    /// 章节编号，例如 7
+    pub number: u8,
+    /// 文件名中编号之后的部分，例如 "enums"
+    pub slug: &'static str,
+    /// 章节源文件的完整内容
+    pub source: &'static str,
+    /// 章节的 main 函数
+    pub run: fn(),
+}
+
+impl Chapter {
+    /// 源文件名，例如 "07_enums.rs"
+    pub fn file_name(&self) -> String {
+        format!("{:02}_{}.rs", self.number, self.slug)
+    }
+
+    /// 从文件头部横幅中取出的标题，例如 "枚举 (Enums)"
+    ///
+    /// 横幅的格式为：
+    ///
+    /// ```text
+    /// // ============================================
+    /// // 07. 枚举 (Enums)
+    /// // ============================================
+    /// ```
+    pub fn title(&self) -> &'static str {
+        let line = self
+            .source
+            .lines()
+            .map(|line| line.trim_start_matches("//").trim())
+            .find(|line| !line.is_empty() && !line.starts_with('='))
+            .unwrap_or("");
+        match line.split_once(". ") {
+            Some((number, title)) if number.chars().all(|c| c.is_ascii_digit()) => title,
+            _ => line,
+        }
+    }
+}
+
+// ========== 章节列表 ==========
+
+macro_rules! chapters {
+    ($($number:literal => $slug:ident, $file:literal;)*) => {
+        $(
+            #[path = $file]
+            pub mod $slug;
+        )*
+
+        static CHAPTERS: &[Chapter] = &[
+            $(
+                Chapter {
+                    number: $number,
+                    slug: stringify!($slug),
+                    source: include_str!($file),
+                    run: $slug::main,
+                },
+            )*
+        ];
+    };
+}
+
+chapters! {
+    1 => variables, "01_variables.rs";
+    2 => data_types, "02_data_types.rs";
+    3 => functions, "03_functions.rs";
+    4 => control_flow, "04_control_flow.rs";
+    5 => ownership, "05_ownership.rs";
+    6 => structs, "06_structs.rs";
+    7 => enums, "07_enums.rs";
+    8 => pattern_matching, "08_pattern_matching.rs";
+    9 => collections, "09_collections.rs";
+    10 => packages_modules, "10_packages_modules.rs";
+    11 => error_handling, "11_error_handling.rs";
+    12 => generics, "12_generics.rs";
+    13 => traits, "13_traits.rs";
+    14 => lifetimes, "14_lifetimes.rs";
+    15 => derive_macros, "15_derive_macros.rs";
+    16 => keywords, "16_keywords.rs";
+}
+
+/// 按编号顺序返回所有章节
+pub fn all() -> &'static [Chapter] {
+    CHAPTERS
+}
+
+/// 按编号（"7"、"07"）、名称（"enums"）或文件名（"07_enums"）查找章节
+pub fn find(query: &str) -> Option<&'static Chapter> {
+    let query = query.trim().trim_end_matches(".rs");
+    if let Ok(number) = query.parse::<u8>() {
+        return CHAPTERS.iter().find(|chapter| chapter.number == number);
+    }
+    CHAPTERS.iter().find(|chapter| {
+        chapter.slug == query || chapter.file_name().trim_end_matches(".rs") == query
+    })
+}
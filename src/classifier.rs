@@ -0,0 +1,636 @@
+// ============================================
+// 范围规则分类器 (Classifier)
+// ============================================
+// 第 04 章和第 08 章用 match 把数字分组：1..=10、11..=20、21..=50 各是一组，
+// 1 | 2 | 3 是“小”，其他的落到 _。这个模块把这样的 match 写成一个规则文件，
+// 在运行时加载，用来给数值或一整串输入分桶（例如统计指标的分布）：
+//
+//     # 注释
+//     type u16                                    值的类型，默认 i32
+//     1..=10 => 1 到 10                           范围：a..=b、a..b、a..、..=b、..b
+//     1 | 2 | 3 => 小                             多个模式用 | 分隔
+//     n @ 300..=399 if n % 2 == 0 => 偶数重定向   绑定和 guard（条件用 && 连接）
+//     _ => 其他                                   通配符，也可以只写一个绑定名
+//
+// 和 match 一样，值按顺序尝试每条规则，第一条匹配的规则决定分组。
+// check 按 rustc 的穷尽性检查报告问题：
+//
+// - 被前面的规则完全覆盖、永远不会匹配的模式（unreachable pattern）
+// - 和前面的规则部分重叠、互不包含的范围，rustc 只在端点重合时警告，这里报告任何这样的重叠
+// - 没有被任何规则覆盖的值（E0004），和 rustc 一样，带 guard 的规则不算覆盖
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::integer::{self, IntType};
+use crate::unicode;
+
+// ========== 区间集合 ==========
+
+/// 互不相交、按顺序排列的闭区间集合
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet(Vec<(i128, i128)>);
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet(Vec::new())
+    }
+
+    pub fn intervals(&self) -> &[(i128, i128)] {
+        &self.0
+    }
+
+    /// 加入 lo..=hi，和已有的区间合并（相邻的也合并）
+    pub fn insert(&mut self, lo: i128, hi: i128) {
+        let mut merged = (lo, hi);
+        let mut kept = Vec::with_capacity(self.0.len() + 1);
+        for &(a, b) in &self.0 {
+            if b.saturating_add(1) < merged.0 || merged.1.saturating_add(1) < a {
+                kept.push((a, b));
+            } else {
+                merged = (merged.0.min(a), merged.1.max(b));
+            }
+        }
+        kept.push(merged);
+        kept.sort_unstable();
+        self.0 = kept;
+    }
+
+    /// lo..=hi 是否被完全覆盖
+    pub fn covers(&self, lo: i128, hi: i128) -> bool {
+        self.0.iter().any(|&(a, b)| a <= lo && hi <= b)
+    }
+
+    /// min..=max 中没有被覆盖的部分
+    pub fn gaps(&self, min: i128, max: i128) -> Vec<(i128, i128)> {
+        let mut gaps = Vec::new();
+        let mut next = Some(min);
+        for &(a, b) in &self.0 {
+            let Some(start) = next else { break };
+            if b < start {
+                continue;
+            }
+            if a > max {
+                break;
+            }
+            if a > start {
+                gaps.push((start, a - 1));
+            }
+            next = b.checked_add(1);
+        }
+        if let Some(start) = next.filter(|&start| start <= max) {
+            gaps.push((start, max));
+        }
+        gaps
+    }
+}
+
+// ========== 规则 ==========
+
+/// 一个模式：值的闭区间，单个值和通配符也表示成区间
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternative {
+    /// 规则文件中的写法
+    pub text: String,
+    pub lo: i128,
+    pub hi: i128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    /// 两个字符的运算符排在前面，先尝试
+    const ALL: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ];
+
+    fn test(self, lhs: i128, rhs: i128) -> bool {
+        match self {
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+        }
+    }
+}
+
+/// guard 中的一个条件：`n op 常数` 或 `n % 除数 op 常数`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    modulus: Option<i128>,
+    comparison: Comparison,
+    rhs: i128,
+}
+
+impl Condition {
+    fn test(&self, value: i128) -> bool {
+        // 和 Rust 的 % 一样，余数的符号跟随被除数
+        let lhs = match self.modulus {
+            Some(modulus) => value % modulus,
+            None => value,
+        };
+        self.comparison.test(lhs, self.rhs)
+    }
+}
+
+/// `if` 后面的条件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guard {
+    pub text: String,
+    conditions: Vec<Condition>,
+}
+
+impl Guard {
+    pub fn test(&self, value: i128) -> bool {
+        self.conditions.iter().all(|c| c.test(value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// 在规则文件中的行号，从 1 开始
+    pub line: usize,
+    pub binding: Option<String>,
+    pub alternatives: Vec<Alternative>,
+    pub guard: Option<Guard>,
+    pub label: String,
+}
+
+impl Rule {
+    pub fn matches(&self, value: i128) -> bool {
+        self.alternatives
+            .iter()
+            .any(|alt| alt.lo <= value && value <= alt.hi)
+            && self.guard.as_ref().is_none_or(|guard| guard.test(value))
+    }
+}
+
+/// 规则文件中的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 行: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ========== 诊断 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// 模式被前面的规则完全覆盖
+    Unreachable {
+        line: usize,
+        pattern: String,
+        covered_by: Vec<usize>,
+    },
+    /// 范围和前面某条规则部分重叠，两者互不包含
+    Overlap {
+        line: usize,
+        pattern: String,
+        other: usize,
+        lo: i128,
+        hi: i128,
+    },
+    /// 没有被覆盖的值
+    NonExhaustive {
+        ty: IntType,
+        gaps: Vec<(i128, i128)>,
+    },
+}
+
+/// 按 rustc 的写法显示区间端点：有符号类型的最小值写成 i32::MIN，最大值写成 u8::MAX，
+/// 其他值带类型后缀，例如 0_u8
+fn endpoint(ty: IntType, value: i128) -> String {
+    if ty.signed() && Some(value) == ty.min().to_i128() {
+        format!("{}::MIN", ty)
+    } else if Some(value) == ty.max().to_i128() {
+        format!("{}::MAX", ty)
+    } else {
+        format!("{}_{}", value, ty)
+    }
+}
+
+/// 区间模式，例如 `i32::MIN..=0_i32`，只有一个值时就是这个值
+pub fn range_pattern(ty: IntType, lo: i128, hi: i128) -> String {
+    if lo == hi {
+        endpoint(ty, lo)
+    } else {
+        format!("{}..={}", endpoint(ty, lo), endpoint(ty, hi))
+    }
+}
+
+/// 像 rustc 一样最多列出 3 个：`a`、`a` and `b`、`a`, `b` and `c`、`a`, `b`, `c` and 2 more
+pub fn not_covered(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| format!("`{}`", item)).collect();
+    match quoted.len() {
+        0 => String::from("`_`"),
+        1 => quoted[0].clone(),
+        2 | 3 => format!(
+            "{} and {}",
+            quoted[..quoted.len() - 1].join(", "),
+            quoted[quoted.len() - 1]
+        ),
+        n => format!("{} and {} more", quoted[..3].join(", "), n - 3),
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::Unreachable {
+                line,
+                pattern,
+                covered_by,
+            } => {
+                let lines: Vec<String> = covered_by.iter().map(|l| l.to_string()).collect();
+                write!(
+                    f,
+                    "第 {} 行: warning: unreachable pattern `{}`（已被第 {} 行覆盖）",
+                    line,
+                    pattern,
+                    lines.join("、")
+                )
+            }
+            Diagnostic::Overlap {
+                line,
+                pattern,
+                other,
+                lo,
+                hi,
+            } => write!(
+                f,
+                "第 {} 行: warning: `{}` 与第 {} 行在 {} 上重叠",
+                line,
+                pattern,
+                other,
+                if lo == hi {
+                    lo.to_string()
+                } else {
+                    format!("{}..={}", lo, hi)
+                }
+            ),
+            Diagnostic::NonExhaustive { ty, gaps } => {
+                let gaps: Vec<String> = gaps
+                    .iter()
+                    .map(|&(lo, hi)| range_pattern(*ty, lo, hi))
+                    .collect();
+                write!(
+                    f,
+                    "error[E0004]: non-exhaustive patterns: {} not covered",
+                    not_covered(&gaps)
+                )
+            }
+        }
+    }
+}
+
+// ========== 规则集 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    pub ty: IntType,
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn parse(text: &str) -> Result<RuleSet, ParseError> {
+        let mut ty = IntType::I32;
+        let mut lines = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| ParseError {
+                line: i + 1,
+                message,
+            };
+            if let Some(name) = line.strip_prefix("type ") {
+                if !lines.is_empty() {
+                    return Err(error(String::from("type 必须写在所有规则之前")));
+                }
+                ty = match IntType::parse(name.trim()) {
+                    // u128 的上界超出了 i128，区间计算放不下
+                    Some(IntType::U128) => return Err(error(String::from("不支持 u128"))),
+                    Some(ty) => ty,
+                    None => return Err(error(format!("不认识的类型 `{}`", name.trim()))),
+                };
+                continue;
+            }
+            lines.push((i + 1, line));
+        }
+
+        let mut rules = Vec::new();
+        for (line, text) in lines {
+            rules.push(parse_rule(ty, line, text).map_err(|message| ParseError { line, message })?);
+        }
+        Ok(RuleSet { ty, rules })
+    }
+
+    fn min(&self) -> i128 {
+        self.ty.min().to_i128().unwrap_or(i128::MIN)
+    }
+
+    fn max(&self) -> i128 {
+        self.ty.max().to_i128().unwrap_or(i128::MAX)
+    }
+
+    /// 按 rustc 的穷尽性检查报告问题，顺序和规则一致，未覆盖的值放在最后
+    pub fn check(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        // 只有不带 guard 的规则算覆盖
+        let mut covered = IntervalSet::new();
+        let mut earlier: Vec<(usize, &Alternative)> = Vec::new();
+        for rule in &self.rules {
+            let mut reachable = Vec::new();
+            for alt in &rule.alternatives {
+                let overlapping: Vec<(usize, &Alternative)> = earlier
+                    .iter()
+                    .filter(|(_, other)| other.lo <= alt.hi && alt.lo <= other.hi)
+                    .copied()
+                    .collect();
+                if covered.covers(alt.lo, alt.hi) {
+                    let mut covered_by: Vec<usize> =
+                        overlapping.iter().map(|(line, _)| *line).collect();
+                    covered_by.dedup();
+                    diagnostics.push(Diagnostic::Unreachable {
+                        line: rule.line,
+                        pattern: alt.text.clone(),
+                        covered_by,
+                    });
+                    continue;
+                }
+                // 先写特例、再写包含它的一般情况是常见写法（例如 304 在 300..400 前面），
+                // 只有两个范围互不包含时才报告
+                for (line, other) in overlapping {
+                    if alt.lo > other.lo || alt.hi < other.hi {
+                        diagnostics.push(Diagnostic::Overlap {
+                            line: rule.line,
+                            pattern: alt.text.clone(),
+                            other: line,
+                            lo: alt.lo.max(other.lo),
+                            hi: alt.hi.min(other.hi),
+                        });
+                    }
+                }
+                reachable.push(alt);
+            }
+            // 不可达的模式不算进后面报告的“已被第几行覆盖”
+            if rule.guard.is_none() {
+                for alt in reachable {
+                    covered.insert(alt.lo, alt.hi);
+                    earlier.push((rule.line, alt));
+                }
+            }
+        }
+        let gaps = covered.gaps(self.min(), self.max());
+        if !gaps.is_empty() {
+            diagnostics.push(Diagnostic::NonExhaustive { ty: self.ty, gaps });
+        }
+        diagnostics
+    }
+
+    /// 第一条匹配的规则；超出类型范围或没有规则匹配时返回 None
+    pub fn classify(&self, value: i128) -> Option<&Rule> {
+        if value < self.min() || value > self.max() {
+            return None;
+        }
+        self.rules.iter().find(|rule| rule.matches(value))
+    }
+
+    /// 把一段文本当作字面量解析后分类，写法和规则文件中的值一样
+    pub fn classify_text(&self, text: &str) -> Result<Option<&Rule>, integer::Error> {
+        let value = integer::operand(text, self.ty)?;
+        Ok(value.to_i128().and_then(|value| self.classify(value)))
+    }
+
+    /// 统计输入中每个分组的值有多少个，每行可以有多个用空白分隔的值
+    pub fn tally(&self, input: impl BufRead) -> io::Result<Tally> {
+        let mut tally = Tally::default();
+        let mut index = HashMap::new();
+        for rule in &self.rules {
+            index.entry(rule.label.as_str()).or_insert_with(|| {
+                tally.counts.push((rule.label.clone(), 0));
+                tally.counts.len() - 1
+            });
+        }
+        for (i, line) in input.lines().enumerate() {
+            for token in line?.split_whitespace() {
+                match self.classify_text(token) {
+                    Ok(Some(rule)) => tally.counts[index[rule.label.as_str()]].1 += 1,
+                    Ok(None) => tally.unmatched += 1,
+                    Err(_) => tally.invalid.push((i + 1, token.to_string())),
+                }
+            }
+        }
+        Ok(tally)
+    }
+}
+
+/// tally 的结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// 每个分组的值个数，按分组在规则文件中第一次出现的顺序
+    pub counts: Vec<(String, usize)>,
+    /// 没有规则匹配的值
+    pub unmatched: usize,
+    /// 无法解析或超出类型范围的值：(行号, 原文)
+    pub invalid: Vec<(usize, String)>,
+}
+
+impl Tally {
+    pub fn render(&self) -> String {
+        let width = self
+            .counts
+            .iter()
+            .map(|(label, _)| unicode::width(label))
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for (label, count) in &self.counts {
+            let pad = width - unicode::width(label);
+            out += &format!("{}{}  {}\n", label, " ".repeat(pad), count);
+        }
+        if self.unmatched > 0 {
+            out += &format!("没有匹配的规则: {}\n", self.unmatched);
+        }
+        for (line, text) in &self.invalid {
+            out += &format!("第 {} 行无法解析: {}\n", line, text);
+        }
+        out
+    }
+}
+
+// ========== 解析 ==========
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn literal(ty: IntType, text: &str) -> Result<i128, String> {
+    integer::operand(text, ty)
+        .map_err(|e| format!("`{}`: {}", text, e))?
+        .to_i128()
+        .ok_or_else(|| format!("`{}` 超出了 i128 的范围", text))
+}
+
+/// `模式 [if 条件] => 分组`
+fn parse_rule(ty: IntType, line: usize, text: &str) -> Result<Rule, String> {
+    let (head, label) = text
+        .split_once("=>")
+        .ok_or_else(|| String::from("缺少 `=>`"))?;
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(String::from("`=>` 后面缺少分组名"));
+    }
+    let (patterns, guard) = match head.split_once(" if ") {
+        Some((patterns, guard)) => (patterns.trim(), Some(guard.trim())),
+        None => (head.trim(), None),
+    };
+
+    // n、n @ 模式、n @ (模式 | 模式)
+    let (binding, patterns) = match patterns.split_once('@') {
+        Some((name, rest)) => {
+            let rest = rest.trim();
+            let rest = match rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+                Some(inner) => inner,
+                None if rest.contains('|') => {
+                    return Err(String::from(
+                        "绑定多个模式时要加括号，例如 n @ (1 | 2)（否则 n 只绑定第一个模式，E0408）",
+                    ));
+                }
+                None => rest,
+            };
+            (Some(name.trim()), rest)
+        }
+        None if is_identifier(patterns) && patterns != "_" => (Some(patterns), "_"),
+        None => (None, patterns),
+    };
+    if let Some(name) = binding.filter(|name| !is_identifier(name)) {
+        return Err(format!("`{}` 不是合法的变量名", name));
+    }
+
+    let min = ty.min().to_i128().unwrap_or(i128::MIN);
+    let max = ty.max().to_i128().unwrap_or(i128::MAX);
+    let mut alternatives = Vec::new();
+    for pattern in patterns.split('|').map(str::trim) {
+        let (lo, hi) = if pattern == "_" {
+            (min, max)
+        } else if let Some((start, end)) = pattern.split_once("..=") {
+            let lo = if start.trim().is_empty() {
+                min
+            } else {
+                literal(ty, start.trim())?
+            };
+            let hi = literal(ty, end.trim())?;
+            if lo > hi {
+                return Err(format!(
+                    "`{}`: lower range bound must be less than or equal to upper (E0030)",
+                    pattern
+                ));
+            }
+            (lo, hi)
+        } else if let Some((start, end)) = pattern.split_once("..") {
+            let lo = if start.trim().is_empty() {
+                min
+            } else {
+                literal(ty, start.trim())?
+            };
+            if end.trim().is_empty() {
+                (lo, max)
+            } else {
+                let end = literal(ty, end.trim())?;
+                if lo >= end {
+                    return Err(format!(
+                        "`{}`: lower range bound must be less than upper (E0579)",
+                        pattern
+                    ));
+                }
+                (lo, end - 1)
+            }
+        } else {
+            let value = literal(ty, pattern)?;
+            (value, value)
+        };
+        alternatives.push(Alternative {
+            text: pattern.to_string(),
+            lo,
+            hi,
+        });
+    }
+
+    let guard = match guard {
+        Some(text) => Some(parse_guard(ty, text, binding)?),
+        None => None,
+    };
+
+    Ok(Rule {
+        line,
+        binding: binding.map(str::to_string),
+        alternatives,
+        guard,
+        label: label.to_string(),
+    })
+}
+
+fn parse_guard(ty: IntType, text: &str, binding: Option<&str>) -> Result<Guard, String> {
+    let mut conditions = Vec::new();
+    for part in text.split("&&").map(str::trim) {
+        let (lhs, comparison, rhs) = Comparison::ALL
+            .iter()
+            .find_map(|&(op, comparison)| {
+                part.split_once(op)
+                    .map(|(lhs, rhs)| (lhs.trim(), comparison, rhs.trim()))
+            })
+            .ok_or_else(|| format!("不认识的条件 `{}`", part))?;
+        let (name, modulus) = match lhs.split_once('%') {
+            Some((name, modulus)) => {
+                let modulus = literal(ty, modulus.trim())?;
+                if modulus == 0 {
+                    return Err(format!("`{}`: 除数为零", part));
+                }
+                (name.trim(), Some(modulus))
+            }
+            None => (lhs, None),
+        };
+        if Some(name) != binding {
+            return Err(format!(
+                "cannot find value `{}` in this scope (E0425)：条件中只能使用模式绑定的变量",
+                name
+            ));
+        }
+        conditions.push(Condition {
+            modulus,
+            comparison,
+            rhs: literal(ty, rhs)?,
+        });
+    }
+    Ok(Guard {
+        text: text.to_string(),
+        conditions,
+    })
+}
@@ -0,0 +1,163 @@
+// ============================================
+// 函数组合子 (Combinators)
+// ============================================
+// 第 03 章的 apply_twice 只接受 fn(i32) -> i32 函数指针，identity 只是把参数原样返回。
+// 这个模块把它们推广成一组泛型组合子，参数可以是普通函数，也可以是捕获了环境的闭包：
+//
+// - identity、apply_twice、apply_n：恒等函数和重复调用
+// - compose、pipe：把两个函数串起来，compose(f, g) 先调用 g，pipe(f, g) 先调用 f
+// - flip：交换二元函数的两个参数
+// - curry、uncurry（以及三元的 curry3、uncurry3）：在 f(a, b) 和 f(a)(b) 之间转换
+// - memoize：缓存 Fn(K) -> V 的结果，同一个参数只调用一次
+// - once：包装 FnOnce，第一次调用返回 Some，之后返回 None
+//
+// 闭包按捕获方式实现 Fn、FnMut、FnOnce 三个 trait 中的一个或几个：
+// 只读取捕获的变量时实现全部三个，修改时只实现 FnMut 和 FnOnce，
+// 把捕获的值移动出去时只实现 FnOnce。组合子对参数的要求尽量宽松：
+// 只调用一次的用 FnOnce，调用多次的用 FnMut，需要通过 & 共享的才要求 Fn。
+// compose 有 compose_mut、compose_once 两个变体，组合结果的 trait 和参数一致。
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+// ========== 恒等和重复 ==========
+
+/// 原样返回参数
+pub fn identity<T>(x: T) -> T {
+    x
+}
+
+/// 调用两次：f(f(x))
+pub fn apply_twice<T>(f: impl FnMut(T) -> T, x: T) -> T {
+    apply_n(f, 2, x)
+}
+
+/// 调用 n 次：f(f(...f(x)))；n 为 0 时返回 x
+pub fn apply_n<T>(mut f: impl FnMut(T) -> T, n: usize, x: T) -> T {
+    let mut value = x;
+    for _ in 0..n {
+        value = f(value);
+    }
+    value
+}
+
+// ========== 组合 ==========
+
+/// 数学上的组合 f ∘ g：先调用 g，再把结果传给 f
+pub fn compose<A, B, C>(f: impl Fn(B) -> C, g: impl Fn(A) -> B) -> impl Fn(A) -> C {
+    move |x| f(g(x))
+}
+
+/// compose 的 FnMut 版本，组合结果也只能通过 &mut 调用
+pub fn compose_mut<A, B, C>(
+    mut f: impl FnMut(B) -> C,
+    mut g: impl FnMut(A) -> B,
+) -> impl FnMut(A) -> C {
+    move |x| f(g(x))
+}
+
+/// compose 的 FnOnce 版本，组合结果只能调用一次
+pub fn compose_once<A, B, C>(
+    f: impl FnOnce(B) -> C,
+    g: impl FnOnce(A) -> B,
+) -> impl FnOnce(A) -> C {
+    move |x| f(g(x))
+}
+
+/// 按书写顺序组合：先调用 f，再把结果传给 g，即 compose(g, f)
+pub fn pipe<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+    compose(g, f)
+}
+
+/// 交换二元函数的两个参数：flip(f)(b, a) == f(a, b)
+pub fn flip<A, B, C>(f: impl Fn(A, B) -> C) -> impl Fn(B, A) -> C {
+    move |b, a| f(a, b)
+}
+
+// ========== 柯里化 ==========
+
+// 返回的内层闭包要在外层闭包返回之后继续使用 f，
+// 多个内层闭包可能同时存在，所以 f 放在 Rc 中共享；
+// 内层闭包可以调用多次，每次都要克隆捕获的参数；
+// Fn(A) -> ... 的返回类型中不能写 impl Trait，所以内层闭包装箱成 Box<dyn Fn>
+
+/// 把二元函数变成一元函数的链：curry(f)(a)(b) == f(a, b)
+pub fn curry<A, B, C, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> C>
+where
+    A: Clone + 'static,
+    B: 'static,
+    C: 'static,
+    F: Fn(A, B) -> C + 'static,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = Rc::clone(&f);
+        Box::new(move |b| f(a.clone(), b))
+    }
+}
+
+/// 把三元函数变成一元函数的链：curry3(f)(a)(b)(c) == f(a, b, c)
+#[allow(clippy::type_complexity)] // 返回类型就是要演示的嵌套闭包
+pub fn curry3<A, B, C, D, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> Box<dyn Fn(C) -> D>>
+where
+    A: Clone + 'static,
+    B: Clone + 'static,
+    C: 'static,
+    D: 'static,
+    F: Fn(A, B, C) -> D + 'static,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = Rc::clone(&f);
+        Box::new(move |b| {
+            let (f, a) = (Rc::clone(&f), a.clone());
+            Box::new(move |c| f(a.clone(), b.clone(), c))
+        })
+    }
+}
+
+/// curry 的逆操作：uncurry(f)(a, b) == f(a)(b)
+pub fn uncurry<A, B, C, G>(f: impl Fn(A) -> G) -> impl Fn(A, B) -> C
+where
+    G: FnOnce(B) -> C,
+{
+    move |a, b| f(a)(b)
+}
+
+/// curry3 的逆操作：uncurry3(f)(a, b, c) == f(a)(b)(c)
+pub fn uncurry3<A, B, C, D, G, H>(f: impl Fn(A) -> G) -> impl Fn(A, B, C) -> D
+where
+    G: FnOnce(B) -> H,
+    H: FnOnce(C) -> D,
+{
+    move |a, b, c| f(a)(b)(c)
+}
+
+// ========== 缓存和一次性调用 ==========
+
+/// 缓存 f 的结果：同一个参数第二次调用时直接返回缓存的值，不再调用 f。
+/// 返回的闭包是 Fn，缓存放在 RefCell 中，通过 & 调用也能修改
+pub fn memoize<K, V>(f: impl Fn(K) -> V) -> impl Fn(K) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    let cache = RefCell::new(HashMap::new());
+    move |key: K| {
+        if let Some(value) = cache.borrow().get(&key) {
+            return V::clone(value);
+        }
+        let value = f(key.clone());
+        cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+}
+
+/// 包装只能调用一次的 FnOnce：第一次调用返回 Some(f(x))，之后都返回 None。
+/// f 放在 Option 中，调用时用 take 把它移出来
+pub fn once<A, T>(f: impl FnOnce(A) -> T) -> impl FnMut(A) -> Option<T> {
+    let mut f = Some(f);
+    move |x| f.take().map(|f| f(x))
+}
@@ -0,0 +1,302 @@
+// ============================================
+// 编译失败示例 (Compile-fail Cases)
+// ============================================
+// 章节中有很多被注释掉的代码，用来说明“这样写会编译错误”，例如：
+//
+//     // x = 6; // error: cannot assign twice to immutable variable
+//
+// 这里为每一处这样的注释登记一个用例：取消注释后单独用 rustc 编译
+// 该章节，确认它确实编译失败，并且产生的是注释所说的那个错误码。
+// 错误码只能手工登记；tests/compile_fail.rs 会扫描章节源码，
+// 章节里新增了这样的注释却没有登记用例时测试失败
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Range;
+
+use crate::chapters::{self, Chapter};
+use crate::toolchain;
+
+/// 一个编译失败用例
+#[derive(Debug, Clone, Copy)]
+pub struct Case {
+    /// 所在章节的名称，例如 "variables"
+    pub chapter: &'static str,
+    /// 被注释掉的第一行（去掉缩进后与源文件完全一致）
+    pub anchor: &'static str,
+    /// 从 anchor 开始需要取消注释的连续行数
+    pub lines: usize,
+    /// 期望的错误码，例如 "E0384"
+    pub code: &'static str,
+}
+
+/// 所有登记的用例
+pub static CASES: &[Case] = &[
+    Case {
+        chapter: "variables",
+        anchor: "// x = 6; // error: cannot assign twice to immutable variable",
+        lines: 1,
+        code: "E0384",
+    },
+    Case {
+        chapter: "variables",
+        anchor: "// println!(\"{}\", scoped_var); // 编译错误",
+        lines: 1,
+        code: "E0425",
+    },
+    Case {
+        chapter: "ownership",
+        anchor: "// println!(\"{}\", s1); // 错误！s1 不再有效",
+        lines: 1,
+        code: "E0382",
+    },
+    Case {
+        chapter: "ownership",
+        anchor: "// println!(\"{}\", s); // 错误！s 不再有效",
+        lines: 1,
+        code: "E0382",
+    },
+    Case {
+        chapter: "ownership",
+        anchor: "// let r3 = &mut s; // 错误！不能同时有可变和不可变引用",
+        lines: 1,
+        code: "E0502",
+    },
+    // 只取消这一行时 dangle 仍是注释，所以错误是找不到函数；
+    // dangle 本身的错误由下一个用例检查
+    Case {
+        chapter: "ownership",
+        anchor: "// let reference_to_nothing = dangle(); // 编译错误",
+        lines: 1,
+        code: "E0425",
+    },
+    Case {
+        chapter: "ownership",
+        anchor: "// fn dangle() -> &String {",
+        lines: 4,
+        code: "E0106",
+    },
+    Case {
+        chapter: "enums",
+        anchor: "// let sum = x + y; // 错误！不能直接相加",
+        lines: 1,
+        code: "E0277",
+    },
+    Case {
+        chapter: "collections",
+        anchor: "// let h = hello[0]; // 错误！",
+        lines: 1,
+        code: "E0277",
+    },
+    Case {
+        chapter: "packages_modules",
+        anchor: "// meal.seasonal_fruit = String::from(\"blueberries\"); // 错误！私有字段",
+        lines: 1,
+        code: "E0616",
+    },
+    Case {
+        chapter: "lifetimes",
+        anchor: "// fn dangle() -> &String {",
+        lines: 4,
+        code: "E0106",
+    },
+    Case {
+        chapter: "derive_macros",
+        anchor: "// #[derive(Copy, Clone)]  // 这会编译错误",
+        lines: 4,
+        code: "E0204",
+    },
+];
+
+/// 检查用例时可能出现的问题
+#[derive(Debug)]
+pub enum Error {
+    /// 用例引用的章节不存在
+    UnknownChapter(&'static str),
+    /// 在章节源码中找不到 anchor，或找到不止一处
+    AnchorNotFound { file: String, anchor: &'static str },
+    /// anchor 之后的某一行不是注释，无法取消注释
+    NotCommented { file: String, line: usize },
+    /// 未修改的章节本身就无法单独编译，用例没有意义
+    BaselineFailed { file: String, codes: Vec<String> },
+    /// 取消注释后仍然编译成功
+    Compiled { file: String },
+    /// 编译失败了，但错误码不是期望的那个
+    WrongCode {
+        file: String,
+        expected: &'static str,
+        actual: Vec<String>,
+    },
+    /// 无法调用 rustc 或读写临时文件
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownChapter(name) => write!(f, "找不到章节 {}", name),
+            Error::AnchorNotFound { file, anchor } => {
+                write!(f, "{} 中没有唯一的一行 `{}`", file, anchor)
+            }
+            Error::NotCommented { file, line } => {
+                write!(f, "{}:{} 不是注释行，无法取消注释", file, line)
+            }
+            Error::BaselineFailed { file, codes } => {
+                write!(f, "{} 本身无法单独编译: {:?}", file, codes)
+            }
+            Error::Compiled { file } => write!(f, "{} 取消注释后仍然编译成功", file),
+            Error::WrongCode {
+                file,
+                expected,
+                actual,
+            } => write!(f, "{} 期望错误 {}，实际得到 {:?}", file, expected, actual),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl Case {
+    /// 用例所在的章节
+    pub fn chapter(&self) -> Result<&'static Chapter, Error> {
+        chapters::find(self.chapter).ok_or(Error::UnknownChapter(self.chapter))
+    }
+
+    /// 生成取消注释后的章节源码，以及被取消注释的行号范围（从 1 开始）
+    pub fn snippet(&self) -> Result<(String, Range<usize>), Error> {
+        let chapter = self.chapter()?;
+        let mut lines: Vec<String> = chapter.source.lines().map(String::from).collect();
+
+        let mut found = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.trim() == self.anchor)
+            .map(|(index, _)| index);
+        let start = match (found.next(), found.next()) {
+            (Some(start), None) => start,
+            _ => {
+                return Err(Error::AnchorNotFound {
+                    file: chapter.file_name(),
+                    anchor: self.anchor,
+                });
+            }
+        };
+
+        for index in start..start + self.lines {
+            let line = lines.get(index).ok_or(Error::NotCommented {
+                file: chapter.file_name(),
+                line: index + 1,
+            })?;
+            let indent = line.len() - line.trim_start().len();
+            let Some(code) = line[indent..].strip_prefix("//") else {
+                return Err(Error::NotCommented {
+                    file: chapter.file_name(),
+                    line: index + 1,
+                });
+            };
+            let code = code.strip_prefix(' ').unwrap_or(code);
+            lines[index] = format!("{}{}", &line[..indent], code);
+        }
+
+        let mut source = lines.join("\n");
+        source.push('\n');
+        Ok((source, start + 1..start + self.lines + 1))
+    }
+
+    /// 编译取消注释后的章节，确认在取消注释的那几行上产生了期望的错误码
+    pub fn check(&self) -> Result<(), Error> {
+        let chapter = self.chapter()?;
+        let file = chapter.file_name();
+
+        let baseline = compile(chapter, chapter.source)?;
+        if !baseline.is_empty() {
+            return Err(Error::BaselineFailed {
+                file,
+                codes: baseline.into_iter().map(|d| d.code).collect(),
+            });
+        }
+
+        let (source, lines) = self.snippet()?;
+        let diagnostics = compile(chapter, &source)?;
+        if diagnostics.is_empty() {
+            Err(Error::Compiled { file })
+        } else if diagnostics
+            .iter()
+            .any(|d| d.code == self.code && lines.contains(&d.line))
+        {
+            Ok(())
+        } else {
+            Err(Error::WrongCode {
+                file,
+                expected: self.code,
+                actual: diagnostics
+                    .into_iter()
+                    .map(|d| format!("{} (第 {} 行)", d.code, d.line))
+                    .collect(),
+            })
+        }
+    }
+}
+
+// ========== 调用 rustc ==========
+
+/// rustc 报告的一个错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 错误码，例如 "E0384"；没有错误码的错误（如 deny 级别的 lint）记为 "error"
+    pub code: String,
+    /// 错误所在的行号，相对于章节源文件（从 1 开始）
+    pub line: usize,
+}
+
+/// 单独编译一章的源码，返回章节文件中的所有错误；编译成功时返回空列表
+pub fn compile(chapter: &Chapter, source: &str) -> Result<Vec<Diagnostic>, Error> {
+    let dir = toolchain::scratch_dir("compile-fail")?;
+    let file = dir.join(chapter.file_name());
+    fs::write(&file, source)?;
+    let output = toolchain::chapter_crate(&dir, chapter.slug, &file, false)
+        .and_then(|root| toolchain::compile(&root, "lib", &dir));
+    fs::remove_dir_all(&dir)?;
+    let output = output?;
+
+    if output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let diagnostics = parse_diagnostics(&stderr, &chapter.file_name());
+    if diagnostics.is_empty() {
+        // 编译失败却没有可以解析的错误行，例如 rustc 本身无法启动
+        return Err(Error::Io(io::Error::other(stderr.into_owned())));
+    }
+    Ok(diagnostics)
+}
+
+/// 解析 `--error-format short` 的输出中属于 `file_name` 的错误，每个错误形如
+/// `/tmp/.../01_variables.rs:13:5: error[E0384]: cannot assign twice to immutable variable `x``
+fn parse_diagnostics(stderr: &str, file_name: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let (location, message) = line.split_once(": error")?;
+            let code = match message.strip_prefix('[') {
+                Some(rest) => rest.split_once(']')?.0.to_string(),
+                None => String::from("error"),
+            };
+            let mut parts = location.rsplitn(3, ':');
+            let _column = parts.next()?;
+            let line = parts.next()?.parse().ok()?;
+            let path = parts.next()?;
+            path.ends_with(file_name)
+                .then_some(Diagnostic { code, line })
+        })
+        .collect()
+}
@@ -0,0 +1,558 @@
+// ============================================
+// 练习模式 (Exercises)
+// ============================================
+// 类似 rustlings：从每一章的编号段落中挑出一处，把它“改坏”
+// （挖空表达式、去掉 mut、删掉生命周期注解），学习者在练习目录中
+// 修好它，再用本地工具链离线编译运行，与原章节中这一段的输出比较
+//
+// 练习编号由章节号和段落的编号组成，例如第 01 章 `// 2. 可变变量` 的练习是 "01-2"；
+// 同一章中编号重复的段落（模块顶层的段落往往重新从 1 编号）依次加上 b、c……，
+// 例如 "14-4b"
+//
+// 练习文件和进度保存在每个用户自己的数据目录中：
+//   $RUST_TUTORIAL_HOME，或 $XDG_DATA_HOME/rust-tutorial，
+//   或 ~/.local/share/rust-tutorial
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::chapters::{self, Chapter};
+use crate::outline::{self, Section};
+use crate::snapshot;
+use crate::toolchain::{self, Failure};
+
+/// 挖空表达式时使用的占位符，它本身无法通过编译
+pub const BLANK: &str = "___";
+
+/// 练习文件中标记被改坏位置的注释
+pub const MARKER: &str = "// 练习：修复下面这一行";
+
+/// 段落被改坏的方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakage {
+    /// `let x = 表达式;` 中的表达式被替换为 `___`
+    BlankedExpression,
+    /// `let mut x` 中的 mut 被去掉
+    RemovedMut { name: String },
+    /// 函数签名中的生命周期注解被删掉
+    RemovedLifetimes,
+}
+
+impl fmt::Display for Breakage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakage::BlankedExpression => write!(f, "有一个表达式被替换成了 {}，请补全它", BLANK),
+            Breakage::RemovedMut { name } => write!(f, "变量 {} 的 mut 被去掉了", name),
+            Breakage::RemovedLifetimes => write!(f, "函数签名中的生命周期注解被删掉了"),
+        }
+    }
+}
+
+/// 一道练习：某一章的某个编号段落，以及它被改坏的那一行
+#[derive(Clone)]
+pub struct Exercise {
+    pub chapter: &'static Chapter,
+    pub section: Section,
+    /// 段落在本章所有编号段落中的序号（从 1 开始）
+    pub index: usize,
+    /// 本章中在它之前、编号与它相同的段落个数
+    pub repeat: usize,
+    /// 被改坏的行（从 0 开始，相对于章节源文件）
+    pub line: usize,
+    /// 改坏之后的那一行
+    pub broken: String,
+    pub breakage: Breakage,
+}
+
+impl Exercise {
+    /// 练习编号，例如 "01-2"、"14-4b"
+    pub fn id(&self) -> String {
+        let suffix = match self.repeat {
+            0 => String::new(),
+            n => char::from(b'a' + n as u8).to_string(),
+        };
+        format!(
+            "{:02}-{}{}",
+            self.chapter.number, self.section.number, suffix
+        )
+    }
+
+    /// 练习文件名，例如 "01-2_variables.rs"
+    pub fn file_name(&self) -> String {
+        format!("{}_{}.rs", self.id(), self.chapter.slug)
+    }
+
+    /// 生成练习文件的内容：整章源码，其中一行被改坏，并在上方加上标记注释
+    pub fn broken_source(&self) -> String {
+        let mut output = format!(
+            "// 练习 {}：{} / {}. {}\n// {}\n// 修改后运行 rust-tutorial exercise check {}\n\n",
+            self.id(),
+            self.chapter.title(),
+            self.section.number,
+            self.section.title,
+            self.breakage,
+            self.id()
+        );
+        for (index, line) in self.chapter.source.lines().enumerate() {
+            if index == self.line {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                output.push_str(indent);
+                output.push_str(MARKER);
+                output.push('\n');
+                output.push_str(&self.broken);
+            } else {
+                output.push_str(line);
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// 某一章的所有练习
+pub fn for_chapter(chapter: &'static Chapter) -> Vec<Exercise> {
+    let lines: Vec<&str> = chapter.source.lines().collect();
+    let sections = outline::sections(chapter.source);
+    sections
+        .iter()
+        .enumerate()
+        .filter_map(|(index, section)| {
+            let (line, broken, breakage) = break_section(&lines, section)?;
+            let repeat = sections[..index]
+                .iter()
+                .filter(|earlier| earlier.number == section.number)
+                .count();
+            Some(Exercise {
+                chapter,
+                section: section.clone(),
+                index: index + 1,
+                repeat,
+                line,
+                broken,
+                breakage,
+            })
+        })
+        .collect()
+}
+
+/// 所有章节的练习，按章节和段落顺序排列
+pub fn all() -> Vec<Exercise> {
+    chapters::all().iter().flat_map(for_chapter).collect()
+}
+
+/// 按编号查找练习，例如 "01-2"、"1-2" 或 "14-4b"
+pub fn find(id: &str) -> Option<Exercise> {
+    let (chapter, section) = id.trim().split_once('-')?;
+    let chapter = chapters::find(chapter)?;
+    for_chapter(chapter).into_iter().find(|exercise| {
+        exercise
+            .id()
+            .split_once('-')
+            .is_some_and(|(_, own)| own.eq_ignore_ascii_case(section))
+    })
+}
+
+// ========== 改坏一个段落 ==========
+
+/// 依次尝试删掉生命周期、去掉 mut、挖空表达式，返回第一个可行的改法
+fn break_section(lines: &[&str], section: &Section) -> Option<(usize, String, Breakage)> {
+    let code: Vec<(usize, &str)> = section
+        .lines
+        .clone()
+        .map(|index| (index, lines[index]))
+        .filter(|(_, line)| {
+            let trimmed = line.trim_start();
+            !trimmed.is_empty() && !trimmed.starts_with("//")
+        })
+        .collect();
+
+    for &(index, line) in &code {
+        if let Some(broken) = remove_lifetimes(line) {
+            return Some((index, broken, Breakage::RemovedLifetimes));
+        }
+    }
+
+    for (position, &(index, line)) in code.iter().enumerate() {
+        let Some(name) = let_mut_name(line) else {
+            continue;
+        };
+        let mutated_later = code[position + 1..]
+            .iter()
+            .any(|(_, later)| mutates(later, name));
+        if mutated_later {
+            let broken = line.replacen("let mut ", "let ", 1);
+            let breakage = Breakage::RemovedMut {
+                name: name.to_string(),
+            };
+            return Some((index, broken, breakage));
+        }
+    }
+
+    // 只挖空之后还会用到的变量，否则随便填什么都能通过
+    for (position, &(index, line)) in code.iter().enumerate() {
+        let Some(broken) = blank_expression(line) else {
+            continue;
+        };
+        let used_later = let_names(line).iter().any(|name| {
+            code[position + 1..]
+                .iter()
+                .any(|(_, later)| contains_word(later, name))
+        });
+        if used_later {
+            return Some((index, broken, Breakage::BlankedExpression));
+        }
+    }
+
+    None
+}
+
+/// `fn name<'a>(x: &'a str, y: &'a str) -> &'a str` 去掉生命周期后无法靠省略规则推断，
+/// 只对这种“返回引用且有多个引用参数”的签名下手
+fn remove_lifetimes(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if !(trimmed.starts_with("fn ") || trimmed.starts_with("pub fn ")) {
+        return None;
+    }
+    let (params, ret) = line.split_once("->")?;
+    if !ret.trim_start().starts_with("&'") || params.matches('&').count() < 2 {
+        return None;
+    }
+
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            result.push(c);
+            continue;
+        }
+        // 跳过生命周期名以及紧随其后的一个空格或逗号
+        while chars
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+        {
+            chars.next();
+        }
+        if chars.peek() == Some(&' ') && result.ends_with('&') {
+            chars.next();
+        } else if chars.peek() == Some(&',') {
+            chars.next();
+            if chars.peek() == Some(&' ') {
+                chars.next();
+            }
+        }
+    }
+
+    let result = result.replace("<>", "").replace(", >", ">");
+    (result != line).then_some(result)
+}
+
+/// `let mut name ...` 中的 name
+fn let_mut_name(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix("let mut ")?;
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    (end > 0).then_some(&rest[..end])
+}
+
+/// `let (a, mut b): (i32, i32) = ...` 绑定的变量名
+fn let_names(line: &str) -> Vec<&str> {
+    let Some(rest) = line.trim_start().strip_prefix("let ") else {
+        return Vec::new();
+    };
+    let pattern = rest.split([':', '=']).next().unwrap_or("");
+    pattern
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty() && *word != "mut" && *word != "_")
+        .collect()
+}
+
+/// `line` 的代码部分是否包含完整的单词 `word`
+fn contains_word(line: &str, word: &str) -> bool {
+    let code = line.split("//").next().unwrap_or(line);
+    code.match_indices(word).any(|(start, _)| {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let before = code[..start].chars().next_back();
+        let after = code[start + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// 这一行是否修改了变量 name（赋值、复合赋值、可变借用或常见的修改方法）
+fn mutates(line: &str, name: &str) -> bool {
+    let code = line.split("//").next().unwrap_or(line);
+    let assigns = [" = ", " += ", " -= ", " *= ", " /= "]
+        .iter()
+        .any(|op| code.trim_start().starts_with(&format!("{}{}", name, op)));
+    let borrows = contains_word(code, name) && code.contains(&format!("&mut {}", name));
+    let methods = [
+        "push", "push_str", "insert", "pop", "entry", "clear", "remove",
+    ]
+    .iter()
+    .any(|method| {
+        let call = format!("{}.{}(", name, method);
+        code.match_indices(&call).any(|(start, _)| {
+            !code[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    });
+    assigns || borrows || methods
+}
+
+/// `let x = 表达式; // 注释` 变为 `let x = ___; // 注释`
+fn blank_expression(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if !trimmed.starts_with("let ") {
+        return None;
+    }
+    let (code, comment) = match line.find(" //") {
+        Some(index) => line.split_at(index),
+        None => (line, ""),
+    };
+    let statement = code.trim_end().strip_suffix(';')?;
+    let (pattern, expression) = statement.split_once(" = ")?;
+    if expression.trim().is_empty() || expression.contains('{') {
+        return None;
+    }
+    Some(format!("{} = {};{}", pattern, BLANK, comment))
+}
+
+// ========== 检查练习 ==========
+
+/// 检查练习文件的结果
+#[derive(Debug)]
+pub enum Outcome {
+    /// 与原章节输出一致
+    Passed,
+    /// 文件中还有 `___` 占位符
+    Unfinished,
+    /// 无法编译，附带 rustc 的输出
+    CompileError(String),
+    /// 编译通过但运行失败（例如 panic），附带标准错误
+    RuntimeError(String),
+    /// 能运行，但输出与原章节不同；能分出段落时只包含这一段的输出
+    WrongOutput { expected: String, actual: String },
+}
+
+/// 插在段落编号注释前面的输出标记：SECTION_START、段落的序号、SECTION_END
+const SECTION_START: char = '\x1e';
+const SECTION_END: char = '\x1f';
+
+/// 编译并运行学习者修改后的练习文件，与原章节中这一段的输出比较
+///
+/// 章节中其他段落的输出不影响结果；分不出这一段的输出时（例如段落在函数体外），
+/// 比较整章的输出。
+pub fn check(exercise: &Exercise, file: &Path) -> io::Result<Outcome> {
+    let source = fs::read_to_string(file)?;
+    let unfinished = source
+        .lines()
+        .any(|line| line.split("//").next().unwrap_or(line).contains(BLANK));
+    if unfinished {
+        return Ok(Outcome::Unfinished);
+    }
+
+    // 先原样编译运行，这样报告的错误和学习者看到的文件完全一致
+    let output = match toolchain::run_chapter(exercise.chapter.slug, file)? {
+        Ok(output) => output,
+        Err(Failure::Compile(stderr)) => return Ok(Outcome::CompileError(stderr)),
+        Err(Failure::Run(stderr)) => return Ok(Outcome::RuntimeError(stderr)),
+    };
+    let (expected, actual) = match section_outputs(exercise, &source)? {
+        Some(outputs) => outputs,
+        None => (original_output(exercise.chapter)?, output),
+    };
+
+    let rules = snapshot::rules(exercise.chapter);
+    let expected = snapshot::normalize(&expected, rules);
+    let actual = snapshot::normalize(&actual, rules);
+    if expected == actual {
+        Ok(Outcome::Passed)
+    } else {
+        Ok(Outcome::WrongOutput { expected, actual })
+    }
+}
+
+/// 原章节和练习文件中练习所在段落的输出
+///
+/// 在函数体内每个编号段落的第一行前面插入打印标记的语句（不增加行），
+/// 两个文件各运行一次，取出两个标记之间属于这一段的输出。段落在函数体外
+/// （输出来自别处的调用）、学习者删掉了编号注释，或者插入标记后无法运行时返回 None
+fn section_outputs(exercise: &Exercise, source: &str) -> io::Result<Option<(String, String)>> {
+    if exercise.section.indent == 0 {
+        return Ok(None);
+    }
+    let original = exercise.chapter.source;
+    let (Some(expected), Some(actual)) =
+        (instrument(original, original), instrument(original, source))
+    else {
+        return Ok(None);
+    };
+    let (Ok(expected), Ok(actual)) = (
+        run_source(exercise.chapter, &expected)?,
+        run_source(exercise.chapter, &actual)?,
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some((
+        section_output(&expected, exercise.index),
+        section_output(&actual, exercise.index),
+    )))
+}
+
+/// 在 `source` 中依次找到原章节 `original` 里函数体内每个编号段落的第一行，
+/// 在它前面加上打印标记的语句；找不到某一行时返回 None
+fn instrument(original: &str, source: &str) -> Option<String> {
+    let headings: Vec<(usize, &str)> = outline::sections(original)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, section)| section.indent > 0)
+        .filter_map(|(index, section)| {
+            Some((index + 1, original.lines().nth(section.lines.start)?))
+        })
+        .collect();
+
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    let mut from = 0;
+    for (index, heading) in headings {
+        let found = from + lines[from..].iter().position(|line| line == heading)?;
+        let indent = &heading[..heading.len() - heading.trim_start().len()];
+        lines[found] = format!(
+            "{}print!(\"{}{}{}\"); {}",
+            indent,
+            SECTION_START.escape_unicode(),
+            index,
+            SECTION_END.escape_unicode(),
+            heading.trim_start()
+        );
+        from = found + 1;
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Some(result)
+}
+
+/// 取出带标记的输出中第 `index` 个段落打印的部分
+fn section_output(output: &str, index: usize) -> String {
+    output
+        .split(SECTION_START)
+        .skip(1)
+        .filter_map(|chunk| {
+            let (label, text) = chunk.split_once(SECTION_END)?;
+            (label.parse() == Ok(index)).then_some(text)
+        })
+        .collect()
+}
+
+/// 把 `source` 当作章节写进临时目录，编译运行
+fn run_source(chapter: &Chapter, source: &str) -> io::Result<Result<String, Failure>> {
+    let dir = toolchain::scratch_dir("exercise-original")?;
+    let file = dir.join(chapter.file_name());
+    let result =
+        fs::write(&file, source).and_then(|()| toolchain::run_chapter(chapter.slug, &file));
+    fs::remove_dir_all(&dir)?;
+    result
+}
+
+/// 用同样的方式编译运行未修改的章节，得到期望的输出
+fn original_output(chapter: &Chapter) -> io::Result<String> {
+    match run_source(chapter, chapter.source)? {
+        Ok(output) => Ok(output),
+        Err(Failure::Compile(message) | Failure::Run(message)) => Err(io::Error::other(format!(
+            "原章节 {} 无法运行:\n{}",
+            chapter.file_name(),
+            message
+        ))),
+    }
+}
+
+// ========== 练习目录和进度 ==========
+
+/// 当前用户的数据目录
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("RUST_TUTORIAL_HOME") {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join("rust-tutorial");
+    }
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    home.join(".local/share/rust-tutorial")
+}
+
+/// 学习者的练习目录和完成进度
+pub struct Workspace {
+    dir: PathBuf,
+    done: BTreeSet<String>,
+}
+
+impl Workspace {
+    /// 打开（必要时创建）`dir` 下的练习目录并读取进度
+    pub fn open(dir: &Path) -> io::Result<Workspace> {
+        fs::create_dir_all(dir.join("exercises"))?;
+        let done = match fs::read_to_string(dir.join("progress.txt")) {
+            Ok(text) => text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from)
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Workspace {
+            dir: dir.to_path_buf(),
+            done,
+        })
+    }
+
+    /// 练习文件的路径
+    pub fn path(&self, exercise: &Exercise) -> PathBuf {
+        self.dir.join("exercises").join(exercise.file_name())
+    }
+
+    /// 确保练习文件存在；已经存在时保留学习者的修改
+    pub fn prepare(&self, exercise: &Exercise) -> io::Result<PathBuf> {
+        let path = self.path(exercise);
+        if !path.exists() {
+            fs::write(&path, exercise.broken_source())?;
+        }
+        Ok(path)
+    }
+
+    /// 用改坏的版本覆盖练习文件
+    pub fn reset(&self, exercise: &Exercise) -> io::Result<PathBuf> {
+        let path = self.path(exercise);
+        fs::write(&path, exercise.broken_source())?;
+        Ok(path)
+    }
+
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        self.done.contains(&exercise.id())
+    }
+
+    /// 记录一道练习已完成并写回进度文件
+    pub fn mark_done(&mut self, exercise: &Exercise) -> io::Result<()> {
+        self.done.insert(exercise.id());
+        let mut text = String::from("# rust-tutorial 已完成的练习\n");
+        for id in &self.done {
+            text.push_str(id);
+            text.push('\n');
+        }
+        fs::write(self.dir.join("progress.txt"), text)
+    }
+
+    /// 第一道还没完成的练习
+    pub fn next<'a>(&self, exercises: &'a [Exercise]) -> Option<&'a Exercise> {
+        exercises.iter().find(|exercise| !self.is_done(exercise))
+    }
+}
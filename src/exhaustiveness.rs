@@ -0,0 +1,996 @@
+// ============================================
+// 穷尽性和可达性检查 (Exhaustiveness)
+// ============================================
+// 第 04 章和第 08 章的 match 几乎都以 `_ => println!("其他")` 结尾：
+// rustc 要求 match 覆盖所有可能的值，否则报 E0004；被前面的分支完全覆盖的分支
+// 永远不会执行，rustc 给出 unreachable pattern 警告。这个模块对一个建模的类型
+// 做同样的检查：
+//
+// - 类型：整数（按 IntType 的范围）、bool、元组、结构体、枚举（包括 Option、Result），
+//   以及只能用通配符或绑定匹配的其他类型（例如 String）
+// - 模式用 Rust 的写法给出：字面量和范围、`|`、`@` 绑定、元组、`Some(Some(v))`、
+//   `Message::Move { x, .. }`、`Point { x: 0, y }`
+// - 带 guard 的分支和 rustc 一样不算覆盖，但仍然检查它是否可达
+//
+// 算法是 rustc 使用的“有用性”（usefulness）检查：一个模式向量对于前面的模式矩阵有用，
+// 当且仅当存在一个值被它匹配、却不被矩阵中任何一行匹配。分支不可达就是它对前面的
+// 分支没有用；match 不穷尽就是通配符 `_` 对所有分支仍然有用，这时算法同时构造出
+// 没被覆盖的值（witness），例如 `i32::MIN..=0_i32` 或 `Some(None)`。
+// 整数的范围先按所有模式的端点切分成互不相交的小段，再逐段当作构造器处理。
+
+use std::fmt;
+use std::rc::Rc;
+
+use crate::classifier;
+use crate::integer::{self, IntType};
+
+// ========== 类型 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ty {
+    Int(IntType),
+    Bool,
+    Tuple(Vec<Ty>),
+    Adt(Rc<Adt>),
+    /// 不展开检查的类型，例如 String，只能用 `_` 或绑定匹配
+    Opaque(&'static str),
+}
+
+impl Ty {
+    pub fn i32() -> Ty {
+        Ty::Int(IntType::I32)
+    }
+
+    /// 标准库的 Option<T>，变体不带路径：`None`、`Some(x)`
+    pub fn option(inner: Ty) -> Ty {
+        Ty::Adt(Rc::new(Adt {
+            name: format!("Option<{}>", inner),
+            kind: AdtKind::Prelude,
+            variants: vec![Variant::unit("None"), Variant::tuple("Some", vec![inner])],
+        }))
+    }
+
+    /// 标准库的 Result<T, E>
+    pub fn result(ok: Ty, err: Ty) -> Ty {
+        Ty::Adt(Rc::new(Adt {
+            name: format!("Result<{}, {}>", ok, err),
+            kind: AdtKind::Prelude,
+            variants: vec![
+                Variant::tuple("Ok", vec![ok]),
+                Variant::tuple("Err", vec![err]),
+            ],
+        }))
+    }
+
+    pub fn enumeration(name: &str, variants: Vec<Variant>) -> Ty {
+        Ty::Adt(Rc::new(Adt {
+            name: name.to_string(),
+            kind: AdtKind::Enum,
+            variants,
+        }))
+    }
+
+    pub fn structure(name: &str, fields: Vec<(&str, Ty)>) -> Ty {
+        Ty::Adt(Rc::new(Adt {
+            name: name.to_string(),
+            kind: AdtKind::Struct,
+            variants: vec![Variant::named(name, fields)],
+        }))
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ty::Int(ty) => write!(f, "{}", ty),
+            Ty::Bool => write!(f, "bool"),
+            Ty::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|ty| ty.to_string()).collect();
+                write!(f, "({})", items.join(", "))
+            }
+            Ty::Adt(adt) => write!(f, "{}", adt.name),
+            Ty::Opaque(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// 结构体或枚举
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adt {
+    pub name: String,
+    pub kind: AdtKind,
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdtKind {
+    /// 只有一个变体，名字就是结构体名
+    Struct,
+    /// 变体写成 `Message::Quit`
+    Enum,
+    /// 变体在 prelude 中，直接写 `Some`、`None`
+    Prelude,
+}
+
+impl Adt {
+    /// 模式中变体的写法
+    fn path(&self, variant: &Variant) -> String {
+        match self.kind {
+            AdtKind::Struct | AdtKind::Prelude => variant.name.clone(),
+            AdtKind::Enum => format!("{}::{}", self.name, variant.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fields {
+    Unit,
+    Tuple(Vec<Ty>),
+    Named(Vec<(String, Ty)>),
+}
+
+impl Variant {
+    pub fn unit(name: &str) -> Variant {
+        Variant {
+            name: name.to_string(),
+            fields: Fields::Unit,
+        }
+    }
+
+    pub fn tuple(name: &str, fields: Vec<Ty>) -> Variant {
+        Variant {
+            name: name.to_string(),
+            fields: Fields::Tuple(fields),
+        }
+    }
+
+    pub fn named(name: &str, fields: Vec<(&str, Ty)>) -> Variant {
+        Variant {
+            name: name.to_string(),
+            fields: Fields::Named(
+                fields
+                    .into_iter()
+                    .map(|(name, ty)| (name.to_string(), ty))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn field_types(&self) -> Vec<Ty> {
+        match &self.fields {
+            Fields::Unit => Vec::new(),
+            Fields::Tuple(types) => types.clone(),
+            Fields::Named(fields) => fields.iter().map(|(_, ty)| ty.clone()).collect(),
+        }
+    }
+}
+
+// ========== 模式 ==========
+
+/// 解析后的模式；绑定已经去掉，只保留它匹配哪些值
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pat {
+    Wild,
+    /// 整数闭区间，单个整数是 lo == hi
+    Range(i128, i128),
+    Bool(bool),
+    /// 元组（变体下标为 0）或结构体、枚举的变体，字段按声明顺序排列
+    Ctor(usize, Vec<Pat>),
+    Or(Vec<Pat>),
+}
+
+/// 构造器：把一个类型的值按“最外层长什么样”分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ctor {
+    Range(i128, i128),
+    Bool(bool),
+    Variant(usize),
+}
+
+fn int_bounds(ty: IntType) -> (i128, i128) {
+    (
+        ty.min().to_i128().unwrap_or(i128::MIN),
+        ty.max().to_i128().unwrap_or(i128::MAX),
+    )
+}
+
+impl Pat {
+    fn ctor(&self) -> Option<Ctor> {
+        match *self {
+            Pat::Range(lo, hi) => Some(Ctor::Range(lo, hi)),
+            Pat::Bool(b) => Some(Ctor::Bool(b)),
+            Pat::Ctor(i, _) => Some(Ctor::Variant(i)),
+            Pat::Wild | Pat::Or(_) => None,
+        }
+    }
+
+    /// 按 rustc 报告 witness 的方式显示
+    fn show(&self, ty: &Ty) -> String {
+        match (self, ty) {
+            (Pat::Wild, _) => String::from("_"),
+            (Pat::Range(lo, hi), Ty::Int(int)) => classifier::range_pattern(*int, *lo, *hi),
+            (Pat::Bool(b), _) => b.to_string(),
+            (Pat::Ctor(_, items), Ty::Tuple(types)) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .zip(types)
+                    .map(|(item, ty)| item.show(ty))
+                    .collect();
+                format!("({})", items.join(", "))
+            }
+            (Pat::Ctor(i, fields), Ty::Adt(adt)) => {
+                let variant = &adt.variants[*i];
+                let path = adt.path(variant);
+                match &variant.fields {
+                    Fields::Unit => path,
+                    Fields::Tuple(types) => {
+                        let items: Vec<String> = fields
+                            .iter()
+                            .zip(types)
+                            .map(|(field, ty)| field.show(ty))
+                            .collect();
+                        format!("{}({})", path, items.join(", "))
+                    }
+                    Fields::Named(named) => {
+                        let shown: Vec<String> = fields
+                            .iter()
+                            .zip(named)
+                            .filter(|(field, _)| **field != Pat::Wild)
+                            .map(|(field, (name, ty))| format!("{}: {}", name, field.show(ty)))
+                            .collect();
+                        if shown.is_empty() {
+                            format!("{} {{ .. }}", path)
+                        } else if shown.len() == named.len() {
+                            format!("{} {{ {} }}", path, shown.join(", "))
+                        } else {
+                            format!("{} {{ {}, .. }}", path, shown.join(", "))
+                        }
+                    }
+                }
+            }
+            (Pat::Or(alternatives), _) => {
+                let items: Vec<String> = alternatives.iter().map(|alt| alt.show(ty)).collect();
+                items.join(" | ")
+            }
+            _ => String::from("_"),
+        }
+    }
+}
+
+/// 模式写错时的错误，措辞参照 rustc
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// 出错的分支，从 0 开始
+    pub arm: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 个分支: {}", self.arm + 1, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ========== 解析 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// 标识符或路径，例如 x、Some、Message::Move
+    Path(String),
+    /// 整数字面量的原文，可能带负号
+    Number(String),
+    Underscore,
+    DotDot,
+    DotDotEq,
+    Punct(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Path(text) | Token::Number(text) => write!(f, "{}", text),
+            Token::Underscore => write!(f, "_"),
+            Token::DotDot => write!(f, ".."),
+            Token::DotDotEq => write!(f, "..="),
+            Token::Punct(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len()
+                && (word(chars[i]) || (chars[i] == ':' && chars.get(i + 1) == Some(&':')))
+            {
+                i += if chars[i] == ':' { 2 } else { 1 };
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(if text == "_" {
+                Token::Underscore
+            } else {
+                Token::Path(text)
+            });
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            i += 1;
+            while i < chars.len() && word(chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token::Number(chars[start..i].iter().collect()));
+        } else if c == '.' && chars.get(i + 1) == Some(&'.') {
+            if chars.get(i + 2) == Some(&'=') {
+                tokens.push(Token::DotDotEq);
+                i += 3;
+            } else {
+                tokens.push(Token::DotDot);
+                i += 2;
+            }
+        } else if "(){},|@:".contains(c) {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        } else {
+            return Err(format!("不认识的字符 `{}`", c));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct(found)) if found == c => Ok(()),
+            Some(found) => Err(format!("expected `{}`, found `{}`", c, found)),
+            None => Err(format!("expected `{}`, found end of pattern", c)),
+        }
+    }
+
+    /// 顶层或括号中的模式，可以用 | 连接
+    fn pattern(&mut self, ty: &Ty) -> Result<Pat, String> {
+        let mut alternatives = vec![self.single(ty)?];
+        while self.eat(&Token::Punct('|')) {
+            alternatives.push(self.single(ty)?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Pat::Or(alternatives)
+        })
+    }
+
+    fn single(&mut self, ty: &Ty) -> Result<Pat, String> {
+        let token = self
+            .next()
+            .ok_or_else(|| String::from("expected pattern, found end of pattern"))?;
+        match token {
+            Token::Underscore => Ok(Pat::Wild),
+            Token::Punct('(') => match ty {
+                Ty::Tuple(types) => {
+                    let items = self.list(types, ')', "tuple")?;
+                    Ok(Pat::Ctor(0, items))
+                }
+                _ => {
+                    let pat = self.pattern(ty)?;
+                    self.expect(')')?;
+                    Ok(pat)
+                }
+            },
+            Token::Number(text) => self.range(ty, Some(&text)),
+            Token::DotDotEq | Token::DotDot => {
+                self.pos -= 1;
+                self.range(ty, None)
+            }
+            Token::Path(path) if path == "true" || path == "false" => match ty {
+                Ty::Bool => Ok(Pat::Bool(path == "true")),
+                _ => Err(format!("mismatched types: expected `{}`, found `bool`", ty)),
+            },
+            Token::Path(path) => {
+                if let Some(pat) = self.variant(ty, &path)? {
+                    return Ok(pat);
+                }
+                if path.contains("::") || path.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    return Err(format!("`{}` 不是 `{}` 的变体", path, ty));
+                }
+                // 绑定：x 或 x @ 子模式
+                if self.eat(&Token::Punct('@')) {
+                    self.single(ty)
+                } else {
+                    Ok(Pat::Wild)
+                }
+            }
+            other => Err(format!("expected pattern, found `{}`", other)),
+        }
+    }
+
+    /// 元组或元组变体的字段；`..` 只能放在最后，表示忽略剩下的字段，例如 `(x, ..)`
+    fn list(&mut self, types: &[Ty], close: char, what: &str) -> Result<Vec<Pat>, String> {
+        let mut items = Vec::new();
+        let mut rest = false;
+        loop {
+            if self.eat(&Token::Punct(close)) {
+                break;
+            }
+            if self.eat(&Token::DotDot) {
+                rest = true;
+                self.eat(&Token::Punct(','));
+                self.expect(close)
+                    .map_err(|_| String::from("这里只支持把 `..` 放在最后"))?;
+                break;
+            }
+            let ty = types.get(items.len()).ok_or_else(|| {
+                format!(
+                    "this pattern has more fields than the corresponding {} ({} fields, E0023)",
+                    what,
+                    types.len()
+                )
+            })?;
+            items.push(self.pattern(ty)?);
+            if !self.eat(&Token::Punct(',')) {
+                self.expect(close)?;
+                break;
+            }
+        }
+        if !rest && items.len() != types.len() {
+            return Err(format!(
+                "this pattern has {} fields, but the corresponding {} has {} fields (E0023)",
+                items.len(),
+                what,
+                types.len()
+            ));
+        }
+        items.resize(types.len(), Pat::Wild);
+        Ok(items)
+    }
+
+    /// 如果 path 是 ty 的变体，解析变体模式
+    fn variant(&mut self, ty: &Ty, path: &str) -> Result<Option<Pat>, String> {
+        let Ty::Adt(adt) = ty else { return Ok(None) };
+        let Some(index) = adt.variants.iter().position(|v| adt.path(v) == path) else {
+            return Ok(None);
+        };
+        let variant = &adt.variants[index];
+        let fields = match &variant.fields {
+            Fields::Unit => Vec::new(),
+            Fields::Tuple(types) => {
+                self.expect('(')?;
+                self.list(types, ')', "tuple variant")?
+            }
+            Fields::Named(named) => {
+                self.expect('{')?;
+                self.named(named)?
+            }
+        };
+        Ok(Some(Pat::Ctor(index, fields)))
+    }
+
+    /// `{ x, y: 0, .. }`
+    fn named(&mut self, named: &[(String, Ty)]) -> Result<Vec<Pat>, String> {
+        let mut fields: Vec<Option<Pat>> = vec![None; named.len()];
+        let mut rest = false;
+        loop {
+            if self.eat(&Token::Punct('}')) {
+                break;
+            }
+            if self.eat(&Token::DotDot) {
+                rest = true;
+                self.expect('}')?;
+                break;
+            }
+            let name = match self.next() {
+                Some(Token::Path(name)) => name,
+                Some(other) => return Err(format!("expected identifier, found `{}`", other)),
+                None => return Err(String::from("expected `}`, found end of pattern")),
+            };
+            let index = named
+                .iter()
+                .position(|(field, _)| *field == name)
+                .ok_or_else(|| format!("variant does not have a field named `{}` (E0026)", name))?;
+            fields[index] = Some(if self.eat(&Token::Punct(':')) {
+                self.pattern(&named[index].1)?
+            } else {
+                Pat::Wild
+            });
+            if !self.eat(&Token::Punct(',')) {
+                self.expect('}')?;
+                break;
+            }
+        }
+        let missing: Vec<&str> = named
+            .iter()
+            .zip(&fields)
+            .filter(|(_, field)| field.is_none())
+            .map(|((name, _), _)| name.as_str())
+            .collect();
+        if !rest && !missing.is_empty() {
+            return Err(format!(
+                "pattern does not mention field `{}` (E0027)",
+                missing.join("`, `")
+            ));
+        }
+        Ok(fields
+            .into_iter()
+            .map(|field| field.unwrap_or(Pat::Wild))
+            .collect())
+    }
+
+    /// 字面量或范围：a、a..=b、a..b、a..、..=b、..b
+    fn range(&mut self, ty: &Ty, start: Option<&str>) -> Result<Pat, String> {
+        let Ty::Int(int) = *ty else {
+            return Err(format!(
+                "mismatched types: expected `{}`, found integer",
+                ty
+            ));
+        };
+        let (min, max) = int_bounds(int);
+        let literal = |text: &str| -> Result<i128, String> {
+            integer::operand(text, int)
+                .map_err(|e| e.to_string())?
+                .to_i128()
+                .ok_or_else(|| format!("`{}` 超出了 i128 的范围", text))
+        };
+        let lo = match start {
+            Some(text) => literal(text)?,
+            None => min,
+        };
+        let inclusive = match self.peek() {
+            Some(Token::DotDotEq) => true,
+            Some(Token::DotDot) => false,
+            _ => return Ok(Pat::Range(lo, lo)),
+        };
+        self.pos += 1;
+        let end = match self.peek() {
+            Some(Token::Number(text)) => {
+                let text = text.clone();
+                self.pos += 1;
+                Some(literal(&text)?)
+            }
+            _ => None,
+        };
+        match (end, inclusive) {
+            (None, false) if start.is_some() => Ok(Pat::Range(lo, max)),
+            (None, _) => Err(String::from("expected the end of the range")),
+            (Some(hi), true) if lo <= hi => Ok(Pat::Range(lo, hi)),
+            (Some(_), true) => Err(String::from(
+                "lower range bound must be less than or equal to upper (E0030)",
+            )),
+            (Some(hi), false) if lo < hi => Ok(Pat::Range(lo, hi - 1)),
+            (Some(_), false) => Err(String::from(
+                "lower range bound must be less than upper (E0579)",
+            )),
+        }
+    }
+}
+
+fn parse(text: &str, ty: &Ty) -> Result<Pat, String> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    let pat = parser.pattern(ty)?;
+    match parser.peek() {
+        None => Ok(pat),
+        Some(token) => Err(format!("unexpected `{}` after pattern", token)),
+    }
+}
+
+// ========== 有用性 ==========
+
+/// 构造器的字段类型
+fn ctor_fields(ty: &Ty, ctor: Ctor) -> Vec<Ty> {
+    match (ty, ctor) {
+        (Ty::Tuple(types), _) => types.clone(),
+        (Ty::Adt(adt), Ctor::Variant(i)) => adt.variants[i].field_types(),
+        _ => Vec::new(),
+    }
+}
+
+/// 把整数范围按端点切成互不相交的小段，每个模式中的范围要么包含一段，要么和它不相交
+fn split_ranges(lo: i128, hi: i128, ranges: &[(i128, i128)]) -> Vec<(i128, i128)> {
+    let mut points = vec![lo];
+    for &(a, b) in ranges {
+        if lo < a && a <= hi {
+            points.push(a);
+        }
+        if let Some(next) = b.checked_add(1).filter(|&next| lo < next && next <= hi) {
+            points.push(next);
+        }
+    }
+    points.sort_unstable();
+    points.dedup();
+    let mut pieces = Vec::new();
+    for (i, &start) in points.iter().enumerate() {
+        let end = points.get(i + 1).map_or(hi, |next| next - 1);
+        pieces.push((start, end));
+    }
+    pieces
+}
+
+/// 类型的全部构造器，整数按列中出现的范围切分
+fn all_ctors(ty: &Ty, seen: &[Ctor]) -> Vec<Ctor> {
+    match ty {
+        Ty::Int(int) => {
+            let (min, max) = int_bounds(*int);
+            split_ranges(min, max, &ranges(seen))
+                .into_iter()
+                .map(|(lo, hi)| Ctor::Range(lo, hi))
+                .collect()
+        }
+        Ty::Bool => vec![Ctor::Bool(false), Ctor::Bool(true)],
+        Ty::Tuple(_) => vec![Ctor::Variant(0)],
+        Ty::Adt(adt) => (0..adt.variants.len()).map(Ctor::Variant).collect(),
+        Ty::Opaque(_) => Vec::new(),
+    }
+}
+
+fn ranges(ctors: &[Ctor]) -> Vec<(i128, i128)> {
+    ctors
+        .iter()
+        .filter_map(|ctor| match *ctor {
+            Ctor::Range(lo, hi) => Some((lo, hi)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 模式的构造器 `covering` 是否包含切分后的构造器 `piece`
+fn covers(covering: Ctor, piece: Ctor) -> bool {
+    match (covering, piece) {
+        (Ctor::Range(a, b), Ctor::Range(lo, hi)) => a <= lo && hi <= b,
+        _ => covering == piece,
+    }
+}
+
+/// 把每一行开头的 | 展开成多行
+fn expand(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+    let mut expanded = Vec::new();
+    for row in rows {
+        match row.first() {
+            Some(Pat::Or(alternatives)) => {
+                let alternatives: Vec<Vec<Pat>> = alternatives
+                    .iter()
+                    .map(|alt| {
+                        std::iter::once(alt.clone())
+                            .chain(row[1..].iter().cloned())
+                            .collect()
+                    })
+                    .collect();
+                expanded.extend(expand(&alternatives));
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+/// 行 `row` 在构造器 `ctor` 下特化：开头是通配符时换成 arity 个通配符，
+/// 开头的构造器包含 ctor 时换成它的字段，否则这一行不可能匹配，返回 None
+fn specialize(row: &[Pat], ctor: Ctor, arity: usize) -> Option<Vec<Pat>> {
+    let head: Vec<Pat> = match &row[0] {
+        Pat::Wild => vec![Pat::Wild; arity],
+        Pat::Ctor(i, fields) if Ctor::Variant(*i) == ctor => fields.clone(),
+        pat => {
+            if !covers(pat.ctor()?, ctor) {
+                return None;
+            }
+            Vec::new()
+        }
+    };
+    Some(head.into_iter().chain(row[1..].iter().cloned()).collect())
+}
+
+/// 用 ctor 和它的字段构造一个 witness 模式
+fn apply(ctor: Ctor, fields: Vec<Pat>) -> Pat {
+    match ctor {
+        Ctor::Range(lo, hi) => Pat::Range(lo, hi),
+        Ctor::Bool(b) => Pat::Bool(b),
+        Ctor::Variant(i) => Pat::Ctor(i, fields),
+    }
+}
+
+/// 模式向量 `v` 对矩阵 `rows` 有用时，返回 witness：被 v 匹配、但不被任何一行匹配的值，
+/// 每个 witness 和 v 一样长；没有用时返回空
+fn useful(rows: &[Vec<Pat>], v: &[Pat], types: &[Ty]) -> Vec<Vec<Pat>> {
+    let Some(first) = v.first() else {
+        return if rows.is_empty() {
+            vec![Vec::new()]
+        } else {
+            Vec::new()
+        };
+    };
+    if let Pat::Or(alternatives) = first {
+        let mut witnesses = Vec::new();
+        for alt in alternatives {
+            let v: Vec<Pat> = std::iter::once(alt.clone())
+                .chain(v[1..].iter().cloned())
+                .collect();
+            witnesses.extend(useful(rows, &v, types));
+        }
+        return witnesses;
+    }
+
+    let rows = expand(rows);
+    let ty = &types[0];
+    let seen: Vec<Ctor> = rows.iter().filter_map(|row| row[0].ctor()).collect();
+
+    let recurse = |ctor: Ctor| -> Vec<Vec<Pat>> {
+        let fields = ctor_fields(ty, ctor);
+        let arity = fields.len();
+        let specialized: Vec<Vec<Pat>> = rows
+            .iter()
+            .filter_map(|row| specialize(row, ctor, arity))
+            .collect();
+        let Some(v) = specialize(v, ctor, arity) else {
+            return Vec::new();
+        };
+        let types: Vec<Ty> = fields
+            .into_iter()
+            .chain(types[1..].iter().cloned())
+            .collect();
+        useful(&specialized, &v, &types)
+            .into_iter()
+            .map(|mut witness| {
+                let rest = witness.split_off(arity);
+                std::iter::once(apply(ctor, witness)).chain(rest).collect()
+            })
+            .collect()
+    };
+
+    if let Some(ctor) = first.ctor() {
+        // 具体的构造器：整数范围按列中的其他范围切分后逐段检查
+        let pieces = match (ty, ctor) {
+            (Ty::Int(_), Ctor::Range(lo, hi)) => split_ranges(lo, hi, &ranges(&seen))
+                .into_iter()
+                .map(|(lo, hi)| Ctor::Range(lo, hi))
+                .collect(),
+            _ => vec![ctor],
+        };
+        return pieces.into_iter().flat_map(recurse).collect();
+    }
+
+    // 通配符：列中出现的构造器覆盖了整个类型时，逐个构造器检查；
+    // 否则缺少的构造器配上开头是通配符的行的 witness，出现过的构造器照常展开，
+    // 和 rustc 一样把两者按构造器的顺序一起报告
+    let all = all_ctors(ty, &seen);
+    let is_missing = |ctor: Ctor| !seen.iter().any(|&s| covers(s, ctor));
+    // 元组和结构体只有一个构造器，总是展开，witness 写成 `(_, _)` 而不是 `_`
+    if !all.is_empty()
+        && (all.iter().all(|&ctor| !is_missing(ctor))
+            || matches!(ty, Ty::Tuple(_))
+            || is_struct(ty))
+    {
+        return all.into_iter().flat_map(recurse).collect();
+    }
+
+    let default: Vec<Vec<Pat>> = rows
+        .iter()
+        .filter(|row| row[0] == Pat::Wild)
+        .map(|row| row[1..].to_vec())
+        .collect();
+    let tails = useful(&default, &v[1..], &types[1..]);
+    let with_tails = |head: Pat| -> Vec<Vec<Pat>> {
+        tails
+            .iter()
+            .map(|tail| {
+                std::iter::once(head.clone())
+                    .chain(tail.iter().cloned())
+                    .collect()
+            })
+            .collect()
+    };
+    // 列中一个构造器都没有时和 rustc 一样报告 `_`
+    if seen.is_empty() {
+        return with_tails(Pat::Wild);
+    }
+
+    let mut witnesses = Vec::new();
+    let mut run: Vec<Ctor> = Vec::new();
+    let flush = |run: &mut Vec<Ctor>, witnesses: &mut Vec<Vec<Pat>>| {
+        for ctor in merge(std::mem::take(run)) {
+            let head = apply(ctor, vec![Pat::Wild; ctor_fields(ty, ctor).len()]);
+            witnesses.extend(with_tails(head));
+        }
+    };
+    for ctor in all {
+        if is_missing(ctor) {
+            run.push(ctor);
+        } else {
+            flush(&mut run, &mut witnesses);
+            witnesses.extend(recurse(ctor));
+        }
+    }
+    flush(&mut run, &mut witnesses);
+    witnesses
+}
+
+fn is_struct(ty: &Ty) -> bool {
+    matches!(ty, Ty::Adt(adt) if adt.kind == AdtKind::Struct)
+}
+
+/// 合并相邻的整数小段，例如 0..=4 和 5..=9 合成 0..=9
+fn merge(ctors: Vec<Ctor>) -> Vec<Ctor> {
+    let mut merged: Vec<Ctor> = Vec::new();
+    for ctor in ctors {
+        match (merged.last_mut(), ctor) {
+            (Some(Ctor::Range(_, end)), Ctor::Range(lo, hi)) if end.checked_add(1) == Some(lo) => {
+                *end = hi;
+            }
+            _ => merged.push(ctor),
+        }
+    }
+    merged
+}
+
+// ========== 检查 match ==========
+
+/// match 的一个分支
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arm {
+    pub pattern: String,
+    pub guard: bool,
+}
+
+impl Arm {
+    pub fn new(pattern: &str) -> Arm {
+        Arm {
+            pattern: pattern.to_string(),
+            guard: false,
+        }
+    }
+
+    /// 带 `if` 条件的分支
+    pub fn guarded(pattern: &str) -> Arm {
+        Arm {
+            pattern: pattern.to_string(),
+            guard: true,
+        }
+    }
+}
+
+/// 不可达的分支，或者 `|` 中不可达的一个模式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreachable {
+    pub arm: usize,
+    /// None 表示整个分支不可达
+    pub alternative: Option<usize>,
+    pub pattern: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub ty: String,
+    pub unreachable: Vec<Unreachable>,
+    /// 没有被覆盖的值，按 rustc 的写法显示
+    pub missing: Vec<String>,
+}
+
+impl Report {
+    pub fn is_exhaustive(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// rustc 风格的诊断，每条一行；没有问题时为空
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for u in &self.unreachable {
+            out += &format!(
+                "第 {} 个分支: warning: unreachable pattern `{}`\n",
+                u.arm + 1,
+                u.pattern
+            );
+        }
+        if !self.missing.is_empty() {
+            out += &format!(
+                "error[E0004]: non-exhaustive patterns: {} not covered\n",
+                classifier::not_covered(&self.missing)
+            );
+        }
+        out
+    }
+}
+
+/// 按括号外的 | 拆分分支的原文
+fn split_alternatives(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            '|' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// 检查对类型 `ty` 的值做 match 时，这些分支是否穷尽、是否有不可达的分支
+pub fn check(ty: &Ty, arms: &[Arm]) -> Result<Report, Error> {
+    let types = [ty.clone()];
+    let mut rows: Vec<Vec<Pat>> = Vec::new();
+    let mut unreachable = Vec::new();
+    for (arm, branch) in arms.iter().enumerate() {
+        let pat = parse(&branch.pattern, ty).map_err(|message| Error { arm, message })?;
+        if useful(&rows, std::slice::from_ref(&pat), &types).is_empty() {
+            unreachable.push(Unreachable {
+                arm,
+                alternative: None,
+                pattern: branch.pattern.clone(),
+            });
+        } else if let Pat::Or(alternatives) = &pat {
+            // 每个模式对前面的分支和同一分支中前面的模式都要有用
+            let texts = split_alternatives(&branch.pattern);
+            let mut before = rows.clone();
+            for (i, alt) in alternatives.iter().enumerate() {
+                if useful(&before, std::slice::from_ref(alt), &types).is_empty() {
+                    unreachable.push(Unreachable {
+                        arm,
+                        alternative: Some(i),
+                        pattern: match texts.get(i) {
+                            Some(text) if texts.len() == alternatives.len() => text.to_string(),
+                            _ => alt.show(ty),
+                        },
+                    });
+                }
+                before.push(vec![alt.clone()]);
+            }
+        }
+        if !branch.guard {
+            rows.push(vec![pat]);
+        }
+    }
+
+    let missing = useful(&rows, &[Pat::Wild], &types)
+        .into_iter()
+        .map(|witness| witness[0].show(ty))
+        .collect();
+    Ok(Report {
+        ty: ty.to_string(),
+        unreachable,
+        missing,
+    })
+}
@@ -0,0 +1,314 @@
+// ============================================
+// 二维几何 (Geometry)
+// ============================================
+// 第 06 章的 Rectangle 只有 width 和 height，没有位置；area 直接算
+// width * height，两个 u32 相乘可能溢出；can_hold 用严格的 >，
+// 一样大的矩形也放不进去。这里把它扩展成布局代码需要的样子：
+//
+// - Point 和第 13 章一样有 x、y 两个 i32 字段，支持 + 和 -，显示为 (x, y)；
+//   也可以和 (i32, i32) 互相转换。第 13 章的 Point、第 12 章的 Point<i32>
+//   在各自的章节里实现了和这里的 Point 互相转换的 From
+// - Rectangle 有左上角 origin 和 width、height，x 向右、y 向下（屏幕坐标）
+//   第 06 章没有位置的 Rectangle 可以转换过来（放在原点），也可以转回去
+// - 矩形覆盖的是半开区间 [x, x + width) × [y, y + height)：
+//   只有边挨着的两个矩形不相交，宽或高为 0 的矩形不包含任何点
+// - 边的坐标用 i64 计算，面积和周长用 u64 返回，不会溢出；
+//   结果的 origin 超出 i32 或尺寸超出 u32 时返回 GeometryError::Overflow
+// - fits_inside 按尺寸判断能不能放进另一个矩形，必要时旋转 90 度
+
+use std::fmt;
+use std::ops::{Add, Sub};
+
+// ========== 点 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// 不会溢出的加法
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
+    /// 到原点的距离，第 12 章 Point<f32>::distance_from_origin 的 i32 版本
+    pub fn distance_from_origin(self) -> f64 {
+        f64::from(self.x).hypot(f64::from(self.y))
+    }
+
+    /// 到另一个点的距离的平方；用 u128 返回，i32 范围两端的点也不会溢出
+    pub fn distance_squared(self, other: Point) -> u128 {
+        let dx = u128::from((i64::from(self.x) - i64::from(other.x)).unsigned_abs());
+        let dy = u128::from((i64::from(self.y) - i64::from(other.y)).unsigned_abs());
+        dx * dx + dy * dy
+    }
+}
+
+// 和第 13 章一样实现 Add，溢出时和 i32 的 + 一样 panic（debug 构建）
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(point: Point) -> (i32, i32) {
+        (point.x, point.y)
+    }
+}
+
+// ========== 错误 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryError {
+    /// 结果的 origin 超出 i32，或者宽高超出 u32
+    Overflow { op: &'static str },
+    /// 求包围盒时没有任何矩形
+    Empty,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::Overflow { op } => {
+                write!(f, "{} 溢出: 结果超出 i32 坐标或 u32 尺寸的范围", op)
+            }
+            GeometryError::Empty => write!(f, "没有矩形，无法求包围盒"),
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+// ========== 矩形 ==========
+
+/// 带位置的轴对齐矩形，覆盖 [x, x + width) × [y, y + height)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rectangle {
+    pub origin: Point,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// fits_inside 的结果：原样放入，还是旋转 90 度后放入
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Upright,
+    Rotated,
+}
+
+impl Rectangle {
+    pub const fn new(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle {
+            origin: Point { x, y },
+            width,
+            height,
+        }
+    }
+
+    pub const fn at(origin: Point, width: u32, height: u32) -> Rectangle {
+        Rectangle {
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// 第 06 章的 Rectangle::square，多了一个位置
+    pub const fn square(origin: Point, size: u32) -> Rectangle {
+        Rectangle::at(origin, size, size)
+    }
+
+    /// 以两个点为对角的矩形，两个点的先后顺序无关
+    pub fn from_corners(a: Point, b: Point) -> Rectangle {
+        let width = (i64::from(a.x) - i64::from(b.x)).unsigned_abs() as u32;
+        let height = (i64::from(a.y) - i64::from(b.y)).unsigned_abs() as u32;
+        Rectangle::new(a.x.min(b.x), a.y.min(b.y), width, height)
+    }
+
+    pub fn left(&self) -> i64 {
+        i64::from(self.origin.x)
+    }
+
+    pub fn top(&self) -> i64 {
+        i64::from(self.origin.y)
+    }
+
+    /// 右边界（不包含），用 i64 表示，不会溢出
+    pub fn right(&self) -> i64 {
+        self.left() + i64::from(self.width)
+    }
+
+    /// 下边界（不包含）
+    pub fn bottom(&self) -> i64 {
+        self.top() + i64::from(self.height)
+    }
+
+    /// 宽或高为 0
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// 面积，u32 × u32 总能放进 u64
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    /// 第 06 章返回 u32 的面积；溢出时返回 None，而不是 panic 或回绕
+    pub fn checked_area(&self) -> Option<u32> {
+        self.width.checked_mul(self.height)
+    }
+
+    pub fn perimeter(&self) -> u64 {
+        2 * (u64::from(self.width) + u64::from(self.height))
+    }
+
+    pub fn contains_point(&self, point: Point) -> bool {
+        let (x, y) = (i64::from(point.x), i64::from(point.y));
+        self.left() <= x && x < self.right() && self.top() <= y && y < self.bottom()
+    }
+
+    /// other 整个在这个矩形里面（边可以重合）；空矩形只要位置在范围内就算
+    pub fn contains(&self, other: &Rectangle) -> bool {
+        self.left() <= other.left()
+            && other.right() <= self.right()
+            && self.top() <= other.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// 两个矩形有面积大于 0 的重叠部分；只是边挨着不算，空矩形和谁都不相交
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.left() < other.right()
+            && other.left() < self.right()
+            && self.top() < other.bottom()
+            && other.top() < self.bottom()
+    }
+
+    /// 重叠部分，不相交时返回 None
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let origin = Point::new(
+            self.origin.x.max(other.origin.x),
+            self.origin.y.max(other.origin.y),
+        );
+        // 重叠部分不会比任何一个矩形大，宽高一定能放进 u32
+        let width = self.right().min(other.right()) - i64::from(origin.x);
+        let height = self.bottom().min(other.bottom()) - i64::from(origin.y);
+        Some(Rectangle::at(origin, width as u32, height as u32))
+    }
+
+    /// 同时包含两个矩形的最小矩形（包围盒）
+    pub fn union(&self, other: &Rectangle) -> Result<Rectangle, GeometryError> {
+        let origin = Point::new(
+            self.origin.x.min(other.origin.x),
+            self.origin.y.min(other.origin.y),
+        );
+        let width = self.right().max(other.right()) - i64::from(origin.x);
+        let height = self.bottom().max(other.bottom()) - i64::from(origin.y);
+        let overflow = GeometryError::Overflow { op: "union" };
+        Ok(Rectangle::at(
+            origin,
+            u32::try_from(width).map_err(|_| overflow)?,
+            u32::try_from(height).map_err(|_| overflow)?,
+        ))
+    }
+
+    /// 包含所有矩形的最小矩形
+    pub fn bounding_box(
+        rectangles: impl IntoIterator<Item = Rectangle>,
+    ) -> Result<Rectangle, GeometryError> {
+        let mut rectangles = rectangles.into_iter();
+        let first = rectangles.next().ok_or(GeometryError::Empty)?;
+        rectangles.try_fold(first, |bounds, rectangle| bounds.union(&rectangle))
+    }
+
+    /// 平移 (dx, dy)
+    pub fn translate(&self, dx: i32, dy: i32) -> Result<Rectangle, GeometryError> {
+        let origin = self
+            .origin
+            .checked_add(Point::new(dx, dy))
+            .ok_or(GeometryError::Overflow { op: "translate" })?;
+        Ok(Rectangle { origin, ..*self })
+    }
+
+    /// 以坐标原点为中心放大 factor 倍：位置和尺寸都乘以 factor
+    pub fn scale(&self, factor: u32) -> Result<Rectangle, GeometryError> {
+        // 坐标先用 i64 相乘，i32 × u32 放得进 i64，再检查结果能不能放回 i32
+        let position = |v: i32| i32::try_from(i64::from(v) * i64::from(factor)).ok();
+        let scaled = || {
+            Some(Rectangle::new(
+                position(self.origin.x)?,
+                position(self.origin.y)?,
+                self.width.checked_mul(factor)?,
+                self.height.checked_mul(factor)?,
+            ))
+        };
+        scaled().ok_or(GeometryError::Overflow { op: "scale" })
+    }
+
+    /// 只按尺寸判断能不能放进 container，可以旋转 90 度；一样大的矩形放得进去。
+    /// 两种方向都行时返回 Upright
+    pub fn fits_inside(&self, container: &Rectangle) -> Option<Orientation> {
+        if self.width <= container.width && self.height <= container.height {
+            Some(Orientation::Upright)
+        } else if self.height <= container.width && self.width <= container.height {
+            Some(Orientation::Rotated)
+        } else {
+            None
+        }
+    }
+
+    /// 旋转 90 度：宽高交换，origin 不变
+    pub fn rotated(&self) -> Rectangle {
+        Rectangle::at(self.origin, self.height, self.width)
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}×{}", self.origin, self.width, self.height)
+    }
+}
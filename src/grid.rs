@@ -0,0 +1,389 @@
+// ============================================
+// 迷宫和网格 (Grid)
+// ============================================
+// 第 04 章用计数器演示了 loop 的 break 返回值、'outer 标签、continue
+// 和用 Vec 当栈的 while let。这个模块把它们用在一个实际的问题上：
+// 解析 ASCII 迷宫，分别用深度优先（Vec 栈）和广度优先（VecDeque 队列）
+// 搜索从 S 到 G 的路径，再把路径画回网格：
+//
+//     #########        #########
+//     #S#.....#        #S#*****#
+//     #.#.###.#        #*#*###*#
+//     #...#.#.#   →    #***#.#*#
+//     #####.#.#        #####.#*#
+//     #......G#        #......G#
+//     #########        #########
+//
+// 格子: # 墙，. 或空格 通路，S 起点，G 终点（各恰好一个）。
+// 找到终点时用 break 'search 直接跳出两层循环，不再搜索剩下的格子。
+// generate 用固定种子的伪随机数生成迷宫，同一个种子总是得到同一个迷宫。
+
+use std::collections::VecDeque;
+use std::fmt;
+
+// ========== 位置 ==========
+
+/// 网格中的位置，row 向下、col 向右
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+/// 上、右、下、左
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+// ========== 解析 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Wall,
+    Open,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 行: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 一个迷宫：矩形的格子，加上起点和终点
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+    pub start: Pos,
+    pub goal: Pos,
+}
+
+impl Grid {
+    /// 解析 ASCII 迷宫，每一行的长度必须相同，末尾的空行会被忽略
+    pub fn parse(text: &str) -> Result<Grid, ParseError> {
+        let rows: Vec<&str> = text.trim_end_matches(['\n', '\r']).lines().collect();
+        if rows.is_empty() || rows[0].is_empty() {
+            return Err(ParseError {
+                line: 1,
+                message: String::from("迷宫是空的"),
+            });
+        }
+
+        let width = rows[0].chars().count();
+        let mut cells = Vec::with_capacity(width * rows.len());
+        let mut start = None;
+        let mut goal = None;
+        for (row, line) in rows.iter().enumerate() {
+            let error = |message: String| ParseError {
+                line: row + 1,
+                message,
+            };
+            let count = line.chars().count();
+            if count != width {
+                return Err(error(format!("有 {} 列，第 1 行有 {} 列", count, width)));
+            }
+            for (col, c) in line.chars().enumerate() {
+                let (cell, slot, name) = match c {
+                    '#' => (Cell::Wall, None, ""),
+                    '.' | ' ' => (Cell::Open, None, ""),
+                    'S' => (Cell::Open, Some(&mut start), "起点 S"),
+                    'G' => (Cell::Open, Some(&mut goal), "终点 G"),
+                    _ => return Err(error(format!("第 {} 列: 未知的格子 `{}`", col + 1, c))),
+                };
+                if let Some(slot) = slot {
+                    if let Some(Pos { row, col }) = *slot {
+                        return Err(error(format!(
+                            "{} 出现了两次（另一个在第 {} 行第 {} 列）",
+                            name,
+                            row + 1,
+                            col + 1
+                        )));
+                    }
+                    *slot = Some(Pos::new(row, col));
+                }
+                cells.push(cell);
+            }
+        }
+
+        let missing = |name: &str| ParseError {
+            line: rows.len(),
+            message: format!("没有{}", name),
+        };
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells,
+            start: start.ok_or_else(|| missing("起点 S"))?,
+            goal: goal.ok_or_else(|| missing("终点 G"))?,
+        })
+    }
+
+    pub fn cell(&self, pos: Pos) -> Cell {
+        self.cells[self.index(pos)]
+    }
+
+    pub fn is_open(&self, pos: Pos) -> bool {
+        self.cell(pos) == Cell::Open
+    }
+
+    /// 上、右、下、左四个方向上不是墙的相邻格子
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS.iter().filter_map(move |&(dr, dc)| {
+            let row = pos.row.checked_add_signed(dr)?;
+            let col = pos.col.checked_add_signed(dc)?;
+            let next = Pos::new(row, col);
+            (row < self.height && col < self.width && self.is_open(next)).then_some(next)
+        })
+    }
+
+    fn index(&self, pos: Pos) -> usize {
+        pos.row * self.width + pos.col
+    }
+
+    /// 只有一个出口的死胡同（不包括起点和终点），按行扫描
+    pub fn dead_ends(&self) -> Vec<Pos> {
+        let mut dead_ends = Vec::new();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let pos = Pos::new(row, col);
+                if !self.is_open(pos) || pos == self.start || pos == self.goal {
+                    continue;
+                }
+                if self.neighbors(pos).count() == 1 {
+                    dead_ends.push(pos);
+                }
+            }
+        }
+        dead_ends
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(&[]))
+    }
+}
+
+// ========== 搜索 ==========
+
+/// 搜索的结果：找到的路径（包括起点和终点）和展开过的格子数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search {
+    pub path: Option<Vec<Pos>>,
+    pub explored: usize,
+}
+
+impl Search {
+    /// 路径的步数，比格子数少 1
+    pub fn steps(&self) -> Option<usize> {
+        self.path.as_ref().map(|path| path.len() - 1)
+    }
+}
+
+impl Grid {
+    /// 深度优先：`while let Some(top) = stack.pop()`，后压入的先展开
+    pub fn dfs(&self) -> Search {
+        let mut came_from: Vec<Option<Pos>> = vec![None; self.cells.len()];
+        let mut visited = vec![false; self.cells.len()];
+        let mut stack = vec![self.start];
+        let mut explored = 0;
+        let mut found = false;
+
+        'search: while let Some(top) = stack.pop() {
+            if visited[self.index(top)] {
+                continue;
+            }
+            visited[self.index(top)] = true;
+            explored += 1;
+            for next in self.neighbors(top) {
+                if visited[self.index(next)] {
+                    continue;
+                }
+                came_from[self.index(next)] = Some(top);
+                if next == self.goal {
+                    found = true;
+                    break 'search;
+                }
+                stack.push(next);
+            }
+        }
+
+        Search {
+            path: found.then(|| self.trace_back(&came_from)),
+            explored,
+        }
+    }
+
+    /// 广度优先：队列先进先出，按距离一层层展开，找到的是最短路径
+    pub fn bfs(&self) -> Search {
+        let mut came_from: Vec<Option<Pos>> = vec![None; self.cells.len()];
+        let mut visited = vec![false; self.cells.len()];
+        let mut queue = VecDeque::from([self.start]);
+        visited[self.index(self.start)] = true;
+        let mut explored = 0;
+        let mut found = false;
+
+        'search: while let Some(front) = queue.pop_front() {
+            explored += 1;
+            for next in self.neighbors(front) {
+                if visited[self.index(next)] {
+                    continue;
+                }
+                visited[self.index(next)] = true;
+                came_from[self.index(next)] = Some(front);
+                if next == self.goal {
+                    found = true;
+                    break 'search;
+                }
+                queue.push_back(next);
+            }
+        }
+
+        Search {
+            path: found.then(|| self.trace_back(&came_from)),
+            explored,
+        }
+    }
+
+    /// 从终点沿 came_from 走回起点
+    fn trace_back(&self, came_from: &[Option<Pos>]) -> Vec<Pos> {
+        let mut path = vec![self.goal];
+        while let Some(prev) = came_from[self.index(*path.last().unwrap())] {
+            path.push(prev);
+        }
+        path.reverse();
+        path
+    }
+
+    /// 把路径画回网格，路径上的格子（起点和终点除外）用 * 表示
+    pub fn render(&self, path: &[Pos]) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let pos = Pos::new(row, col);
+                out.push(if pos == self.start {
+                    'S'
+                } else if pos == self.goal {
+                    'G'
+                } else if self.cell(pos) == Cell::Wall {
+                    '#'
+                } else if path.contains(&pos) {
+                    '*'
+                } else {
+                    '.'
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// ========== 伪随机数 ==========
+
+/// SplitMix64：状态只有一个 u64，同一个种子总是产生同一个序列
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// 0..n 中均匀分布的数：丢掉会让取模结果偏向小数的那部分，
+    /// 用 break 从 loop 返回值
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "below(0)");
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                break (x % n) as usize;
+            }
+        }
+    }
+}
+
+// ========== 生成 ==========
+
+impl Grid {
+    /// 生成 rows × cols 个房间的迷宫（网格大小是 2·rows+1 × 2·cols+1），
+    /// 起点在左上角，终点在右下角。
+    /// 用深度优先的回溯法打通墙壁，得到的迷宫任意两点之间恰好有一条路
+    pub fn generate(rows: usize, cols: usize, seed: u64) -> Grid {
+        assert!(rows > 0 && cols > 0, "迷宫至少要有一个房间");
+        let height = 2 * rows + 1;
+        let width = 2 * cols + 1;
+        let mut grid = Grid {
+            width,
+            height,
+            cells: vec![Cell::Wall; width * height],
+            start: Pos::new(1, 1),
+            goal: Pos::new(height - 2, width - 2),
+        };
+        let mut rng = Rng::new(seed);
+        let mut visited = vec![false; rows * cols];
+        let room = |pos: Pos| (pos.row / 2) * cols + pos.col / 2;
+
+        let start = grid.start;
+        let i = grid.index(start);
+        grid.cells[i] = Cell::Open;
+        visited[room(start)] = true;
+        let mut stack = vec![start];
+        while let Some(&top) = stack.last() {
+            // 还没去过的相邻房间，隔着一堵墙
+            let choices: Vec<Pos> = DIRECTIONS
+                .iter()
+                .filter_map(|&(dr, dc)| {
+                    let row = top.row.checked_add_signed(2 * dr)?;
+                    let col = top.col.checked_add_signed(2 * dc)?;
+                    let next = Pos::new(row, col);
+                    (row < height && col < width && !visited[room(next)]).then_some(next)
+                })
+                .collect();
+            if choices.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let next = choices[rng.below(choices.len())];
+            let wall = Pos::new((top.row + next.row) / 2, (top.col + next.col) / 2);
+            for pos in [wall, next] {
+                let i = grid.index(pos);
+                grid.cells[i] = Cell::Open;
+            }
+            visited[room(next)] = true;
+            stack.push(next);
+        }
+        grid
+    }
+}
@@ -0,0 +1,460 @@
+// ============================================
+// 多语言消息 (i18n)
+// ============================================
+// 章节中带文字的输出都通过 t! 宏从消息目录中取出，例如：
+//
+//     println!("{}", t!("functions.parameters", x, y));
+//
+// 消息目录位于 locales/<语言>.txt，每行一条 `键 = 模板`：
+//
+//     # 以 # 开头的行是注释
+//     functions.parameters = 参数值: x = {}, y = {}
+//
+// 模板中的 {} 按顺序替换为参数，{0}、{1} 按位置替换（译文需要调整语序时使用），
+// {{ 和 }} 表示花括号本身。参数只需要实现 Display，需要 {:?} 或 {:p} 的地方
+// 在调用处先用 format! 格式化好。
+//
+// 输出语言由 rust-tutorial 的 --lang 参数指定；没有指定时依次查看 LC_ALL、
+// LC_MESSAGES 和 LANG 环境变量，都无法识别时使用中文。
+//
+// 这个模块会随章节一起被单独编译（见 toolchain::chapter_crate），
+// 所以只能依赖标准库，不能引用 crate 中的其他模块。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+
+/// 取出当前语言的消息并用参数格式化，得到一个 String
+///
+/// ```text
+/// t!("functions.another_fn")
+/// t!("functions.parameters", x, y)
+/// ```
+macro_rules! t {
+    ($key:literal $(, $arg:expr)* $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg as &dyn ::std::fmt::Display),*])
+    };
+}
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lang {
+    Zh,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Zh, Lang::En];
+
+    /// 语言代码，也是消息目录的文件名，例如 "zh"
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        }
+    }
+
+    /// 解析 "zh"、"en" 这样的语言代码，或 "zh_CN.UTF-8"、"en_US" 这样的 locale
+    pub fn parse(name: &str) -> Option<Lang> {
+        let name = name.trim().to_ascii_lowercase();
+        match name.split(['_', '-', '.', '@']).next() {
+            Some("zh") => Some(Lang::Zh),
+            Some("en") => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// 消息目录文件的内容
+    pub fn catalog(self) -> &'static str {
+        match self {
+            Lang::Zh => include_str!("../locales/zh.txt"),
+            Lang::En => include_str!("../locales/en.txt"),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+// ========== 选择语言 ==========
+
+static CURRENT: OnceLock<Lang> = OnceLock::new();
+
+/// 指定输出语言；必须在第一条消息输出之前调用，否则返回 false
+pub fn set_lang(lang: Lang) -> bool {
+    CURRENT.set(lang).is_ok()
+}
+
+/// 当前的输出语言
+pub fn lang() -> Lang {
+    *CURRENT.get_or_init(lang_from_env)
+}
+
+fn lang_from_env() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find_map(|value| Lang::parse(&value))
+        .unwrap_or(Lang::Zh)
+}
+
+// ========== 消息目录 ==========
+
+/// 消息目录中的一条消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// 所在的行号（从 1 开始）
+    pub line: usize,
+    pub key: &'a str,
+    pub template: &'a str,
+}
+
+/// 解析消息目录；格式错误的行以 (行号, 说明) 的形式一并返回
+pub fn parse(text: &str) -> (Vec<Entry<'_>>, Vec<(usize, String)>) {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, template)) = trimmed.split_once('=') else {
+            errors.push((line, format!("缺少 \"=\": {}", trimmed)));
+            continue;
+        };
+        let (key, template) = (key.trim(), template.trim());
+        let valid_key = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        if !valid_key {
+            errors.push((line, format!("无效的键: {:?}", key)));
+        } else if let Some(first) = entries.iter().find(|entry| entry.key == key) {
+            errors.push((
+                line,
+                format!("重复的键 {}（第 {} 行已定义）", key, first.line),
+            ));
+        } else if let Err(message) = arity(template) {
+            errors.push((line, format!("{}: {}", key, message)));
+        } else {
+            entries.push(Entry {
+                line,
+                key,
+                template,
+            });
+        }
+    }
+
+    (entries, errors)
+}
+
+fn table(lang: Lang) -> &'static HashMap<&'static str, &'static str> {
+    static ZH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+    static EN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+
+    let cell = match lang {
+        Lang::Zh => &ZH,
+        Lang::En => &EN,
+    };
+    cell.get_or_init(|| {
+        let (entries, _) = parse(lang.catalog());
+        entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.template))
+            .collect()
+    })
+}
+
+/// 当前语言中 `key` 对应的模板
+pub fn template(key: &str) -> &str {
+    template_in(lang(), key)
+}
+
+/// `lang` 中 `key` 对应的模板
+///
+/// 缺少这条消息时退回中文，中文也没有时返回键本身，
+/// 这样遗漏的翻译在输出中一眼就能看出来。
+pub fn template_in(lang: Lang, key: &str) -> &str {
+    table(lang)
+        .get(key)
+        .or_else(|| table(Lang::Zh).get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+/// t! 宏的实现：取出模板并替换占位符
+pub fn format(key: &str, args: &[&dyn Display]) -> String {
+    render(template(key), args)
+}
+
+// ========== 模板 ==========
+
+enum Piece<'a> {
+    Text(&'a str),
+    Arg(usize),
+}
+
+/// 把模板拆成文字和占位符
+fn pieces(template: &str) -> Result<Vec<Piece<'_>>, String> {
+    let mut pieces = Vec::new();
+    let mut next = 0;
+    let mut rest = template;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            pieces.push(Piece::Text(&rest[..index]));
+        }
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+
+        // {{ 和 }} 是转义的花括号
+        if rest.starts_with(brace) {
+            pieces.push(Piece::Text(brace));
+            rest = &rest[1..];
+            continue;
+        }
+        if brace == "}" {
+            return Err(String::from("多余的 \"}\"，花括号本身要写成 }}"));
+        }
+
+        let Some(end) = rest.find('}') else {
+            return Err(String::from("\"{\" 没有对应的 \"}\""));
+        };
+        let inside = &rest[..end];
+        let position = if inside.is_empty() {
+            next += 1;
+            next - 1
+        } else {
+            inside.parse().map_err(|_| {
+                format!(
+                    "无效的占位符 {{{}}}，只支持 {{}} 和 {{0}}、{{1}} ...",
+                    inside
+                )
+            })?
+        };
+        pieces.push(Piece::Arg(position));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest));
+    }
+    Ok(pieces)
+}
+
+/// 模板需要的参数个数（最大的位置编号加 1）
+pub fn arity(template: &str) -> Result<usize, String> {
+    Ok(pieces(template)?
+        .iter()
+        .filter_map(|piece| match piece {
+            Piece::Arg(position) => Some(position + 1),
+            Piece::Text(_) => None,
+        })
+        .max()
+        .unwrap_or(0))
+}
+
+/// 用参数替换模板中的占位符；无效的模板和缺少参数的占位符原样输出
+pub fn render(template: &str, args: &[&dyn Display]) -> String {
+    let Ok(pieces) = pieces(template) else {
+        return template.to_string();
+    };
+
+    let mut output = String::with_capacity(template.len());
+    for piece in pieces {
+        match piece {
+            Piece::Text(text) => output.push_str(text),
+            Piece::Arg(position) => match args.get(position) {
+                Some(arg) => output.push_str(&arg.to_string()),
+                None => output.push_str(&format!("{{{}}}", position)),
+            },
+        }
+    }
+    output
+}
+
+// ========== 检查消息目录 ==========
+
+/// 源码中的一次 t! 调用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage<'a> {
+    pub key: &'a str,
+    /// 传入的参数个数
+    pub args: usize,
+    /// 所在的行号（从 1 开始）
+    pub line: usize,
+}
+
+/// 找出源码中所有的 t! 调用；注释中的调用会被忽略
+pub fn usages(source: &str) -> Vec<Usage<'_>> {
+    let mut usages = Vec::new();
+
+    for (start, _) in source.match_indices("t!(") {
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let before = &source[line_start..start];
+        let is_macro = !before
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if !is_macro || before.contains("//") {
+            continue;
+        }
+
+        let rest = source[start + 3..].trim_start();
+        let Some(rest) = rest.strip_prefix('"') else {
+            continue;
+        };
+        let Some(end) = rest.find('"') else {
+            continue;
+        };
+        usages.push(Usage {
+            key: &rest[..end],
+            args: count_args(&rest[end + 1..]),
+            line: source[..start].matches('\n').count() + 1,
+        });
+    }
+
+    usages
+}
+
+/// 数出键之后、右括号之前的参数个数（只在最外层的逗号处分隔）
+fn count_args(rest: &str) -> usize {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut args = 0;
+    let mut pending = false;
+
+    for c in rest.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                pending = true;
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                pending = true;
+            }
+            ')' | ']' | '}' if depth == 0 => break,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                if pending {
+                    args += 1;
+                }
+                pending = false;
+            }
+            c if !c.is_whitespace() => pending = true,
+            _ => {}
+        }
+    }
+
+    // 键后面的第一个逗号之前没有参数，不计数；最后一个参数后面可能没有逗号
+    if pending {
+        args += 1;
+    }
+    args
+}
+
+/// 一个消息目录的检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub lang: Lang,
+    /// 格式错误：无效的行、重复的键、无效的占位符
+    pub errors: Vec<String>,
+    /// 源码中使用了、目录中却没有的键
+    pub missing: Vec<String>,
+    /// 目录中有、源码中却没有使用的键
+    pub unused: Vec<String>,
+    /// 模板需要的参数个数与调用处传入的不一致
+    pub mismatched: Vec<String>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+            && self.missing.is_empty()
+            && self.unused.is_empty()
+            && self.mismatched.is_empty()
+    }
+}
+
+/// 对照源码检查一个消息目录；`sources` 是 (文件名, 源码) 的列表
+pub fn check(lang: Lang, catalog: &str, sources: &[(&str, &str)]) -> Report {
+    let (entries, errors) = parse(catalog);
+    let templates: BTreeMap<&str, &str> = entries
+        .iter()
+        .map(|entry| (entry.key, entry.template))
+        .collect();
+
+    let mut report = Report {
+        lang,
+        errors: errors
+            .into_iter()
+            .map(|(line, message)| format!("第 {} 行: {}", line, message))
+            .collect(),
+        missing: Vec::new(),
+        unused: Vec::new(),
+        mismatched: Vec::new(),
+    };
+
+    let mut used = BTreeSet::new();
+    for (file, source) in sources {
+        for usage in usages(source) {
+            let location = format!("{} 第 {} 行", file, usage.line);
+            match templates.get(usage.key) {
+                None => report
+                    .missing
+                    .push(format!("{}（{}）", usage.key, location)),
+                Some(template) => {
+                    let wanted = arity(template).unwrap_or(0);
+                    if wanted != usage.args {
+                        report.mismatched.push(format!(
+                            "{}: 模板需要 {} 个参数，{} 传入了 {} 个",
+                            usage.key, wanted, location, usage.args
+                        ));
+                    }
+                }
+            }
+            used.insert(usage.key);
+        }
+    }
+
+    report.unused = entries
+        .iter()
+        .filter(|entry| !used.contains(entry.key))
+        .map(|entry| format!("{}（第 {} 行）", entry.key, entry.line))
+        .collect();
+    report
+}
+
+/// 在一行代码后面附上其中 t! 消息在 `lang` 中的模板，方便阅读
+///
+/// 例如 `println!("{}", t!("generics.largest_char", result));` 会变成
+/// `println!("{}", t!("generics.largest_char", result)); // "最大字符: {}"`
+pub fn annotate(lang: Lang, line: &str) -> String {
+    let templates: Vec<String> = usages(line)
+        .iter()
+        .map(|usage| format!("{:?}", template_in(lang, usage.key)))
+        .collect();
+    if templates.is_empty() {
+        line.to_string()
+    } else {
+        format!("{} // {}", line, templates.join(", "))
+    }
+}
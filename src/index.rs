@@ -0,0 +1,129 @@
+// ============================================
+// 关键字和概念索引 (Index)
+// ============================================
+// 记录每个关键字、类型、宏和生命周期在哪一章、哪一节、哪一行出现，
+// 并附上第 16 章关键字分类总结中的说明。
+//
+// 索引表由 build.rs 在编译期扫描章节源码生成（扫描逻辑见 scan.rs），
+// 所以总是和章节代码保持一致。
+
+pub use crate::scan::KeywordKind;
+
+use crate::chapters::{self, Chapter};
+
+/// 第 16 章关键字分类总结中的一个关键字
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keyword {
+    pub name: &'static str,
+    pub kind: KeywordKind,
+    /// 严格关键字的分组，例如 "所有权和借用"；其他类别为空
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+/// 一个词在章节代码中出现的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    /// 章节编号
+    pub chapter: u8,
+    /// 行号（从 1 开始）
+    pub line: usize,
+    /// 所在的小节和编号段落，例如 "闭包 / 12. 闭包"
+    pub section: &'static str,
+}
+
+impl Occurrence {
+    pub fn chapter(&self) -> &'static Chapter {
+        chapters::all()
+            .iter()
+            .find(|chapter| chapter.number == self.chapter)
+            .expect("索引中的章节编号来自章节文件")
+    }
+
+    /// 这一行源码，去掉首尾空白
+    pub fn code(&self) -> &'static str {
+        self.chapter()
+            .source
+            .lines()
+            .nth(self.line - 1)
+            .unwrap_or("")
+            .trim()
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/keyword_index.rs"));
+
+/// 查询结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// 索引中的词，例如 "move"、"HashMap"、"vec!"、"'static"
+    pub term: &'static str,
+    pub keyword: Option<&'static Keyword>,
+    pub occurrences: &'static [Occurrence],
+}
+
+/// 关键字分类总结中的所有关键字
+pub fn keywords() -> &'static [Keyword] {
+    KEYWORDS
+}
+
+/// 索引中所有的词，按字典序排列
+pub fn terms() -> impl Iterator<Item = &'static str> {
+    TERMS.iter().map(|(term, _)| *term)
+}
+
+/// 一个词在章节代码中出现的所有位置
+pub fn occurrences(term: &str) -> &'static [Occurrence] {
+    find(term).map_or(&[], |(_, occurrences)| occurrences)
+}
+
+fn find(term: &str) -> Option<(&'static str, &'static [Occurrence])> {
+    let index = TERMS
+        .binary_search_by(|(probe, _)| (*probe).cmp(term))
+        .ok()?;
+    Some(TERMS[index])
+}
+
+/// 查询一个词；宏写不写末尾的 `!` 都可以，例如 `println` 和 `println!`
+///
+/// 既不在代码中出现、也不在关键字总结中的词返回 None。
+pub fn lookup(query: &str) -> Option<Entry> {
+    let query = query.trim();
+    let name = query.strip_suffix('!').unwrap_or(query);
+    let keyword = KEYWORDS.iter().find(|keyword| keyword.name == name);
+    let found = find(name).or_else(|| find(&format!("{}!", name)));
+
+    match (keyword, found) {
+        (None, None) => None,
+        (Some(keyword), found) => Some(Entry {
+            term: keyword.name,
+            keyword: Some(keyword),
+            occurrences: found.map_or(&[], |(_, occurrences)| occurrences),
+        }),
+        (None, Some((term, occurrences))) => Some(Entry {
+            term,
+            keyword: None,
+            occurrences,
+        }),
+    }
+}
+
+/// 找不到时的候选词：忽略大小写后包含查询内容的词，最多 `limit` 个
+pub fn suggestions(query: &str, limit: usize) -> Vec<&'static str> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    terms()
+        .chain(KEYWORDS.iter().map(|keyword| keyword.name))
+        .filter(|term| term.to_lowercase().contains(&query))
+        .fold(Vec::new(), |mut found, term| {
+            if !found.contains(&term) {
+                found.push(term);
+            }
+            found
+        })
+        .into_iter()
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,895 @@
+// ============================================
+// 整数字面量和溢出 (Integer)
+// ============================================
+// 第 02 章介绍了 i8…i128、u8…u128 等整数类型，以及 98_222、0xff、0o77、
+// 0b1111_0000、b'A' 这些字面量写法。这个模块：
+//
+// - 按 rustc 的规则解析整数字面量：下划线、进制前缀、字节字面量、类型后缀，
+//   以及带负号的写法（-128i8）
+// - 推断字面量的类型：有后缀用后缀，字节字面量是 u8，否则默认 i32
+// - 报告超出类型范围的字面量，措辞和建议的类型与 rustc 的 overflowing_literals 一致
+// - 在同一个类型下用 checked、wrapping、saturating、overflowing 四种方式计算，
+//   并排比较溢出时的结果
+//
+// 计算直接调用标准库中对应类型的方法（例如 u8::wrapping_add），结果和真实程序一致。
+
+use std::fmt;
+
+// ========== 整数类型 ==========
+
+/// Rust 的 12 种整数类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntType {
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+        }
+    }
+
+    /// 按名称查找，例如 "u8"
+    pub fn parse(name: &str) -> Option<IntType> {
+        IntType::ALL.into_iter().find(|ty| ty.name() == name)
+    }
+
+    pub fn signed(self) -> bool {
+        self.name().starts_with('i')
+    }
+
+    /// 位数；isize 和 usize 取决于编译目标
+    pub fn bits(self) -> u32 {
+        dispatch!(self, T => T::BITS)
+    }
+
+    pub fn min(self) -> Integer {
+        dispatch!(self, T => Integer::from_native(self, T::MIN))
+    }
+
+    pub fn max(self) -> Integer {
+        dispatch!(self, T => Integer::from_native(self, T::MAX))
+    }
+
+    /// 能表示 `magnitude`（为负数时表示 -magnitude）的最小有符号或无符号类型，
+    /// 只在 8、16、32、64、128 位中选择
+    fn fit(signed: bool, magnitude: u128, negative: bool) -> IntType {
+        let candidates = if signed {
+            [
+                IntType::I8,
+                IntType::I16,
+                IntType::I32,
+                IntType::I64,
+                IntType::I128,
+            ]
+        } else {
+            [
+                IntType::U8,
+                IntType::U16,
+                IntType::U32,
+                IntType::U64,
+                IntType::U128,
+            ]
+        };
+        candidates
+            .into_iter()
+            .find(|ty| ty.contains(magnitude, negative))
+            .unwrap_or(candidates[4])
+    }
+
+    /// 这个类型能否表示 `magnitude`（为负数时表示 -magnitude）
+    fn contains(self, magnitude: u128, negative: bool) -> bool {
+        match (self.signed(), negative) {
+            (false, true) => magnitude == 0,
+            (false, false) => magnitude <= self.max().bits,
+            // 有符号类型：正数最大 2^(n-1) - 1，负数最小 -2^(n-1)
+            (true, false) => magnitude < 1 << (self.bits() - 1),
+            (true, true) => magnitude <= 1 << (self.bits() - 1),
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 对每种整数类型执行同一段代码，代码中用 `T` 表示对应的原生类型
+macro_rules! dispatch {
+    ($ty:expr, $t:ident => $body:expr) => {
+        match $ty {
+            IntType::I8 => {
+                type $t = i8;
+                $body
+            }
+            IntType::I16 => {
+                type $t = i16;
+                $body
+            }
+            IntType::I32 => {
+                type $t = i32;
+                $body
+            }
+            IntType::I64 => {
+                type $t = i64;
+                $body
+            }
+            IntType::I128 => {
+                type $t = i128;
+                $body
+            }
+            IntType::Isize => {
+                type $t = isize;
+                $body
+            }
+            IntType::U8 => {
+                type $t = u8;
+                $body
+            }
+            IntType::U16 => {
+                type $t = u16;
+                $body
+            }
+            IntType::U32 => {
+                type $t = u32;
+                $body
+            }
+            IntType::U64 => {
+                type $t = u64;
+                $body
+            }
+            IntType::U128 => {
+                type $t = u128;
+                $body
+            }
+            IntType::Usize => {
+                type $t = usize;
+                $body
+            }
+        }
+    };
+}
+use dispatch;
+
+// ========== 整数值 ==========
+
+/// 某个整数类型的值
+///
+/// 内部保存按补码扩展到 128 位的二进制位，取值时再截断回原来的类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Integer {
+    pub ty: IntType,
+    bits: u128,
+}
+
+impl Integer {
+    /// 从原生整数构造，例如 `Integer::from_native(IntType::I8, -1i8)`
+    fn from_native<T: Into<Wide>>(ty: IntType, value: T) -> Integer {
+        let bits = match value.into() {
+            Wide::Signed(value) => value as u128,
+            Wide::Unsigned(value) => value,
+        };
+        Integer { ty, bits }
+    }
+
+    /// 把一个数转换成给定类型的值，超出范围时返回 None
+    pub fn new(ty: IntType, value: i128) -> Option<Integer> {
+        let (magnitude, negative) = (value.unsigned_abs(), value < 0);
+        Integer::from_magnitude(ty, magnitude, negative)
+    }
+
+    fn from_magnitude(ty: IntType, magnitude: u128, negative: bool) -> Option<Integer> {
+        if !ty.contains(magnitude, negative) {
+            return None;
+        }
+        let bits = if negative {
+            magnitude.wrapping_neg()
+        } else {
+            magnitude
+        };
+        Some(Integer { ty, bits })
+    }
+
+    /// 截断到给定类型，和 `as` 转换的结果一样，例如 255 截断为 i8 得到 -1
+    fn truncate(ty: IntType, magnitude: u128, negative: bool) -> Integer {
+        let bits = if negative {
+            magnitude.wrapping_neg()
+        } else {
+            magnitude
+        };
+        dispatch!(ty, T => Integer::from_native(ty, bits as T))
+    }
+
+    /// 数值，u128 中超过 i128::MAX 的值返回 None
+    pub fn to_i128(self) -> Option<i128> {
+        match dispatch!(self.ty, T => Wide::from(self.bits as T)) {
+            Wide::Signed(value) => Some(value),
+            Wide::Unsigned(value) => i128::try_from(value).ok(),
+        }
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.bits == 0
+    }
+
+    /// 按类型宽度显示的二进制位，例如 -1i8 为 "1111_1111"
+    pub fn binary(self) -> String {
+        let width = self.ty.bits() as usize;
+        let digits = format!("{:0width$b}", self.bits & mask(self.ty.bits()));
+        group(&digits, 4)
+    }
+}
+
+/// 低 `bits` 位全为 1 的掩码
+fn mask(bits: u32) -> u128 {
+    if bits >= 128 {
+        u128::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+/// 从右往左每 `size` 位插入一个下划线
+fn group(digits: &str, size: usize) -> String {
+    let mut out = String::new();
+    for (index, c) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(size) {
+            out.push('_');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// 原生整数统一转换成的宽类型
+enum Wide {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+macro_rules! impl_wide {
+    ($variant:ident, $wide:ty: $($t:ty),*) => {
+        $(impl From<$t> for Wide {
+            fn from(value: $t) -> Wide {
+                Wide::$variant(value as $wide)
+            }
+        })*
+    };
+}
+impl_wide!(Signed, i128: i8, i16, i32, i64, i128, isize);
+impl_wide!(Unsigned, u128: u8, u16, u32, u64, u128, usize);
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        dispatch!(self.ty, T => write!(f, "{}", self.bits as T))
+    }
+}
+
+// ========== 字面量 ==========
+
+/// 字面量的写法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+    /// 字节字面量 b'A'
+    Byte,
+}
+
+impl Radix {
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal | Radix::Byte => 10,
+            Radix::Hex => 16,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => "二进制",
+            Radix::Octal => "八进制",
+            Radix::Decimal => "十进制",
+            Radix::Hex => "十六进制",
+            Radix::Byte => "字节",
+        }
+    }
+}
+
+/// 解析出的整数字面量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    /// 原始写法，例如 "-0x7f_i8"
+    pub text: String,
+    pub radix: Radix,
+    /// 前面是否有负号
+    pub negative: bool,
+    /// 不带符号的数值
+    pub magnitude: u128,
+    /// 类型后缀，例如 255u8 中的 u8
+    pub suffix: Option<IntType>,
+}
+
+impl Literal {
+    /// 字面量的类型：有后缀用后缀，字节字面量是 u8，其他默认 i32
+    ///
+    /// rustc 会优先根据上下文推断（例如 `let x: u64 = 5;`），上下文中没有
+    /// 约束时才使用默认的 i32，这里相当于没有上下文的情况。
+    pub fn ty(&self) -> IntType {
+        match (self.suffix, self.radix) {
+            (Some(ty), _) => ty,
+            (None, Radix::Byte) => IntType::U8,
+            (None, _) => IntType::I32,
+        }
+    }
+
+    /// 按推断出的类型取值
+    pub fn value(&self) -> Result<Integer, Error> {
+        self.value_as(self.ty())
+    }
+
+    /// 在期望类型为 `ty` 的上下文中取值，例如 `let x: u8 = 255;`
+    pub fn value_as(&self, ty: IntType) -> Result<Integer, Error> {
+        let found = self.ty();
+        if (self.suffix.is_some() || self.radix == Radix::Byte) && found != ty {
+            return Err(Error::Mismatched {
+                expected: ty,
+                found,
+            });
+        }
+        if self.negative && !ty.signed() {
+            return Err(Error::NegatedUnsigned(ty));
+        }
+        Integer::from_magnitude(ty, self.magnitude, self.negative).ok_or_else(|| {
+            Error::OutOfRange(OutOfRange {
+                literal: self.text.clone(),
+                ty,
+                decimal: self.magnitude,
+                radix: self.radix,
+                negative: self.negative,
+                becomes: Integer::truncate(ty, self.magnitude, self.negative),
+                suggestion: suggest(ty, self.magnitude, self.negative),
+            })
+        })
+    }
+}
+
+/// 超出范围时建议的类型，规则与 rustc 的 overflowing_literals 一致：
+/// isize/usize 不给建议；无符号类型建议能放下的最小无符号类型；
+/// 有符号类型的正数如果能放进同样位数的无符号类型，建议无符号类型，否则建议更大的有符号类型
+fn suggest(ty: IntType, magnitude: u128, negative: bool) -> Option<IntType> {
+    match ty {
+        IntType::Isize | IntType::Usize => None,
+        _ if !ty.signed() => Some(IntType::fit(false, magnitude, false)),
+        _ if negative => Some(IntType::fit(true, magnitude, true)),
+        _ => {
+            let unsigned = IntType::fit(false, magnitude, false);
+            if unsigned.bits() == ty.bits() {
+                Some(unsigned)
+            } else {
+                Some(IntType::fit(true, magnitude, false))
+            }
+        }
+    }
+}
+
+/// 超出类型范围的字面量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub literal: String,
+    pub ty: IntType,
+    /// 字面量的十进制数值（不带符号）
+    pub decimal: u128,
+    pub radix: Radix,
+    pub negative: bool,
+    /// 按位截断后得到的值（如果允许编译的话）
+    pub becomes: Integer,
+    pub suggestion: Option<IntType>,
+}
+
+/// 解析或取值时的错误，措辞与 rustc 一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Empty,
+    /// 不是数字开头，例如 "_1" 是标识符
+    NotANumber(String),
+    /// 例如 0b102 中的 2
+    InvalidDigit {
+        digit: char,
+        radix: Radix,
+    },
+    /// 例如 "0x" 或 "0x_"
+    NoDigits(Radix),
+    /// 超过了 u128 能表示的最大值
+    TooLarge,
+    /// 不认识的后缀，例如 5u7
+    InvalidSuffix(String),
+    /// 浮点数字面量，例如 1.5、1e3、2f32
+    Float(String),
+    /// 字节字面量的内容不是单个 ASCII 字符或转义
+    InvalidByte(String),
+    /// 有后缀的字面量出现在期望其他类型的地方
+    Mismatched {
+        expected: IntType,
+        found: IntType,
+    },
+    /// 给无符号类型加负号
+    NegatedUnsigned(IntType),
+    OutOfRange(OutOfRange),
+}
+
+impl Error {
+    /// rustc 附带的 note 和 help，每行一条
+    pub fn notes(&self) -> Vec<String> {
+        match self {
+            Error::TooLarge => vec![format!("note: value exceeds limit of `{}`", u128::MAX)],
+            Error::NegatedUnsigned(_) => {
+                vec![String::from("note: unsigned values cannot be negated")]
+            }
+            Error::OutOfRange(e) => {
+                let sign = if e.negative { "-" } else { "" };
+                let mut notes = vec![if e.radix == Radix::Decimal || e.radix == Radix::Byte {
+                    format!(
+                        "note: the literal `{}` does not fit into the type `{}` whose range is `{}..={}`",
+                        e.literal,
+                        e.ty,
+                        e.ty.min(),
+                        e.ty.max()
+                    )
+                } else {
+                    format!(
+                        "note: the literal `{}` (decimal `{}{}`) does not fit into the type `{}` and will become `{}{}`",
+                        e.literal, sign, e.decimal, e.ty, e.becomes, e.ty
+                    )
+                }];
+                if let Some(suggestion) = e.suggestion {
+                    notes.push(format!(
+                        "help: consider using the type `{}` instead",
+                        suggestion
+                    ));
+                }
+                notes
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "error: expected an integer literal"),
+            Error::NotANumber(text) => {
+                write!(f, "error: `{}` is not an integer literal", text)
+            }
+            Error::InvalidDigit { digit, radix } => write!(
+                f,
+                "error: invalid digit for a base {} literal: `{}`",
+                radix.base(),
+                digit
+            ),
+            Error::NoDigits(_) => write!(f, "error: no valid digits found for number"),
+            Error::TooLarge => write!(f, "error: integer literal is too large"),
+            Error::InvalidSuffix(suffix) => {
+                write!(f, "error: invalid suffix `{}` for number literal", suffix)
+            }
+            Error::Float(text) => write!(
+                f,
+                "error: `{}` is a floating-point literal, not an integer literal",
+                text
+            ),
+            Error::InvalidByte(text) => {
+                write!(f, "error: invalid byte literal `{}`", text)
+            }
+            Error::Mismatched { expected, found } => write!(
+                f,
+                "error[E0308]: mismatched types: expected `{}`, found `{}`",
+                expected, found
+            ),
+            Error::NegatedUnsigned(ty) => write!(
+                f,
+                "error[E0600]: cannot apply unary operator `-` to type `{}`",
+                ty
+            ),
+            Error::OutOfRange(e) => write!(f, "error: literal out of range for `{}`", e.ty),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// 解析整数字面量，例如 "98_222"、"0xff"、"0b1111_0000"、"b'A'"、"255u8"、"-128i8"
+pub fn parse(text: &str) -> Result<Literal, Error> {
+    let text = text.trim();
+    let (negative, body) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+    if body.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    if let Some(byte) = body.strip_prefix("b'") {
+        let magnitude = parse_byte(byte).ok_or_else(|| Error::InvalidByte(body.to_string()))?;
+        let literal = Literal {
+            text: text.to_string(),
+            radix: Radix::Byte,
+            negative,
+            magnitude: u128::from(magnitude),
+            suffix: None,
+        };
+        if negative {
+            return Err(Error::NegatedUnsigned(IntType::U8));
+        }
+        return Ok(literal);
+    }
+
+    if !body.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(Error::NotANumber(body.to_string()));
+    }
+
+    let (radix, rest) = match body.get(..2) {
+        Some("0x") => (Radix::Hex, &body[2..]),
+        Some("0o") => (Radix::Octal, &body[2..]),
+        Some("0b") => (Radix::Binary, &body[2..]),
+        _ => (Radix::Decimal, body),
+    };
+
+    // 数字部分：十六进制包含 a-f，后缀从第一个不属于数字的字母开始。
+    // 所以 0x1f32 是十六进制数 0x1f32，而不是带 f32 后缀的 0x1。
+    let is_digit = |c: char| c == '_' || c.is_digit(if radix == Radix::Hex { 16 } else { 10 });
+    let split = rest.find(|c: char| !is_digit(c)).unwrap_or(rest.len());
+    let (digits, suffix) = rest.split_at(split);
+
+    let suffix = match suffix {
+        "" => None,
+        _ => match IntType::parse(suffix) {
+            Some(ty) => Some(ty),
+            // 1.5、1e3、2f32 是浮点数，不是整数
+            None if radix == Radix::Decimal
+                && (suffix.starts_with(['.', 'e', 'E']) || matches!(suffix, "f32" | "f64")) =>
+            {
+                return Err(Error::Float(body.to_string()));
+            }
+            None => return Err(Error::InvalidSuffix(suffix.to_string())),
+        },
+    };
+
+    let mut magnitude: u128 = 0;
+    let mut any_digit = false;
+    for c in digits.chars().filter(|&c| c != '_') {
+        let digit = c
+            .to_digit(radix.base())
+            .ok_or(Error::InvalidDigit { digit: c, radix })?;
+        magnitude = magnitude
+            .checked_mul(u128::from(radix.base()))
+            .and_then(|m| m.checked_add(u128::from(digit)))
+            .ok_or(Error::TooLarge)?;
+        any_digit = true;
+    }
+    if !any_digit {
+        return Err(Error::NoDigits(radix));
+    }
+
+    Ok(Literal {
+        text: text.to_string(),
+        radix,
+        negative,
+        magnitude,
+        suffix,
+    })
+}
+
+/// 解析字面量并在期望类型为 `ty` 的上下文中取值，例如计算器的操作数
+pub fn operand(text: &str, ty: IntType) -> Result<Integer, Error> {
+    parse(text)?.value_as(ty)
+}
+
+/// b'...' 中引号内的部分（含结尾的引号），返回字节值
+fn parse_byte(rest: &str) -> Option<u8> {
+    let inner = rest.strip_suffix('\'')?;
+    let byte = match inner.strip_prefix('\\') {
+        Some("n") => b'\n',
+        Some("r") => b'\r',
+        Some("t") => b'\t',
+        Some("0") => b'\0',
+        Some("\\") => b'\\',
+        Some("'") => b'\'',
+        Some("\"") => b'"',
+        Some(escape) => {
+            u8::from_str_radix(escape.strip_prefix('x').filter(|hex| hex.len() == 2)?, 16).ok()?
+        }
+        None => {
+            let mut chars = inner.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() || !c.is_ascii() || c == '\'' {
+                return None;
+            }
+            c as u8
+        }
+    };
+    Some(byte)
+}
+
+// ========== 溢出计算 ==========
+
+/// 可以比较的运算
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    /// 乘方，指数是 u32
+    Pow,
+    /// 左移，位数是 u32
+    Shl,
+    /// 取负，只有一个操作数
+    Neg,
+}
+
+impl Op {
+    pub const ALL: [Op; 8] = [
+        Op::Add,
+        Op::Sub,
+        Op::Mul,
+        Op::Div,
+        Op::Rem,
+        Op::Pow,
+        Op::Shl,
+        Op::Neg,
+    ];
+
+    /// 方法名中的部分，例如 checked_add 中的 "add"
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Pow => "pow",
+            Op::Shl => "shl",
+            Op::Neg => "neg",
+        }
+    }
+
+    /// 运算符写法；Rust 没有乘方运算符，pow 和 neg 用方法名
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => "pow",
+            Op::Shl => "<<",
+            Op::Neg => "neg",
+        }
+    }
+
+    /// 按方法名或运算符查找，例如 "add" 或 "+"
+    pub fn parse(text: &str) -> Option<Op> {
+        Op::ALL
+            .into_iter()
+            .find(|op| op.name() == text || op.symbol() == text)
+    }
+
+    /// 第二个操作数是否固定为 u32（pow 的指数、移位的位数）
+    pub fn takes_u32(self) -> bool {
+        matches!(self, Op::Pow | Op::Shl)
+    }
+
+    pub fn unary(self) -> bool {
+        self == Op::Neg
+    }
+}
+
+/// 溢出处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Semantics {
+    Checked,
+    Wrapping,
+    Saturating,
+    Overflowing,
+}
+
+impl Semantics {
+    pub const ALL: [Semantics; 4] = [
+        Semantics::Checked,
+        Semantics::Wrapping,
+        Semantics::Saturating,
+        Semantics::Overflowing,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Semantics::Checked => "checked",
+            Semantics::Wrapping => "wrapping",
+            Semantics::Saturating => "saturating",
+            Semantics::Overflowing => "overflowing",
+        }
+    }
+
+    /// 对应的方法名，例如 "wrapping_add"
+    pub fn method(self, op: Op) -> String {
+        format!("{}_{}", self.name(), op.name())
+    }
+}
+
+/// 一种方式的计算结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// checked_*：溢出时为 None
+    Checked(Option<Integer>),
+    /// wrapping_* 和 saturating_*
+    Value(Integer),
+    /// overflowing_*：结果和是否溢出
+    Overflowing(Integer, bool),
+    /// 调用会 panic，例如除以零
+    Panic(&'static str),
+    /// 标准库没有这个方法，例如 saturating_rem、u8::saturating_neg
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Checked(Some(value)) => write!(f, "Some({})", value),
+            Outcome::Checked(None) => write!(f, "None"),
+            Outcome::Value(value) => write!(f, "{}", value),
+            Outcome::Overflowing(value, overflowed) => write!(f, "({}, {})", value, overflowed),
+            Outcome::Panic(message) => write!(f, "panic: {}", message),
+            Outcome::Missing => write!(f, "（没有这个方法）"),
+        }
+    }
+}
+
+/// 用四种方式计算 `a op b` 的结果
+///
+/// 二元运算中 `b` 必须和 `a` 类型相同；pow 和 shl 的 `b` 必须是 u32；
+/// neg 不需要 `b`。类型不符时返回 Mismatched 错误。
+pub fn evaluate(
+    op: Op,
+    a: Integer,
+    b: Option<Integer>,
+) -> Result<Vec<(Semantics, Outcome)>, Error> {
+    let ty = a.ty;
+    let expected = if op.takes_u32() { IntType::U32 } else { ty };
+    let b = match (op.unary(), b) {
+        (true, _) => Integer::from_native(ty, 0u8),
+        (false, Some(b)) if b.ty == expected => b,
+        (false, Some(b)) => {
+            return Err(Error::Mismatched {
+                expected,
+                found: b.ty,
+            });
+        }
+        (false, None) => return Err(Error::Empty),
+    };
+
+    // 除以零时除了 checked 以外都会 panic
+    let zero_divisor = match op {
+        Op::Div if b.is_zero() => Some("attempt to divide by zero"),
+        Op::Rem if b.is_zero() => Some("attempt to calculate the remainder with a divisor of zero"),
+        _ => None,
+    };
+
+    let outcomes = dispatch!(ty, T => {
+        let x = a.bits as T;
+        let y = b.bits as T;
+        let n = b.bits as u32;
+        let int = |value: T| Integer::from_native(ty, value);
+        let checked = |value: Option<T>| Outcome::Checked(value.map(int));
+        let value = |value: T| Outcome::Value(int(value));
+        let overflowing = |(value, overflowed): (T, bool)| Outcome::Overflowing(int(value), overflowed);
+
+        match op {
+            Op::Add => [checked(x.checked_add(y)), value(x.wrapping_add(y)), value(x.saturating_add(y)), overflowing(x.overflowing_add(y))],
+            Op::Sub => [checked(x.checked_sub(y)), value(x.wrapping_sub(y)), value(x.saturating_sub(y)), overflowing(x.overflowing_sub(y))],
+            Op::Mul => [checked(x.checked_mul(y)), value(x.wrapping_mul(y)), value(x.saturating_mul(y)), overflowing(x.overflowing_mul(y))],
+            Op::Div | Op::Rem if zero_divisor.is_some() => {
+                let panic = Outcome::Panic(zero_divisor.unwrap());
+                let saturating = if op == Op::Rem { Outcome::Missing } else { panic };
+                [checked(None), panic, saturating, panic]
+            }
+            Op::Div => [checked(x.checked_div(y)), value(x.wrapping_div(y)), value(x.saturating_div(y)), overflowing(x.overflowing_div(y))],
+            Op::Rem => [checked(x.checked_rem(y)), value(x.wrapping_rem(y)), Outcome::Missing, overflowing(x.overflowing_rem(y))],
+            Op::Pow => [checked(x.checked_pow(n)), value(x.wrapping_pow(n)), value(x.saturating_pow(n)), overflowing(x.overflowing_pow(n))],
+            Op::Shl => [checked(x.checked_shl(n)), value(x.wrapping_shl(n)), Outcome::Missing, overflowing(x.overflowing_shl(n))],
+            Op::Neg => {
+                // saturating_neg 只有有符号类型才有，它等价于 0.saturating_sub(x)
+                let saturating = if ty.signed() { value((0 as T).saturating_sub(x)) } else { Outcome::Missing };
+                [checked(x.checked_neg()), value(x.wrapping_neg()), saturating, overflowing(x.overflowing_neg())]
+            }
+        }
+    });
+
+    Ok(Semantics::ALL.into_iter().zip(outcomes).collect())
+}
+
+/// 不用这些方法、直接写运算符时的行为：debug 构建溢出时 panic，release 构建回绕
+/// （和 wrapping_* 的结果相同）；MIN / -1 和 MIN % -1 例外，关掉溢出检查也会 panic；
+/// 无符号类型不能取负，是编译错误
+pub fn plain_operator(op: Op, ty: IntType, outcomes: &[(Semantics, Outcome)]) -> String {
+    if op == Op::Neg && !ty.signed() {
+        return Error::NegatedUnsigned(ty).to_string();
+    }
+    let overflowed = outcomes
+        .iter()
+        .any(|(_, outcome)| matches!(outcome, Outcome::Overflowing(_, true)));
+    let wrapped = outcomes
+        .iter()
+        .find(|(semantics, _)| *semantics == Semantics::Wrapping)
+        .map(|(_, outcome)| *outcome);
+
+    match (op, wrapped) {
+        (_, Some(Outcome::Panic(message))) => format!("panic: {}", message),
+        (Op::Div | Op::Rem, Some(_)) if overflowed => format!(
+            "panic: attempt to {} with overflow（debug 和 release 构建都会 panic，不受 overflow-checks 影响）",
+            verb(op)
+        ),
+        (_, Some(wrapped)) if overflowed => format!(
+            "debug 构建 panic: attempt to {} with overflow；release 构建得到 {}",
+            verb(op),
+            wrapped
+        ),
+        (_, Some(Outcome::Value(value))) => value.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn verb(op: Op) -> &'static str {
+    match op {
+        Op::Add => "add",
+        Op::Sub => "subtract",
+        Op::Mul | Op::Pow => "multiply",
+        Op::Div => "divide",
+        Op::Rem => "calculate the remainder",
+        Op::Shl => "shift left",
+        Op::Neg => "negate",
+    }
+}
@@ -0,0 +1,254 @@
+// ============================================
+// 字符串驻留 (Interner)
+// ============================================
+// 第 05 章对比了 clone() 的深拷贝和移动；第 09 章统计单词时用从字面量借用的
+// &str 作为 HashMap 的键。如果单词来自临时的字符串，就只能给每个键复制一份
+// String。驻留（interning）换一种方式共享：
+//
+// - 每个不同的字符串只存一份，存进一个只追加的字符串区（arena）
+// - 调用者拿到的是 4 字节的 Symbol，它实现了 Copy，可以随意复制、比较和哈希，
+//   不会借用任何东西，也不需要 clone()
+// - resolve 把 Symbol 换回 &str，这个引用和 Interner 活得一样久
+//
+// 字符串区按块分配：当前块放不下时才分配新的块，已经放进去的字符串不会移动。
+// 查找表是开放寻址的哈希表，表里只存 Symbol，比较时再去字符串区取出内容，
+// 所以每个字符串在内存中只有一份，也不需要 unsafe。
+//
+// 位置用 u32 记录：最多驻留 u32::MAX - 1 个字符串（u32::MAX 在哈希表里表示空槽），
+// 每个字符串和每一块都不能超过 4 GiB，超出时 panic，而不是截断后返回别的字符串。
+//
+// 这个模块只依赖标准库，第 09 章通过 crate::interner 使用它。
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+/// 驻留字符串的编号；只在创建它的 Interner 中有意义
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// 第几个被驻留的字符串，从 0 开始
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// 字符串在字符串区中的位置
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    chunk: u32,
+    start: u32,
+    len: u32,
+}
+
+/// 内存使用情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// 不同字符串的个数
+    pub strings: usize,
+    /// 字符串内容的总字节数
+    pub bytes: usize,
+    /// 字符串区的块数
+    pub chunks: usize,
+    /// 字符串区分配的总容量（字节）
+    pub arena_capacity: usize,
+    /// 位置表和哈希表占用的字节数
+    pub index_bytes: usize,
+    /// 到目前为止分配内存的次数（新块、位置表和哈希表扩容）
+    pub allocations: usize,
+}
+
+impl Stats {
+    /// 占用的总字节数
+    pub fn total_bytes(&self) -> usize {
+        self.arena_capacity + self.index_bytes
+    }
+}
+
+/// 空槽；Symbol 的编号总是小于它
+const EMPTY: u32 = u32::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Interner {
+    chunks: Vec<String>,
+    chunk_size: usize,
+    entries: Vec<Entry>,
+    /// 开放寻址的哈希表，存 Symbol 的编号；长度总是 2 的幂
+    table: Vec<u32>,
+    hasher: RandomState,
+    allocations: usize,
+}
+
+impl Default for Interner {
+    fn default() -> Interner {
+        Interner::new()
+    }
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::with_chunk_size(4096)
+    }
+
+    /// 字符串区每块的大小；比它长的字符串单独占一块
+    pub fn with_chunk_size(chunk_size: usize) -> Interner {
+        assert!(chunk_size > 0, "chunk_size 必须大于 0");
+        Interner {
+            chunks: Vec::new(),
+            chunk_size,
+            entries: Vec::new(),
+            table: Vec::new(),
+            hasher: RandomState::new(),
+            allocations: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 已经驻留过就返回原来的 Symbol，否则把字符串复制进字符串区
+    pub fn get_or_intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+        let symbol = Symbol(
+            u32::try_from(self.entries.len())
+                .ok()
+                .filter(|&index| index < EMPTY)
+                .expect("驻留的字符串超过 u32::MAX - 1 个"),
+        );
+        // 保持装载因子不超过 1/2
+        if (self.entries.len() + 1) * 2 > self.table.len() {
+            self.grow_table();
+        }
+        let entry = self.store(s);
+        if self.entries.len() == self.entries.capacity() {
+            self.allocations += 1;
+        }
+        self.entries.push(entry);
+        let slot = self.find_slot(s);
+        self.table[slot] = symbol.0;
+        symbol
+    }
+
+    /// 查找已经驻留的字符串，不会分配
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        if self.table.is_empty() {
+            return None;
+        }
+        match self.table[self.find_slot(s)] {
+            EMPTY => None,
+            index => Some(Symbol(index)),
+        }
+    }
+
+    /// Symbol 对应的字符串，借用字符串区
+    ///
+    /// Symbol 来自另一个 Interner 时可能 panic 或返回别的字符串
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        let entry = self.entries[symbol.index()];
+        let start = entry.start as usize;
+        &self.chunks[entry.chunk as usize][start..start + entry.len as usize]
+    }
+
+    /// 按驻留的先后顺序遍历
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            interner: self,
+            next: 0,
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            strings: self.entries.len(),
+            bytes: self.entries.iter().map(|entry| entry.len as usize).sum(),
+            chunks: self.chunks.len(),
+            arena_capacity: self.chunks.iter().map(String::capacity).sum(),
+            index_bytes: self.entries.capacity() * size_of::<Entry>()
+                + self.table.capacity() * size_of::<u32>(),
+            allocations: self.allocations,
+        }
+    }
+
+    /// 把字符串追加到最后一块；放不下时分配新的一块，已有的块不会扩容
+    fn store(&mut self, s: &str) -> Entry {
+        let fits = self
+            .chunks
+            .last()
+            .is_some_and(|chunk| chunk.capacity() - chunk.len() >= s.len());
+        if !fits {
+            self.chunks
+                .push(String::with_capacity(self.chunk_size.max(s.len())));
+            self.allocations += 1;
+        }
+        let chunk = self.chunks.len() - 1;
+        let start = self.chunks[chunk].len();
+        self.chunks[chunk].push_str(s);
+        Entry {
+            chunk: u32::try_from(chunk).expect("字符串区超过 u32 块"),
+            start: u32::try_from(start).expect("字符串区的一块超过 4 GiB"),
+            len: u32::try_from(s.len()).expect("字符串超过 4 GiB"),
+        }
+    }
+
+    /// s 所在的槽，或者应该放入 s 的空槽（线性探测）
+    fn find_slot(&self, s: &str) -> usize {
+        let mask = self.table.len() - 1;
+        let mut slot = self.hasher.hash_one(s) as usize & mask;
+        loop {
+            match self.table[slot] {
+                EMPTY => return slot,
+                index if self.resolve(Symbol(index)) == s => return slot,
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+
+    /// 哈希表容量翻倍，重新放入所有 Symbol
+    fn grow_table(&mut self) {
+        let capacity = (self.table.len() * 2).max(8);
+        self.table = vec![EMPTY; capacity];
+        self.allocations += 1;
+        for i in 0..self.entries.len() {
+            // get_or_intern 保证了编号小于 EMPTY
+            let index = u32::try_from(i).expect("Symbol 的编号小于 u32::MAX");
+            let slot = self.find_slot(self.resolve(Symbol(index)));
+            self.table[slot] = index;
+        }
+    }
+}
+
+/// 按驻留顺序遍历的迭代器，见 [`Interner::iter`]
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    interner: &'a Interner,
+    next: u32,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Symbol, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next as usize == self.interner.len() {
+            return None;
+        }
+        let symbol = Symbol(self.next);
+        self.next += 1;
+        Some((symbol, self.interner.resolve(symbol)))
+    }
+}
+
+impl<'a> IntoIterator for &'a Interner {
+    type Item = (Symbol, &'a str);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
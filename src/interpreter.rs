@@ -0,0 +1,1623 @@
+// ============================================
+// 变量绑定解释器 (Interpreter)
+// ============================================
+// 一个只支持 Rust 一小部分语法的解释器，在运行时检查第 01 章讲到的绑定规则：
+//
+// - 变量默认不可变，给不可变变量第二次赋值会报 E0384
+// - mut 变量可以修改，但类型不能变（E0308）；遮蔽则可以换成新类型
+// - 代码块 { ... } 引入新的作用域，离开代码块后里面的变量不可访问（E0425）
+// - let (a, b) = ... 按模式解构
+// - 先声明后赋值的 `let x;` 在赋值前不能使用（E0381）
+//
+// 支持的语法：let / const / static、赋值和 += 等复合赋值、代码块、if/else、
+// 整数（带或不带 i32、u8 等后缀）、浮点数、bool、char、字符串、元组和 .0 字段、
+// 算术、比较和逻辑运算、字符串的 .len()，以及 println!。
+//
+// 整数在内部都用 i128 保存，所以 u128 只支持到 i128::MAX，超过时按溢出报告。
+//
+// 错误信息沿用 rustc 的措辞，并附带出错位置的代码片段。
+// REPL 模式（rust-tutorial repl）在每次输入后打印作用域栈，可以直接看到遮蔽的过程。
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// ========== 值和类型 ==========
+
+/// 整数类型及其取值范围
+const INT_TYPES: [(&str, i128, i128); 12] = [
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+    ("i128", i128::MIN, i128::MAX),
+    ("isize", i64::MIN as i128, i64::MAX as i128),
+    ("u8", 0, u8::MAX as i128),
+    ("u16", 0, u16::MAX as i128),
+    ("u32", 0, u32::MAX as i128),
+    ("u64", 0, u64::MAX as i128),
+    // 整数都用 i128 保存，u128 只能到 i128::MAX
+    ("u128", 0, i128::MAX),
+    ("usize", 0, u64::MAX as i128),
+];
+
+fn int_type(name: &str) -> Option<(&'static str, i128, i128)> {
+    INT_TYPES.iter().copied().find(|(ty, _, _)| *ty == name)
+}
+
+/// 解释器中的值
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// 整数和它的类型，例如 (5, "i32")
+    Int(i128, &'static str),
+    /// 浮点数和它的类型（"f64" 或 "f32"）
+    Float(f64, &'static str),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    Tuple(Vec<Value>),
+}
+
+impl Value {
+    pub const UNIT: Value = Value::Tuple(Vec::new());
+
+    /// 类型名，例如 "i32"、"&str"、"(i32, bool)"
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Int(_, ty) | Value::Float(_, ty) => ty.to_string(),
+            Value::Bool(_) => String::from("bool"),
+            Value::Char(_) => String::from("char"),
+            Value::Str(_) => String::from("&str"),
+            Value::Tuple(items) if items.len() == 1 => format!("({},)", items[0].type_name()),
+            Value::Tuple(items) => {
+                let types: Vec<String> = items.iter().map(Value::type_name).collect();
+                format!("({})", types.join(", "))
+            }
+        }
+    }
+
+    /// `{:?}` 的格式
+    pub fn debug(&self) -> String {
+        match self {
+            Value::Char(c) => format!("{:?}", c),
+            Value::Str(s) => format!("{:?}", s),
+            Value::Tuple(items) if items.len() == 1 => format!("({},)", items[0].debug()),
+            Value::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(Value::debug).collect();
+                format!("({})", items.join(", "))
+            }
+            other => other.to_string(),
+        }
+    }
+}
+
+/// `{}` 的格式；元组没有实现 Display，调用处需要先检查
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n, _) => write!(f, "{}", n),
+            Value::Float(x, "f32") => write!(f, "{}", *x as f32),
+            Value::Float(x, _) => write!(f, "{}", x),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Tuple(_) => write!(f, "{}", self.debug()),
+        }
+    }
+}
+
+// ========== 错误 ==========
+
+/// 解释过程中的错误，措辞与 rustc 一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// rustc 的错误编号，例如 "E0384"；语法错误和运行时 panic 没有编号
+    pub code: Option<&'static str>,
+    pub message: String,
+    /// 出错位置（从 1 开始）
+    pub line: usize,
+    pub column: usize,
+    /// 附加的提示，例如 "consider making this binding mutable: `mut x`"
+    pub help: Option<String>,
+    /// 运行时 panic（例如除以零、溢出），而不是编译错误
+    pub panic: bool,
+}
+
+impl Error {
+    fn new(code: &'static str, message: String, pos: Pos) -> Error {
+        Error {
+            code: Some(code),
+            message,
+            line: pos.line,
+            column: pos.column,
+            help: None,
+            panic: false,
+        }
+    }
+
+    fn syntax(message: String, pos: Pos) -> Error {
+        Error {
+            code: None,
+            message,
+            line: pos.line,
+            column: pos.column,
+            help: None,
+            panic: false,
+        }
+    }
+
+    fn panic(message: String, pos: Pos) -> Error {
+        Error {
+            panic: true,
+            ..Error::syntax(message, pos)
+        }
+    }
+
+    fn with_help(mut self, help: String) -> Error {
+        self.help = Some(help);
+        self
+    }
+
+    /// 像 rustc 一样附上出错的那一行代码和指向出错位置的 ^
+    pub fn render(&self, source: &str, file_name: &str) -> String {
+        let mut out = format!("{}\n", self);
+        let width = self.line.to_string().len();
+        let pad = " ".repeat(width);
+        out.push_str(&format!(
+            "{}--> {}:{}:{}\n",
+            pad, file_name, self.line, self.column
+        ));
+        if let Some(code) = source.lines().nth(self.line - 1) {
+            out.push_str(&format!("{} |\n", pad));
+            out.push_str(&format!("{} | {}\n", self.line, code));
+            let indent: String = code
+                .chars()
+                .take(self.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            out.push_str(&format!("{} | {}^\n", pad, indent));
+        }
+        if let Some(help) = &self.help {
+            out.push_str(&format!("{} = help: {}\n", pad, help));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.code {
+            _ if self.panic => write!(f, "panicked: {}", self.message),
+            Some(code) => write!(f, "error[{}]: {}", code, self.message),
+            None => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ========== 词法分析 ==========
+
+/// 源码中的位置（从 1 开始）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    /// 整数字面量和可选的类型后缀
+    Int(i128, Option<&'static str>),
+    Float(f64, Option<&'static str>),
+    Str(String),
+    Char(char),
+    Punct(&'static str),
+    Eof,
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tok::Ident(name) => write!(f, "`{}`", name),
+            Tok::Int(n, _) => write!(f, "`{}`", n),
+            Tok::Float(x, _) => write!(f, "`{}`", x),
+            Tok::Str(s) => write!(f, "`{:?}`", s),
+            Tok::Char(c) => write!(f, "`{:?}`", c),
+            Tok::Punct(p) => write!(f, "`{}`", p),
+            Tok::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+// 较长的符号放在前面，优先匹配
+const PUNCTS: [&str; 27] = [
+    "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "+", "-", "*", "/", "%", "=",
+    "<", ">", "!", "(", ")", "{", "}", ",", ";", ":",
+];
+const MORE_PUNCTS: [&str; 3] = [".", "&", "'"];
+
+fn lex(source: &str) -> Result<Vec<(Tok, Pos)>, Error> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let (mut i, mut line, mut column) = (0, 1, 1);
+
+    // 前进 n 个字符，同时维护行号和列号
+    let advance = |i: &mut usize, line: &mut usize, column: &mut usize, n: usize| {
+        for _ in 0..n {
+            if chars[*i] == '\n' {
+                *line += 1;
+                *column = 1;
+            } else {
+                *column += 1;
+            }
+            *i += 1;
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let pos = Pos { line, column };
+        let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+
+        if c.is_whitespace() {
+            advance(&mut i, &mut line, &mut column, 1);
+        } else if rest == "//" {
+            while i < chars.len() && chars[i] != '\n' {
+                advance(&mut i, &mut line, &mut column, 1);
+            }
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                advance(&mut i, &mut line, &mut column, 1);
+            }
+            let is_float = i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit();
+            if is_float {
+                advance(&mut i, &mut line, &mut column, 1);
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    advance(&mut i, &mut line, &mut column, 1);
+                }
+            }
+            let text: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+            tokens.push((number(&text, pos)?, pos));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                advance(&mut i, &mut line, &mut column, 1);
+            }
+            tokens.push((Tok::Ident(chars[start..i].iter().collect()), pos));
+        } else if c == '"' {
+            advance(&mut i, &mut line, &mut column, 1);
+            let mut text = String::new();
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(Error::syntax(
+                            String::from("unterminated double quote string"),
+                            pos,
+                        ));
+                    }
+                    Some('"') => break,
+                    Some('\\') => {
+                        let escaped = chars.get(i + 1).copied().unwrap_or('\\');
+                        text.push(unescape(escaped, pos)?);
+                        advance(&mut i, &mut line, &mut column, 2);
+                    }
+                    Some(&c) => {
+                        text.push(c);
+                        advance(&mut i, &mut line, &mut column, 1);
+                    }
+                }
+            }
+            advance(&mut i, &mut line, &mut column, 1);
+            tokens.push((Tok::Str(text), pos));
+        } else if c == '\'' && chars.get(i + 2) == Some(&'\'') {
+            tokens.push((Tok::Char(chars[i + 1]), pos));
+            advance(&mut i, &mut line, &mut column, 3);
+        } else if c == '\'' && chars.get(i + 1) == Some(&'\\') && chars.get(i + 3) == Some(&'\'') {
+            tokens.push((Tok::Char(unescape(chars[i + 2], pos)?), pos));
+            advance(&mut i, &mut line, &mut column, 4);
+        } else if let Some(punct) = PUNCTS
+            .iter()
+            .chain(&MORE_PUNCTS)
+            .find(|p| chars[i..].starts_with(&p.chars().collect::<Vec<_>>()))
+        {
+            tokens.push((Tok::Punct(punct), pos));
+            advance(&mut i, &mut line, &mut column, punct.len());
+        } else {
+            return Err(Error::syntax(format!("unknown start of token: {}", c), pos));
+        }
+    }
+    tokens.push((Tok::Eof, Pos { line, column }));
+    Ok(tokens)
+}
+
+fn unescape(c: char, pos: Pos) -> Result<char, Error> {
+    match c {
+        'n' => Ok('\n'),
+        't' => Ok('\t'),
+        'r' => Ok('\r'),
+        '0' => Ok('\0'),
+        '\\' | '\'' | '"' => Ok(c),
+        _ => Err(Error::syntax(
+            format!("unknown character escape: `{}`", c),
+            pos,
+        )),
+    }
+}
+
+/// 解析 "100000"、"5u8"、"2.5"、"1.0f32" 这样的数字
+fn number(text: &str, pos: Pos) -> Result<Tok, Error> {
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (digits, suffix) = text.split_at(split);
+
+    if digits.contains('.') || suffix.starts_with('f') {
+        let suffix = match suffix {
+            "" => None,
+            "f64" => Some("f64"),
+            "f32" => Some("f32"),
+            _ => return Err(invalid_suffix(suffix, pos)),
+        };
+        let value = digits
+            .parse()
+            .map_err(|_| Error::syntax(format!("invalid float literal `{}`", text), pos))?;
+        return Ok(Tok::Float(value, suffix));
+    }
+
+    let suffix = match suffix {
+        "" => None,
+        _ => Some(
+            int_type(suffix)
+                .ok_or_else(|| invalid_suffix(suffix, pos))?
+                .0,
+        ),
+    };
+    // 整数都用 i128 保存，u128 也只能到 i128::MAX
+    let value = digits.parse().map_err(|_| {
+        Error::syntax(
+            String::from(
+                "integer literal is too large (this interpreter stores integers as i128, \
+                 so u128 stops at i128::MAX)",
+            ),
+            pos,
+        )
+    })?;
+    Ok(Tok::Int(value, suffix))
+}
+
+fn invalid_suffix(suffix: &str, pos: Pos) -> Error {
+    Error::syntax(
+        format!("invalid suffix `{}` for number literal", suffix),
+        pos,
+    )
+}
+
+// ========== 语法树 ==========
+
+/// let 左边的模式
+#[derive(Debug, Clone, PartialEq)]
+enum Pattern {
+    Binding { name: String, mutable: bool },
+    Tuple(Vec<Pattern>),
+    Wildcard,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stmt {
+    Let {
+        pattern: Pattern,
+        ty: Option<String>,
+        init: Option<Expr>,
+        pos: Pos,
+    },
+    Assign {
+        name: String,
+        /// "=" 或 "+="、"-=" 等
+        op: &'static str,
+        value: Expr,
+        pos: Pos,
+    },
+    /// 表达式语句；`value` 为 true 表示没有分号，是代码块的值
+    Expr { expr: Expr, value: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    /// 字面量；`typed` 为 false 表示没有后缀，类型可以由上下文决定
+    Lit {
+        value: Value,
+        typed: bool,
+    },
+    Var(String, Pos),
+    Unary(&'static str, Box<Expr>, Pos),
+    Binary(&'static str, Box<Expr>, Box<Expr>, Pos),
+    Tuple(Vec<Expr>),
+    Field(Box<Expr>, usize, Pos),
+    Method(Box<Expr>, String, Pos),
+    Block(Vec<Stmt>),
+    If(Box<Expr>, Vec<Stmt>, Option<Box<Expr>>, Pos),
+    Println(String, Vec<Expr>, Pos),
+}
+
+impl Expr {
+    /// 代码块、if 这样的表达式作为语句时可以不写分号
+    fn is_block_like(&self) -> bool {
+        matches!(self, Expr::Block(_) | Expr::If(..))
+    }
+}
+
+// ========== 语法分析 ==========
+
+struct Parser {
+    tokens: Vec<(Tok, Pos)>,
+    index: usize,
+    /// REPL 中最后一条语句可以省略分号
+    lenient: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.index].0
+    }
+
+    fn pos(&self) -> Pos {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Tok, Pos) {
+        let token = self.tokens[self.index].clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Tok::Punct(p) if *p == punct)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(name) if name == keyword)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = self.is_punct(punct);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<Pos, Error> {
+        if self.is_punct(punct) {
+            Ok(self.next().1)
+        } else {
+            Err(Error::syntax(
+                format!("expected `{}`, found {}", punct, self.peek()),
+                self.pos(),
+            ))
+        }
+    }
+
+    /// 语句末尾的分号；REPL 中输入末尾的分号可以省略
+    fn expect_semicolon(&mut self) -> Result<(), Error> {
+        if self.lenient && *self.peek() == Tok::Eof {
+            return Ok(());
+        }
+        self.expect(";").map(|_| ())
+    }
+
+    fn ident(&mut self) -> Result<(String, Pos), Error> {
+        match self.next() {
+            (Tok::Ident(name), pos) => Ok((name, pos)),
+            (other, pos) => Err(Error::syntax(
+                format!("expected identifier, found {}", other),
+                pos,
+            )),
+        }
+    }
+
+    /// 语句序列，直到 `}` 或输入结束
+    fn statements(&mut self) -> Result<Vec<Stmt>, Error> {
+        let mut stmts = Vec::new();
+        while !self.is_punct("}") && *self.peek() != Tok::Eof {
+            if self.eat(";") {
+                continue;
+            }
+            stmts.push(self.statement()?);
+        }
+        Ok(stmts)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, Error> {
+        let pos = self.pos();
+        if self.is_keyword("let") {
+            self.next();
+            let pattern = self.pattern()?;
+            let ty = if self.eat(":") {
+                Some(self.ty()?)
+            } else {
+                None
+            };
+            let init = if self.eat("=") {
+                Some(self.expr()?)
+            } else {
+                None
+            };
+            self.expect_semicolon()?;
+            return Ok(Stmt::Let {
+                pattern,
+                ty,
+                init,
+                pos,
+            });
+        }
+
+        if self.is_keyword("const") || self.is_keyword("static") {
+            let (keyword, _) = self.ident()?;
+            let (name, _) = self.ident()?;
+            if !self.eat(":") {
+                return Err(Error::syntax(
+                    format!("missing type for `{}` item", keyword),
+                    self.pos(),
+                ));
+            }
+            let ty = self.ty()?;
+            self.expect("=")?;
+            let init = self.expr()?;
+            self.expect_semicolon()?;
+            return Ok(Stmt::Let {
+                pattern: Pattern::Binding {
+                    name,
+                    mutable: false,
+                },
+                ty: Some(ty),
+                init: Some(init),
+                pos,
+            });
+        }
+
+        // 赋值语句：标识符后面紧跟 = 或 += 等
+        if let Tok::Ident(name) = self.peek().clone()
+            && let Some((Tok::Punct(op), _)) = self.tokens.get(self.index + 1)
+            && matches!(*op, "=" | "+=" | "-=" | "*=" | "/=" | "%=")
+        {
+            let op = *op;
+            self.next();
+            self.next();
+            let value = self.expr()?;
+            self.expect_semicolon()?;
+            return Ok(Stmt::Assign {
+                name,
+                op,
+                value,
+                pos,
+            });
+        }
+
+        let expr = self.expr()?;
+        if self.eat(";") {
+            Ok(Stmt::Expr { expr, value: false })
+        } else if self.is_punct("}") || *self.peek() == Tok::Eof {
+            Ok(Stmt::Expr { expr, value: true })
+        } else if expr.is_block_like() {
+            Ok(Stmt::Expr { expr, value: false })
+        } else {
+            Err(Error::syntax(
+                format!("expected `;`, found {}", self.peek()),
+                self.pos(),
+            ))
+        }
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, Error> {
+        if self.eat("(") {
+            let mut items = Vec::new();
+            while !self.is_punct(")") {
+                items.push(self.pattern()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+            return Ok(Pattern::Tuple(items));
+        }
+        let mutable = self.is_keyword("mut");
+        if mutable {
+            self.next();
+        }
+        let (name, _) = self.ident()?;
+        if name == "_" && !mutable {
+            Ok(Pattern::Wildcard)
+        } else {
+            Ok(Pattern::Binding { name, mutable })
+        }
+    }
+
+    /// 类型注解，返回规范化的类型名，例如 "&str"、"(i32, bool)"
+    fn ty(&mut self) -> Result<String, Error> {
+        if self.eat("&") {
+            // &'static str
+            if self.eat("'") {
+                self.ident()?;
+            }
+            let (name, _) = self.ident()?;
+            return Ok(format!("&{}", name));
+        }
+        if self.eat("(") {
+            let mut items = Vec::new();
+            while !self.is_punct(")") {
+                items.push(self.ty()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+            return Ok(if items.len() == 1 {
+                format!("({},)", items[0])
+            } else {
+                format!("({})", items.join(", "))
+            });
+        }
+        Ok(self.ident()?.0)
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        self.binary(0)
+    }
+
+    /// 按优先级解析二元运算，`level` 越大优先级越高
+    fn binary(&mut self, level: usize) -> Result<Expr, Error> {
+        const LEVELS: [&[&str]; 5] = [
+            &["||"],
+            &["&&"],
+            &["==", "!=", "<", ">", "<=", ">="],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut left = self.binary(level + 1)?;
+        while let Tok::Punct(op) = *self.peek() {
+            if !LEVELS[level].contains(&op) {
+                break;
+            }
+            let pos = self.next().1;
+            let right = self.binary(level + 1)?;
+            // 比较运算不能连写，例如 a < b < c
+            if level == 2
+                && let Tok::Punct(next) = *self.peek()
+                && LEVELS[2].contains(&next)
+            {
+                return Err(Error::syntax(
+                    String::from("comparison operators cannot be chained"),
+                    self.pos(),
+                ));
+            }
+            left = Expr::Binary(op, Box::new(left), Box::new(right), pos);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        let pos = self.pos();
+        if self.eat("-") {
+            return Ok(Expr::Unary("-", Box::new(self.unary()?), pos));
+        }
+        if self.eat("!") {
+            return Ok(Expr::Unary("!", Box::new(self.unary()?), pos));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.primary()?;
+        while self.is_punct(".") {
+            let pos = self.next().1;
+            match self.next() {
+                (Tok::Int(index, None), _) => {
+                    expr = Expr::Field(Box::new(expr), index as usize, pos)
+                }
+                (Tok::Ident(name), name_pos) => {
+                    self.expect("(")?;
+                    self.expect(")")?;
+                    expr = Expr::Method(Box::new(expr), name, name_pos);
+                }
+                (other, pos) => {
+                    return Err(Error::syntax(format!("unexpected token: {}", other), pos));
+                }
+            }
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        let (token, pos) = self.next();
+        let lit = |value| Ok(Expr::Lit { value, typed: true });
+        match token {
+            Tok::Int(n, ty) => Ok(Expr::Lit {
+                value: Value::Int(n, ty.unwrap_or("i32")),
+                typed: ty.is_some(),
+            }),
+            Tok::Float(x, ty) => Ok(Expr::Lit {
+                value: Value::Float(x, ty.unwrap_or("f64")),
+                typed: ty.is_some(),
+            }),
+            Tok::Str(s) => lit(Value::Str(s)),
+            Tok::Char(c) => lit(Value::Char(c)),
+            Tok::Ident(name) => match name.as_str() {
+                "true" => lit(Value::Bool(true)),
+                "false" => lit(Value::Bool(false)),
+                "if" => self.if_expr(pos),
+                "println" if self.is_punct("!") => self.println(pos),
+                _ => Ok(Expr::Var(name, pos)),
+            },
+            Tok::Punct("(") => {
+                let mut items = Vec::new();
+                let mut trailing_comma = false;
+                while !self.is_punct(")") {
+                    items.push(self.expr()?);
+                    trailing_comma = self.eat(",");
+                    if !trailing_comma {
+                        break;
+                    }
+                }
+                self.expect(")")?;
+                if items.len() == 1 && !trailing_comma {
+                    Ok(items.pop().unwrap())
+                } else {
+                    Ok(Expr::Tuple(items))
+                }
+            }
+            Tok::Punct("{") => {
+                let stmts = self.statements()?;
+                self.expect("}")?;
+                Ok(Expr::Block(stmts))
+            }
+            other => Err(Error::syntax(
+                format!("expected expression, found {}", other),
+                pos,
+            )),
+        }
+    }
+
+    fn if_expr(&mut self, pos: Pos) -> Result<Expr, Error> {
+        let condition = self.expr()?;
+        self.expect("{")?;
+        let then = self.statements()?;
+        self.expect("}")?;
+        let otherwise = if self.is_keyword("else") {
+            self.next();
+            if self.is_keyword("if") {
+                let pos = self.next().1;
+                Some(Box::new(self.if_expr(pos)?))
+            } else {
+                self.expect("{")?;
+                let stmts = self.statements()?;
+                self.expect("}")?;
+                Some(Box::new(Expr::Block(stmts)))
+            }
+        } else {
+            None
+        };
+        Ok(Expr::If(Box::new(condition), then, otherwise, pos))
+    }
+
+    fn println(&mut self, pos: Pos) -> Result<Expr, Error> {
+        self.expect("!")?;
+        self.expect("(")?;
+        let format = match self.next() {
+            (Tok::Str(format), _) => format,
+            (other, pos) => {
+                return Err(Error::syntax(
+                    format!("format argument must be a string literal, found {}", other),
+                    pos,
+                ));
+            }
+        };
+        let mut args = Vec::new();
+        while self.eat(",") {
+            if self.is_punct(")") {
+                break;
+            }
+            args.push(self.expr()?);
+        }
+        self.expect(")")?;
+        Ok(Expr::Println(format, args, pos))
+    }
+}
+
+fn parse(source: &str, lenient: bool) -> Result<Vec<Stmt>, Error> {
+    let mut parser = Parser {
+        tokens: lex(source)?,
+        index: 0,
+        lenient,
+    };
+    let stmts = parser.statements()?;
+    match parser.peek() {
+        Tok::Eof => Ok(stmts),
+        other => Err(Error::syntax(
+            format!("unexpected closing delimiter: {}", other),
+            parser.pos(),
+        )),
+    }
+}
+
+// ========== 作用域 ==========
+
+/// 一个变量绑定
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub mutable: bool,
+    /// 声明时的类型注解，或第一次赋值时的类型
+    pub ty: Option<String>,
+    /// `let x;` 声明后、赋值前为 None
+    pub value: Option<Value>,
+}
+
+/// 一层作用域中的绑定，按声明顺序排列，同名的后者遮蔽前者
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scope {
+    pub bindings: Vec<Binding>,
+}
+
+// ========== 解释执行 ==========
+
+/// 解释器：保存作用域栈，多次调用 run 时全局作用域中的绑定会保留
+#[derive(Debug, Clone)]
+pub struct Interpreter {
+    scopes: Vec<Scope>,
+    output: String,
+    /// 每条语句执行后把作用域栈写入输出
+    pub trace: bool,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter {
+            scopes: vec![Scope::default()],
+            output: String::new(),
+            trace: false,
+        }
+    }
+
+    /// 当前的作用域栈，第一个是最外层
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// 执行一段程序，返回 println! 的输出；出错时之前的输出可以用 take_output 取出
+    pub fn run(&mut self, source: &str) -> Result<String, Error> {
+        self.eval_source(source, false).map(|_| self.take_output())
+    }
+
+    /// 取出并清空还没有返回的输出
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    /// 执行一段程序；`lenient` 为 true 时末尾的分号可以省略。
+    /// 返回最后一个没有分号的表达式的值。
+    fn eval_source(&mut self, source: &str, lenient: bool) -> Result<Option<Value>, Error> {
+        let stmts = parse(source, lenient)?;
+        // 出错时丢弃这次输入中新建的内层作用域
+        let depth = self.scopes.len();
+        let result = self.statements(&stmts);
+        self.scopes.truncate(depth);
+        result
+    }
+
+    fn statements(&mut self, stmts: &[Stmt]) -> Result<Option<Value>, Error> {
+        let mut value = None;
+        for stmt in stmts {
+            value = self.statement(stmt)?;
+            if self.trace {
+                let scopes = render_scopes(&self.scopes);
+                self.output.push_str(&scopes);
+            }
+        }
+        Ok(value)
+    }
+
+    fn statement(&mut self, stmt: &Stmt) -> Result<Option<Value>, Error> {
+        match stmt {
+            Stmt::Let {
+                pattern,
+                ty,
+                init,
+                pos,
+            } => {
+                let value = match init {
+                    Some(init) => Some(self.eval(init, ty.as_deref())?),
+                    None => None,
+                };
+                if let (Some(ty), Some(value)) = (ty, &value)
+                    && value.type_name() != *ty
+                {
+                    return Err(mismatched(ty, &value.type_name(), *pos));
+                }
+                match value {
+                    Some(value) => self.bind(pattern, value, *pos)?,
+                    None => match pattern {
+                        Pattern::Binding { name, mutable } => self.declare(Binding {
+                            name: name.clone(),
+                            mutable: *mutable,
+                            ty: ty.clone(),
+                            value: None,
+                        }),
+                        Pattern::Wildcard => {}
+                        Pattern::Tuple(_) => {
+                            return Err(Error::syntax(
+                                String::from("tuple patterns in `let` need an initializer"),
+                                *pos,
+                            ));
+                        }
+                    },
+                }
+                Ok(None)
+            }
+            Stmt::Assign {
+                name,
+                op,
+                value,
+                pos,
+            } => {
+                self.assign(name, op, value, *pos)?;
+                Ok(None)
+            }
+            Stmt::Expr { expr, value } => {
+                let result = self.eval(expr, None)?;
+                Ok(value.then_some(result))
+            }
+        }
+    }
+
+    fn declare(&mut self, binding: Binding) {
+        self.scopes.last_mut().unwrap().bindings.push(binding);
+    }
+
+    /// 按模式把值绑定到当前作用域
+    fn bind(&mut self, pattern: &Pattern, value: Value, pos: Pos) -> Result<(), Error> {
+        match pattern {
+            Pattern::Wildcard => Ok(()),
+            Pattern::Binding { name, mutable } => {
+                self.declare(Binding {
+                    name: name.clone(),
+                    mutable: *mutable,
+                    ty: Some(value.type_name()),
+                    value: Some(value),
+                });
+                Ok(())
+            }
+            Pattern::Tuple(patterns) => match value {
+                Value::Tuple(items) if items.len() == patterns.len() => {
+                    for (pattern, item) in patterns.iter().zip(items) {
+                        self.bind(pattern, item, pos)?;
+                    }
+                    Ok(())
+                }
+                Value::Tuple(items) => Err(Error::new(
+                    "E0308",
+                    format!(
+                        "mismatched types: expected a tuple with {} elements, found one with {} elements",
+                        patterns.len(),
+                        items.len()
+                    ),
+                    pos,
+                )),
+                other => Err(Error::new(
+                    "E0308",
+                    format!(
+                        "mismatched types: expected `{}`, found tuple",
+                        other.type_name()
+                    ),
+                    pos,
+                )),
+            },
+        }
+    }
+
+    /// 从内向外查找最近的同名绑定
+    fn lookup(&mut self, name: &str, pos: Pos) -> Result<&mut Binding, Error> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.bindings.iter_mut().rev())
+            .find(|binding| binding.name == name)
+            .ok_or_else(|| {
+                Error::new(
+                    "E0425",
+                    format!("cannot find value `{}` in this scope", name),
+                    pos,
+                )
+            })
+    }
+
+    fn assign(
+        &mut self,
+        name: &str,
+        op: &'static str,
+        value: &Expr,
+        pos: Pos,
+    ) -> Result<(), Error> {
+        let expected = self.lookup(name, pos)?.ty.clone();
+        let new_value = if op == "=" {
+            self.eval(value, expected.as_deref())?
+        } else {
+            // x += 1 等价于 x = x + 1，但要求 x 已经初始化
+            let current = self.read(name, pos)?;
+            let right = self.eval(value, Some(&current.type_name()))?;
+            binary(&op[..1], current, right, pos)?
+        };
+
+        let binding = self.lookup(name, pos)?;
+        if !binding.mutable && binding.value.is_some() {
+            let help = format!("consider making this binding mutable: `mut {}`", name);
+            return Err(Error::new(
+                "E0384",
+                format!("cannot assign twice to immutable variable `{}`", name),
+                pos,
+            )
+            .with_help(help));
+        }
+        if let Some(ty) = &binding.ty
+            && *ty != new_value.type_name()
+        {
+            return Err(mismatched(ty, &new_value.type_name(), pos));
+        }
+        binding.ty = Some(new_value.type_name());
+        binding.value = Some(new_value);
+        Ok(())
+    }
+
+    fn read(&mut self, name: &str, pos: Pos) -> Result<Value, Error> {
+        self.lookup(name, pos)?.value.clone().ok_or_else(|| {
+            Error::new(
+                "E0381",
+                format!("used binding `{}` isn't initialized", name),
+                pos,
+            )
+        })
+    }
+
+    /// 求值；`expected` 是上下文期望的类型，没有后缀的数字字面量会采用它
+    fn eval(&mut self, expr: &Expr, expected: Option<&str>) -> Result<Value, Error> {
+        match expr {
+            Expr::Lit { value, typed } => Ok(retype(value.clone(), *typed, expected)),
+            Expr::Var(name, pos) => self.read(name, *pos),
+            Expr::Unary(op, operand, pos) => {
+                let value = self.eval(operand, expected)?;
+                match (*op, value) {
+                    ("-", Value::Int(n, ty)) if ty.starts_with('i') => match n.checked_neg() {
+                        Some(n) => check_range(n, ty, "negate", *pos),
+                        None => Err(overflow(ty, "negate", *pos)),
+                    },
+                    ("-", Value::Float(x, ty)) => Ok(Value::Float(-x, ty)),
+                    ("!", Value::Bool(b)) => Ok(Value::Bool(!b)),
+                    ("!", Value::Int(n, ty)) => Ok(Value::Int(!n, ty)),
+                    (op, value) => Err(Error::new(
+                        "E0600",
+                        format!(
+                            "cannot apply unary operator `{}` to type `{}`",
+                            op,
+                            value.type_name()
+                        ),
+                        *pos,
+                    )),
+                }
+            }
+            Expr::Binary(op, left, right, pos) => {
+                let comparison = matches!(*op, "==" | "!=" | "<" | ">" | "<=" | ">=");
+                let operand_type = if comparison || matches!(*op, "&&" | "||") {
+                    None
+                } else {
+                    expected
+                };
+                // 一边是没有后缀的字面量时，让它采用另一边的类型，例如 spaces + 1
+                let (left, right) = if is_untyped(left) && !is_untyped(right) {
+                    let right = self.eval(right, operand_type)?;
+                    let left = self.eval(left, Some(&right.type_name()))?;
+                    (left, right)
+                } else {
+                    let left = self.eval(left, operand_type)?;
+                    let right = self.eval(right, Some(&left.type_name()))?;
+                    (left, right)
+                };
+                binary(op, left, right, *pos)
+            }
+            Expr::Tuple(items) => {
+                let types = expected.and_then(tuple_types);
+                let mut values = Vec::new();
+                for (index, item) in items.iter().enumerate() {
+                    let ty = types.as_ref().and_then(|types| types.get(index));
+                    values.push(self.eval(item, ty.map(String::as_str))?);
+                }
+                Ok(Value::Tuple(values))
+            }
+            Expr::Field(tuple, index, pos) => match self.eval(tuple, None)? {
+                Value::Tuple(mut items) if *index < items.len() => Ok(items.swap_remove(*index)),
+                value => Err(Error::new(
+                    "E0609",
+                    format!("no field `{}` on type `{}`", index, value.type_name()),
+                    *pos,
+                )),
+            },
+            Expr::Method(receiver, name, pos) => {
+                let value = self.eval(receiver, None)?;
+                match (&value, name.as_str()) {
+                    (Value::Str(s), "len") => Ok(Value::Int(s.len() as i128, "usize")),
+                    _ => Err(Error::new(
+                        "E0599",
+                        format!(
+                            "no method named `{}` found for type `{}` in the current scope",
+                            name,
+                            value.type_name()
+                        ),
+                        *pos,
+                    )),
+                }
+            }
+            Expr::Block(stmts) => self.block(stmts, expected),
+            Expr::If(condition, then, otherwise, pos) => {
+                let condition = match self.eval(condition, None)? {
+                    Value::Bool(b) => b,
+                    other => return Err(mismatched("bool", &other.type_name(), *pos)),
+                };
+                if condition {
+                    self.block(then, expected)
+                } else {
+                    match otherwise {
+                        Some(otherwise) => self.eval(otherwise, expected),
+                        None => Ok(Value::UNIT),
+                    }
+                }
+            }
+            Expr::Println(format, args, pos) => {
+                let mut values = Vec::new();
+                for arg in args {
+                    values.push(self.eval(arg, None)?);
+                }
+                let line = self.format(format, &values, *pos)?;
+                self.output.push_str(&line);
+                self.output.push('\n');
+                Ok(Value::UNIT)
+            }
+        }
+    }
+
+    /// 在新的作用域中执行代码块，离开时丢弃其中的绑定
+    fn block(&mut self, stmts: &[Stmt], expected: Option<&str>) -> Result<Value, Error> {
+        self.scopes.push(Scope::default());
+        // 代码块的值受期望类型影响：只处理最后一条表达式
+        let result = match stmts.split_last() {
+            Some((Stmt::Expr { expr, value: true }, init)) => self
+                .statements(init)
+                .and_then(|_| self.eval(expr, expected)),
+            _ => self.statements(stmts).map(|_| Value::UNIT),
+        };
+        self.scopes.pop();
+        result
+    }
+
+    /// println! 的格式化：支持 {}、{:?}、{name}、{name:?} 以及 {{ 和 }}
+    fn format(&mut self, format: &str, args: &[Value], pos: Pos) -> Result<String, Error> {
+        let mut out = String::new();
+        let mut next_arg = 0;
+        let mut chars = format.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let spec: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let (name, debug) = match spec.split_once(':') {
+                        Some((name, "?")) => (name, true),
+                        Some((_, other)) => {
+                            return Err(Error::syntax(
+                                format!("unsupported format spec `{}`", other),
+                                pos,
+                            ));
+                        }
+                        None => (spec.as_str(), false),
+                    };
+                    let value = if name.is_empty() {
+                        next_arg += 1;
+                        args.get(next_arg - 1).cloned().ok_or_else(|| {
+                            Error::syntax(
+                                String::from("format string has more placeholders than arguments"),
+                                pos,
+                            )
+                        })?
+                    } else {
+                        self.read(name, pos)?
+                    };
+                    if debug {
+                        out.push_str(&value.debug());
+                    } else if let Value::Tuple(_) = value {
+                        return Err(Error::new(
+                            "E0277",
+                            format!(
+                                "`{}` doesn't implement `std::fmt::Display`",
+                                value.type_name()
+                            ),
+                            pos,
+                        ));
+                    } else {
+                        out.push_str(&value.to_string());
+                    }
+                }
+                '}' => {
+                    return Err(Error::syntax(
+                        String::from("invalid format string: unmatched `}` found"),
+                        pos,
+                    ));
+                }
+                _ => out.push(c),
+            }
+        }
+
+        if next_arg != args.len() {
+            return Err(Error::syntax(String::from("argument never used"), pos));
+        }
+        Ok(out)
+    }
+}
+
+fn mismatched(expected: &str, found: &str, pos: Pos) -> Error {
+    Error::new(
+        "E0308",
+        format!(
+            "mismatched types: expected `{}`, found `{}`",
+            expected, found
+        ),
+        pos,
+    )
+}
+
+fn is_untyped(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit { typed, .. } => !typed,
+        Expr::Unary("-", operand, _) => is_untyped(operand),
+        _ => false,
+    }
+}
+
+/// 没有后缀的数字字面量采用期望的类型，例如 `let x: u8 = 5;` 中的 5
+fn retype(value: Value, typed: bool, expected: Option<&str>) -> Value {
+    match (value, expected) {
+        (Value::Int(n, _), Some(ty)) if !typed && int_type(ty).is_some() => {
+            Value::Int(n, int_type(ty).unwrap().0)
+        }
+        (Value::Float(x, _), Some("f32")) if !typed => Value::Float(x, "f32"),
+        (value, _) => value,
+    }
+}
+
+/// "(i32, bool)" -> ["i32", "bool"]；只拆最外层
+fn tuple_types(ty: &str) -> Option<Vec<String>> {
+    let inner = ty.strip_prefix('(')?.strip_suffix(')')?;
+    let mut types = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (index, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(inner[start..index].trim().to_string());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        types.push(last.to_string());
+    }
+    Some(types)
+}
+
+fn check_range(n: i128, ty: &'static str, verb: &str, pos: Pos) -> Result<Value, Error> {
+    let (_, min, max) = int_type(ty).unwrap();
+    if n < min || n > max {
+        Err(overflow(ty, verb, pos))
+    } else {
+        Ok(Value::Int(n, ty))
+    }
+}
+
+/// 运算结果超出类型的范围；中间结果超出 i128 时也走这里，而不是让解释器自己 panic
+fn overflow(ty: &str, verb: &str, pos: Pos) -> Error {
+    let note = if ty == "u128" {
+        " (this interpreter stores u128 as i128, so u128 stops at i128::MAX)"
+    } else {
+        ""
+    };
+    Error::panic(format!("attempt to {} with overflow{}", verb, note), pos)
+}
+
+fn binary(op: &str, left: Value, right: Value, pos: Pos) -> Result<Value, Error> {
+    let verb = match op {
+        "+" => "add",
+        "-" => "subtract",
+        "*" => "multiply",
+        "/" => "divide",
+        "%" => "calculate the remainder",
+        _ => "compare",
+    };
+
+    match (op, &left, &right) {
+        ("&&", Value::Bool(a), Value::Bool(b)) => return Ok(Value::Bool(*a && *b)),
+        ("||", Value::Bool(a), Value::Bool(b)) => return Ok(Value::Bool(*a || *b)),
+        ("&&" | "||", _, _) => {
+            let found = if matches!(left, Value::Bool(_)) {
+                &right
+            } else {
+                &left
+            };
+            return Err(mismatched("bool", &found.type_name(), pos));
+        }
+        _ => {}
+    }
+
+    if matches!(op, "==" | "!=" | "<" | ">" | "<=" | ">=") {
+        if left.type_name() != right.type_name() {
+            return Err(mismatched(&left.type_name(), &right.type_name(), pos));
+        }
+        let ordering = match (&left, &right) {
+            (Value::Int(a, _), Value::Int(b, _)) => a.partial_cmp(b),
+            (Value::Float(a, _), Value::Float(b, _)) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.partial_cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.partial_cmp(b),
+            _ if matches!(op, "==" | "!=") => Some(if left == right {
+                std::cmp::Ordering::Equal
+            } else {
+                std::cmp::Ordering::Less
+            }),
+            _ => {
+                return Err(Error::new(
+                    "E0369",
+                    format!(
+                        "binary operation `{}` cannot be applied to type `{}`",
+                        op,
+                        left.type_name()
+                    ),
+                    pos,
+                ));
+            }
+        };
+        let result = match (op, ordering) {
+            (_, None) => false,
+            ("==", Some(o)) => o.is_eq(),
+            ("!=", Some(o)) => o.is_ne(),
+            ("<", Some(o)) => o.is_lt(),
+            (">", Some(o)) => o.is_gt(),
+            ("<=", Some(o)) => o.is_le(),
+            (_, Some(o)) => o.is_ge(),
+        };
+        return Ok(Value::Bool(result));
+    }
+
+    match (&left, &right) {
+        (Value::Int(a, ty), Value::Int(b, other)) if ty == other => {
+            let (a, b) = (*a, *b);
+            if matches!(op, "/" | "%") && b == 0 {
+                let message = if op == "/" {
+                    "attempt to divide by zero"
+                } else {
+                    "attempt to calculate the remainder with a divisor of zero"
+                };
+                return Err(Error::panic(String::from(message), pos));
+            }
+            let result = match op {
+                "+" => a.checked_add(b),
+                "-" => a.checked_sub(b),
+                "*" => a.checked_mul(b),
+                "/" => a.checked_div(b),
+                _ => a.checked_rem(b),
+            };
+            match result {
+                Some(result) => check_range(result, ty, verb, pos),
+                None => Err(overflow(ty, verb, pos)),
+            }
+        }
+        (Value::Float(a, ty), Value::Float(b, other)) if ty == other => {
+            let result = match op {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" => a / b,
+                _ => a % b,
+            };
+            Ok(Value::Float(result, ty))
+        }
+        (Value::Int(..) | Value::Float(..), Value::Int(..) | Value::Float(..)) => {
+            Err(mismatched(&left.type_name(), &right.type_name(), pos))
+        }
+        _ => Err(Error::new(
+            "E0369",
+            format!(
+                "cannot {} `{}` to `{}`",
+                verb,
+                right.type_name(),
+                left.type_name()
+            ),
+            pos,
+        )),
+    }
+}
+
+// ========== 作用域栈的显示 ==========
+
+/// 把作用域栈画成文本；被遮蔽的绑定会标出来
+///
+/// ```text
+/// 作用域 #0
+///   spaces: &str = "   "    (已被遮蔽)
+///   spaces: usize = 3
+/// ```
+pub fn render_scopes(scopes: &[Scope]) -> String {
+    let mut out = String::new();
+    for (depth, scope) in scopes.iter().enumerate() {
+        out.push_str(&format!("{}作用域 #{}\n", "  ".repeat(depth), depth));
+        if scope.bindings.is_empty() {
+            out.push_str(&format!("{}  （空）\n", "  ".repeat(depth)));
+        }
+        for (index, binding) in scope.bindings.iter().enumerate() {
+            // 同一作用域中后面的同名绑定，或者内层作用域中的同名绑定都会遮蔽它
+            let shadowed = scope.bindings[index + 1..]
+                .iter()
+                .chain(scopes[depth + 1..].iter().flat_map(|s| &s.bindings))
+                .any(|other| other.name == binding.name);
+
+            let mut line = format!(
+                "{}  {}{}: {}",
+                "  ".repeat(depth),
+                if binding.mutable { "mut " } else { "" },
+                binding.name,
+                binding.ty.as_deref().unwrap_or("_")
+            );
+            match &binding.value {
+                Some(value) => line.push_str(&format!(" = {}", value.debug())),
+                None => line.push_str("（未初始化）"),
+            }
+            if shadowed {
+                line.push_str("    (已被遮蔽)");
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+// ========== REPL ==========
+
+const REPL_HELP: &str = "\
+输入 Rust 语句，例如 let x = 5; 或 { let y = x + 1; }
+末尾的分号可以省略；括号没有配对时会继续读取下一行。
+  :scope     显示作用域栈
+  :trace     切换跟踪模式：每条语句执行后显示作用域栈（可以看到代码块内部）
+  :reset     清空所有绑定
+  :help      显示帮助
+  :quit      退出";
+
+/// 一段输入中还没有配对的括号数；字符串和注释中的括号不算
+fn open_brackets(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '/' if !in_string && chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            '{' | '(' if !in_string => depth += 1,
+            '}' | ')' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// 交互式运行：每次输入后打印输出、表达式的值，以及全局作用域有变化时的作用域栈
+pub fn repl(input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    let mut interpreter = Interpreter::new();
+    writeln!(out, "变量绑定解释器，输入 :help 查看帮助，:quit 退出")?;
+
+    loop {
+        write!(out, "rust> ")?;
+        out.flush()?;
+
+        // 读取一条完整的输入：括号没有配对时继续读下一行
+        let mut source = String::new();
+        loop {
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(());
+            }
+            source.push_str(&line);
+            if open_brackets(&source) <= 0 {
+                break;
+            }
+            write!(out, "  ... ")?;
+            out.flush()?;
+        }
+
+        match source.trim() {
+            "" => continue,
+            ":quit" | ":q" => return Ok(()),
+            ":help" => {
+                writeln!(out, "{}", REPL_HELP)?;
+                continue;
+            }
+            ":scope" => {
+                write!(out, "{}", render_scopes(interpreter.scopes()))?;
+                continue;
+            }
+            ":trace" => {
+                interpreter.trace = !interpreter.trace;
+                let state = if interpreter.trace {
+                    "开启"
+                } else {
+                    "关闭"
+                };
+                writeln!(out, "跟踪模式已{}", state)?;
+                continue;
+            }
+            ":reset" => {
+                interpreter = Interpreter {
+                    trace: interpreter.trace,
+                    ..Interpreter::new()
+                };
+                writeln!(out, "已清空所有绑定")?;
+                continue;
+            }
+            command if command.starts_with(':') => {
+                writeln!(out, "未知的命令: {}（输入 :help 查看帮助）", command)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        let before = interpreter.scopes().to_vec();
+        let result = interpreter.eval_source(&source, true);
+        write!(out, "{}", interpreter.take_output())?;
+        match result {
+            Ok(value) => {
+                if let Some(value) = value.filter(|value| *value != Value::UNIT) {
+                    writeln!(out, "{}", value.debug())?;
+                }
+                if !interpreter.trace && interpreter.scopes() != before.as_slice() {
+                    write!(out, "{}", render_scopes(interpreter.scopes()))?;
+                }
+            }
+            Err(error) => write!(out, "{}", error.render(&source, "<repl>"))?,
+        }
+    }
+}
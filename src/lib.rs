@@ -0,0 +1,37 @@
+// ============================================
+// rust-tutorial
+// ============================================
+// 每一章都是 src/ 下的一个独立源文件，这里把它们组织成一个库，
+// 由 src/main.rs 中的 rust-tutorial 命令行调用
+
+// i18n 中的 t! 宏要在章节模块之前声明，章节中才能直接使用
+#[macro_use]
+pub mod i18n;
+
+pub mod accounts;
+pub mod arithmetic;
+pub mod book;
+pub mod borrowck;
+pub mod calculator;
+pub mod capture;
+pub mod casts;
+pub mod chapters;
+pub mod classifier;
+pub mod combinators;
+pub mod compile_fail;
+pub mod exercise;
+pub mod exhaustiveness;
+pub mod geometry;
+pub mod grid;
+pub mod index;
+pub mod integer;
+pub mod interner;
+pub mod interpreter;
+pub mod outline;
+pub mod quiz;
+pub mod scan;
+pub mod snapshot;
+pub mod spatial;
+pub mod tokenizer;
+pub mod toolchain;
+pub mod unicode;
@@ -1,3 +1,1083 @@
-fn main() {
-    println!("Hello, world!");
+// ============================================
+// rust-tutorial 命令行
+// ============================================
+// 用法：
+//   rust-tutorial list              列出所有章节
+//   rust-tutorial run <章节>        运行某一章，例如 run 07 或 run enums
+//   rust-tutorial run --all         按顺序运行所有章节
+//   rust-tutorial exercise ...      练习模式，见 EXERCISE_USAGE
+//   rust-tutorial quiz ...          章节测验，见 QUIZ_USAGE
+//   rust-tutorial i18n check        检查消息目录是否与章节中的 t! 调用一致
+//   rust-tutorial book <目录>       把章节生成为 mdBook 和单文件 HTML
+//   rust-tutorial where <词>        查找关键字、类型、宏或生命周期在哪些章节出现
+//   rust-tutorial repl [文件]       变量绑定解释器：交互运行，或解释执行一个文件
+//   rust-tutorial int ...           整数字面量解析和溢出计算，见 INT_USAGE
+//   rust-tutorial unicode <字符串>  逐字符列出字节、编码、类别和字形簇，并检查切片
+//   rust-tutorial casts [类型]      as 转换表，和 From / TryFrom 对比
+//   rust-tutorial calc [表达式]     整数表达式计算器：计算一个表达式，或进入交互模式
+//   rust-tutorial closures [场景]   用带日志的值演示闭包的三种捕获方式
+//   rust-tutorial rules <文件> [值]  按 match 风格的规则文件给数值分组，并检查规则
+//   rust-tutorial maze <文件>       用深度优先和广度优先搜索解 ASCII 迷宫
+//   rust-tutorial borrow <文件>     模拟所有权和借用检查，报告 rustc 风格的错误
+//
+// 任何子命令前都可以加 --lang <语言>（zh 或 en）指定章节输出的语言
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+
+use rust_tutorial::book;
+use rust_tutorial::borrowck::Program;
+use rust_tutorial::calculator;
+use rust_tutorial::capture;
+use rust_tutorial::casts::{self, Type};
+use rust_tutorial::chapters::{self, Chapter};
+use rust_tutorial::classifier::RuleSet;
+use rust_tutorial::exercise::{self, Exercise, Outcome, Workspace};
+use rust_tutorial::grid::{Grid, Search};
+use rust_tutorial::i18n::{self, Lang};
+use rust_tutorial::index;
+use rust_tutorial::integer::{self, IntType, Op};
+use rust_tutorial::interpreter::{self, Interpreter};
+use rust_tutorial::quiz;
+use rust_tutorial::unicode;
+
+const USAGE: &str = "\
+用法:
+  rust-tutorial list              列出所有章节
+  rust-tutorial run <章节>        运行某一章（编号或名称，例如 07、enums）
+  rust-tutorial run --all         按顺序运行所有章节
+  rust-tutorial exercise <子命令>  练习模式（exercise help 查看详情）
+  rust-tutorial quiz <章节>       章节测验（quiz help 查看详情）
+  rust-tutorial i18n check        检查消息目录（locales/）是否与章节代码一致
+  rust-tutorial book <目录> [--output]
+                                  把章节生成为 mdBook 项目和单文件 HTML，
+                                  --output 把每一节的运行输出嵌入代码块下方
+  rust-tutorial where <词>        查找关键字、类型、宏或生命周期出现的位置，
+                                  例如 where move、where HashMap、where \'static
+  rust-tutorial repl [文件]       按第 01 章的绑定规则解释执行 let、代码块和表达式；
+                                  不指定文件时进入交互模式，显示每一步后的作用域栈
+  rust-tutorial int <子命令>      整数字面量和溢出（int help 查看详情）
+  rust-tutorial unicode <字符串> [a..b]
+                                  逐字符列出字节偏移、UTF-8、UTF-16、标量值、类别和字形簇；
+                                  给出 a..b 时检查 &s[a..b] 是否合法
+  rust-tutorial casts [类型] [--markdown]
+                                  列出各类型的边界值用 as 转换成其他类型的结果，
+                                  并和 From / TryFrom 对比；--markdown 输出书中的附录页
+  rust-tutorial calc [表达式]     计算整数表达式，例如 calc '3 + 4 * 2'；支持 + - * / % ^、
+                                  括号、函数（abs、min、max、gcd、sqrt）和 let 变量，
+                                  不指定表达式时进入交互模式
+  rust-tutorial closures [场景]   打印闭包捕获场景的事件时间线：借用、移动、克隆、释放
+                                  和调用闭包用的 trait（Fn、FnMut、FnOnce）
+  rust-tutorial rules <文件> [值...]
+                                  加载 match 风格的规则文件（见 rules/），报告不可达、重叠的
+                                  规则和没有覆盖的值；给出值时逐个分组，否则统计标准输入
+  rust-tutorial maze <文件>       解 ASCII 迷宫（见 mazes/），画出广度优先找到的最短路径，
+                                  并比较深度优先和广度优先的步数和展开的格子数
+  rust-tutorial maze --seed <种子> [行 列]
+                                  用种子生成一个迷宫（默认 8 行 20 列个房间）并求解
+  rust-tutorial borrow <文件> [--trace]
+                                  按所有权和借用规则逐条执行脚本（见 borrowck/），报告
+                                  E0382、E0499、E0502、E0505、E0597 等错误；
+                                  --trace 在每一步之后打印变量的所有权状态表
+
+选项:
+  --lang <语言>                   章节输出的语言: zh（默认）或 en
+                                  不指定时按 LC_ALL、LC_MESSAGES、LANG 环境变量选择";
+
+const EXERCISE_USAGE: &str = "\
+用法:
+  rust-tutorial exercise next             准备下一道未完成的练习
+  rust-tutorial exercise check [编号]     检查练习（默认为下一道未完成的）
+  rust-tutorial exercise list [章节]      列出练习及完成情况
+  rust-tutorial exercise reset <编号>     把练习文件恢复为初始的错误版本
+
+练习文件和进度保存在 $RUST_TUTORIAL_HOME（默认 ~/.local/share/rust-tutorial）";
+
+const QUIZ_USAGE: &str = "\
+用法:
+  rust-tutorial quiz <章节>          回答某一章的测验题
+  rust-tutorial quiz check [章节]    运行章节代码，校验题库中的答案是否仍然正确
+
+题库位于 quizzes/ 目录，格式说明见 src/quiz.rs";
+
+const INT_USAGE: &str = "\
+用法:
+  rust-tutorial int <字面量>...              解析整数字面量，显示类型、数值和二进制位，
+                                             例如 int 98_222 0xff 0o77 0b1111_0000 b'A' 128i8
+  rust-tutorial int <类型> <a> <运算> [b]    用 checked、wrapping、saturating、overflowing
+                                             四种方式计算并比较，例如 int u8 200 + 100
+
+运算: + - '*' / % '<<'（shl）pow neg，也可以写方法名 add、sub、mul 等
+pow 和 << 的右操作数是 u32，neg 只需要一个操作数";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match take_lang(&mut args) {
+        Ok(Some(lang)) => {
+            i18n::set_lang(lang);
+        }
+        Ok(None) => {}
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match args.as_slice() {
+        ["list"] => {
+            list();
+            ExitCode::SUCCESS
+        }
+        ["run", "--all"] => {
+            for chapter in chapters::all() {
+                run(chapter);
+            }
+            ExitCode::SUCCESS
+        }
+        ["run", query] => match chapters::find(query) {
+            Some(chapter) => {
+                run(chapter);
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("找不到章节: {query}");
+                eprintln!("使用 rust-tutorial list 查看所有章节");
+                ExitCode::FAILURE
+            }
+        },
+        ["exercise", rest @ ..] => exercise(rest),
+        ["quiz", rest @ ..] => quiz(rest),
+        ["i18n", "check"] => i18n_check(),
+        ["where", term] => where_used(term),
+        ["repl"] => repl(),
+        ["repl", file] => interpret(file),
+        ["int", rest @ ..] => int(rest),
+        ["unicode", text] => inspect_unicode(text, None),
+        ["unicode", text, range] => inspect_unicode(text, Some(range)),
+        ["casts"] => print_casts(&Type::ALL),
+        ["casts", "--markdown"] => {
+            print!("{}", casts::markdown());
+            ExitCode::SUCCESS
+        }
+        ["casts", name] => match Type::parse(name) {
+            Some(ty) => print_casts(&[ty]),
+            None => {
+                eprintln!("未知的类型: {name}");
+                eprintln!("可选: i8 … i128、isize、u8 … u128、usize、f32、f64、char、bool");
+                ExitCode::FAILURE
+            }
+        },
+        ["calc"] => calc_repl(),
+        ["calc", rest @ ..] => calc(&rest.join(" ")),
+        ["closures"] => {
+            let timelines: Vec<String> = capture::scenarios().iter().map(|s| s.render()).collect();
+            print!("{}", timelines.join("\n"));
+            ExitCode::SUCCESS
+        }
+        ["closures", name] => match capture::find(name) {
+            Some(scenario) => {
+                print!("{}", scenario.render());
+                ExitCode::SUCCESS
+            }
+            None => {
+                let names: Vec<&str> = capture::scenarios().iter().map(|s| s.name).collect();
+                eprintln!("没有名为 {name} 的场景（可选: {}）", names.join("、"));
+                ExitCode::FAILURE
+            }
+        },
+        ["rules", file, values @ ..] => classify(file, values),
+        ["maze", "--seed", seed, size @ ..] => generate_maze(seed, size),
+        ["maze", file] => match fs::read_to_string(file) {
+            Ok(text) => match Grid::parse(&text) {
+                Ok(grid) => solve_maze(&grid),
+                Err(e) => {
+                    eprintln!("{file} {e}");
+                    ExitCode::FAILURE
+                }
+            },
+            Err(e) => {
+                eprintln!("无法读取 {file}: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        ["borrow", file] => check_borrows(file, false),
+        ["borrow", file, "--trace"] => check_borrows(file, true),
+        ["book", dir] => generate_book(dir, false),
+        ["book", dir, "--output"] | ["book", "--output", dir] => generate_book(dir, true),
+        [] | ["help" | "-h" | "--help"] => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 从参数中取出 `--lang zh` 或 `--lang=zh`
+fn take_lang(args: &mut Vec<&str>) -> Result<Option<Lang>, String> {
+    let Some(index) = args
+        .iter()
+        .position(|arg| *arg == "--lang" || arg.starts_with("--lang="))
+    else {
+        return Ok(None);
+    };
+
+    let name = match args[index].strip_prefix("--lang=") {
+        Some(name) => {
+            args.remove(index);
+            name
+        }
+        None if index + 1 < args.len() => {
+            let name = args[index + 1];
+            args.drain(index..=index + 1);
+            name
+        }
+        None => return Err(String::from("--lang 后面需要指定语言: zh 或 en")),
+    };
+
+    match Lang::parse(name) {
+        Some(lang) => Ok(Some(lang)),
+        None => Err(format!("不支持的语言: {name}（可选: zh、en）")),
+    }
+}
+
+// ========== 子命令 ==========
+
+fn list() {
+    for chapter in chapters::all() {
+        println!(
+            "{:02}  {:<18} {}",
+            chapter.number,
+            chapter.slug,
+            chapter.title()
+        );
+    }
+}
+
+fn run(chapter: &Chapter) {
+    println!(
+        "========== {:02}. {} ==========",
+        chapter.number,
+        chapter.title()
+    );
+    (chapter.run)();
+    println!();
+}
+
+// ========== 练习模式 ==========
+
+fn exercise(args: &[&str]) -> ExitCode {
+    let mut workspace = match Workspace::open(&exercise::data_dir()) {
+        Ok(workspace) => workspace,
+        Err(e) => {
+            eprintln!("无法打开练习目录 {}: {e}", exercise::data_dir().display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let exercises = exercise::all();
+
+    let result = match args {
+        ["next"] => match workspace.next(&exercises) {
+            Some(next) => exercise_next(&workspace, next),
+            None => {
+                println!("所有 {} 道练习都已完成！", exercises.len());
+                Ok(true)
+            }
+        },
+        ["check"] => match workspace.next(&exercises) {
+            Some(next) => exercise_check(&mut workspace, &exercises, next),
+            None => {
+                println!("所有 {} 道练习都已完成！", exercises.len());
+                Ok(true)
+            }
+        },
+        ["check", id] => match exercise::find(id) {
+            Some(found) => exercise_check(&mut workspace, &exercises, &found),
+            None => unknown_exercise(id),
+        },
+        ["list"] => {
+            exercise_list(&workspace, &exercises);
+            Ok(true)
+        }
+        ["list", query] => match chapters::find(query) {
+            Some(chapter) => {
+                exercise_list(&workspace, &exercise::for_chapter(chapter));
+                Ok(true)
+            }
+            None => {
+                eprintln!("找不到章节: {query}");
+                Ok(false)
+            }
+        },
+        ["reset", id] => match exercise::find(id) {
+            Some(found) => workspace.reset(&found).map(|path| {
+                println!("已重置练习 {}: {}", found.id(), path.display());
+                true
+            }),
+            None => unknown_exercise(id),
+        },
+        ["help" | "-h" | "--help"] => {
+            println!("{EXERCISE_USAGE}");
+            Ok(true)
+        }
+        _ => {
+            eprintln!("{EXERCISE_USAGE}");
+            Ok(false)
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("错误: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn unknown_exercise(id: &str) -> io::Result<bool> {
+    eprintln!("找不到练习: {id}");
+    eprintln!("使用 rust-tutorial exercise list 查看所有练习");
+    Ok(false)
+}
+
+fn exercise_next(workspace: &Workspace, next: &Exercise) -> io::Result<bool> {
+    let path = workspace.prepare(next)?;
+    println!(
+        "练习 {}：{} / {}. {}",
+        next.id(),
+        next.chapter.title(),
+        next.section.number,
+        next.section.title
+    );
+    println!("提示: {}", next.breakage);
+    println!("文件: {}", path.display());
+    println!();
+    println!("修改完成后运行: rust-tutorial exercise check {}", next.id());
+    Ok(true)
+}
+
+fn exercise_check(
+    workspace: &mut Workspace,
+    exercises: &[Exercise],
+    target: &Exercise,
+) -> io::Result<bool> {
+    let path = workspace.prepare(target)?;
+    println!("检查练习 {}: {}", target.id(), path.display());
+
+    match exercise::check(target, &path)? {
+        Outcome::Passed => {
+            workspace.mark_done(target)?;
+            println!("通过！输出与原章节一致");
+            match workspace.next(exercises) {
+                Some(next) => println!("下一道练习: rust-tutorial exercise next ({})", next.id()),
+                None => println!("所有 {} 道练习都已完成！", exercises.len()),
+            }
+            Ok(true)
+        }
+        Outcome::Unfinished => {
+            println!("文件中还有 {} 占位符，请先补全它", exercise::BLANK);
+            println!("提示: {}", target.breakage);
+            Ok(false)
+        }
+        Outcome::CompileError(stderr) => {
+            println!("编译失败:");
+            print!("{stderr}");
+            println!("提示: {}", target.breakage);
+            Ok(false)
+        }
+        Outcome::RuntimeError(stderr) => {
+            println!("运行失败:");
+            print!("{stderr}");
+            Ok(false)
+        }
+        Outcome::WrongOutput { expected, actual } => {
+            println!("输出与原章节不一致:");
+            for (line, (want, got)) in expected.lines().zip(actual.lines()).enumerate() {
+                if want != got {
+                    println!("  第 {} 行", line + 1);
+                    println!("    期望: {want}");
+                    println!("    实际: {got}");
+                    break;
+                }
+            }
+            if expected.lines().count() != actual.lines().count() {
+                println!(
+                    "  期望 {} 行输出，实际 {} 行",
+                    expected.lines().count(),
+                    actual.lines().count()
+                );
+            }
+            Ok(false)
+        }
+    }
+}
+
+fn exercise_list(workspace: &Workspace, exercises: &[Exercise]) {
+    let done = exercises.iter().filter(|e| workspace.is_done(e)).count();
+    for exercise in exercises {
+        println!(
+            "{} {:<6} {:<18} {}. {}",
+            if workspace.is_done(exercise) {
+                "✓"
+            } else {
+                " "
+            },
+            exercise.id(),
+            exercise.chapter.slug,
+            exercise.section.number,
+            exercise.section.title
+        );
+    }
+    println!();
+    println!("已完成 {done}/{}", exercises.len());
+}
+
+// ========== 章节测验 ==========
+
+fn quiz(args: &[&str]) -> ExitCode {
+    let result = match args {
+        ["check"] => quiz_check(&quiz::all()),
+        ["check", query] => match chapters::find(query).and_then(quiz::for_chapter) {
+            Some(bank) => quiz_check(&[bank]),
+            None => unknown_quiz(query),
+        },
+        ["help" | "-h" | "--help"] => {
+            println!("{QUIZ_USAGE}");
+            Ok(true)
+        }
+        [query] => match chapters::find(query).and_then(quiz::for_chapter) {
+            Some(bank) => quiz_run(&bank),
+            None => unknown_quiz(query),
+        },
+        _ => {
+            eprintln!("{QUIZ_USAGE}");
+            Ok(false)
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("错误: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn unknown_quiz(query: &str) -> io::Result<bool> {
+    eprintln!("找不到章节或题库: {query}");
+    eprintln!("使用 rust-tutorial list 查看所有章节");
+    Ok(false)
+}
+
+fn quiz_run(bank: &quiz::Bank) -> io::Result<bool> {
+    let questions = bank
+        .questions()
+        .map_err(|e| io::Error::other(format!("{}: {e}", bank.file_name)))?;
+    let title = format!("{:02}. {}", bank.chapter.number, bank.chapter.title());
+    let score = quiz::run(
+        &title,
+        &questions,
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+    )?;
+    Ok(score.missed.is_empty())
+}
+
+fn quiz_check(banks: &[quiz::Bank]) -> io::Result<bool> {
+    let runner = env::current_exe()?;
+    let mut ok = true;
+
+    for bank in banks {
+        let problems = quiz::check(&runner, bank)?;
+        if problems.is_empty() {
+            println!("✓ {}", bank.file_name);
+        } else {
+            ok = false;
+            println!("✗ {}", bank.file_name);
+            for problem in problems {
+                println!("    {problem}");
+            }
+        }
+    }
+    Ok(ok)
+}
+
+// ========== 关键字索引 ==========
+
+fn where_used(query: &str) -> ExitCode {
+    let Some(entry) = index::lookup(query) else {
+        eprintln!("索引中没有: {query}");
+        let suggestions = index::suggestions(query, 10);
+        if !suggestions.is_empty() {
+            eprintln!("你是不是要找: {}", suggestions.join(", "));
+        }
+        return ExitCode::FAILURE;
+    };
+
+    match entry.keyword {
+        Some(keyword) if keyword.category.is_empty() => println!(
+            "{}（{}）: {}",
+            keyword.name,
+            keyword.kind.name(),
+            keyword.description
+        ),
+        Some(keyword) => println!(
+            "{}（{} · {}）: {}",
+            keyword.name,
+            keyword.kind.name(),
+            keyword.category,
+            keyword.description
+        ),
+        None => println!("{}", entry.term),
+    }
+    println!();
+
+    if entry.occurrences.is_empty() {
+        println!("章节代码中没有用到 {}", entry.term);
+        return ExitCode::SUCCESS;
+    }
+
+    println!("共 {} 处:", entry.occurrences.len());
+    for occurrence in entry.occurrences {
+        let location = format!("{}:{}", occurrence.chapter().file_name(), occurrence.line);
+        if occurrence.section.is_empty() {
+            println!("  {location}");
+        } else {
+            println!("  {location}  {}", occurrence.section);
+        }
+        println!("      {}", occurrence.code());
+    }
+    ExitCode::SUCCESS
+}
+
+// ========== 解释器 ==========
+
+fn repl() -> ExitCode {
+    match interpreter::repl(&mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("错误: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn interpret(file: &str) -> ExitCode {
+    let source = match fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("无法读取 {file}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut interpreter = Interpreter::new();
+    match interpreter.run(&source) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            print!("{}", interpreter.take_output());
+            eprint!("{}", error.render(&source, file));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ========== 整数字面量 ==========
+
+fn int(args: &[&str]) -> ExitCode {
+    match args {
+        [] | ["help" | "-h" | "--help"] => {
+            println!("{INT_USAGE}");
+            ExitCode::SUCCESS
+        }
+        [ty, rest @ ..] if IntType::parse(ty).is_some() => {
+            let ty = IntType::parse(ty).unwrap();
+            match rest {
+                [a, op, b] => int_calculate(ty, a, op, Some(b)),
+                [a, op] => int_calculate(ty, a, op, None),
+                _ => {
+                    eprintln!("{INT_USAGE}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        literals => {
+            let mut ok = true;
+            for (index, text) in literals.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                ok &= int_literal(text);
+            }
+            if ok {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+fn print_int_error(error: &integer::Error) {
+    println!("  {error}");
+    for note in error.notes() {
+        println!("    = {note}");
+    }
+}
+
+fn int_literal(text: &str) -> bool {
+    println!("{text}");
+    let literal = match integer::parse(text) {
+        Ok(literal) => literal,
+        Err(error) => {
+            print_int_error(&error);
+            return false;
+        }
+    };
+
+    let ty = literal.ty();
+    let reason = match (literal.suffix, literal.radix) {
+        (Some(_), _) => "后缀指定",
+        (None, integer::Radix::Byte) => "字节字面量总是 u8",
+        (None, _) => "没有后缀，也没有上下文时默认为 i32",
+    };
+    println!("  写法: {}", literal.radix.name());
+    println!("  类型: {ty}（{reason}）");
+    match literal.value() {
+        Ok(value) => {
+            println!("  数值: {value}");
+            println!("  二进制: {}", value.binary());
+            println!("  范围: {}..={}", ty.min(), ty.max());
+            true
+        }
+        Err(error) => {
+            print_int_error(&error);
+            false
+        }
+    }
+}
+
+fn int_calculate(ty: IntType, a: &str, op: &str, b: Option<&str>) -> ExitCode {
+    let Some(op) = Op::parse(op) else {
+        eprintln!("不认识的运算: {op}");
+        eprintln!("{INT_USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let rhs_ty = if op.takes_u32() { IntType::U32 } else { ty };
+    let a = integer::operand(a, ty);
+    let b = b.map(|b| integer::operand(b, rhs_ty)).transpose();
+    let (a, b) = match (a, b) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("{error}");
+            for note in error.notes() {
+                eprintln!("  = {note}");
+            }
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let outcomes = match integer::evaluate(op, a, b) {
+        Ok(outcomes) => outcomes,
+        Err(integer::Error::Empty) => {
+            eprintln!("{} 需要两个操作数", op.name());
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match b {
+        Some(b) => println!("{ty}: {a} {} {b}", op.symbol()),
+        None => println!("{ty}: {}({a})", op.name()),
+    }
+    for (semantics, outcome) in &outcomes {
+        println!("  {:<18}{outcome}", semantics.method(op));
+    }
+    // 中文占两列，不能直接用 {:<18} 对齐
+    println!(
+        "  {}{}",
+        pad(&plain_label(op), 18),
+        integer::plain_operator(op, ty, &outcomes)
+    );
+    ExitCode::SUCCESS
+}
+
+/// 最后一行的标题：直接写运算符，pow 没有运算符，是不带前缀的 .pow()
+fn plain_label(op: integer::Op) -> String {
+    match op {
+        integer::Op::Pow => ".pow()".to_string(),
+        _ => format!("运算符 {}", op.symbol()),
+    }
+}
+
+/// 按终端显示宽度在右边补空格
+fn pad(text: &str, width: usize) -> String {
+    let fill = width.saturating_sub(unicode::width(text));
+    format!("{text}{}", " ".repeat(fill))
+}
+
+// ========== Unicode ==========
+
+fn inspect_unicode(text: &str, range: Option<&str>) -> ExitCode {
+    let chars = unicode::inspect(text);
+    let clusters = unicode::graphemes(text);
+    let utf16: usize = chars.iter().map(|info| info.utf16.len()).sum();
+    println!(
+        "\"{text}\": {} 个 char，{} 个字节，{utf16} 个 UTF-16 单元，{} 个字形簇",
+        chars.len(),
+        text.len(),
+        clusters.len()
+    );
+    println!();
+
+    let header = [
+        "字节",
+        "字符",
+        "UTF-8",
+        "UTF-16",
+        "标量值",
+        "类别",
+        "字形簇",
+    ];
+    let widths = [6, 10, 14, 12, 10, 20, 0];
+    let row = |cells: [String; 7]| {
+        let line: String = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| pad(cell, width))
+            .collect();
+        println!("  {}", line.trim_end());
+    };
+    row(header.map(String::from));
+    for info in &chars {
+        let hex = |units: Vec<String>| units.join(" ");
+        row([
+            info.bytes().start.to_string(),
+            info.display(),
+            hex(info.utf8.iter().map(|b| format!("{b:02X}")).collect()),
+            hex(info.utf16.iter().map(|u| format!("{u:04X}")).collect()),
+            info.scalar(),
+            format!("{} {}", info.category, info.category.description()),
+            format!("#{}", info.cluster),
+        ]);
+    }
+    println!();
+
+    let boundaries: Vec<String> = unicode::boundaries(text)
+        .iter()
+        .map(usize::to_string)
+        .collect();
+    println!("字符边界: {}", boundaries.join(", "));
+    let clusters: Vec<String> = clusters
+        .iter()
+        .map(|(offset, cluster)| format!("{offset}:\"{cluster}\""))
+        .collect();
+    println!("字形簇: {}", clusters.join(" "));
+
+    let Some(range) = range else {
+        return ExitCode::SUCCESS;
+    };
+    let parsed = range
+        .split_once("..")
+        .and_then(|(a, b)| Some((a.parse().ok()?, b.parse().ok()?)));
+    let Some((start, end)) = parsed else {
+        eprintln!("切片范围的格式是 起点..终点，例如 0..4");
+        return ExitCode::FAILURE;
+    };
+
+    println!();
+    match unicode::slice(text, start..end) {
+        Ok(slice) => {
+            println!("&s[{start}..{end}] = {slice:?}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("&s[{start}..{end}] 不合法: {error}");
+            println!("标准库会 panic: {}", error.panic_message(text));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ========== as 转换 ==========
+
+fn print_casts(types: &[Type]) -> ExitCode {
+    for line in casts::LEGEND {
+        println!("{line}");
+    }
+    for &ty in types {
+        let table = casts::table(ty);
+        println!();
+        print!("{}", table.render());
+
+        // 被拒绝的转换附上 rustc 的报错
+        for row in &table.rows {
+            if let Err(error) = row.cells[0].cast {
+                match error.help() {
+                    Some(help) => println!("  {error}（help: {help}）"),
+                    None => println!("  {error}"),
+                }
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// ========== 计算器 ==========
+
+fn calc_repl() -> ExitCode {
+    match calculator::repl(&mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("错误: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn calc(source: &str) -> ExitCode {
+    match calculator::eval(source) {
+        Ok(value) => {
+            println!("{value}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprint!("{}", error.render(source));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ========== 规则分类 ==========
+
+fn classify(file: &str, values: &[&str]) -> ExitCode {
+    let rules = match fs::read_to_string(file) {
+        Ok(text) => match RuleSet::parse(&text) {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("{file} {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(e) => {
+            eprintln!("无法读取 {file}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let diagnostics = rules.check();
+    for diagnostic in &diagnostics {
+        eprintln!("{diagnostic}");
+    }
+    if !diagnostics.is_empty() {
+        eprintln!();
+    }
+
+    if values.is_empty() {
+        return match rules.tally(io::stdin().lock()) {
+            Ok(tally) => {
+                print!("{}", tally.render());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("错误: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    for value in values {
+        match rules.classify_text(value) {
+            Ok(Some(rule)) => println!("{value} => {}（第 {} 行）", rule.label, rule.line),
+            Ok(None) => println!("{value} => 没有匹配的规则"),
+            Err(e) => println!("{value} => {e}"),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// ========== 迷宫 ==========
+
+fn generate_maze(seed: &str, size: &[&str]) -> ExitCode {
+    let Ok(seed) = seed.parse::<u64>() else {
+        eprintln!("种子必须是非负整数: {seed}");
+        return ExitCode::FAILURE;
+    };
+    let (rows, cols) = match size {
+        [] => (8, 20),
+        [rows, cols] => match (rows.parse::<usize>(), cols.parse::<usize>()) {
+            (Ok(rows @ 1..=100), Ok(cols @ 1..=100)) => (rows, cols),
+            _ => {
+                eprintln!("行数和列数必须是 1 到 100 的整数");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    solve_maze(&Grid::generate(rows, cols, seed))
+}
+
+fn solve_maze(grid: &Grid) -> ExitCode {
+    let bfs = grid.bfs();
+    let dfs = grid.dfs();
+    print!("{}", grid.render(bfs.path.as_deref().unwrap_or_default()));
+    println!();
+    let summary = |name: &str, search: &Search| match search.steps() {
+        Some(steps) => println!("{name}: {steps} 步，展开了 {} 个格子", search.explored),
+        None => println!(
+            "{name}: 没有从 S 到 G 的路径，展开了 {} 个格子",
+            search.explored
+        ),
+    };
+    summary("广度优先", &bfs);
+    summary("深度优先", &dfs);
+    if bfs.path.is_some() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// ========== 借用检查 ==========
+
+fn check_borrows(file: &str, trace: bool) -> ExitCode {
+    let source = match fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("无法读取 {file}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let program = match Program::parse(&source) {
+        Ok(program) => program,
+        Err(e) => {
+            eprintln!("{file} {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let run = program.run();
+    if trace {
+        print!("{}", run.trace());
+    }
+    if run.violations.is_empty() {
+        println!("{} 条语句，没有违反所有权和借用规则", program.len());
+        return ExitCode::SUCCESS;
+    }
+    for violation in &run.violations {
+        eprintln!("{}", violation.render(&source, file));
+    }
+    eprintln!("共 {} 个错误", run.violations.len());
+    ExitCode::FAILURE
+}
+
+// ========== 书籍 ==========
+
+fn generate_book(dir: &str, with_output: bool) -> ExitCode {
+    let mut pages: Vec<book::Page> = chapters::all().iter().map(book::page).collect();
+
+    if with_output {
+        // 每一章都要单独编译一次，并行进行
+        let outputs: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = pages
+                .iter()
+                .map(|page| scope.spawn(|| book::section_output(page.chapter)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        for (page, output) in pages.iter_mut().zip(outputs) {
+            match output {
+                Ok(output) => book::embed_output(page, &output),
+                Err(e) => {
+                    eprintln!("错误: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    match book::write(Path::new(dir), &pages) {
+        Ok(files) => {
+            for file in &files {
+                println!("{}", file.display());
+            }
+            println!();
+            println!(
+                "已生成 {} 个文件，可以用 mdbook build {dir} 构建网站",
+                files.len()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("无法写入 {dir}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ========== 消息目录 ==========
+
+fn i18n_check() -> ExitCode {
+    let sources: Vec<(String, &str)> = chapters::all()
+        .iter()
+        .map(|chapter| (chapter.file_name(), chapter.source))
+        .collect();
+    let sources: Vec<(&str, &str)> = sources
+        .iter()
+        .map(|(file_name, source)| (file_name.as_str(), *source))
+        .collect();
+
+    let mut ok = true;
+    for lang in Lang::ALL {
+        let report = i18n::check(lang, lang.catalog(), &sources);
+        if report.is_ok() {
+            println!("✓ locales/{lang}.txt");
+            continue;
+        }
+
+        ok = false;
+        println!("✗ locales/{lang}.txt");
+        let groups = [
+            ("格式错误", &report.errors),
+            ("缺少的消息", &report.missing),
+            ("没有使用的消息", &report.unused),
+            ("参数个数不一致", &report.mismatched),
+        ];
+        for (title, items) in groups {
+            if !items.is_empty() {
+                println!("  {title}:");
+                for item in items {
+                    println!("    {item}");
+                }
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
@@ -0,0 +1,120 @@
+// ============================================
+// 章节结构 (Outline)
+// ============================================
+// 每一章的源码都遵循同样的结构：
+//
+//     // ============================================
+//     // 07. 枚举 (Enums)                  <- 横幅标题
+//     // ============================================
+//     // 枚举允许你定义一个类型 ...         <- 简介
+//
+//     // ========== 基本枚举 ==========    <- 小节
+//
+//     // 1. 定义枚举                       <- 编号的讲解，后面紧跟代码
+//
+// 这里把源码解析成带行号范围的编号段落，供练习模式等功能使用
+
+use std::ops::Range;
+
+/// 一个以 `// N. 标题` 开头的编号段落
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// 注释中的编号
+    pub number: u32,
+    /// 编号之后的标题，例如 "基本变量声明"
+    pub title: String,
+    /// 所属的 `// ========== 小节 ==========`，没有时为 None
+    pub group: Option<String>,
+    /// 段落所占的行（从 0 开始，不含末尾空行），第一行就是编号注释
+    pub lines: Range<usize>,
+    /// 编号注释的缩进；main 函数中的段落为 4，模块顶层的段落为 0
+    pub indent: usize,
+}
+
+/// 如果这一行是 `// ========== 标题 ==========`，返回标题
+pub fn group_title(line: &str) -> Option<&str> {
+    let title = line
+        .trim()
+        .strip_prefix("//")?
+        .trim()
+        .strip_prefix("==========")?
+        .strip_suffix("==========")?
+        .trim();
+    // 文件开头横幅的 "// ====...====" 本身不是小节
+    (!title.is_empty() && !title.starts_with('=')).then_some(title)
+}
+
+/// 如果这一行是 `// N. 标题`，返回编号和标题
+pub fn numbered(line: &str) -> Option<(u32, &str)> {
+    let rest = line.trim().strip_prefix("//")?.trim_start();
+    let (number, title) = rest.split_once(". ")?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((number.parse().ok()?, title.trim()))
+}
+
+/// 解析一章源码中的所有编号段落
+///
+/// 段落从编号注释开始，到下一个编号注释、下一个小节标题，
+/// 或者缩进比它更小的非空行（例如 main 函数的右花括号）之前结束。
+pub fn sections(source: &str) -> Vec<Section> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut group: Option<String> = None;
+    let mut open: Option<Section> = None;
+
+    // 跳过文件开头的横幅和简介，其中的 "07. 枚举" 不是编号段落
+    let header = lines
+        .iter()
+        .position(|line| !line.trim_start().starts_with("//"))
+        .unwrap_or(lines.len());
+
+    for (index, line) in lines.iter().enumerate().skip(header) {
+        let indent = line.len() - line.trim_start().len();
+        let is_group = group_title(line);
+        let is_numbered = numbered(line);
+
+        let closes = match &open {
+            Some(section) => {
+                is_group.is_some()
+                    || is_numbered.is_some()
+                    || (!line.trim().is_empty() && indent < section.indent)
+            }
+            None => false,
+        };
+        if closes {
+            let mut section = open.take().unwrap();
+            section.lines.end = index;
+            sections.push(section);
+        }
+
+        if let Some(title) = is_group {
+            group = Some(title.to_string());
+        } else if let Some((number, title)) = is_numbered {
+            open = Some(Section {
+                number,
+                title: title.to_string(),
+                group: group.clone(),
+                lines: index..index,
+                indent,
+            });
+        }
+    }
+
+    if let Some(mut section) = open {
+        section.lines.end = lines.len();
+        sections.push(section);
+    }
+
+    // 去掉每个段落末尾的空行
+    for section in &mut sections {
+        while section.lines.end > section.lines.start + 1
+            && lines[section.lines.end - 1].trim().is_empty()
+        {
+            section.lines.end -= 1;
+        }
+    }
+
+    sections
+}
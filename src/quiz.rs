@@ -0,0 +1,512 @@
+// ============================================
+// 章节测验 (Quiz)
+// ============================================
+// 每一章在 quizzes/NN_名称.txt 中有一份题库，包含两种题目：
+//   - 选择题：给出一段章节代码和若干选项，选出正确的一个或几个
+//   - 输出题：给出一段章节代码，写出它运行时打印的内容
+//
+// 题库是纯文本格式，方便直接编辑：
+//
+//     # 以 # 开头的行是注释，空行会被忽略
+//
+//     [output]                             <- 新的一道输出题
+//     question: largest(&char_list) 打印出什么？
+//     code: let char_list = vec!['y', 'm', 'a', 'q'];
+//     code: let result = largest(&char_list);
+//     code: println!("{}", t!("generics.largest_char", result));
+//     answer: 最大字符: y                   <- 期望的输出，多行输出写多个 answer
+//     explain: char 按 Unicode 标量值比较    <- 可选，答题后显示
+//
+//     [choice]                             <- 新的一道选择题
+//     question: Message2::Hello { id: 5 } 匹配哪个分支？
+//     code: ...
+//     right: id: id_variable @ 3..=7        <- 正确选项，可以有多个
+//     wrong: id: 10..=12                    <- 错误选项
+//     wrong: id
+//
+// 选项按书写顺序编为 A、B、C ...；code 行冒号后的第一个空格之后的内容
+// 原样保留（包括缩进），其余字段会去掉首尾空白。
+//
+// 题目中的 code 行必须和章节源码中的某一行（去掉首尾空白后）完全相同，
+// 输出题的 answer 行必须连续地出现在章节的实际输出中。validate 负责检查
+// 这两点，这样章节代码改动后，过时的题目会被测试发现。
+//
+// answer 写的是章节的中文输出（quiz check 用 --lang zh 运行章节）。答题时
+// 含有 t! 调用的 code 行后面会附上对应的中文模板，见 i18n::annotate。
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::chapters::{self, Chapter};
+use crate::i18n::{self, Lang};
+use crate::snapshot;
+
+/// 一道题目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    /// `[choice]` / `[output]` 所在的行号（从 1 开始），用于报告错误
+    pub line: usize,
+    pub prompt: String,
+    /// 题目引用的章节代码
+    pub code: Vec<String>,
+    pub kind: Kind,
+    pub explain: Option<String>,
+}
+
+/// 题目类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// 选择题：每个选项以及它是否正确
+    Choice { options: Vec<(String, bool)> },
+    /// 输出题：期望的输出行
+    Output { answer: Vec<String> },
+}
+
+/// 题库中的格式错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 出错的行号（从 1 开始）
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 行: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ========== 解析题库 ==========
+
+/// 解析题库文本
+pub fn parse(text: &str) -> Result<Vec<Question>, ParseError> {
+    let mut questions: Vec<Question> = Vec::new();
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // 1. [choice] / [output] 开始一道新题
+        if let Some(kind) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let kind = match kind {
+                "choice" => Kind::Choice {
+                    options: Vec::new(),
+                },
+                "output" => Kind::Output { answer: Vec::new() },
+                other => {
+                    return Err(ParseError {
+                        line,
+                        message: format!("未知的题目类型 [{}]，只支持 [choice] 和 [output]", other),
+                    });
+                }
+            };
+            if let Some(last) = questions.last() {
+                finish(last)?;
+            }
+            questions.push(Question {
+                line,
+                prompt: String::new(),
+                code: Vec::new(),
+                kind,
+                explain: None,
+            });
+            continue;
+        }
+
+        // 2. key: value 形式的字段
+        let Some((key, value)) = raw.split_once(':') else {
+            return Err(ParseError {
+                line,
+                message: format!("无法识别的行: {}", trimmed),
+            });
+        };
+        let Some(question) = questions.last_mut() else {
+            return Err(ParseError {
+                line,
+                message: String::from("第一道题之前缺少 [choice] 或 [output]"),
+            });
+        };
+
+        let key = key.trim();
+        let text = value.trim().to_string();
+        match (key, &mut question.kind) {
+            ("question", _) if question.prompt.is_empty() => question.prompt = text,
+            ("question", _) => {
+                question.prompt.push('\n');
+                question.prompt.push_str(&text);
+            }
+            ("code", _) => {
+                let code = value.strip_prefix(' ').unwrap_or(value);
+                question.code.push(code.trim_end().to_string());
+            }
+            ("explain", _) => question.explain = Some(text),
+            ("right", Kind::Choice { options }) => options.push((text, true)),
+            ("wrong", Kind::Choice { options }) => options.push((text, false)),
+            ("answer", Kind::Output { answer }) => answer.push(text),
+            ("right" | "wrong", Kind::Output { .. }) => {
+                return Err(ParseError {
+                    line,
+                    message: format!("输出题不能有 {} 选项", key),
+                });
+            }
+            ("answer", Kind::Choice { .. }) => {
+                return Err(ParseError {
+                    line,
+                    message: String::from("选择题用 right / wrong 给出选项，不能有 answer"),
+                });
+            }
+            _ => {
+                return Err(ParseError {
+                    line,
+                    message: format!("未知的字段: {}", key),
+                });
+            }
+        }
+    }
+
+    if let Some(last) = questions.last() {
+        finish(last)?;
+    }
+    Ok(questions)
+}
+
+/// 检查一道题是否完整
+fn finish(question: &Question) -> Result<(), ParseError> {
+    let error = |message: &str| {
+        Err(ParseError {
+            line: question.line,
+            message: message.to_string(),
+        })
+    };
+
+    if question.prompt.is_empty() {
+        return error("题目缺少 question");
+    }
+    match &question.kind {
+        Kind::Choice { options } => {
+            if !options.iter().any(|(_, right)| *right) {
+                return error("选择题至少需要一个 right 选项");
+            }
+            if !options.iter().any(|(_, right)| !*right) {
+                return error("选择题至少需要一个 wrong 选项");
+            }
+            if options.len() > 26 {
+                return error("选择题最多 26 个选项");
+            }
+        }
+        Kind::Output { answer } => {
+            if answer.is_empty() {
+                return error("输出题至少需要一行 answer");
+            }
+        }
+    }
+    Ok(())
+}
+
+// ========== 题库列表 ==========
+
+/// 一章的题库
+pub struct Bank {
+    pub chapter: &'static Chapter,
+    /// 题库文件名，例如 "12_generics.txt"
+    pub file_name: &'static str,
+    /// 题库文件的完整内容
+    pub text: &'static str,
+}
+
+impl Bank {
+    pub fn questions(&self) -> Result<Vec<Question>, ParseError> {
+        parse(self.text)
+    }
+}
+
+macro_rules! banks {
+    ($($file:literal,)*) => {
+        static BANKS: &[(&str, &str)] = &[
+            $(($file, include_str!(concat!("../quizzes/", $file))),)*
+        ];
+    };
+}
+
+banks! {
+    "01_variables.txt",
+    "02_data_types.txt",
+    "03_functions.txt",
+    "04_control_flow.txt",
+    "05_ownership.txt",
+    "06_structs.txt",
+    "07_enums.txt",
+    "08_pattern_matching.txt",
+    "09_collections.txt",
+    "10_packages_modules.txt",
+    "11_error_handling.txt",
+    "12_generics.txt",
+    "13_traits.txt",
+    "14_lifetimes.txt",
+    "15_derive_macros.txt",
+    "16_keywords.txt",
+}
+
+/// 某一章的题库；题库文件名与章节文件名相同，扩展名为 .txt
+pub fn for_chapter(chapter: &'static Chapter) -> Option<Bank> {
+    let stem = chapter.file_name();
+    let stem = stem.trim_end_matches(".rs");
+    BANKS
+        .iter()
+        .find(|(file, _)| file.trim_end_matches(".txt") == stem)
+        .map(|&(file_name, text)| Bank {
+            chapter,
+            file_name,
+            text,
+        })
+}
+
+/// 按章节顺序返回所有题库
+pub fn all() -> Vec<Bank> {
+    chapters::all().iter().filter_map(for_chapter).collect()
+}
+
+// ========== 校验题库 ==========
+
+/// 题目与章节代码或输出不一致的地方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// 题目所在的行号（从 1 开始）
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "第 {} 行: {}", self.line, self.message)
+    }
+}
+
+/// 对照章节源码和（规范化后的）章节输出校验题目
+pub fn validate(questions: &[Question], source: &str, output: &str) -> Vec<Problem> {
+    let source_lines: Vec<&str> = source.lines().map(str::trim).collect();
+    let output_lines: Vec<&str> = output.lines().collect();
+    let mut problems = Vec::new();
+
+    for question in questions {
+        // 1. 引用的代码必须仍然存在于章节中
+        for code in &question.code {
+            let code = code.trim();
+            if !code.is_empty() && !source_lines.contains(&code) {
+                problems.push(Problem {
+                    line: question.line,
+                    message: format!("章节源码中找不到代码: {}", code),
+                });
+            }
+        }
+
+        // 2. 输出题的答案必须连续出现在章节输出中
+        if let Kind::Output { answer } = &question.kind {
+            let found = output_lines.windows(answer.len()).any(|window| {
+                window
+                    .iter()
+                    .zip(answer)
+                    .all(|(got, want)| got.trim() == want)
+            });
+            if !found {
+                problems.push(Problem {
+                    line: question.line,
+                    message: format!("章节输出中没有这段答案: {}", answer.join(" / ")),
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+/// 用 `runner`（rust-tutorial 可执行文件）运行章节，校验题库中的所有题目
+pub fn check(runner: &Path, bank: &Bank) -> io::Result<Vec<Problem>> {
+    let questions = match bank.questions() {
+        Ok(questions) => questions,
+        Err(e) => {
+            return Ok(vec![Problem {
+                line: e.line,
+                message: e.message,
+            }]);
+        }
+    };
+    let output = snapshot::capture_normalized(runner, bank.chapter)?;
+    Ok(validate(&questions, bank.chapter.source, &output))
+}
+
+// ========== 答题 ==========
+
+/// 一次测验的成绩
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+    /// 答错或没有作答的题目序号（从 1 开始）
+    pub missed: Vec<usize>,
+}
+
+/// 把选项序号转换为字母，0 -> 'A'
+fn letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+/// 解析选择题的作答，例如 "b"、"A C"、"a,c"；有无效字母时返回 None
+fn parse_choice(input: &str, count: usize) -> Option<Vec<usize>> {
+    let mut picked = Vec::new();
+    for c in input.chars() {
+        if c.is_whitespace() || c == ',' || c == '，' {
+            continue;
+        }
+        let index = (c.to_ascii_uppercase() as usize).checked_sub('A' as usize)?;
+        if index >= count {
+            return None;
+        }
+        if !picked.contains(&index) {
+            picked.push(index);
+        }
+    }
+    picked.sort_unstable();
+    (!picked.is_empty()).then_some(picked)
+}
+
+/// 读取一行输入；输入结束时返回 None
+fn read_line(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+/// 在终端中依次作答所有题目，返回成绩
+///
+/// 输入提前结束（例如按下 Ctrl-D）时停止答题，剩下的题目计为未作答。
+pub fn run(
+    title: &str,
+    questions: &[Question],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<Score> {
+    let mut score = Score {
+        correct: 0,
+        total: questions.len(),
+        missed: Vec::new(),
+    };
+
+    writeln!(out, "========== {} 测验 ==========", title)?;
+
+    for (index, question) in questions.iter().enumerate() {
+        writeln!(out)?;
+        writeln!(out, "第 {}/{} 题", index + 1, questions.len())?;
+        writeln!(out, "{}", question.prompt)?;
+        if !question.code.is_empty() {
+            writeln!(out)?;
+            for code in &question.code {
+                writeln!(out, "    {}", i18n::annotate(Lang::Zh, code))?;
+            }
+            writeln!(out)?;
+        }
+
+        let answered = match &question.kind {
+            Kind::Choice { options } => ask_choice(options, input, out)?,
+            Kind::Output { answer } => ask_output(answer, input, out)?,
+        };
+        let Some(right) = answered else {
+            writeln!(out)?;
+            writeln!(out, "输入结束，停止答题")?;
+            score.missed.extend(index + 1..=questions.len());
+            break;
+        };
+
+        if right {
+            score.correct += 1;
+        } else {
+            score.missed.push(index + 1);
+        }
+        if let Some(explain) = &question.explain {
+            writeln!(out, "解析: {}", explain)?;
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(out, "得分: {}/{}", score.correct, score.total)?;
+    if !score.missed.is_empty() {
+        let missed: Vec<String> = score.missed.iter().map(usize::to_string).collect();
+        writeln!(out, "答错的题目: {}", missed.join(", "))?;
+    }
+    Ok(score)
+}
+
+/// 作答一道选择题；返回 None 表示输入已结束
+fn ask_choice(
+    options: &[(String, bool)],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<Option<bool>> {
+    for (index, (text, _)) in options.iter().enumerate() {
+        writeln!(out, "  {}. {}", letter(index), text)?;
+    }
+    let expected: Vec<usize> = (0..options.len()).filter(|&i| options[i].1).collect();
+    let hint = if expected.len() > 1 {
+        "（多选）"
+    } else {
+        ""
+    };
+
+    let picked = loop {
+        write!(out, "你的答案{}: ", hint)?;
+        out.flush()?;
+        let Some(line) = read_line(input)? else {
+            return Ok(None);
+        };
+        match parse_choice(&line, options.len()) {
+            Some(picked) => break picked,
+            None => writeln!(out, "请输入 A 到 {} 之间的字母", letter(options.len() - 1))?,
+        }
+    };
+
+    let right = picked == expected;
+    if right {
+        writeln!(out, "✓ 正确")?;
+    } else {
+        let letters: String = expected.iter().map(|&i| letter(i)).collect();
+        writeln!(out, "✗ 错误，正确答案是 {}", letters)?;
+    }
+    Ok(Some(right))
+}
+
+/// 作答一道输出题；返回 None 表示输入已结束
+fn ask_output(
+    answer: &[String],
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<Option<bool>> {
+    writeln!(out, "这段代码会打印什么？（共 {} 行）", answer.len())?;
+
+    let mut right = true;
+    for want in answer {
+        write!(out, "> ")?;
+        out.flush()?;
+        let Some(line) = read_line(input)? else {
+            return Ok(None);
+        };
+        right &= line.trim() == want;
+    }
+
+    if right {
+        writeln!(out, "✓ 正确")?;
+    } else {
+        writeln!(out, "✗ 错误，实际输出是:")?;
+        for want in answer {
+            writeln!(out, "  {}", want)?;
+        }
+    }
+    Ok(Some(right))
+}
@@ -0,0 +1,265 @@
+// ============================================
+// 关键字索引的扫描 (Scan)
+// ============================================
+// 从章节源码中找出关键字、类型、宏和生命周期出现的位置，
+// 并解析第 16 章末尾注释中的关键字分类总结。
+//
+// build.rs 在编译期用这里的函数生成 index 模块使用的索引表，章节改动后
+// 索引会随着重新编译自动更新。build.rs 通过 #[path] 引入这个模块和
+// outline.rs，所以这里只能依赖标准库和 outline。
+
+use crate::outline;
+
+/// 关键字的类别，对应第 16 章总结中的三个部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordKind {
+    /// 严格关键字
+    Strict,
+    /// 保留关键字
+    Reserved,
+    /// 弱关键字
+    Weak,
+}
+
+impl KeywordKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            KeywordKind::Strict => "严格关键字",
+            KeywordKind::Reserved => "保留关键字",
+            KeywordKind::Weak => "弱关键字",
+        }
+    }
+}
+
+/// 关键字分类总结中的一条
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogueEntry {
+    pub name: String,
+    pub kind: KeywordKind,
+    /// 严格关键字的分组，例如 "所有权和借用"；其他类别为空
+    pub category: String,
+    pub description: String,
+}
+
+/// 解析第 16 章 `/* ... */` 中的关键字分类总结
+///
+/// 只读取严格、保留和弱关键字三个部分中 `- 名称: 说明` 形式的行，
+/// `- false/true: ...` 这样的行拆成多条。
+pub fn catalogue(source: &str) -> Vec<CatalogueEntry> {
+    let mut entries = Vec::new();
+    let mut kind = None;
+    let mut category = String::new();
+
+    for line in source.lines().map(str::trim) {
+        if let Some(title) = line
+            .strip_prefix("==========")
+            .and_then(|rest| rest.strip_suffix("=========="))
+            .map(str::trim)
+        {
+            kind = if title.starts_with("严格关键字") {
+                Some(KeywordKind::Strict)
+            } else if title.starts_with("保留关键字") {
+                Some(KeywordKind::Reserved)
+            } else if title.starts_with("弱关键字") {
+                Some(KeywordKind::Weak)
+            } else {
+                None
+            };
+            category.clear();
+            continue;
+        }
+        let Some(kind) = kind else {
+            continue;
+        };
+
+        // "3. 所有权和借用（3个）：" 这样的分组标题
+        if let Some((number, title)) = line.split_once(". ")
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+        {
+            category = title.split('（').next().unwrap_or(title).to_string();
+            continue;
+        }
+
+        let Some((names, description)) = line
+            .strip_prefix("- ")
+            .and_then(|item| item.split_once(": "))
+        else {
+            continue;
+        };
+        for name in names.split('/') {
+            entries.push(CatalogueEntry {
+                name: name.trim().to_string(),
+                kind,
+                category: category.clone(),
+                description: description.trim().to_string(),
+            });
+        }
+    }
+    entries
+}
+
+// ========== 扫描代码 ==========
+
+/// 代码中的一个词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// 标识符、`name!` 形式的宏或 `'a` 形式的生命周期
+    pub text: String,
+    /// 所在的行（从 1 开始）
+    pub line: usize,
+}
+
+/// 找出源码中所有的标识符、宏名和生命周期，跳过注释、字符串和字符字面量
+pub fn tokens(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    let is_start = |c: char| c.is_alphabetic() || c == '_';
+    let is_continue = |c: char| c.is_alphanumeric() || c == '_';
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            // 块注释可以嵌套
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+            }
+        } else if c == 'r' && matches!(next, Some('"' | '#')) && !preceded_by_ident(&chars, i) {
+            // 原始字符串 r"..." 或 r#"..."#
+            let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+            if chars.get(i + 1 + hashes) != Some(&'"') {
+                // r#ident 原始标识符
+                i += 1 + hashes;
+                continue;
+            }
+            i += 2 + hashes;
+            while i < chars.len() {
+                if chars[i] == '"' && chars[i + 1..].iter().take(hashes).all(|&c| c == '#') {
+                    i += 1 + hashes;
+                    break;
+                }
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+        } else if c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                match chars[i] {
+                    '\\' => i += 1,
+                    '\n' => line += 1,
+                    _ => {}
+                }
+                i += 1;
+            }
+            i += 1;
+        } else if c == '\'' {
+            // 'x'、'\n' 是字符字面量，'a、'static 是生命周期或标签
+            if next == Some('\\') {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+            } else if chars.get(i + 2) == Some(&'\'') {
+                i += 3;
+            } else if next.is_some_and(is_start) {
+                let start = i;
+                i += 1;
+                while i < chars.len() && is_continue(chars[i]) {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    line,
+                });
+            } else {
+                i += 1;
+            }
+        } else if is_start(c) && !preceded_by_ident(&chars, i) {
+            let start = i;
+            while i < chars.len() && is_continue(chars[i]) {
+                i += 1;
+            }
+            let mut text: String = chars[start..i].iter().collect();
+            // 宏调用，但不包括 != 运算符
+            if chars.get(i) == Some(&'!') && chars.get(i + 1) != Some(&'=') {
+                text.push('!');
+            }
+            tokens.push(Token { text, line });
+        } else {
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// `chars[i]` 是否紧跟在数字或标识符后面，例如 `1u8` 中的 `u8`
+fn preceded_by_ident(chars: &[char], i: usize) -> bool {
+    i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_')
+}
+
+/// 一个词是否进入索引：关键字、生命周期、宏，以及大写开头的类型、Trait 和变体名
+pub fn is_indexed(text: &str, keywords: &[&str]) -> bool {
+    keywords.contains(&text)
+        || text.starts_with('\'')
+        || text.ends_with('!')
+        || text.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+// ========== 小节 ==========
+
+/// 每一行（从 1 开始编号，下标 0 不用）所在的小节，例如 "闭包 / 12. 闭包"
+///
+/// 编号段落之外的行只有小节名，文件开头的行为空字符串。
+pub fn section_labels(source: &str) -> Vec<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut labels = vec![String::new(); lines.len() + 1];
+
+    let mut group = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(title) = outline::group_title(line) {
+            group = title.to_string();
+        }
+        labels[index + 1] = group.clone();
+    }
+
+    for section in outline::sections(source) {
+        let title = format!("{}. {}", section.number, section.title);
+        for index in section.lines {
+            labels[index + 1] = match &section.group {
+                Some(group) => format!("{} / {}", group, title),
+                None => title.clone(),
+            };
+        }
+    }
+    labels
+}
@@ -0,0 +1,168 @@
+// ============================================
+// 章节输出快照 (Snapshots)
+// ============================================
+// 运行某一章并捕获它的标准输出，再把其中不确定的部分（指针地址、
+// HashMap/HashSet 的迭代顺序）规范化，得到可以签入仓库比较的文本
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crate::chapters::Chapter;
+use crate::i18n::Lang;
+use crate::toolchain;
+
+/// 一条规范化规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalize {
+    /// 把 `{:p}` 打印出的 `0x7ffc...` 地址替换为 `0x<ptr>`
+    Pointers,
+    /// 把一行中 `{a, b, c}` 形式的集合元素排序（HashSet / HashMap 的 `{:?}` 输出）
+    UnorderedSets,
+    /// 把连续出现、内容恰好是 `lines` 的几行按 `lines` 的顺序排列（遍历 HashMap 逐行打印）
+    ///
+    /// 按这几行本身而不是前面的提示文字定位，这样规则与输出语言无关。
+    UnorderedLines { lines: &'static [&'static str] },
+}
+
+/// 某一章需要的规范化规则；输出完全确定的章节返回空切片
+pub fn rules(chapter: &Chapter) -> &'static [Normalize] {
+    match chapter.slug {
+        "pattern_matching" | "keywords" => &[Normalize::Pointers],
+        "collections" => &[
+            Normalize::UnorderedSets,
+            Normalize::UnorderedLines {
+                lines: &["Blue: 10", "Yellow: 50"],
+            },
+        ],
+        _ => &[],
+    }
+}
+
+/// 依次应用规范化规则
+pub fn normalize(output: &str, rules: &[Normalize]) -> String {
+    let mut lines: Vec<String> = output.lines().map(String::from).collect();
+
+    for rule in rules {
+        match *rule {
+            Normalize::Pointers => {
+                for line in &mut lines {
+                    *line = replace_pointers(line);
+                }
+            }
+            Normalize::UnorderedSets => {
+                for line in &mut lines {
+                    *line = sort_sets(line);
+                }
+            }
+            Normalize::UnorderedLines { lines: wanted } => {
+                let mut sorted = wanted.to_vec();
+                sorted.sort_unstable();
+                let found = lines.windows(wanted.len()).position(|window| {
+                    let mut window: Vec<&str> = window.iter().map(String::as_str).collect();
+                    window.sort_unstable();
+                    window == sorted
+                });
+                if let Some(start) = found {
+                    for (line, want) in lines[start..].iter_mut().zip(wanted) {
+                        *line = want.to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    let mut normalized = lines.join("\n");
+    if output.ends_with('\n') {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+fn replace_pointers(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(index) = rest.find("0x") {
+        result.push_str(&rest[..index]);
+        let digits = rest[index + 2..]
+            .bytes()
+            .take_while(u8::is_ascii_hexdigit)
+            .count();
+        if digits == 0 {
+            result.push_str("0x");
+        } else {
+            result.push_str("0x<ptr>");
+        }
+        rest = &rest[index + 2 + digits..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn sort_sets(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    // 只处理不含嵌套的 {...}，例如 {3, 1, 2} 或 {"b": 1, "a": 2}
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open + 1..].find(['{', '}']).map(|i| open + 1 + i) else {
+            break;
+        };
+        if rest.as_bytes()[close] == b'{' {
+            result.push_str(&rest[..close]);
+            rest = &rest[close..];
+            continue;
+        }
+
+        let mut items: Vec<&str> = rest[open + 1..close].split(", ").collect();
+        items.sort_unstable();
+        result.push_str(&rest[..=open]);
+        result.push_str(&items.join(", "));
+        result.push('}');
+        rest = &rest[close + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+// ========== 捕获输出 ==========
+
+/// 用 `runner`（rust-tutorial 可执行文件）运行一章并返回它的标准输出
+///
+/// 每一章都在一个新建的临时目录中运行，这样第 11 章创建的 hello.txt
+/// 之类的文件不会影响当前目录，也不会影响下一次运行。
+/// 快照固定使用中文输出，不受运行测试的环境变量影响。
+pub fn capture(runner: &Path, chapter: &Chapter) -> io::Result<String> {
+    capture_in(runner, chapter, Lang::Zh)
+}
+
+/// 与 [`capture`] 相同，但使用 `lang` 指定的输出语言
+pub fn capture_in(runner: &Path, chapter: &Chapter, lang: Lang) -> io::Result<String> {
+    let dir = toolchain::scratch_dir("snapshot")?;
+    let output = Command::new(runner)
+        .args(["--lang", lang.code(), "run", chapter.slug])
+        .current_dir(&dir)
+        .output();
+    fs::remove_dir_all(&dir)?;
+    let output = output?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "运行 {} 失败 ({}):\n{}",
+            chapter.file_name(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// 运行一章并按该章的规则规范化输出
+pub fn capture_normalized(runner: &Path, chapter: &Chapter) -> io::Result<String> {
+    capture(runner, chapter).map(|output| normalize(&output, rules(chapter)))
+}
@@ -0,0 +1,756 @@
+// ============================================
+// 空间索引 (Spatial Index)
+// ============================================
+// 有了位置之后，最常见的问题是“哪些矩形在这里”和“哪些矩形撞在一起”。
+// 逐个检查所有矩形（暴力法）每次查询是 O(n)，找所有相交的矩形对是 O(n²)。
+// 这里有三种实现，都实现 SpatialIndex：
+//
+// - BruteForce：逐个检查，作为参考实现，测试用它验证另外两种
+// - UniformGrid：把平面切成固定大小的格子，矩形登记在它覆盖的每个格子里；
+//   覆盖的格子太多的矩形单独放一个列表，每次查询都检查
+// - QuadTree：覆盖整个 i32 坐标范围的四叉树，节点装满后分成四块，
+//   矩形放在能完整装下它的最深的节点里
+//
+// 约定和 geometry 模块一致：矩形是半开区间，只是边挨着不算相交，
+// 宽或高为 0 的矩形不会被任何查询找到。查询结果按 Id 排序，
+// 三种实现对同样的操作序列给出完全相同的结果。
+// Id 按插入顺序分配，删除之后不会复用。
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::geometry::{Point, Rectangle};
+
+/// 矩形在索引中的编号，由 insert 返回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(u32);
+
+impl Id {
+    /// 第几个插入的矩形，从 0 开始
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+pub trait SpatialIndex {
+    fn insert(&mut self, rectangle: Rectangle) -> Id;
+
+    /// 删除并返回矩形；Id 不存在或已经删除时返回 None
+    fn remove(&mut self, id: Id) -> Option<Rectangle>;
+
+    fn get(&self, id: Id) -> Option<Rectangle>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 包含这个点的矩形
+    fn query_point(&self, point: Point) -> Vec<Id>;
+
+    /// 和 region 相交的矩形
+    fn query_region(&self, region: &Rectangle) -> Vec<Id>;
+
+    /// 离 point 最近的矩形和距离的平方（点在矩形里时为 0）；
+    /// 距离是到矩形里最近的整点的距离，一样近时取 Id 最小的
+    fn nearest(&self, point: Point) -> Option<(Id, u128)>;
+
+    /// 所有相交的矩形对 (a, b)，a < b
+    fn overlapping_pairs(&self) -> Vec<(Id, Id)>;
+}
+
+// ========== 公共部分 ==========
+
+/// 按 Id 存放矩形；删除只留下空位
+#[derive(Debug, Clone, Default)]
+struct Slots {
+    rectangles: Vec<Option<Rectangle>>,
+    len: usize,
+}
+
+impl Slots {
+    fn insert(&mut self, rectangle: Rectangle) -> Id {
+        let id = Id(u32::try_from(self.rectangles.len()).expect("矩形超过 u32 个"));
+        self.rectangles.push(Some(rectangle));
+        self.len += 1;
+        id
+    }
+
+    fn remove(&mut self, id: Id) -> Option<Rectangle> {
+        let rectangle = self.rectangles.get_mut(id.index())?.take()?;
+        self.len -= 1;
+        Some(rectangle)
+    }
+
+    fn get(&self, id: Id) -> Option<Rectangle> {
+        self.rectangles.get(id.index()).copied().flatten()
+    }
+
+    /// 索引里登记的一定是没删除的矩形
+    fn live(&self, id: Id) -> Rectangle {
+        self.get(id).expect("索引中的矩形已经删除")
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (Id, Rectangle)> + '_ {
+        self.rectangles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rectangle)| Some((Id(i as u32), (*rectangle)?)))
+    }
+}
+
+/// 点到 [low, high) 的距离；区间不能为空
+fn axis_distance(p: i32, low: i64, high: i64) -> u128 {
+    let p = i64::from(p);
+    let distance = if p < low {
+        low - p
+    } else if p >= high {
+        p - high + 1
+    } else {
+        0
+    };
+    distance as u128
+}
+
+/// 点到非空矩形里最近的整点的距离的平方
+fn distance_squared(rectangle: &Rectangle, point: Point) -> u128 {
+    let dx = axis_distance(point.x, rectangle.left(), rectangle.right());
+    let dy = axis_distance(point.y, rectangle.top(), rectangle.bottom());
+    dx * dx + dy * dy
+}
+
+/// 记录目前最近的 (距离, Id)；元组比较保证一样近时 Id 小的优先
+fn closer(best: &mut Option<(u128, Id)>, candidate: (u128, Id)) {
+    if best.is_none_or(|best| candidate < best) {
+        *best = Some(candidate);
+    }
+}
+
+fn ordered(a: Id, b: Id) -> (Id, Id) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+// ========== 暴力法 ==========
+
+/// 参考实现：每次查询都检查所有矩形
+#[derive(Debug, Clone, Default)]
+pub struct BruteForce {
+    slots: Slots,
+}
+
+impl BruteForce {
+    pub fn new() -> BruteForce {
+        BruteForce::default()
+    }
+}
+
+impl SpatialIndex for BruteForce {
+    fn insert(&mut self, rectangle: Rectangle) -> Id {
+        self.slots.insert(rectangle)
+    }
+
+    fn remove(&mut self, id: Id) -> Option<Rectangle> {
+        self.slots.remove(id)
+    }
+
+    fn get(&self, id: Id) -> Option<Rectangle> {
+        self.slots.get(id)
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len
+    }
+
+    fn query_point(&self, point: Point) -> Vec<Id> {
+        self.slots
+            .iter()
+            .filter(|(_, rectangle)| rectangle.contains_point(point))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn query_region(&self, region: &Rectangle) -> Vec<Id> {
+        self.slots
+            .iter()
+            .filter(|(_, rectangle)| rectangle.intersects(region))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn nearest(&self, point: Point) -> Option<(Id, u128)> {
+        let mut best = None;
+        for (id, rectangle) in self.slots.iter() {
+            if !rectangle.is_empty() {
+                closer(&mut best, (distance_squared(&rectangle, point), id));
+            }
+        }
+        best.map(|(distance, id)| (id, distance))
+    }
+
+    fn overlapping_pairs(&self) -> Vec<(Id, Id)> {
+        let live: Vec<(Id, Rectangle)> = self.slots.iter().collect();
+        let mut pairs = Vec::new();
+        for (i, (a, ra)) in live.iter().enumerate() {
+            for (b, rb) in &live[i + 1..] {
+                if ra.intersects(rb) {
+                    pairs.push((*a, *b));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+// ========== 均匀网格 ==========
+
+/// 一个矩形最多登记在这么多格子里，再多就放进 oversized
+const MAX_CELLS_PER_RECTANGLE: i64 = 64;
+
+/// 格子坐标的范围，两端都包含
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cells {
+    x0: i64,
+    y0: i64,
+    x1: i64,
+    y1: i64,
+}
+
+impl Cells {
+    /// 格子数；覆盖整个 i32 范围时会超过 i64，饱和到 i64::MAX
+    fn count(&self) -> i64 {
+        (self.x1 - self.x0 + 1).saturating_mul(self.y1 - self.y0 + 1)
+    }
+
+    fn contains(&self, (x, y): (i64, i64)) -> bool {
+        self.x0 <= x && x <= self.x1 && self.y0 <= y && y <= self.y1
+    }
+
+    fn union(&self, other: &Cells) -> Cells {
+        Cells {
+            x0: self.x0.min(other.x0),
+            y0: self.y0.min(other.y0),
+            x1: self.x1.max(other.x1),
+            y1: self.y1.max(other.y1),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (i64, i64)> {
+        let Cells { x0, y0, x1, y1 } = *self;
+        (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| (x, y)))
+    }
+}
+
+/// 边长为 cell_size 的正方形格子；只保存有矩形的格子
+#[derive(Debug, Clone)]
+pub struct UniformGrid {
+    slots: Slots,
+    cell_size: u32,
+    cells: HashMap<(i64, i64), Vec<Id>>,
+    /// 覆盖的格子超过 MAX_CELLS_PER_RECTANGLE 的矩形
+    oversized: Vec<Id>,
+    /// 登记过矩形的格子的范围，删除时不缩小；限制最近邻搜索的圈数
+    extent: Option<Cells>,
+}
+
+impl UniformGrid {
+    /// cell_size 取矩形常见的大小效果最好
+    pub fn new(cell_size: u32) -> UniformGrid {
+        assert!(cell_size > 0, "cell_size 必须大于 0");
+        UniformGrid {
+            slots: Slots::default(),
+            cell_size,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            extent: None,
+        }
+    }
+
+    fn cell(&self, v: i64) -> i64 {
+        v.div_euclid(i64::from(self.cell_size))
+    }
+
+    /// 非空矩形覆盖的格子
+    fn cells_of(&self, rectangle: &Rectangle) -> Cells {
+        Cells {
+            x0: self.cell(rectangle.left()),
+            y0: self.cell(rectangle.top()),
+            x1: self.cell(rectangle.right() - 1),
+            y1: self.cell(rectangle.bottom() - 1),
+        }
+    }
+
+    /// 第 k 圈的格子离 point 至少多远（距离的平方）
+    fn ring_bound(&self, k: i64) -> u128 {
+        if k == 0 {
+            return 0;
+        }
+        let distance = (k as u128 - 1) * u128::from(self.cell_size) + 1;
+        distance * distance
+    }
+}
+
+/// 以 (cx, cy) 为中心、切比雪夫距离为 k 的一圈格子
+fn ring(cx: i64, cy: i64, k: i64) -> impl Iterator<Item = (i64, i64)> {
+    let rows = (cx - k..=cx + k).flat_map(move |x| {
+        let bottom = (k > 0).then_some((x, cy + k));
+        std::iter::once((x, cy - k)).chain(bottom)
+    });
+    let columns = (cy - k + 1..cy + k).flat_map(move |y| [(cx - k, y), (cx + k, y)]);
+    rows.chain(columns)
+}
+
+impl SpatialIndex for UniformGrid {
+    fn insert(&mut self, rectangle: Rectangle) -> Id {
+        let id = self.slots.insert(rectangle);
+        if rectangle.is_empty() {
+            return id;
+        }
+        let cells = self.cells_of(&rectangle);
+        if cells.count() > MAX_CELLS_PER_RECTANGLE {
+            self.oversized.push(id);
+            return id;
+        }
+        for cell in cells.iter() {
+            self.cells.entry(cell).or_default().push(id);
+        }
+        self.extent = Some(match self.extent {
+            Some(extent) => extent.union(&cells),
+            None => cells,
+        });
+        id
+    }
+
+    fn remove(&mut self, id: Id) -> Option<Rectangle> {
+        let rectangle = self.slots.remove(id)?;
+        if rectangle.is_empty() {
+            return Some(rectangle);
+        }
+        let cells = self.cells_of(&rectangle);
+        if cells.count() > MAX_CELLS_PER_RECTANGLE {
+            self.oversized.retain(|&other| other != id);
+            return Some(rectangle);
+        }
+        for cell in cells.iter() {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|&other| other != id);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some(rectangle)
+    }
+
+    fn get(&self, id: Id) -> Option<Rectangle> {
+        self.slots.get(id)
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len
+    }
+
+    fn query_point(&self, point: Point) -> Vec<Id> {
+        let cell = (self.cell(i64::from(point.x)), self.cell(i64::from(point.y)));
+        let in_cell = self.cells.get(&cell).into_iter().flatten();
+        let mut found: Vec<Id> = in_cell
+            .chain(&self.oversized)
+            .copied()
+            .filter(|&id| self.slots.live(id).contains_point(point))
+            .collect();
+        found.sort();
+        found
+    }
+
+    fn query_region(&self, region: &Rectangle) -> Vec<Id> {
+        if region.is_empty() {
+            return Vec::new();
+        }
+        let cells = self.cells_of(region);
+        // 区域比所有有矩形的格子还多时，直接遍历这些格子
+        let candidates: Vec<Id> = if cells.count() > self.cells.len() as i64 {
+            self.cells
+                .iter()
+                .filter(|(cell, _)| cells.contains(**cell))
+                .flat_map(|(_, ids)| ids)
+                .copied()
+                .collect()
+        } else {
+            cells
+                .iter()
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .copied()
+                .collect()
+        };
+        let mut found: Vec<Id> = candidates
+            .into_iter()
+            .chain(self.oversized.iter().copied())
+            .filter(|&id| self.slots.live(id).intersects(region))
+            .collect();
+        // 跨格子的矩形会被找到多次
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    fn nearest(&self, point: Point) -> Option<(Id, u128)> {
+        let mut best = None;
+        let consider = |best: &mut Option<(u128, Id)>, id: Id| {
+            let rectangle = self.slots.live(id);
+            closer(best, (distance_squared(&rectangle, point), id));
+        };
+        for &id in &self.oversized {
+            consider(&mut best, id);
+        }
+        if let Some(extent) = self.extent {
+            let (cx, cy) = (self.cell(i64::from(point.x)), self.cell(i64::from(point.y)));
+            // 最多搜索到把 extent 整个包进来的那一圈
+            let rings = [
+                cx - extent.x0,
+                extent.x1 - cx,
+                cy - extent.y0,
+                extent.y1 - cy,
+            ]
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            .max(0);
+            let searched = (2 * rings + 1).saturating_mul(2 * rings + 1);
+            if searched > 4 * self.cells.len() as i64 {
+                // 点离所有格子都很远：一圈圈找要走过大量空格子，不如全部检查
+                self.cells
+                    .values()
+                    .flatten()
+                    .for_each(|&id| consider(&mut best, id));
+            } else {
+                for k in 0..=rings {
+                    if best.is_some_and(|(distance, _)| self.ring_bound(k) > distance) {
+                        break;
+                    }
+                    for cell in ring(cx, cy, k) {
+                        self.cells
+                            .get(&cell)
+                            .into_iter()
+                            .flatten()
+                            .for_each(|&id| consider(&mut best, id));
+                    }
+                }
+            }
+        }
+        best.map(|(distance, id)| (id, distance))
+    }
+
+    fn overlapping_pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs = Vec::new();
+        for ids in self.cells.values() {
+            for (i, &a) in ids.iter().enumerate() {
+                let ra = self.slots.live(a);
+                for &b in &ids[i + 1..] {
+                    if ra.intersects(&self.slots.live(b)) {
+                        pairs.push(ordered(a, b));
+                    }
+                }
+            }
+        }
+        for &a in &self.oversized {
+            let ra = self.slots.live(a);
+            for (b, rb) in self.slots.iter() {
+                // 两个都在 oversized 里的只算一次
+                let counted = self.oversized.contains(&b) && b <= a;
+                if b != a && !counted && ra.intersects(&rb) {
+                    pairs.push(ordered(a, b));
+                }
+            }
+        }
+        // 两个矩形共同覆盖的每个格子里都会找到它们一次
+        pairs.sort();
+        pairs.dedup();
+        pairs
+    }
+}
+
+// ========== 四叉树 ==========
+
+/// 节点里的矩形超过这个数就分裂
+const NODE_CAPACITY: usize = 8;
+
+/// 四叉树节点覆盖的正方形 [left, left + size) × [top, top + size)
+#[derive(Debug, Clone)]
+struct Node {
+    left: i64,
+    top: i64,
+    size: i64,
+    items: Vec<Id>,
+    /// 四个子节点在 nodes 中连续存放，这是第一个的下标；
+    /// 顺序是左上、右上、左下、右下
+    children: Option<usize>,
+}
+
+impl Node {
+    fn new(left: i64, top: i64, size: i64) -> Node {
+        Node {
+            left,
+            top,
+            size,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// 能完整装下 rectangle 的子节点是第几个
+    fn quadrant(&self, rectangle: &Rectangle) -> Option<usize> {
+        let mid_x = self.left + self.size / 2;
+        let mid_y = self.top + self.size / 2;
+        let column = if rectangle.right() <= mid_x {
+            0
+        } else if rectangle.left() >= mid_x {
+            1
+        } else {
+            return None;
+        };
+        let row = if rectangle.bottom() <= mid_y {
+            0
+        } else if rectangle.top() >= mid_y {
+            2
+        } else {
+            return None;
+        };
+        Some(row + column)
+    }
+
+    fn point_quadrant(&self, point: Point) -> usize {
+        let mid_x = self.left + self.size / 2;
+        let mid_y = self.top + self.size / 2;
+        let column = usize::from(i64::from(point.x) >= mid_x);
+        let row = usize::from(i64::from(point.y) >= mid_y);
+        2 * row + column
+    }
+
+    fn intersects(&self, region: &Rectangle) -> bool {
+        region.left() < self.left + self.size
+            && self.left < region.right()
+            && region.top() < self.top + self.size
+            && self.top < region.bottom()
+    }
+
+    fn distance_squared(&self, point: Point) -> u128 {
+        let dx = axis_distance(point.x, self.left, self.left + self.size);
+        let dy = axis_distance(point.y, self.top, self.top + self.size);
+        dx * dx + dy * dy
+    }
+}
+
+/// 根节点覆盖整个 i32 坐标范围，不需要随着矩形扩大。
+///
+/// 矩形的左上角总在 i32 范围内，右边和下边却可以伸出去，例如
+/// `Rectangle::new(i32::MAX - 1, 0, u32::MAX, 2)`。插入时只比较子节点的中线，
+/// 这样的矩形会一路分到那一侧最深的节点，伸出节点的部分都在 i32 范围之外。
+/// 查询不受影响：矩形和查询区域（或点）重叠的部分总包含一个 i32 范围内的点，
+/// 它落在存放矩形的节点里，所以节点的范围仍然可以用来剪枝，到点的距离也仍是下界
+#[derive(Debug, Clone)]
+pub struct QuadTree {
+    slots: Slots,
+    nodes: Vec<Node>,
+    /// 每个 Id 所在的节点；空矩形和已删除的是 None
+    location: Vec<Option<usize>>,
+}
+
+impl Default for QuadTree {
+    fn default() -> QuadTree {
+        QuadTree::new()
+    }
+}
+
+impl QuadTree {
+    pub fn new() -> QuadTree {
+        let size = 1i64 << 32;
+        QuadTree {
+            slots: Slots::default(),
+            nodes: vec![Node::new(i64::from(i32::MIN), i64::from(i32::MIN), size)],
+            location: Vec::new(),
+        }
+    }
+
+    /// 节点数，包括空的节点；删除矩形不会合并节点
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 树的深度，只有根节点时为 1
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut stack = vec![(0, 1)];
+        while let Some((node, level)) = stack.pop() {
+            depth = depth.max(level);
+            if let Some(first) = self.nodes[node].children {
+                stack.extend((first..first + 4).map(|child| (child, level + 1)));
+            }
+        }
+        depth
+    }
+
+    /// 节点太满时分成四块，把能完整放进子节点的矩形移下去
+    fn split(&mut self, node: usize) {
+        let Node {
+            left, top, size, ..
+        } = self.nodes[node];
+        if self.nodes[node].children.is_some()
+            || self.nodes[node].items.len() <= NODE_CAPACITY
+            || size < 2
+        {
+            return;
+        }
+        let first = self.nodes.len();
+        let half = size / 2;
+        for (dy, dx) in [(0, 0), (0, half), (half, 0), (half, half)] {
+            self.nodes.push(Node::new(left + dx, top + dy, half));
+        }
+        self.nodes[node].children = Some(first);
+
+        let items = std::mem::take(&mut self.nodes[node].items);
+        for id in items {
+            let target = match self.nodes[node].quadrant(&self.slots.live(id)) {
+                Some(quadrant) => first + quadrant,
+                None => node,
+            };
+            self.nodes[target].items.push(id);
+            self.location[id.index()] = Some(target);
+        }
+        for child in first..first + 4 {
+            self.split(child);
+        }
+    }
+
+    fn pairs_below(&self, node: usize, ancestors: &mut Vec<Id>, pairs: &mut Vec<(Id, Id)>) {
+        let items = &self.nodes[node].items;
+        for (i, &a) in items.iter().enumerate() {
+            let ra = self.slots.live(a);
+            for &b in items[i + 1..].iter().chain(ancestors.iter()) {
+                if ra.intersects(&self.slots.live(b)) {
+                    pairs.push(ordered(a, b));
+                }
+            }
+        }
+        if let Some(first) = self.nodes[node].children {
+            let depth = ancestors.len();
+            ancestors.extend(items);
+            for child in first..first + 4 {
+                self.pairs_below(child, ancestors, pairs);
+            }
+            ancestors.truncate(depth);
+        }
+    }
+}
+
+impl SpatialIndex for QuadTree {
+    fn insert(&mut self, rectangle: Rectangle) -> Id {
+        let id = self.slots.insert(rectangle);
+        self.location.push(None);
+        if rectangle.is_empty() {
+            return id;
+        }
+        let mut node = 0;
+        while let Some(first) = self.nodes[node].children {
+            match self.nodes[node].quadrant(&rectangle) {
+                Some(quadrant) => node = first + quadrant,
+                None => break,
+            }
+        }
+        self.nodes[node].items.push(id);
+        self.location[id.index()] = Some(node);
+        self.split(node);
+        id
+    }
+
+    fn remove(&mut self, id: Id) -> Option<Rectangle> {
+        let rectangle = self.slots.remove(id)?;
+        if let Some(node) = self.location[id.index()].take() {
+            self.nodes[node].items.retain(|&other| other != id);
+        }
+        Some(rectangle)
+    }
+
+    fn get(&self, id: Id) -> Option<Rectangle> {
+        self.slots.get(id)
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len
+    }
+
+    fn query_point(&self, point: Point) -> Vec<Id> {
+        let mut found = Vec::new();
+        let mut node = 0;
+        loop {
+            let current = &self.nodes[node];
+            found.extend(
+                current
+                    .items
+                    .iter()
+                    .filter(|&&id| self.slots.live(id).contains_point(point)),
+            );
+            match current.children {
+                Some(first) => node = first + current.point_quadrant(point),
+                None => break,
+            }
+        }
+        found.sort();
+        found
+    }
+
+    fn query_region(&self, region: &Rectangle) -> Vec<Id> {
+        if region.is_empty() {
+            return Vec::new();
+        }
+        let mut found = Vec::new();
+        // 只展开和 region 相交的节点；伸出节点的矩形和 region 的重叠部分
+        // 总有一个 i32 范围内的点落在节点里，不会因此漏掉
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let current = &self.nodes[node];
+            found.extend(
+                current
+                    .items
+                    .iter()
+                    .filter(|&&id| self.slots.live(id).intersects(region)),
+            );
+            if let Some(first) = current.children {
+                stack.extend(
+                    (first..first + 4).filter(|&child| self.nodes[child].intersects(region)),
+                );
+            }
+        }
+        found.sort();
+        found
+    }
+
+    fn nearest(&self, point: Point) -> Option<(Id, u128)> {
+        let mut best = None;
+        // 按节点到点的距离从近到远展开；节点的距离是里面矩形距离的下界
+        let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+        while let Some(Reverse((bound, node))) = heap.pop() {
+            if best.is_some_and(|(distance, _)| bound > distance) {
+                break;
+            }
+            let current = &self.nodes[node];
+            for &id in &current.items {
+                let rectangle = self.slots.live(id);
+                closer(&mut best, (distance_squared(&rectangle, point), id));
+            }
+            if let Some(first) = current.children {
+                for child in first..first + 4 {
+                    heap.push(Reverse((self.nodes[child].distance_squared(point), child)));
+                }
+            }
+        }
+        best.map(|(distance, id)| (id, distance))
+    }
+
+    fn overlapping_pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs = Vec::new();
+        self.pairs_below(0, &mut Vec::new(), &mut pairs);
+        pairs.sort();
+        pairs
+    }
+}
@@ -0,0 +1,426 @@
+// ============================================
+// 零拷贝分词器 (Tokenizer)
+// ============================================
+// 第 14 章的 first_word 逐字节找 b' '，返回 &s[0..i]；第 05 章用写死的字节范围
+// 切出 &s[0..5] 和 &s[6..11]。这里把它们推广成一个分词器：
+//
+//     let mut tokens = Tokenizer::new("let x = \"hi\\n\"; // 3.14");
+//     tokens.next()  →  Word "let" 0..3
+//
+// - 每个记号的 text 都是借用原字符串的 &str，和 first_word 的返回值一样，
+//   生命周期与输入相同；分词过程中不分配内存
+// - 单词（字母、数字、下划线）、数字（1_000、3.14）、字符串字面量（支持转义）、
+//   其他字符各自成为一个标点记号
+// - 空白按 char::is_whitespace 判断，包括全角空格 U+3000 和不换行空格 U+00A0
+// - 记号的边界总是落在字符边界上：多字节字符不会被切开，
+//   组合标记（例如 e + U+0301）跟在前一个字符后面，不会单独成为记号
+//
+// LineMap 把字节偏移换算成行号和列号（按字符计），用来报告错误位置。
+
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
+use crate::unicode;
+
+// ========== 位置 ==========
+
+/// 字节范围 start..end，总是落在字符边界上
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// 从原字符串中取出这一段，等价于 &text[start..end]
+    pub fn slice<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.start..self.end]
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Range<usize> {
+        span.start..span.end
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// 行号和列号，都从 1 开始；列号按字符计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// 字节偏移到行列的换算表：记下每一行开头的偏移，查找时二分
+#[derive(Debug, Clone)]
+pub struct LineMap<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> LineMap<'a> {
+    pub fn new(text: &'a str) -> LineMap<'a> {
+        let starts = [0]
+            .into_iter()
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineMap { text, starts }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// 偏移所在的行和列；offset 可以等于 text.len()（指向末尾）
+    ///
+    /// 偏移不在字符边界上时 panic，和 &text[..offset] 一样
+    pub fn locate(&self, offset: usize) -> Location {
+        assert!(
+            self.text.is_char_boundary(offset),
+            "byte index {} is not a char boundary",
+            offset
+        );
+        let line = self.starts.partition_point(|&start| start <= offset);
+        let start = self.starts[line - 1];
+        Location {
+            line,
+            column: self.text[start..offset].chars().count() + 1,
+        }
+    }
+
+    /// 第 line 行的内容（不含换行符）
+    pub fn line(&self, line: usize) -> &'a str {
+        let start = self.starts[line - 1];
+        let end = self
+            .starts
+            .get(line)
+            .map_or(self.text.len(), |&next| next - 1);
+        self.text[start..end].trim_end_matches('\r')
+    }
+
+    /// 标出一段代码：
+    ///
+    /// ```text
+    /// 第 2 行第 9 列: 未结束的字符串
+    ///   let s = "hello
+    ///           ^^^^^^
+    /// ```
+    ///
+    /// 跨行的范围只标到第一行末尾；宽字符按终端列数对齐
+    pub fn render(&self, span: Span, message: &str) -> String {
+        let at = self.locate(span.start);
+        let code = self.line(at.line);
+        let line_start = self.starts[at.line - 1];
+        let before = &self.text[line_start..span.start];
+        let end = span.end.min(line_start + code.len());
+        let marked = unicode::width(&self.text[span.start..end]).max(1);
+        format!(
+            "第 {} 行第 {} 列: {}\n  {}\n  {}{}\n",
+            at.line,
+            at.column,
+            message,
+            code,
+            " ".repeat(unicode::width(before)),
+            "^".repeat(marked)
+        )
+    }
+}
+
+// ========== 记号 ==========
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// 以字母或下划线开头，由字母、数字和下划线组成
+    Word,
+    /// 以 ASCII 数字开头：42、1_000、3.14
+    Number,
+    /// 带引号的字符串字面量，text 包括两边的引号
+    Str,
+    /// 其他任意一个字符
+    Punct,
+}
+
+/// 一个记号，text 借用自被分词的字符串
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+impl<'a> Token<'a> {
+    /// 字符串字面量去掉引号后的内容，转义序列原样保留
+    pub fn unquoted(&self) -> &'a str {
+        match self.kind {
+            Kind::Str => &self.text[1..self.text.len() - 1],
+            _ => self.text,
+        }
+    }
+
+    /// 处理转义之后的内容；没有转义时直接借用，不分配内存
+    pub fn unescape(&self) -> Cow<'a, str> {
+        let inner = self.unquoted();
+        if self.kind != Kind::Str || !inner.contains('\\') {
+            return Cow::Borrowed(inner);
+        }
+        let mut out = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            // 分词时已经检查过转义，这里不会失败
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('0') => out.push('\0'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                    out.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+                }
+                Some(other) => out.push(other),
+                None => {}
+            }
+        }
+        Cow::Owned(out)
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            Kind::Word => "单词",
+            Kind::Number => "数字",
+            Kind::Str => "字符串",
+            Kind::Punct => "标点",
+        };
+        write!(f, "{} {:?} {}", kind, self.text, self.span)
+    }
+}
+
+// ========== 错误 ==========
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// 到结尾都没有遇到右引号
+    UnterminatedString(Span),
+    /// 不认识的转义，例如 \q；span 是反斜杠和后面的字符
+    UnknownEscape(Span),
+    /// \u 后面不是 {1 到 6 个十六进制数字}，或者不是合法的 Unicode 标量值；
+    /// 缺少 } 时 span 只到最后一个十六进制数字
+    InvalidUnicodeEscape(Span),
+}
+
+impl TokenError {
+    pub fn span(&self) -> Span {
+        match *self {
+            TokenError::UnterminatedString(span)
+            | TokenError::UnknownEscape(span)
+            | TokenError::InvalidUnicodeEscape(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenError::UnterminatedString(_) => write!(f, "未结束的字符串"),
+            TokenError::UnknownEscape(_) => write!(f, "未知的转义序列"),
+            TokenError::InvalidUnicodeEscape(_) => write!(f, "无效的 Unicode 转义"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+// ========== 分词 ==========
+
+/// 逐个产生记号的迭代器；遇到错误时产生 Err，然后从错误之后继续
+#[derive(Debug, Clone)]
+pub struct Tokenizer<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(text: &'a str) -> Tokenizer<'a> {
+        Tokenizer { text, pos: 0 }
+    }
+
+    /// 剩下还没分词的部分
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// 吃掉满足条件的字符，以及跟在它们后面的组合标记
+    fn eat_while(&mut self, pred: impl Fn(char) -> bool) {
+        while let Some(c) = self.peek() {
+            if !pred(c) && !is_mark(c) {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// 读一个字符串字面量，pos 在左引号之后
+    fn string(&mut self, start: usize) -> Result<(), TokenError> {
+        let mut error = None;
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return error.map_or(Ok(()), Err);
+                }
+                '\\' => {
+                    let at = self.pos + i;
+                    let next = chars.next();
+                    let end = next.map_or(at + 1, |(j, c)| self.pos + j + c.len_utf8());
+                    let valid = match next.map(|(_, c)| c) {
+                        Some('n' | 't' | 'r' | '0' | '\\' | '"' | '\'') => true,
+                        Some('u') => {
+                            // {} 里是 1 到 6 个十六进制数字；遇到第一个别的字符就停下，
+                            // 缺少 } 时不再往后找，后面的右引号照样结束字符串
+                            let mut end = end;
+                            let mut digits = 0;
+                            let mut value = 0;
+                            let braced = chars.clone().next().is_some_and(|(_, c)| c == '{');
+                            if braced {
+                                chars.next();
+                                end += 1;
+                                while digits < 6 {
+                                    let Some(digit) =
+                                        chars.clone().next().and_then(|(_, c)| c.to_digit(16))
+                                    else {
+                                        break;
+                                    };
+                                    chars.next();
+                                    end += 1;
+                                    digits += 1;
+                                    value = value * 16 + digit;
+                                }
+                            }
+                            let closed =
+                                braced && chars.clone().next().is_some_and(|(_, c)| c == '}');
+                            if closed {
+                                chars.next();
+                                end += 1;
+                            }
+                            let scalar = (closed && digits > 0)
+                                .then(|| char::from_u32(value))
+                                .flatten();
+                            if scalar.is_none() {
+                                error.get_or_insert(TokenError::InvalidUnicodeEscape(Span::new(
+                                    at, end,
+                                )));
+                            }
+                            continue;
+                        }
+                        _ => false,
+                    };
+                    if !valid {
+                        error.get_or_insert(TokenError::UnknownEscape(Span::new(at, end)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.pos = self.text.len();
+        Err(TokenError::UnterminatedString(Span::new(start, self.pos)))
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token<'a>, TokenError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.eat_while(char::is_whitespace);
+        let start = self.pos;
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+
+        let kind = if c.is_alphabetic() || c == '_' {
+            self.eat_while(|c| c.is_alphanumeric() || c == '_');
+            Kind::Word
+        } else if c.is_ascii_digit() {
+            self.eat_while(|c| c.is_ascii_digit() || c == '_');
+            // 小数点后面必须是数字，1..5 中的 .. 不是小数
+            let bytes = self.rest().as_bytes();
+            if bytes.first() == Some(&b'.') && bytes.get(1).is_some_and(u8::is_ascii_digit) {
+                self.pos += 1;
+                self.eat_while(|c| c.is_ascii_digit() || c == '_');
+            }
+            Kind::Number
+        } else if c == '"' {
+            if let Err(error) = self.string(start) {
+                return Some(Err(error));
+            }
+            Kind::Str
+        } else {
+            // 标点后面的组合标记也算在这个记号里
+            self.eat_while(|_| false);
+            Kind::Punct
+        };
+
+        let span = Span::new(start, self.pos);
+        Some(Ok(Token {
+            kind,
+            text: span.slice(self.text),
+            span,
+        }))
+    }
+}
+
+fn is_mark(c: char) -> bool {
+    !c.is_ascii() && unicode::category(c).is_mark()
+}
+
+/// 分词，遇到第一个错误就停止
+pub fn tokenize(text: &str) -> Result<Vec<Token<'_>>, TokenError> {
+    Tokenizer::new(text).collect()
+}
+
+/// 所有的单词和数字，跳过标点、字符串和错误
+pub fn words(text: &str) -> impl Iterator<Item = &str> {
+    Tokenizer::new(text)
+        .filter_map(Result::ok)
+        .filter(|token| matches!(token.kind, Kind::Word | Kind::Number))
+        .map(|token| token.text)
+}
+
+/// 第 14 章 first_word 的推广：第一个单词，没有时返回空字符串。
+/// 和按空格切分不同，"hello, world" 的第一个单词是 "hello" 而不是 "hello,"
+pub fn first_word(text: &str) -> &str {
+    words(text).next().unwrap_or(&text[text.len()..])
+}
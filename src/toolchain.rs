@@ -0,0 +1,163 @@
+// ============================================
+// 调用本地工具链 (Toolchain)
+// ============================================
+// 把一章的源码（可能经过修改）单独交给本地 rustc 编译，
+// 编译失败示例、练习模式和书籍生成都依赖这里的函数
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::i18n::{self, Lang};
+
+// 编译产物使用的 crate 名称
+const CRATE_NAME: &str = "tutorial";
+
+/// 本地 rustc 的路径，可以用 RUSTC 环境变量覆盖
+pub fn rustc() -> PathBuf {
+    env::var_os("RUSTC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("rustc"))
+}
+
+/// 章节通过 `crate::` 使用的库模块；它们只依赖标准库，可以原样复制
+const SUPPORT_MODULES: [(&str, &str); 3] = [
+    ("arithmetic", include_str!("arithmetic.rs")),
+    ("geometry", include_str!("geometry.rs")),
+    ("interner", include_str!("interner.rs")),
+];
+
+/// 在 `dir` 中生成一个 crate 根文件，把 `file` 作为 `chapters::<slug>` 模块引入，
+/// 返回根文件的路径
+///
+/// 这与章节在 rust-tutorial 中的模块路径保持一致，`crate::chapters::...`
+/// 形式的绝对路径也能正常解析，而 rustc 报告的行号仍然指向 `file` 本身。
+/// 章节使用的 t! 宏、消息目录和 [`SUPPORT_MODULES`] 也会一起写入 `dir`，
+/// 所以不依赖仓库中的源码。
+/// `with_main` 为 true 时追加一个调用章节 main 的 main 函数，得到可以运行的程序。
+pub fn chapter_crate(dir: &Path, slug: &str, file: &Path, with_main: bool) -> io::Result<PathBuf> {
+    // 保持 src/i18n.rs 与 locales/ 的相对位置，i18n.rs 中的 include_str! 才能找到消息目录
+    let i18n = dir.join("src").join("i18n.rs");
+    fs::create_dir_all(dir.join("src"))?;
+    fs::create_dir_all(dir.join("locales"))?;
+    fs::write(&i18n, include_str!("i18n.rs"))?;
+    for lang in Lang::ALL {
+        fs::write(
+            dir.join("locales").join(format!("{}.txt", lang.code())),
+            lang.catalog(),
+        )?;
+    }
+
+    let mut root = format!(
+        "#![allow(unused)]\n#[macro_use]\n#[path = {:?}]\nmod i18n;\n",
+        i18n.display().to_string()
+    );
+    for (name, source) in SUPPORT_MODULES {
+        let path = dir.join("src").join(format!("{}.rs", name));
+        fs::write(&path, source)?;
+        root.push_str(&format!(
+            "#[path = {:?}]\npub mod {};\n",
+            path.display().to_string(),
+            name
+        ));
+    }
+    root.push_str(&format!(
+        "pub mod chapters {{\n    #[path = {:?}]\n    pub mod {};\n}}\n",
+        file.display().to_string(),
+        slug
+    ));
+    let name = if with_main {
+        root.push_str(&format!(
+            "fn main() {{\n    chapters::{}::main();\n}}\n",
+            slug
+        ));
+        "main.rs"
+    } else {
+        "lib.rs"
+    };
+
+    let path = dir.join(name);
+    fs::write(&path, root)?;
+    Ok(path)
+}
+
+/// [`run_chapter`] 失败的原因，附带 rustc 或程序的标准错误输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Compile(String),
+    Run(String),
+}
+
+/// 把 `file` 作为章节 `slug` 的模块编译成可执行文件，在临时目录中运行并返回标准输出
+///
+/// 单独编译的章节从环境变量读取语言，这里传入当前选择的语言，
+/// 所以输出与 `rust-tutorial run` 一致。
+pub fn run_chapter(slug: &str, file: &Path) -> io::Result<Result<String, Failure>> {
+    let dir = scratch_dir("chapter")?;
+    let result = (|| {
+        let root = chapter_crate(&dir, slug, file, true)?;
+        let output = compile(&root, "bin", &dir)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+            return Ok(Err(Failure::Compile(stderr)));
+        }
+
+        let run_dir = dir.join("run");
+        fs::create_dir(&run_dir)?;
+        let output = Command::new(binary_path(&dir))
+            .env("LC_ALL", i18n::lang().code())
+            .current_dir(&run_dir)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+            return Ok(Err(Failure::Run(stderr)));
+        }
+        Ok(Ok(String::from_utf8_lossy(&output.stdout).into_owned()))
+    })();
+    fs::remove_dir_all(&dir)?;
+    result
+}
+
+/// 用 rustc 编译一个 crate 根文件
+///
+/// `crate_type` 为 "lib" 时只做检查（`--emit metadata`），为 "bin" 时生成
+/// 可执行文件 [`binary_path`]；产物都放在 `out_dir` 中。
+pub fn compile(root: &Path, crate_type: &str, out_dir: &Path) -> io::Result<Output> {
+    let mut command = Command::new(rustc());
+    command
+        .args(["--edition", "2024", "--crate-type", crate_type])
+        .args(["--crate-name", CRATE_NAME, "--error-format", "short"])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(root);
+    if crate_type == "lib" {
+        command.args(["--emit", "metadata"]);
+    }
+    command.output()
+}
+
+/// `compile(.., "bin", out_dir)` 生成的可执行文件路径
+pub fn binary_path(out_dir: &Path) -> PathBuf {
+    out_dir.join(format!("{}{}", CRATE_NAME, env::consts::EXE_SUFFIX))
+}
+
+/// 在系统临时目录中创建一个本进程独占的空目录
+pub fn scratch_dir(label: &str) -> io::Result<PathBuf> {
+    // 同一进程中的多个线程可能同时需要临时目录，用计数器区分
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = env::temp_dir().join(format!(
+        "rust-tutorial-{}-{}-{}",
+        label,
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
@@ -0,0 +1,724 @@
+// ============================================
+// Unicode 字符和字符串检查 (Unicode)
+// ============================================
+// 第 02 章说 char 占 4 个字节，表示一个 Unicode 标量值（'ℤ'、'😻'）；
+// 第 09 章说 String 是 UTF-8 编码，&hello[0..4] 必须落在字符边界上，
+// "नमस्ते" 用 chars() 和 bytes() 遍历的结果不一样。这个模块把这些概念摊开来看：
+//
+// - 每个字符的字节偏移、UTF-8 字节、UTF-16 编码单元、标量值和通用类别
+// - 哪些字节偏移是字符边界
+// - 按扩展字形簇（extended grapheme cluster，UAX #29）分组：
+//   "नमस्ते" 有 6 个 char，但只有 4 个字形簇
+// - 检查 &s[a..b] 是否合法，不合法时给出和标准库 panic 相同的说明，而不是 panic
+//
+// 通用类别来自 unicode/general_category.txt（从 UCD 导出），字形簇的断开属性
+// 由通用类别和下面几张小表推导，规则和数据都按 Unicode 14.0.0。
+//
+// 注意：Unicode 15.1 新增的规则 GB9c 会把天城文等文字中"辅音 + virama + 辅音"
+// 连成一个字形簇（"नमस्ते" 变成 न / म / स्ते 三组），它依赖 14.0 的数据里没有的
+// Indic_Conjunct_Break 属性，这里没有实现；按 14.0 的规则是 न / म / स् / ते 四组，
+// 和《Rust 程序设计语言》书中的说法一致。
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+// ========== 通用类别 ==========
+
+/// Unicode 通用类别（General Category）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Lu,
+    Ll,
+    Lt,
+    Lm,
+    Lo,
+    Mn,
+    Mc,
+    Me,
+    Nd,
+    Nl,
+    No,
+    Pc,
+    Pd,
+    Ps,
+    Pe,
+    Pi,
+    Pf,
+    Po,
+    Sm,
+    Sc,
+    Sk,
+    So,
+    Zs,
+    Zl,
+    Zp,
+    Cc,
+    Cf,
+    Cs,
+    Co,
+    Cn,
+}
+
+impl Category {
+    pub const ALL: [Category; 30] = [
+        Category::Lu,
+        Category::Ll,
+        Category::Lt,
+        Category::Lm,
+        Category::Lo,
+        Category::Mn,
+        Category::Mc,
+        Category::Me,
+        Category::Nd,
+        Category::Nl,
+        Category::No,
+        Category::Pc,
+        Category::Pd,
+        Category::Ps,
+        Category::Pe,
+        Category::Pi,
+        Category::Pf,
+        Category::Po,
+        Category::Sm,
+        Category::Sc,
+        Category::Sk,
+        Category::So,
+        Category::Zs,
+        Category::Zl,
+        Category::Zp,
+        Category::Cc,
+        Category::Cf,
+        Category::Cs,
+        Category::Co,
+        Category::Cn,
+    ];
+
+    /// 两字母缩写，例如 "Lu"
+    pub fn code(self) -> &'static str {
+        match self {
+            Category::Lu => "Lu",
+            Category::Ll => "Ll",
+            Category::Lt => "Lt",
+            Category::Lm => "Lm",
+            Category::Lo => "Lo",
+            Category::Mn => "Mn",
+            Category::Mc => "Mc",
+            Category::Me => "Me",
+            Category::Nd => "Nd",
+            Category::Nl => "Nl",
+            Category::No => "No",
+            Category::Pc => "Pc",
+            Category::Pd => "Pd",
+            Category::Ps => "Ps",
+            Category::Pe => "Pe",
+            Category::Pi => "Pi",
+            Category::Pf => "Pf",
+            Category::Po => "Po",
+            Category::Sm => "Sm",
+            Category::Sc => "Sc",
+            Category::Sk => "Sk",
+            Category::So => "So",
+            Category::Zs => "Zs",
+            Category::Zl => "Zl",
+            Category::Zp => "Zp",
+            Category::Cc => "Cc",
+            Category::Cf => "Cf",
+            Category::Cs => "Cs",
+            Category::Co => "Co",
+            Category::Cn => "Cn",
+        }
+    }
+
+    /// 中文说明，例如 "大写字母"
+    pub fn description(self) -> &'static str {
+        match self {
+            Category::Lu => "大写字母",
+            Category::Ll => "小写字母",
+            Category::Lt => "首字母大写字母",
+            Category::Lm => "修饰字母",
+            Category::Lo => "其他字母",
+            Category::Mn => "非间距组合标记",
+            Category::Mc => "间距组合标记",
+            Category::Me => "封闭组合标记",
+            Category::Nd => "十进制数字",
+            Category::Nl => "字母数字",
+            Category::No => "其他数字",
+            Category::Pc => "连接标点",
+            Category::Pd => "破折号",
+            Category::Ps => "开标点",
+            Category::Pe => "闭标点",
+            Category::Pi => "前引号",
+            Category::Pf => "后引号",
+            Category::Po => "其他标点",
+            Category::Sm => "数学符号",
+            Category::Sc => "货币符号",
+            Category::Sk => "修饰符号",
+            Category::So => "其他符号",
+            Category::Zs => "空格",
+            Category::Zl => "行分隔符",
+            Category::Zp => "段分隔符",
+            Category::Cc => "控制字符",
+            Category::Cf => "格式字符",
+            Category::Cs => "代理",
+            Category::Co => "私用",
+            Category::Cn => "未分配",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Category> {
+        Category::ALL.into_iter().find(|c| c.code() == code)
+    }
+
+    /// 组合标记：单独显示时需要一个底座
+    pub fn is_mark(self) -> bool {
+        matches!(self, Category::Mn | Category::Mc | Category::Me)
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// 解析后的类别表：按起始码位排序的 (起始, 结束, 类别)
+fn table() -> &'static [(u32, u32, Category)] {
+    static TABLE: OnceLock<Vec<(u32, u32, Category)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        include_str!("../unicode/general_category.txt")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (range, code) = line
+                    .split_once(';')
+                    .expect("类别表的每一行是 `范围 ; 类别`");
+                let range = range.trim();
+                let (start, end) = range.split_once("..").unwrap_or((range, range));
+                let hex =
+                    |text: &str| u32::from_str_radix(text, 16).expect("类别表中的码位是十六进制");
+                let category = Category::parse(code.trim()).expect("类别表中的类别是两字母缩写");
+                (hex(start), hex(end), category)
+            })
+            .collect()
+    })
+}
+
+/// 一个字符的通用类别
+pub fn category(c: char) -> Category {
+    let table = table();
+    let code = c as u32;
+    let index = table.partition_point(|&(start, _, _)| start <= code);
+    match index.checked_sub(1).map(|i| table[i]) {
+        Some((_, end, category)) if code <= end => category,
+        _ => Category::Cn,
+    }
+}
+
+// ========== 字形簇 ==========
+
+/// UAX #29 的字形簇断开属性（Grapheme_Cluster_Break）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gcb {
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    Other,
+}
+
+/// 不是 Mn/Me 但属于 Grapheme_Extend 的字符（Other_Grapheme_Extend），
+/// 以及算作 Extend 的肤色修饰符
+const EXTEND: &[(u32, u32)] = &[
+    (0x09BE, 0x09BE),
+    (0x09D7, 0x09D7),
+    (0x0B3E, 0x0B3E),
+    (0x0B57, 0x0B57),
+    (0x0BBE, 0x0BBE),
+    (0x0BD7, 0x0BD7),
+    (0x0CC2, 0x0CC2),
+    (0x0CD5, 0x0CD6),
+    (0x0D3E, 0x0D3E),
+    (0x0D57, 0x0D57),
+    (0x0DCF, 0x0DCF),
+    (0x0DDF, 0x0DDF),
+    (0x1B35, 0x1B35),
+    (0x200C, 0x200C),
+    (0x302E, 0x302F),
+    (0xFF9E, 0xFF9F),
+    (0x1133E, 0x1133E),
+    (0x11357, 0x11357),
+    (0x114B0, 0x114B0),
+    (0x114BD, 0x114BD),
+    (0x115AF, 0x115AF),
+    (0x11930, 0x11930),
+    (0x1D165, 0x1D165),
+    (0x1D16E, 0x1D172),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0020, 0xE007F),
+];
+
+/// 是 Mc 但不算 SpacingMark 的字符
+const NOT_SPACING_MARK: &[(u32, u32)] = &[
+    (0x102B, 0x102C),
+    (0x1038, 0x1038),
+    (0x1062, 0x1064),
+    (0x1067, 0x106D),
+    (0x1083, 0x1083),
+    (0x1087, 0x108C),
+    (0x108F, 0x108F),
+    (0x109A, 0x109C),
+    (0x1A61, 0x1A61),
+    (0x1A63, 0x1A64),
+    (0xAA7B, 0xAA7B),
+    (0xAA7D, 0xAA7D),
+    (0x11720, 0x11721),
+];
+
+/// 写在前面、和后面的字符连在一起的字符（Prepend）
+const PREPEND: &[(u32, u32)] = &[
+    (0x0600, 0x0605),
+    (0x06DD, 0x06DD),
+    (0x070F, 0x070F),
+    (0x0890, 0x0891),
+    (0x08E2, 0x08E2),
+    (0x0D4E, 0x0D4E),
+    (0x110BD, 0x110BD),
+    (0x110CD, 0x110CD),
+    (0x111C2, 0x111C3),
+    (0x1193F, 0x1193F),
+    (0x11941, 0x11941),
+    (0x11A3A, 0x11A3A),
+    (0x11A84, 0x11A89),
+    (0x11D46, 0x11D46),
+];
+
+/// 表情符号等 Extended_Pictographic 字符，用于 "👨‍👩‍👧" 这样用 ZWJ 连接的序列
+const PICTOGRAPHIC: &[(u32, u32)] = &[
+    (0x00A9, 0x00A9),
+    (0x00AE, 0x00AE),
+    (0x203C, 0x203C),
+    (0x2049, 0x2049),
+    (0x2122, 0x2122),
+    (0x2139, 0x2139),
+    (0x2194, 0x2199),
+    (0x21A9, 0x21AA),
+    (0x231A, 0x231B),
+    (0x2328, 0x2328),
+    (0x2388, 0x2388),
+    (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3),
+    (0x23F8, 0x23FA),
+    (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6),
+    (0x25C0, 0x25C0),
+    (0x25FB, 0x25FE),
+    (0x2600, 0x2605),
+    (0x2607, 0x2612),
+    (0x2614, 0x2685),
+    (0x2690, 0x2705),
+    (0x2708, 0x2712),
+    (0x2714, 0x2714),
+    (0x2716, 0x2716),
+    (0x271D, 0x271D),
+    (0x2721, 0x2721),
+    (0x2728, 0x2728),
+    (0x2733, 0x2734),
+    (0x2744, 0x2744),
+    (0x2747, 0x2747),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2763, 0x2767),
+    (0x2795, 0x2797),
+    (0x27A1, 0x27A1),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2934, 0x2935),
+    (0x2B05, 0x2B07),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x3030, 0x3030),
+    (0x303D, 0x303D),
+    (0x3297, 0x3297),
+    (0x3299, 0x3299),
+    (0x1F000, 0x1F0FF),
+    (0x1F10D, 0x1F10F),
+    (0x1F12F, 0x1F12F),
+    (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5),
+    (0x1F201, 0x1F20F),
+    (0x1F21A, 0x1F21A),
+    (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A),
+    (0x1F23C, 0x1F23F),
+    (0x1F249, 0x1F3FA),
+    (0x1F400, 0x1F53D),
+    (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F774, 0x1F77F),
+    (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F),
+    (0x1F848, 0x1F84F),
+    (0x1F85A, 0x1F85F),
+    (0x1F888, 0x1F88F),
+    (0x1F8AE, 0x1F8FF),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+];
+
+fn in_table(c: char, table: &[(u32, u32)]) -> bool {
+    let code = c as u32;
+    table
+        .iter()
+        .any(|&(start, end)| start <= code && code <= end)
+}
+
+fn grapheme_break(c: char) -> Gcb {
+    let code = c as u32;
+    match c {
+        '\r' => return Gcb::Cr,
+        '\n' => return Gcb::Lf,
+        '\u{200D}' => return Gcb::Zwj,
+        '\u{1F1E6}'..='\u{1F1FF}' => return Gcb::RegionalIndicator,
+        '\u{1100}'..='\u{115F}' | '\u{A960}'..='\u{A97C}' => return Gcb::L,
+        '\u{1160}'..='\u{11A7}' | '\u{D7B0}'..='\u{D7C6}' => return Gcb::V,
+        '\u{11A8}'..='\u{11FF}' | '\u{D7CB}'..='\u{D7FB}' => return Gcb::T,
+        // 谚文音节：每 28 个一组，组内第一个是 LV，其余是 LVT
+        '\u{AC00}'..='\u{D7A3}' if (code - 0xAC00).is_multiple_of(28) => return Gcb::Lv,
+        '\u{AC00}'..='\u{D7A3}' => return Gcb::Lvt,
+        '\u{0E33}' | '\u{0EB3}' => return Gcb::SpacingMark,
+        _ => {}
+    }
+    if in_table(c, EXTEND) {
+        return Gcb::Extend;
+    }
+    if in_table(c, PREPEND) {
+        return Gcb::Prepend;
+    }
+    match category(c) {
+        Category::Mn | Category::Me => Gcb::Extend,
+        Category::Mc if !in_table(c, NOT_SPACING_MARK) => Gcb::SpacingMark,
+        Category::Cc | Category::Cf | Category::Zl | Category::Zp => Gcb::Control,
+        _ => Gcb::Other,
+    }
+}
+
+/// 按扩展字形簇切分，返回每一簇的字节偏移和内容，类似 unicode-segmentation 的
+/// `grapheme_indices(true)`
+pub fn graphemes(s: &str) -> Vec<(usize, &str)> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<Gcb> = None;
+    // GB11：当前是否处在 ExtPict Extend* 之后（可能还跟着一个 ZWJ）
+    let mut pictographic = false;
+    // GB12/13：当前簇中连续的区域指示符个数
+    let mut regional = 0;
+
+    for (offset, c) in s.char_indices() {
+        let gcb = grapheme_break(c);
+        if let Some(prev_gcb) = prev {
+            let joined = joins(prev_gcb, c, gcb, pictographic, regional);
+            if !joined {
+                clusters.push((start, &s[start..offset]));
+                start = offset;
+                regional = 0;
+            }
+        }
+
+        pictographic = if in_table(c, PICTOGRAPHIC) {
+            true
+        } else {
+            pictographic && matches!(gcb, Gcb::Extend | Gcb::Zwj) && prev != Some(Gcb::Zwj)
+        };
+        regional = if gcb == Gcb::RegionalIndicator {
+            regional + 1
+        } else {
+            0
+        };
+        prev = Some(gcb);
+    }
+    if start < s.len() {
+        clusters.push((start, &s[start..]));
+    }
+    clusters
+}
+
+/// 两个相邻字符之间是否不断开，规则编号对应 UAX #29
+fn joins(prev: Gcb, c: char, next: Gcb, pictographic: bool, regional: usize) -> bool {
+    match (prev, next) {
+        // GB3
+        (Gcb::Cr, Gcb::Lf) => true,
+        // GB4、GB5
+        (Gcb::Control | Gcb::Cr | Gcb::Lf, _) | (_, Gcb::Control | Gcb::Cr | Gcb::Lf) => false,
+        // GB6、GB7、GB8：谚文音节
+        (Gcb::L, Gcb::L | Gcb::V | Gcb::Lv | Gcb::Lvt) => true,
+        (Gcb::Lv | Gcb::V, Gcb::V | Gcb::T) => true,
+        (Gcb::Lvt | Gcb::T, Gcb::T) => true,
+        // GB9、GB9a、GB9b
+        (_, Gcb::Extend | Gcb::Zwj | Gcb::SpacingMark) => true,
+        (Gcb::Prepend, _) => true,
+        // GB11：表情符号 + ZWJ + 表情符号
+        (Gcb::Zwj, _) if pictographic && in_table(c, PICTOGRAPHIC) => true,
+        // GB12、GB13：区域指示符两两成对（国旗）
+        (Gcb::RegionalIndicator, Gcb::RegionalIndicator) => regional % 2 == 1,
+        // GB999
+        _ => false,
+    }
+}
+
+// ========== 逐字符检查 ==========
+
+/// 字符串中一个 char 的信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharInfo {
+    /// 第几个 char（从 0 开始）
+    pub index: usize,
+    /// 起始字节偏移
+    pub offset: usize,
+    pub char: char,
+    /// UTF-8 编码的字节
+    pub utf8: Vec<u8>,
+    /// UTF-16 编码单元；BMP 之外的字符是一对代理
+    pub utf16: Vec<u16>,
+    pub category: Category,
+    /// 所在的字形簇（从 0 开始）
+    pub cluster: usize,
+}
+
+impl CharInfo {
+    /// 字节范围
+    pub fn bytes(&self) -> Range<usize> {
+        self.offset..self.offset + self.utf8.len()
+    }
+
+    /// 标量值，例如 "U+2124"
+    pub fn scalar(&self) -> String {
+        format!("U+{:04X}", self.char as u32)
+    }
+
+    /// 适合单独显示的形式：组合标记前面加上 ◌，控制字符等转义
+    pub fn display(&self) -> String {
+        match self.category {
+            category if category.is_mark() => format!("◌{}", self.char),
+            Category::Cc
+            | Category::Cf
+            | Category::Zl
+            | Category::Zp
+            | Category::Zs
+            | Category::Cn => self.char.escape_unicode().to_string(),
+            _ => self.char.to_string(),
+        }
+    }
+}
+
+/// 逐个字符列出字节偏移、编码、类别和所在的字形簇
+pub fn inspect(s: &str) -> Vec<CharInfo> {
+    let clusters = graphemes(s);
+    let mut cluster = 0;
+    s.char_indices()
+        .enumerate()
+        .map(|(index, (offset, c))| {
+            while cluster + 1 < clusters.len() && clusters[cluster + 1].0 <= offset {
+                cluster += 1;
+            }
+            let mut utf8 = [0; 4];
+            let mut utf16 = [0; 2];
+            CharInfo {
+                index,
+                offset,
+                char: c,
+                utf8: c.encode_utf8(&mut utf8).as_bytes().to_vec(),
+                utf16: c.encode_utf16(&mut utf16).to_vec(),
+                category: category(c),
+                cluster,
+            }
+        })
+        .collect()
+}
+
+/// 所有的字符边界，包括 0 和 s.len()
+pub fn boundaries(s: &str) -> Vec<usize> {
+    s.char_indices()
+        .map(|(offset, _)| offset)
+        .chain([s.len()])
+        .collect()
+}
+
+/// 在终端中占的列数：组合标记和格式字符占 0 列，东亚宽字符和表情占 2 列
+pub fn width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match category(c) {
+            Category::Mn | Category::Me | Category::Cf => 0,
+            _ if is_wide(c) => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{303E}'
+        | '\u{3041}'..='\u{33FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{A000}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1F64F}'
+        | '\u{1F900}'..='\u{1F9FF}'
+        | '\u{20000}'..='\u{3FFFD}')
+}
+
+// ========== 切片检查 ==========
+
+/// `&s[start..end]` 不合法的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SliceError {
+    /// 起点超出字符串长度
+    StartOutOfBounds { start: usize, len: usize },
+    /// 终点超出字符串长度
+    EndOutOfBounds { end: usize, len: usize },
+    /// 起点大于终点
+    StartAfterEnd { start: usize, end: usize },
+    /// 起点或终点落在一个字符的中间
+    NotCharBoundary {
+        /// 是起点（true）还是终点
+        start: bool,
+        index: usize,
+        /// 被切开的字符和它的字节范围
+        char: char,
+        bytes: Range<usize>,
+    },
+}
+
+impl SliceError {
+    /// 和标准库 panic 时相同的消息，例如
+    /// "end byte index 3 is not a char boundary; it is inside 'д' (bytes 2..4) of `Здравствуйте`"
+    pub fn panic_message(&self, s: &str) -> String {
+        // 标准库最多显示字符串的前 256 个字节
+        let mut len = s.len().min(256);
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+        let ellipsis = if len < s.len() { "[...]" } else { "" };
+        let shown = format!("`{}`{}", &s[..len], ellipsis);
+
+        match self {
+            SliceError::StartOutOfBounds { start, .. } => {
+                format!("start byte index {} is out of bounds of {}", start, shown)
+            }
+            SliceError::EndOutOfBounds { end, .. } => {
+                format!("end byte index {} is out of bounds of {}", end, shown)
+            }
+            SliceError::StartAfterEnd { start, end } => {
+                format!("begin > end ({} > {}) when slicing {}", start, end, shown)
+            }
+            SliceError::NotCharBoundary {
+                start,
+                index,
+                char,
+                bytes,
+            } => format!(
+                "{} byte index {} is not a char boundary; it is inside {:?} (bytes {:?}) of {}",
+                if *start { "start" } else { "end" },
+                index,
+                char,
+                bytes,
+                shown
+            ),
+        }
+    }
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SliceError::StartOutOfBounds { start, len } => {
+                write!(f, "起点 {} 超出了字符串的长度 {} 字节", start, len)
+            }
+            SliceError::EndOutOfBounds { end, len } => {
+                write!(f, "终点 {} 超出了字符串的长度 {} 字节", end, len)
+            }
+            SliceError::StartAfterEnd { start, end } => {
+                write!(f, "起点 {} 大于终点 {}", start, end)
+            }
+            SliceError::NotCharBoundary {
+                start,
+                index,
+                char,
+                bytes,
+            } => write!(
+                f,
+                "{} {} 落在字符 {:?} 的中间（它占字节 {}..{}），应改为 {} 或 {}",
+                if *start { "起点" } else { "终点" },
+                index,
+                char,
+                bytes.start,
+                bytes.end,
+                bytes.start,
+                bytes.end
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SliceError {}
+
+/// 检查 `&s[range]` 是否合法：合法时返回切片，否则返回原因（按标准库检查的顺序）
+pub fn slice(s: &str, range: Range<usize>) -> Result<&str, SliceError> {
+    let Range { start, end } = range;
+    if start > s.len() {
+        return Err(SliceError::StartOutOfBounds {
+            start,
+            len: s.len(),
+        });
+    }
+    if end > s.len() {
+        return Err(SliceError::EndOutOfBounds { end, len: s.len() });
+    }
+    if start > end {
+        return Err(SliceError::StartAfterEnd { start, end });
+    }
+    for (is_start, index) in [(true, start), (false, end)] {
+        if !s.is_char_boundary(index) {
+            let char_start = (0..index)
+                .rev()
+                .find(|&i| s.is_char_boundary(i))
+                .unwrap_or(0);
+            let char = s[char_start..]
+                .chars()
+                .next()
+                .expect("char_start 是字符边界");
+            return Err(SliceError::NotCharBoundary {
+                start: is_start,
+                index,
+                char,
+                bytes: char_start..char_start + char.len_utf8(),
+            });
+        }
+    }
+    Ok(&s[start..end])
+}
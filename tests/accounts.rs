@@ -0,0 +1,344 @@
+// ============================================
+// 账户存储测试
+// ============================================
+
+use std::fs;
+use std::io;
+
+use rust_tutorial::accounts::{self, AccountError, Accounts, User};
+
+/// 第 06 章和第 15 章里出现过的用户
+fn sample() -> Accounts {
+    let mut accounts = Accounts::new();
+    accounts
+        .create("someusername123", "someone@example.com")
+        .unwrap();
+    accounts
+        .create("anotherusername567", "another@example.com")
+        .unwrap();
+    accounts.create("alice", "alice@example.com").unwrap();
+    accounts
+}
+
+#[test]
+fn creates_accounts_with_unique_names_and_emails() {
+    let mut accounts = sample();
+    assert_eq!(accounts.len(), 3);
+    let alice = accounts.find_by_username("ALICE").unwrap();
+    assert_eq!(
+        *alice,
+        User {
+            id: 3,
+            username: String::from("alice"),
+            email: String::from("alice@example.com"),
+            sign_in_count: 0,
+            active: true,
+        }
+    );
+    assert_eq!(
+        accounts
+            .find_by_email("Alice@Example.COM")
+            .map(|user| user.id),
+        Some(3)
+    );
+
+    // 只是大小写不同，也算重复
+    assert_eq!(
+        accounts.create("Alice", "alice2@example.com"),
+        Err(AccountError::UsernameTaken(String::from("Alice")))
+    );
+    assert_eq!(
+        accounts.create("alice2", "ALICE@example.com"),
+        Err(AccountError::EmailTaken(String::from("ALICE@example.com")))
+    );
+    // 失败的创建不占用 id
+    let bob = accounts.create("Bob", "Bob@Example.com").unwrap();
+    assert_eq!((bob.id, bob.username.as_str()), (4, "Bob"));
+    assert_eq!(
+        accounts.find_by_username("bob").unwrap().email,
+        "Bob@Example.com"
+    );
+    assert_eq!(accounts.find_by_username("nobody"), None);
+}
+
+#[test]
+fn validates_usernames_and_email_syntax() {
+    for email in [
+        "someone@example.com",
+        "first.last+tag@mail.example.co.uk",
+        "o'brien@example.org",
+        "x@a-b.io",
+    ] {
+        assert_eq!(accounts::check_email(email), Ok(()), "{email}");
+    }
+    for (email, reason) in [
+        ("someone.example.com", "缺少 @"),
+        ("@example.com", "@ 前面不能为空"),
+        ("someone@", "@ 后面不能为空"),
+        ("someone@localhost", "域名至少要有两段，例如 example.com"),
+        ("a..b@example.com", "@ 前面的点不能在开头、结尾或连续出现"),
+        (".a@example.com", "@ 前面的点不能在开头、结尾或连续出现"),
+        ("a b@example.com", "@ 前面含有不允许的字符"),
+        ("a@b@example.com", "@ 前面含有不允许的字符"),
+        ("a@example..com", "域名中的点不能在开头、结尾或连续出现"),
+        ("a@-example.com", "域名的每一段不能以 - 开头或结尾"),
+        ("a@exa_mple.com", "域名只能包含字母、数字和 -"),
+        ("a@example.123", "顶级域名不能全是数字"),
+    ] {
+        assert_eq!(accounts::check_email(email), Err(reason), "{email}");
+    }
+    let long_local = format!("{}@example.com", "a".repeat(65));
+    assert_eq!(
+        accounts::check_email(&long_local),
+        Err("@ 前面不能超过 64 个字节")
+    );
+
+    assert_eq!(accounts::check_username("user_1.x-y"), Ok(()));
+    assert_eq!(accounts::check_username(""), Err("不能为空"));
+    assert_eq!(
+        accounts::check_username("_x"),
+        Err("必须以英文字母或数字开头")
+    );
+    assert_eq!(
+        accounts::check_username("a b"),
+        Err("只能包含英文字母、数字、_、- 和 .")
+    );
+    // 只接受 ASCII：逐个字符转小写分不出 STRASSE 和 straße、开尔文符号 K 和 k
+    assert_eq!(
+        accounts::check_username("用户"),
+        Err("必须以英文字母或数字开头")
+    );
+    for username in ["straße", "\u{212A}elvin"] {
+        assert!(accounts::check_username(username).is_err(), "{username}");
+    }
+    assert_eq!(
+        accounts::check_username(&"a".repeat(33)),
+        Err("不能超过 32 个字符")
+    );
+
+    let mut accounts = Accounts::new();
+    let error = accounts.create("bad name", "x@example.com").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "用户名 \"bad name\" 无效: 只能包含英文字母、数字、_、- 和 ."
+    );
+    assert!(accounts.create("good", "not-an-email").is_err());
+    assert!(accounts.is_empty());
+    accounts.create("STRASSE", "a@example.com").unwrap();
+    accounts.create("kelvin", "b@example.com").unwrap();
+    assert!(accounts.create("straße", "c@example.com").is_err());
+    assert!(accounts.create("\u{212A}elvin", "d@example.com").is_err());
+    assert_eq!(accounts.len(), 2);
+}
+
+#[test]
+fn updates_with_struct_update_syntax() {
+    let mut accounts = sample();
+    // 第 06 章：user1.email = String::from("anotheremail@example.com")
+    let user1 = accounts
+        .update(1, |user| User {
+            email: String::from("anotheremail@example.com"),
+            ..user
+        })
+        .unwrap();
+    assert_eq!(user1.email, "anotheremail@example.com");
+    assert_eq!(user1.username, "someusername123");
+    // 旧邮箱释放出来，新邮箱可以查到
+    assert_eq!(accounts.find_by_email("someone@example.com"), None);
+    assert_eq!(
+        accounts
+            .find_by_email("AnotherEmail@example.com")
+            .map(|u| u.id),
+        Some(1)
+    );
+
+    // 只改大小写：和自己不冲突
+    accounts
+        .update(3, |user| User {
+            username: String::from("Alice"),
+            ..user
+        })
+        .unwrap();
+    assert_eq!(accounts.get(3).unwrap().username, "Alice");
+
+    // 和别人冲突、格式不对、改了 id：账户保持原样
+    let before = accounts.clone();
+    assert_eq!(
+        accounts.update(3, |user| User {
+            email: String::from("ANOTHER@example.com"),
+            ..user
+        }),
+        Err(AccountError::EmailTaken(String::from(
+            "ANOTHER@example.com"
+        )))
+    );
+    assert!(matches!(
+        accounts.update(3, |user| User {
+            email: String::from("broken"),
+            ..user
+        }),
+        Err(AccountError::InvalidEmail { .. })
+    ));
+    assert_eq!(
+        accounts.update(3, |user| User { id: 9, ..user }),
+        Err(AccountError::IdChanged { id: 3, new_id: 9 })
+    );
+    assert_eq!(accounts, before);
+    assert_eq!(
+        accounts.update(42, |user| user).unwrap_err().to_string(),
+        "找不到 id 为 42 的账户"
+    );
+}
+
+#[test]
+fn records_sign_ins_and_queries_by_active_state() {
+    let mut accounts = sample();
+    for _ in 0..3 {
+        accounts.record_sign_in(3).unwrap();
+    }
+    assert_eq!(accounts.record_sign_in(1).unwrap().sign_in_count, 1);
+    assert_eq!(accounts.get(3).unwrap().sign_in_count, 3);
+
+    let deactivated = accounts.deactivate(2).unwrap();
+    assert!(!deactivated.active);
+    assert_eq!(accounts.record_sign_in(2), Err(AccountError::Inactive(2)));
+    assert_eq!(accounts.record_sign_in(7), Err(AccountError::NotFound(7)));
+
+    let ids =
+        |active: bool| -> Vec<u32> { accounts.by_active(active).map(|user| user.id).collect() };
+    assert_eq!(ids(true), [1, 3]);
+    assert_eq!(ids(false), [2]);
+
+    // 重新启用也用更新语法
+    accounts
+        .update(2, |user| User {
+            active: true,
+            ..user
+        })
+        .unwrap();
+    assert_eq!(accounts.record_sign_in(2).unwrap().sign_in_count, 1);
+    assert_eq!(accounts.by_active(false).count(), 0);
+}
+
+#[test]
+fn deleting_frees_names_but_never_reuses_ids() {
+    let mut accounts = sample();
+    let removed = accounts.delete(3).unwrap();
+    assert_eq!(removed.username, "alice");
+    assert_eq!(accounts.delete(3), Err(AccountError::NotFound(3)));
+    assert_eq!(accounts.get(3), None);
+    assert_eq!(accounts.find_by_username("alice"), None);
+
+    let alice = accounts.create("ALICE", "alice@example.com").unwrap();
+    assert_eq!(alice.id, 4);
+    let ids: Vec<u32> = accounts.iter().map(|user| user.id).collect();
+    assert_eq!(ids, [1, 2, 4]);
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let mut accounts = sample();
+    accounts.record_sign_in(1).unwrap();
+    accounts.deactivate(2).unwrap();
+    accounts.delete(3).unwrap();
+
+    let text = accounts.to_text();
+    assert_eq!(
+        text,
+        "# rust-tutorial 账户\n\
+         next_id 4\n\
+         1 someusername123 someone@example.com 1 active\n\
+         2 anotherusername567 another@example.com 0 inactive\n"
+    );
+
+    let dir = std::env::temp_dir().join(format!("rust-tutorial-accounts-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("accounts.txt");
+    // 文件不存在时是空的仓库
+    assert!(Accounts::load(&path).unwrap().is_empty());
+    accounts.save(&path).unwrap();
+    let mut loaded = Accounts::load(&path).unwrap();
+    assert_eq!(loaded, accounts);
+    // next_id 也保存了：删掉的 3 不会复用
+    assert_eq!(loaded.create("carol", "carol@example.com").unwrap().id, 4);
+
+    fs::write(
+        &path,
+        "next_id 3\n1 a a@example.com 0 active\n2 A b@example.com 0 active\n",
+    )
+    .unwrap();
+    let error = Accounts::load(&path).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(
+        error.to_string().ends_with("第 3 行: 用户名 A 已经被使用"),
+        "{error}"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+
+    // 手工编辑出错时报告行号
+    for (text, message) in [
+        (
+            "1 a a@example.com 0",
+            "第 1 行: 应该有 5 个字段（id 用户名 邮箱 登录次数 状态），实际有 4 个",
+        ),
+        (
+            "# 注释\n\nx a a@example.com 0 active",
+            "第 3 行: id 不是有效的数字: x",
+        ),
+        (
+            "1 a a@example.com 0 maybe",
+            "第 1 行: 未知的状态 maybe，只能是 active 或 inactive",
+        ),
+        (
+            "1 a a@example.com 0 active\n1 b b@example.com 0 active",
+            "第 2 行: 重复的 id 1",
+        ),
+        (
+            "1 a a@example 0 active",
+            "第 1 行: 邮箱 \"a@example\" 无效: 域名至少要有两段，例如 example.com",
+        ),
+        (
+            "next_id 2\n5 a a@example.com 0 active",
+            "第 1 行: next_id 2 不大于已有的 id 5",
+        ),
+    ] {
+        assert_eq!(Accounts::parse(text).unwrap_err().to_string(), message);
+    }
+    // 没有 next_id 时接着最大的 id 编号
+    let mut parsed = Accounts::parse("7 a a@example.com 2 inactive\n").unwrap();
+    assert_eq!(parsed.create("b", "b@example.com").unwrap().id, 8);
+}
+
+#[test]
+fn reports_when_ids_run_out() {
+    // 第一个 id 是 u32::MAX - 1，之后就没有可用的 id 了
+    let text = format!("next_id {}\n", u32::MAX - 1);
+    let mut accounts = Accounts::parse(&text).unwrap();
+    let last = accounts.create("alice", "alice@example.com").unwrap();
+    assert_eq!(last.id, u32::MAX - 1);
+    assert_eq!(
+        accounts.create("bob", "bob@example.com"),
+        Err(AccountError::IdsExhausted)
+    );
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts.find_by_username("alice").unwrap().id, u32::MAX - 1);
+    assert_eq!(accounts.find_by_username("bob"), None);
+
+    // 保存再读回来，仍然不能创建
+    let mut reloaded = Accounts::parse(&accounts.to_text()).unwrap();
+    assert_eq!(
+        reloaded
+            .create("bob", "bob@example.com")
+            .unwrap_err()
+            .to_string(),
+        "账户 id 已经用完，不能再创建账户"
+    );
+
+    // 文件里的 id 不能是 u32::MAX
+    assert_eq!(
+        Accounts::parse("4294967295 alice alice@example.com 0 active")
+            .unwrap_err()
+            .to_string(),
+        "第 1 行: id 不能是 4294967295（u32::MAX）"
+    );
+}
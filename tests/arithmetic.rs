@@ -0,0 +1,191 @@
+// ============================================
+// 整数算术测试
+// ============================================
+
+use rust_tutorial::arithmetic::{self, Error, Int, Op, saturating, wrapping};
+
+#[test]
+fn divide_in_chapters_03_and_11() {
+    let functions = include_str!("snapshots/03_functions.txt");
+    assert!(functions.contains("10 / 2 = Ok(5)\n"));
+    assert!(functions.contains("10 / 0 = Err(DivisionByZero { op: Divide, dividend: 10 })\n"));
+    assert!(
+        functions
+            .contains("i32::MIN / -1 = Err(Overflow { op: Divide, lhs: -2147483648, rhs: -1 })\n")
+    );
+
+    let error_handling = include_str!("snapshots/11_error_handling.txt");
+    assert!(
+        error_handling.contains("结果: 5\n错误: 除数不能为零\n错误: 除法结果超出了 i32 的范围\n")
+    );
+}
+
+/// 对照在 i128 中精确计算的结果检查；`exact` 为 None 表示除数为零
+fn check<T>(op: Op, a: T, b: T, actual: Result<T, Error<T>>, exact: Option<i128>)
+where
+    T: Int + Into<i128>,
+{
+    let (min, max) = (T::MIN.into(), T::MAX.into());
+    match (exact, actual) {
+        (
+            None,
+            Err(Error::DivisionByZero {
+                op: error_op,
+                dividend,
+            }),
+        ) => {
+            assert_eq!((error_op, dividend.into()), (op, a.into()));
+        }
+        (Some(n), Ok(value)) => assert_eq!(n, value.into(), "{a} {op:?} {b}"),
+        (Some(n), Err(Error::Overflow { .. })) => assert!(n > max, "{a} {op:?} {b}"),
+        (Some(n), Err(Error::Underflow { .. })) => assert!(n < min, "{a} {op:?} {b}"),
+        (exact, actual) => panic!("{a} {op:?} {b}: {exact:?} vs {actual:?}"),
+    }
+}
+
+#[test]
+fn checked_operations_agree_with_wider_arithmetic() {
+    for a in i8::MIN..=i8::MAX {
+        for b in i8::MIN..=i8::MAX {
+            let (x, y) = (i128::from(a), i128::from(b));
+            check(Op::Add, a, b, arithmetic::add(a, b), Some(x + y));
+            check(Op::Subtract, a, b, arithmetic::subtract(a, b), Some(x - y));
+            check(Op::Multiply, a, b, arithmetic::multiply(a, b), Some(x * y));
+            check(Op::Divide, a, b, arithmetic::divide(a, b), x.checked_div(y));
+            // MIN % -1 在数学上是 0，但和标准库一样报告为溢出
+            if (a, b) == (i8::MIN, -1) {
+                assert_eq!(
+                    arithmetic::remainder(a, b),
+                    Err(Error::Overflow {
+                        op: Op::Remainder,
+                        lhs: a,
+                        rhs: b
+                    })
+                );
+            } else {
+                check(
+                    Op::Remainder,
+                    a,
+                    b,
+                    arithmetic::remainder(a, b),
+                    x.checked_rem(y),
+                );
+            }
+
+            // 饱和运算把精确结果截到范围内，回绕运算截断到低 8 位
+            let clamp = |n: i128| n.clamp(-128, 127) as i8;
+            assert_eq!(saturating::add(a, b), clamp(x + y));
+            assert_eq!(saturating::subtract(a, b), clamp(x - y));
+            assert_eq!(saturating::multiply(a, b), clamp(x * y));
+            assert_eq!(wrapping::add(a, b), (x + y) as i8);
+            assert_eq!(wrapping::subtract(a, b), (x - y) as i8);
+            assert_eq!(wrapping::multiply(a, b), (x * y) as i8);
+            if b != 0 {
+                assert_eq!(saturating::divide(a, b), Ok(clamp(x / y)));
+                assert_eq!(wrapping::divide(a, b), Ok((x / y) as i8));
+                assert_eq!(saturating::remainder(a, b), Ok((x % y) as i8));
+                assert_eq!(wrapping::remainder(a, b), Ok((x % y) as i8));
+            }
+        }
+    }
+
+    for a in u8::MIN..=u8::MAX {
+        for b in u8::MIN..=u8::MAX {
+            let (x, y) = (i128::from(a), i128::from(b));
+            check(Op::Add, a, b, arithmetic::add(a, b), Some(x + y));
+            check(Op::Subtract, a, b, arithmetic::subtract(a, b), Some(x - y));
+            check(Op::Multiply, a, b, arithmetic::multiply(a, b), Some(x * y));
+            check(Op::Divide, a, b, arithmetic::divide(a, b), x.checked_div(y));
+            check(
+                Op::Remainder,
+                a,
+                b,
+                arithmetic::remainder(a, b),
+                x.checked_rem(y),
+            );
+        }
+    }
+}
+
+#[test]
+fn division_by_zero_is_an_error_in_every_mode() {
+    let zero = Err(Error::DivisionByZero {
+        op: Op::Divide,
+        dividend: 7,
+    });
+    assert_eq!(arithmetic::divide(7, 0), zero);
+    assert_eq!(saturating::divide(7, 0), zero);
+    assert_eq!(wrapping::divide(7, 0), zero);
+    assert_eq!(
+        wrapping::remainder(7u64, 0),
+        Err(Error::DivisionByZero {
+            op: Op::Remainder,
+            dividend: 7
+        })
+    );
+
+    assert_eq!(saturating::divide(i32::MIN, -1), Ok(i32::MAX));
+    assert_eq!(wrapping::divide(i32::MIN, -1), Ok(i32::MIN));
+    assert_eq!(saturating::remainder(i32::MIN, -1), Ok(0));
+}
+
+#[test]
+fn pow_with_signed_and_large_exponents() {
+    for base in i8::MIN..=i8::MAX {
+        for exponent in -2i8..=10 {
+            let result = arithmetic::pow(base, exponent);
+            if exponent < 0 {
+                assert_eq!(result, Err(Error::NegativeExponent { base, exponent }));
+                continue;
+            }
+            let exact = i128::from(base).pow(exponent as u32);
+            check(Op::Pow, base, exponent, result, Some(exact));
+            let clamped = exact.clamp(-128, 127) as i8;
+            assert_eq!(saturating::pow(base, exponent), Ok(clamped));
+            assert_eq!(
+                wrapping::pow(base, exponent),
+                Ok(base.wrapping_pow(exponent as u32))
+            );
+        }
+    }
+
+    assert_eq!(arithmetic::pow(-2i32, 31), Ok(i32::MIN));
+    assert!(matches!(
+        arithmetic::pow(-2i32, 33),
+        Err(Error::Underflow { .. })
+    ));
+    // 指数超过 u32 时，只有 0、1、-1 的幂不溢出
+    assert_eq!(arithmetic::pow(-1i64, i64::MAX), Ok(-1));
+    assert_eq!(arithmetic::pow(-1i64, i64::MAX - 1), Ok(1));
+    assert_eq!(arithmetic::pow(1u64, u64::MAX), Ok(1));
+    assert!(matches!(
+        arithmetic::pow(2u64, u64::MAX),
+        Err(Error::Overflow { .. })
+    ));
+    assert_eq!(saturating::pow(-3i64, i64::MAX), Ok(i64::MIN));
+    // 3 的 2^33 次幂：回绕结果只和 3 的阶有关，这里用两次 wrapping_pow 计算
+    let expected = 3u64.wrapping_pow(1 << 31).wrapping_pow(4);
+    assert_eq!(wrapping::pow(3u64, 1 << 33), Ok(expected));
+}
+
+#[test]
+fn errors_describe_the_expression() {
+    assert_eq!(
+        arithmetic::divide(10, 0).unwrap_err().to_string(),
+        "除数为零: 10 / 0"
+    );
+    assert_eq!(
+        arithmetic::add(i32::MAX, 1).unwrap_err().to_string(),
+        "结果大于 i32 的最大值 2147483647: 2147483647 + 1"
+    );
+    assert_eq!(
+        arithmetic::subtract(0u8, 1).unwrap_err().to_string(),
+        "结果小于 u8 的最小值 0: 0 - 1"
+    );
+    assert_eq!(
+        arithmetic::pow(2i64, -1).unwrap_err().to_string(),
+        "指数不能为负数: 2.pow(-1)"
+    );
+    let error: Box<dyn std::error::Error> = Box::new(arithmetic::remainder(5, 0).unwrap_err());
+    assert_eq!(error.to_string(), "除数为零: 5 % 0");
+}
@@ -0,0 +1,168 @@
+// ============================================
+// 书籍生成测试
+// ============================================
+
+use std::fs;
+
+use rust_tutorial::book::{self, Block};
+use rust_tutorial::chapters;
+use rust_tutorial::toolchain;
+
+fn heading(level: usize, text: &str) -> Block {
+    let text = text.to_string();
+    Block::Heading {
+        level,
+        text,
+        line: 0,
+    }
+}
+
+/// 去掉行号，方便和期望的标题比较
+fn without_lines(blocks: &[Block]) -> Vec<Block> {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading { level, text, .. } => heading(*level, text),
+            other => other.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn every_source_line_ends_up_in_the_page() {
+    for chapter in chapters::all() {
+        let page = book::page(chapter);
+
+        // 横幅和简介之后的非空行，去掉 main 函数的首尾两行
+        let expected = chapter
+            .source
+            .lines()
+            .skip_while(|line| line.trim_start().starts_with("//"))
+            .filter(|line| !line.trim().is_empty())
+            .count()
+            - 2;
+        let actual: usize = page
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Heading { .. } => 1,
+                Block::Prose(lines) | Block::Code(lines) => {
+                    lines.iter().filter(|line| !line.trim().is_empty()).count()
+                }
+                Block::Output(_) => 0,
+            })
+            .sum();
+        assert_eq!(actual, expected, "{}", chapter.file_name());
+    }
+}
+
+#[test]
+fn parses_headings_prose_and_code() {
+    let page = book::page(chapters::find("variables").unwrap());
+    assert_eq!(page.title(), "01. 变量 (Variables)");
+    assert_eq!(page.file_name(), "01_variables.md");
+    assert_eq!(
+        page.intro,
+        ["Rust 中的变量默认是不可变的（immutable），这是 Rust 安全性的重要特性之一"]
+    );
+
+    // main 函数体去掉一层缩进；被注释掉的代码留在代码块里
+    assert_eq!(
+        without_lines(&page.blocks[..5]),
+        [
+            heading(3, "1. 基本变量声明"),
+            Block::Prose(vec![String::from("使用 let 关键字声明变量，默认不可变")]),
+            Block::Code(vec![
+                String::from("let x = 5;"),
+                String::from("println!(\"x = {}\", x);"),
+            ]),
+            Block::Prose(vec![String::from(
+                "下面的代码会编译错误，因为 x 是不可变的"
+            )]),
+            Block::Code(vec![String::from(
+                "// x = 6; // error: cannot assign twice to immutable variable"
+            )]),
+        ]
+    );
+
+    // 小节标题，以及嵌套在 impl 中、不参与排版的注释
+    let page = book::page(chapters::find("structs").unwrap());
+    assert_eq!(
+        without_lines(&page.blocks[..1]),
+        [heading(2, "定义和实例化结构体")]
+    );
+    let blocks = without_lines(&page.blocks);
+    let prose = Block::Prose(vec![String::from("impl 块用于定义方法")]);
+    let index = blocks.iter().position(|block| *block == prose).unwrap();
+    let Block::Code(code) = &blocks[index + 1] else {
+        panic!("正文之后应该是代码块");
+    };
+    assert_eq!(code[0], "impl Rectangle {");
+    assert!(code.contains(&String::from(
+        "    // 方法：第一个参数是 &self（不可变引用）"
+    )));
+}
+
+#[test]
+fn embeds_section_output_below_the_code() {
+    let chapter = chapters::find("variables").unwrap();
+    let mut page = book::page(chapter);
+    let outputs = book::section_output(chapter).unwrap();
+    book::embed_output(&mut page, &outputs);
+
+    let first = page
+        .blocks
+        .iter()
+        .position(|block| matches!(block, Block::Output(_)))
+        .unwrap();
+    assert_eq!(page.blocks[first], Block::Output(String::from("x = 5\n")));
+    assert!(matches!(page.blocks[first - 1], Block::Code(_)));
+    assert!(matches!(page.blocks[first + 1], Block::Heading { .. }));
+
+    let markdown = book::markdown(&page);
+    assert!(markdown.starts_with("# 01. 变量 (Variables)\n\n"));
+    assert!(markdown.contains("```\n\n输出:\n\n```text\nx = 5\n```\n\n### 2. 可变变量"));
+}
+
+#[test]
+fn markdown_annotates_messages_and_html_escapes() {
+    let page = book::page(chapters::find("generics").unwrap());
+    let markdown = book::markdown(&page);
+    assert!(markdown.contains(
+        "println!(\"{}\", t!(\"generics.largest_char\", result)); // \"最大字符: {}\"\n"
+    ));
+
+    let html = book::html(&[page]);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<a href=\"#generics\">12. 泛型 (Generics)</a>"));
+    assert!(html.contains("fn largest&lt;T: PartialOrd + Copy&gt;"));
+    assert!(!html.contains("fn largest<T"));
+}
+
+#[test]
+fn writes_an_mdbook_project_and_html() {
+    let dir = toolchain::scratch_dir("book-test").unwrap();
+    let pages: Vec<_> = chapters::all().iter().map(book::page).collect();
+    let files = book::write(&dir, &pages).unwrap();
+    assert_eq!(files.len(), chapters::all().len() + 4);
+
+    let summary = fs::read_to_string(dir.join("src").join("SUMMARY.md")).unwrap();
+    assert!(summary.contains("- [07. 枚举 (Enums)](07_enums.md)\n"));
+    assert!(summary.ends_with("---\n\n[附录：as 类型转换](casts.md)\n"));
+    assert!(
+        fs::read_to_string(dir.join("src").join(book::CASTS_FILE))
+            .unwrap()
+            .starts_with("# 附录：as 类型转换\n")
+    );
+    for page in &pages {
+        assert!(dir.join("src").join(page.file_name()).is_file());
+    }
+    assert!(
+        fs::read_to_string(dir.join("book.toml"))
+            .unwrap()
+            .contains("src = \"src\"")
+    );
+    assert!(dir.join("rust-tutorial.html").is_file());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
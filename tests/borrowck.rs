@@ -0,0 +1,324 @@
+// ============================================
+// 所有权和借用检查模拟器测试
+// ============================================
+
+use rust_tutorial::borrowck::{Program, Run};
+
+const OWNERSHIP: &str = include_str!("../src/05_ownership.rs");
+const ERRORS: &str = include_str!("../borrowck/errors.rs");
+
+fn run(source: &str) -> Run {
+    Program::parse(source)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .run()
+}
+
+/// 第 05 章 main 的函数体，加上 main 之后的函数声明（函数体会被跳过）
+fn chapter_script() -> String {
+    let body = OWNERSHIP
+        .split_once("pub fn main() {\n")
+        .unwrap()
+        .1
+        .replacen("\n}\n", "\n", 1);
+    // t! 是章节的翻译宏，换成 format!
+    body.replace("t!(\"ownership.length\", ", "format!(\"{} {}\", ")
+}
+
+/// 把一行注释掉的代码恢复出来
+fn uncomment(script: &str, line: &str) -> String {
+    assert!(script.contains(&format!("// {line}")), "{line}");
+    script.replacen(&format!("// {line}"), line, 1)
+}
+
+#[test]
+fn chapter_05_runs_clean_and_its_commented_errors_are_caught() {
+    let script = chapter_script();
+    let clean = run(&script);
+    assert_eq!(clean.codes(), [] as [&str; 0], "{:?}", clean.violations);
+
+    // 移动之后的两处 println!
+    let moved = run(&uncomment(&script, "println!(\"{}\", s1);"));
+    assert_eq!(moved.codes(), ["E0382"]);
+    assert_eq!(moved.violations[0].message, "borrow of moved value: `s1`");
+    let moved = run(&uncomment(&script, "println!(\"{}\", s);"));
+    assert_eq!(moved.codes(), ["E0382"]);
+    assert_eq!(
+        moved.violations[0].labels[0].text, "value moved here",
+        "takes_ownership(s) 处移动"
+    );
+
+    // r1、r2 之后还要用，不能同时可变借用
+    let conflict = run(&uncomment(&script, "let r3 = &mut s;"));
+    assert_eq!(conflict.codes(), ["E0502"]);
+    assert_eq!(
+        conflict.violations[0].message,
+        "cannot borrow `s` as mutable because it is also borrowed as immutable"
+    );
+
+    // 悬垂引用：恢复 dangle 的整个定义
+    let dangle = script
+        .replace("// fn dangle", "fn dangle")
+        .replace("// } // s 在这里被释放", "} // s 在这里被释放");
+    assert_eq!(run(&dangle).codes(), ["E0106"]);
+}
+
+#[test]
+fn renders_errors_like_rustc() {
+    let run = run(ERRORS);
+    assert_eq!(
+        run.codes(),
+        ["E0382", "E0499", "E0502", "E0505", "E0597", "E0106"]
+    );
+    assert_eq!(
+        run.violations[0].render(ERRORS, "errors.rs"),
+        "\
+error[E0382]: borrow of moved value: `s1`
+ --> errors.rs:7:24
+  |
+6 | let s2 = s1;
+  |          -- value moved here
+7 | println!(\"{}, world!\", s1);
+  |                        ^^ value borrowed here after move
+  |
+  = note: move occurs because `s1` has type `String`, which does not implement the `Copy` trait
+"
+    );
+    assert_eq!(
+        run.violations[4].render(ERRORS, "errors.rs"),
+        "\
+error[E0597]: `x` does not live long enough
+  --> errors.rs:31:9
+   |
+30 |     let x = 5;
+   |         - binding `x` declared here
+31 |     r = &x;
+   |         ^^ borrowed value does not live long enough
+32 | }
+   | - `x` dropped here while still borrowed
+33 | println!(\"r: {}\", r);
+   |                   - borrow later used here
+"
+    );
+    assert_eq!(run.violations[2].line(), 18);
+}
+
+#[test]
+fn borrows_end_at_last_use() {
+    // 非词法生命周期：r1 最后一次使用之后可以可变借用
+    let nll = "
+let mut s = String::from(\"hello\");
+let r1 = &s;
+println!(\"{r1}\");
+let r2 = &mut s;
+r2.push_str(\", world\");
+println!(\"{s}\");
+";
+    assert!(run(nll).violations.is_empty());
+
+    // 同一段代码，r1 在可变借用之后再用一次就不行了
+    let late = format!("{nll}println!(\"{{r1}}\");\n");
+    let run = run(&late);
+    assert_eq!(run.codes(), ["E0502"]);
+    let labels: Vec<&str> = run.violations[0]
+        .labels
+        .iter()
+        .map(|label| label.text.as_str())
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "immutable borrow occurs here",
+            "mutable borrow occurs here",
+            "immutable borrow later used here"
+        ]
+    );
+    // println!("{s}") 时 r2 已经不再使用，和 r1 一样只是不可变借用，不冲突
+    assert_eq!(run.violations[0].line(), 5);
+
+    // 两个可变借用：第一个不再使用就没有冲突
+    let sequential =
+        "let mut v = vec![1, 2];\nlet a = &mut v;\na.push(3);\nlet b = &mut v;\nb.push(4);\n";
+    assert!(self::run(sequential).violations.is_empty());
+    let overlapping = format!("{sequential}a.push(5);\n");
+    assert_eq!(self::run(&overlapping).codes(), ["E0499"]);
+}
+
+#[test]
+fn moves_copies_and_scopes() {
+    let run = run("
+let x = 5;
+let y = x;
+let t = (x, 1.5);
+let u = t;
+let v = vec![String::from(\"a\")];
+let w = v;
+let n = v.len();
+let s = String::from(\"hi\");
+let r = &s;
+let moved = s;
+println!(\"{t:?} {u:?} {r}\");
+");
+    // 整数、元组是 Copy；Vec 和 String 被移动
+    assert_eq!(run.codes(), ["E0382", "E0505"]);
+    assert_eq!(run.violations[0].message, "borrow of moved value: `v`");
+    assert_eq!(
+        run.violations[0].note.as_deref(),
+        Some(
+            "move occurs because `v` has type `Vec<String>`, which does not implement the `Copy` trait"
+        )
+    );
+    assert_eq!(
+        run.violations[1].message,
+        "cannot move out of `s` because it is borrowed"
+    );
+
+    // 块内借用的值在块外使用：E0597；块内用完则没问题
+    let inner =
+        "let r;\n{\n    let s = String::from(\"a\");\n    r = &s;\n    println!(\"{r}\");\n}\n";
+    assert!(self::run(inner).violations.is_empty());
+    let outer = format!("{inner}println!(\"{{r}}\");\n");
+    assert_eq!(self::run(&outer).codes(), ["E0597"]);
+}
+
+#[test]
+fn other_ownership_errors() {
+    let codes = |source: &str| run(source).codes();
+    assert_eq!(codes("let x = 1;\nx = 2;"), ["E0384"]);
+    assert_eq!(codes("let x: i32;\nprintln!(\"{}\", x);"), ["E0381"]);
+    assert_eq!(
+        codes("let x: i32;\nx = 1;\nprintln!(\"{x}\");"),
+        [] as [&str; 0]
+    );
+    assert_eq!(codes("println!(\"{}\", missing);"), ["E0425"]);
+    assert_eq!(codes("let s = String::new();\ns.push('a');"), ["E0596"]);
+    assert_eq!(
+        codes("let mut s = String::new();\nlet r = &s;\nr.push('a');"),
+        ["E0596"]
+    );
+    assert_eq!(
+        codes("let mut s = String::new();\nlet r = &s;\n*r = String::new();"),
+        ["E0594"]
+    );
+    assert_eq!(
+        codes("let mut x = 1;\nlet r = &x;\nx = 2;\nprintln!(\"{r}\");"),
+        ["E0506"]
+    );
+    assert_eq!(
+        codes("let mut x = 1;\nlet m = &mut x;\nlet y = x;\n*m += 1;"),
+        ["E0503"]
+    );
+    assert_eq!(
+        codes("let s = String::new();\nlet r = &s;\nlet t = *r;"),
+        ["E0507"]
+    );
+    assert_eq!(codes("calculate_length();"), ["E0061"]);
+    assert_eq!(codes("fn longest(x: &str, y: &str) -> &str {}"), ["E0106"]);
+    assert_eq!(
+        codes("fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {}"),
+        [] as [&str; 0]
+    );
+
+    let error = |source: &str| Program::parse(source).unwrap_err().to_string();
+    assert_eq!(
+        error("{\nlet x = 1;"),
+        "第 2 行: this file contains an unclosed delimiter `{`"
+    );
+    assert_eq!(
+        error("let x = 1;\n}"),
+        "第 2 行: unexpected closing delimiter: `}`"
+    );
+    assert_eq!(error("let = 5;"), "第 1 行: expected identifier, found `=`");
+
+    // 不支持的语法不是未定义的名字，不报告 E0425
+    for (source, keyword) in [
+        ("let x = 5;\nif x > 3 {\nprintln!(\"{}\", x);\n}", "if"),
+        ("let x = 5;\nlet y = if x { 1 } else { 2 };", "if"),
+        ("let x = 5;\nwhile(x) {}", "while"),
+        ("loop {}", "loop"),
+        ("struct Point { x: i32 }", "struct"),
+    ] {
+        let error = Program::parse(source).unwrap_err();
+        assert!(error.unsupported, "{source}");
+        assert!(!error.to_string().contains("E0"), "{error}");
+        assert!(
+            error.message.starts_with(&format!("`{keyword}`")),
+            "{error}"
+        );
+    }
+    assert_eq!(
+        error("let x = 5;\nreturn x;"),
+        "第 2 行: 模拟器不支持的语法: `return`（只支持直线代码，没有 if、match、循环和类型定义）"
+    );
+    // 真正没有定义的名字仍然是 E0425
+    let unbound = run("let y = z;\nfoo(1);");
+    assert_eq!(unbound.codes(), ["E0425", "E0425"]);
+    assert_eq!(
+        unbound.violations[0].message,
+        "cannot find value `z` in this scope"
+    );
+    assert_eq!(
+        unbound.violations[1].message,
+        "cannot find function `foo` in this scope"
+    );
+    assert!(!Program::parse("let = 5;").unwrap_err().unsupported);
+}
+
+#[test]
+fn trace_prints_the_ownership_table_after_each_step() {
+    let run = run("
+let mut s = String::from(\"hello\");
+let r1 = &s;
+{
+    let s2 = s.clone();
+    takes_ownership(s2);
+}
+println!(\"{r1}\");
+let r2 = &mut s;
+r2.push('!');
+");
+    assert!(run.violations.is_empty());
+    assert_eq!(
+        run.trace(),
+        "\
+第 2 行  let mut s = String::from(\"hello\");
+  s  String  有效
+
+第 3 行  let r1 = &s;
+  s   String   有效，被 r1 借用
+  r1  &String  借用 s
+
+第 4 行  {
+  s   String   有效，被 r1 借用
+  r1  &String  借用 s
+
+第 5 行  let s2 = s.clone();
+  s     String   有效，被 r1 借用
+  r1    &String  借用 s
+    s2  String   有效
+
+第 6 行  takes_ownership(s2);
+  s     String   有效，被 r1 借用
+  r1    &String  借用 s
+    s2  String   已移动到 takes_ownership()
+
+第 7 行  }
+  s   String   有效，被 r1 借用
+  r1  &String  借用 s
+
+第 8 行  println!(\"{r1}\");
+  s   String   有效
+  r1  &String  借用 s，之后不再使用
+
+第 9 行  let r2 = &mut s;
+  s   String       有效，被 r2 可变借用
+  r1  &String      借用 s，之后不再使用
+  r2  &mut String  可变借用 s
+
+第 10 行  r2.push('!');
+  s   String       有效
+  r1  &String      借用 s，之后不再使用
+  r2  &mut String  可变借用 s，之后不再使用
+
+"
+    );
+}
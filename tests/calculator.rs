@@ -0,0 +1,219 @@
+// ============================================
+// 表达式计算器测试
+// ============================================
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rust_tutorial::arithmetic::{self, Op};
+use rust_tutorial::calculator::{self, Arity, Calculator, ErrorKind, FnResult};
+
+fn eval(source: &str) -> i64 {
+    calculator::eval(source).unwrap_or_else(|error| panic!("{source}: {error}"))
+}
+
+fn error(source: &str) -> calculator::Error {
+    calculator::eval(source).expect_err(source)
+}
+
+#[test]
+fn expressions_from_chapter_03() {
+    let snapshot = include_str!("snapshots/03_functions.txt");
+    for (expression, line) in [
+        ("3 + 4", "3 + 4 = 7"),
+        ("5 * 6", "5 * 6 = 30"),
+        ("7 + 8", "7 + 8 = 15"),
+    ] {
+        assert!(snapshot.contains(line));
+        assert_eq!(format!("{expression} = {}", eval(expression)), line);
+    }
+    assert_eq!(eval("10 / 2"), 5);
+    assert_eq!(eval("3 + 4 * 2"), 11);
+}
+
+#[test]
+fn precedence_and_associativity() {
+    assert_eq!(eval("(3 + 4) * 2"), 14);
+    assert_eq!(eval("10 - 4 - 3"), 3);
+    assert_eq!(eval("100 / 10 / 5"), 2);
+    assert_eq!(eval("2 ^ 3 ^ 2"), 512);
+    assert_eq!(eval("2 * 3 ^ 2"), 18);
+    assert_eq!(eval("17 % 5 * 2"), 4);
+    // 一元负号比 ^ 低、比 * 高
+    assert_eq!(eval("-2 ^ 2"), -4);
+    assert_eq!(eval("(-2) ^ 2"), 4);
+    assert_eq!(eval("2 * -3"), -6);
+    assert_eq!(eval("--5"), 5);
+    assert_eq!(eval("2 ^ -0"), 1);
+    // 除法和求余向零取整
+    assert_eq!(eval("-7 / 2"), -3);
+    assert_eq!(eval("-7 % 2"), -1);
+    assert_eq!(eval("1_000 * 1_000"), 1_000_000);
+}
+
+#[test]
+fn functions_and_variables() {
+    assert_eq!(eval("max(2, 7) ^ 2 % 10"), 9);
+    assert_eq!(eval("min(4, -1, 9)"), -1);
+    assert_eq!(eval("abs(-12) + gcd(12, -18)"), 18);
+    assert_eq!(eval("sqrt(99)"), 9);
+
+    let mut calculator = Calculator::new();
+    assert_eq!(calculator.eval("let x = 6"), Ok(6));
+    assert_eq!(calculator.eval("let y = x * 7"), Ok(42));
+    assert_eq!(calculator.eval("let x = x + 1"), Ok(7));
+    assert_eq!(calculator.eval("y - x"), Ok(35));
+    let variables: Vec<(&str, i64)> = calculator
+        .variables()
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect();
+    assert_eq!(variables, [("x", 7), ("y", 42)]);
+
+    // 出错的 let 不会定义变量
+    assert!(calculator.eval("let z = 1 / 0").is_err());
+    assert!(!calculator.variables().contains_key("z"));
+}
+
+#[test]
+fn registry_accepts_function_pointers_and_closures() {
+    fn add(args: &[i64]) -> FnResult {
+        arithmetic::add(args[0], args[1]).map_err(|error| error.to_string())
+    }
+
+    let mut calculator = Calculator::new();
+    // 和第 03 章的 `let func: fn(i32, i32) -> i32 = add;` 一样，先存进函数指针类型的变量
+    let func: fn(&[i64]) -> FnResult = add;
+    calculator.define("add", Arity::Exactly(2), func);
+    assert_eq!(calculator.eval("add(7, 8)"), Ok(15));
+
+    // 闭包捕获环境中的值
+    let factor = 3;
+    calculator.define_closure(
+        "triple",
+        Arity::Exactly(1),
+        move |args| Ok(args[0] * factor),
+    );
+    assert_eq!(calculator.eval("triple(add(1, 2))"), Ok(9));
+
+    // 闭包通过共享的 Cell 记录调用次数
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    calculator.define_closure("count", Arity::AtLeastOne, move |args| {
+        counter.set(counter.get() + 1);
+        Ok(args.len() as i64)
+    });
+    assert_eq!(calculator.eval("count(1, 2, 3) + count(4)"), Ok(4));
+    assert_eq!(calls.get(), 2);
+
+    let names: Vec<&str> = calculator
+        .functions()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(
+        names,
+        ["abs", "add", "count", "gcd", "max", "min", "sqrt", "triple"]
+    );
+
+    let e = calculator.eval("add(9223372036854775807, 1)").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "add: 结果大于 i64 的最大值 9223372036854775807: 9223372036854775807 + 1"
+    );
+}
+
+#[test]
+fn errors_point_at_the_problem() {
+    let e = error("7 / (2 - 2)");
+    assert_eq!(e.column, 3);
+    assert_eq!(
+        e.kind,
+        ErrorKind::Arithmetic(arithmetic::Error::DivisionByZero {
+            op: Op::Divide,
+            dividend: 7
+        })
+    );
+    assert_eq!(
+        e.render("7 / (2 - 2)"),
+        "7 / (2 - 2)\n  ^ 除数为零: 7 / 0\n"
+    );
+
+    let e = error("3 + * 2");
+    assert_eq!(e.column, 5);
+    assert_eq!(
+        e.to_string(),
+        "这里需要数字、变量、函数调用或 `(`，却遇到了 `*`"
+    );
+    assert_eq!(
+        error("(1 + 2").to_string(),
+        "这里需要 `)`，却遇到了 输入结尾"
+    );
+    assert_eq!(error("1 2").column, 3);
+    assert_eq!(error("2 # 3").kind, ErrorKind::UnexpectedChar('#'));
+    assert_eq!(
+        error("99999999999999999999").kind,
+        ErrorKind::NumberTooLarge(String::from("99999999999999999999"))
+    );
+    assert_eq!(error("x + 1").to_string(), "没有定义变量 x");
+    assert_eq!(error("foo(1)").to_string(), "没有名为 foo 的函数");
+    assert_eq!(
+        error("gcd(1)").to_string(),
+        "gcd 需要 2 个参数，传入了 1 个"
+    );
+    assert_eq!(
+        error("max()").to_string(),
+        "max 至少需要 1 个参数，传入了 0 个"
+    );
+    assert_eq!(error("2 ^ -1").to_string(), "指数不能为负数: 2.pow(-1)");
+    assert!(matches!(
+        error("-(-9223372036854775807 - 1)").kind,
+        ErrorKind::Arithmetic(arithmetic::Error::Overflow { .. })
+    ));
+    assert_eq!(error("let 1 = 2").column, 5);
+}
+
+#[test]
+fn deep_nesting_is_an_error_not_a_stack_overflow() {
+    let depth = calculator::MAX_DEPTH;
+    // 括号、负号、^ 链和长长的 + 链都让语法树变深
+    let parens = format!("{}1{}", "(".repeat(50_000), ")".repeat(50_000));
+    let e = error(&parens);
+    assert_eq!(e.kind, ErrorKind::TooDeep);
+    // 指向第 MAX_DEPTH + 1 个括号
+    assert_eq!(e.column, depth + 1);
+    assert_eq!(e.to_string(), format!("表达式嵌套太深，最多 {depth} 层"));
+    for source in [
+        "-".repeat(50_000) + "1",
+        vec!["1"; 50_000].join(" ^ "),
+        vec!["1"; 50_000].join(" + "),
+        "abs(".repeat(50_000),
+    ] {
+        assert_eq!(
+            calculator::parse(&source).unwrap_err().kind,
+            ErrorKind::TooDeep
+        );
+    }
+
+    // 限制以内的照常计算
+    let nested = format!("{}1{}", "(".repeat(100), ")".repeat(100));
+    assert_eq!(eval(&nested), 1);
+    assert_eq!(eval(&vec!["1"; 200].join(" + ")), 200);
+    assert_eq!(eval(&("-".repeat(100) + "7")), 7);
+}
+
+#[test]
+fn repl_session() {
+    let input = "\
+let r = 5
+3 * r ^ 2
+:vars
+r / 0
+:quit
+";
+    let mut out = Vec::new();
+    calculator::repl(&mut input.as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("calc> 5\ncalc> 75\ncalc> r = 5\n"));
+    assert!(out.contains("calc> r / 0\n  ^ 除数为零: 5 / 0\n"));
+}
@@ -0,0 +1,176 @@
+// ============================================
+// 闭包捕获场景测试
+// ============================================
+
+use rust_tutorial::capture::{self, ClosureTrait, Event, Log, Tracked};
+
+fn timeline(name: &str) -> Vec<String> {
+    let scenario = capture::find(name).unwrap();
+    scenario
+        .timeline()
+        .iter()
+        .map(|event| event.to_string())
+        .collect()
+}
+
+#[test]
+fn fn_closure_only_borrows() {
+    assert_eq!(
+        timeline("borrow"),
+        [
+            "创建 list",
+            "调用 only_borrows（Fn）",
+            "借用 &list",
+            "调用 only_borrows（Fn）",
+            "借用 &list",
+            "借用 &list",
+            "-- list 仍有 3 个元素",
+            "-- 作用域结束",
+            "释放 list",
+        ]
+    );
+}
+
+#[test]
+fn fn_mut_closure_borrows_mutably() {
+    assert_eq!(
+        timeline("mutate"),
+        [
+            "创建 counter",
+            "调用 increment（FnMut）",
+            "可变借用 &mut counter",
+            "调用 increment（FnMut）",
+            "可变借用 &mut counter",
+            "借用 &counter",
+            "-- counter = 2",
+            "-- 作用域结束",
+            "释放 counter",
+        ]
+    );
+}
+
+#[test]
+fn fn_once_closure_drops_during_the_call() {
+    // 捕获的值在调用中释放，早于调用之后的任何事件
+    assert_eq!(
+        timeline("consume"),
+        [
+            "创建 s",
+            "调用 takes_ownership（FnOnce）",
+            "借用 &s",
+            "释放 s",
+            "-- 调用结束",
+        ]
+    );
+}
+
+#[test]
+fn moved_values_live_as_long_as_the_closure() {
+    assert_eq!(
+        timeline("move"),
+        [
+            "创建 s",
+            "移动 s",
+            "调用 greet（Fn）",
+            "借用 &s",
+            "调用 greet（Fn）",
+            "借用 &s",
+            "-- drop(greet)",
+            "释放 s",
+            "-- 作用域结束",
+        ]
+    );
+    assert_eq!(
+        timeline("clone"),
+        [
+            "创建 data",
+            "克隆 data → data'",
+            "移动 data'",
+            "调用 sum（Fn）",
+            "借用 &data'",
+            "借用 &data",
+            "-- 原来的 data 仍有 3 个元素",
+            "-- drop(sum)",
+            "释放 data'",
+            "-- 作用域结束",
+            "释放 data",
+        ]
+    );
+    assert_eq!(
+        timeline("return"),
+        [
+            "创建 count",
+            "-- make_counter 返回",
+            "调用 next（FnMut）",
+            "可变借用 &mut count",
+            "借用 &count",
+            "调用 next（FnMut）",
+            "可变借用 &mut count",
+            "借用 &count",
+            "-- drop(next)",
+            "释放 count",
+            "-- 作用域结束",
+        ]
+    );
+}
+
+#[test]
+fn every_scenario_calls_through_its_trait_and_releases_everything() {
+    for scenario in capture::scenarios() {
+        let events = scenario.timeline();
+        let calls: Vec<ClosureTrait> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Call { via, .. } => Some(*via),
+                _ => None,
+            })
+            .collect();
+        assert!(!calls.is_empty(), "{}", scenario.name);
+        assert!(calls.iter().all(|via| *via == scenario.implements));
+
+        // 每个创建或克隆出的值都恰好释放一次
+        let mut alive: Vec<&str> = Vec::new();
+        for event in &events {
+            match event {
+                Event::Create(name) | Event::Clone { to: name, .. } => alive.push(name),
+                Event::Drop(name) => {
+                    let i = alive.iter().position(|n| n == name).unwrap();
+                    alive.remove(i);
+                }
+                _ => {}
+            }
+        }
+        assert!(alive.is_empty(), "{}: {alive:?}", scenario.name);
+
+        let rendered = scenario.render();
+        assert!(rendered.starts_with(&format!("[{}] ", scenario.name)));
+        assert_eq!(rendered.lines().count(), events.len() + 1);
+    }
+}
+
+#[test]
+fn tracked_values_log_their_own_lifecycle() {
+    let log = Log::default();
+    {
+        let mut a = Tracked::new(&log, "a", vec![1]);
+        a.get_mut().push(2);
+        let b = a.clone();
+        assert_eq!(b.name(), "a'");
+        assert_eq!(b.get(), &[1, 2]);
+        let c = b.moved();
+        drop(c);
+    }
+    let events: Vec<String> = log.events().iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        events,
+        [
+            "创建 a",
+            "可变借用 &mut a",
+            "克隆 a → a'",
+            "借用 &a'",
+            "移动 a'",
+            "释放 a'",
+            "释放 a",
+        ]
+    );
+}
@@ -0,0 +1,212 @@
+// ============================================
+// as 类型转换表测试
+// ============================================
+
+use rust_tutorial::casts::{self, Conversion, InvalidCast, Trait, Type, Value};
+
+fn cast(value: Value, to: Type) -> String {
+    casts::cast(value, to).unwrap().to_string()
+}
+
+#[test]
+fn integer_as_u8_as_char_from_chapter_02() {
+    let snapshot = include_str!("snapshots/02_data_types.txt");
+    let integer = Value::Signed(Type::I32, 65);
+    let byte = casts::cast(integer, Type::U8).unwrap();
+    assert_eq!(byte, Value::Unsigned(Type::U8, 65));
+    assert_eq!(casts::cast(byte, Type::Char), Ok(Value::Char('A')));
+    assert!(snapshot.contains("integer 65 as char: A\n"));
+
+    // i32 不能直接转换成 char
+    let error = casts::cast(integer, Type::Char).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "error[E0604]: only `u8` can be cast as `char`, not `i32`"
+    );
+    assert_eq!(
+        error.help(),
+        Some("try `char::from_u32` instead (via a `u32`)")
+    );
+}
+
+#[test]
+fn truncation_sign_change_and_saturation() {
+    assert_eq!(cast(Value::Signed(Type::I32, 300), Type::U8), "44");
+    assert_eq!(cast(Value::Signed(Type::I32, -1), Type::U32), "4294967295");
+    assert_eq!(cast(Value::Unsigned(Type::U8, 255), Type::I8), "-1");
+    assert_eq!(cast(Value::Signed(Type::I8, -128), Type::U16), "65408");
+
+    // 浮点数转整数向零取整并饱和，NaN 变成 0
+    assert_eq!(cast(Value::Float(Type::F64, -1.9), Type::I32), "-1");
+    assert_eq!(cast(Value::Float(Type::F64, -1.9), Type::U8), "0");
+    assert_eq!(cast(Value::Float(Type::F64, f64::NAN), Type::I32), "0");
+    assert_eq!(
+        cast(Value::Float(Type::F64, f64::INFINITY), Type::U8),
+        "255"
+    );
+    assert_eq!(
+        cast(Value::Float(Type::F32, f64::NEG_INFINITY), Type::I64),
+        i64::MIN.to_string()
+    );
+
+    // 超出 f32 范围的值变成 inf
+    assert_eq!(
+        cast(Value::Unsigned(Type::U128, u128::MAX), Type::F32),
+        "inf"
+    );
+    assert_eq!(cast(Value::Float(Type::F64, f64::MAX), Type::F32), "inf");
+    assert_eq!(
+        cast(Value::Unsigned(Type::U32, u32::MAX.into()), Type::F32),
+        "4294967300.0"
+    );
+
+    // char 转整数截断到低位
+    assert_eq!(cast(Value::Char('😻'), Type::U16), "63035");
+    assert_eq!(cast(Value::Char(char::MAX), Type::U32), "1114111");
+    assert_eq!(cast(Value::Bool(true), Type::I128), "1");
+}
+
+#[test]
+fn invalid_casts_match_rustc() {
+    let error = |from: Value, to| casts::cast(from, to).unwrap_err();
+    let bool_error = error(Value::Signed(Type::I32, 1), Type::Bool);
+    assert_eq!(
+        bool_error,
+        InvalidCast {
+            from: Type::I32,
+            to: Type::Bool
+        }
+    );
+    assert_eq!(
+        bool_error.to_string(),
+        "error[E0054]: cannot cast `i32` as `bool`"
+    );
+    assert_eq!(
+        bool_error.help(),
+        Some("compare with zero instead: `x != 0`")
+    );
+    assert_eq!(
+        error(Value::Bool(true), Type::F64).to_string(),
+        "error[E0606]: casting `bool` as `f64` is invalid"
+    );
+    assert_eq!(
+        error(Value::Char('A'), Type::F32).help(),
+        Some("cast through an integer first")
+    );
+    assert_eq!(
+        error(Value::Float(Type::F32, 1.0), Type::Char).code(),
+        "E0604"
+    );
+    assert_eq!(
+        casts::cast(Value::Char('x'), Type::Char),
+        Ok(Value::Char('x'))
+    );
+}
+
+#[test]
+fn from_and_try_from() {
+    // 0x110000 不是合法的 Unicode 标量值
+    let scalar = Value::Unsigned(Type::U32, 0x110000);
+    assert_eq!(
+        casts::convert(scalar, Type::Char),
+        Conversion::TryFrom(None)
+    );
+    assert_eq!(
+        casts::convert(Value::Unsigned(Type::U32, 0x1F63B), Type::Char),
+        Conversion::TryFrom(Some(Value::Char('😻')))
+    );
+    assert_eq!(
+        casts::convert(Value::Char('Ā'), Type::U8),
+        Conversion::TryFrom(None)
+    );
+    assert_eq!(
+        casts::convert(Value::Unsigned(Type::U8, 65), Type::Char),
+        Conversion::From(Value::Char('A'))
+    );
+
+    // bool 不能用 as 转成 f64，但有 From
+    assert_eq!(
+        casts::convert(Value::Bool(true), Type::F64),
+        Conversion::From(Value::Float(Type::F64, 1.0))
+    );
+    // 浮点数和整数之间只有 as
+    assert_eq!(
+        casts::convert(Value::Float(Type::F64, 1.0), Type::I32),
+        Conversion::Missing
+    );
+    assert_eq!(
+        casts::convert(Value::Signed(Type::I64, 1), Type::F64),
+        Conversion::Missing
+    );
+    // 位数取决于平台的 isize、usize 只有少数 From
+    assert_eq!(
+        casts::convert(Value::Unsigned(Type::U16, 1), Type::Usize).provided_by(),
+        Some(Trait::From)
+    );
+    assert_eq!(
+        casts::convert(Value::Unsigned(Type::U32, 1), Type::Usize).provided_by(),
+        Some(Trait::TryFrom)
+    );
+}
+
+#[test]
+fn conversions_agree_with_as_when_they_succeed() {
+    for table in casts::tables() {
+        assert_eq!(table.rows.len(), Type::ALL.len());
+        for row in &table.rows {
+            for cell in &row.cells {
+                let converted = match cell.conversion {
+                    Conversion::From(value) | Conversion::TryFrom(Some(value)) => value,
+                    _ => continue,
+                };
+                assert_eq!(converted.ty(), row.to);
+                if let Ok(value) = cell.cast {
+                    assert!(value.same(converted), "{} as {}", cell.input, row.to);
+                }
+            }
+            // From 和 TryFrom 只取决于类型，不取决于值
+            let traits: Vec<_> = row
+                .cells
+                .iter()
+                .map(|cell| cell.conversion.provided_by())
+                .collect();
+            assert!(traits.windows(2).all(|pair| pair[0] == pair[1]));
+        }
+    }
+
+    // 整数之间：值不变时 TryFrom 成功，变了（截断或符号改变）时失败
+    for table in casts::tables()
+        .iter()
+        .filter(|table| table.from.is_integer())
+    {
+        for row in table.rows.iter().filter(|row| row.to.is_integer()) {
+            for cell in &row.cells {
+                let unchanged = cell.cast.unwrap().to_string() == cell.input.to_string();
+                let succeeded = cell.conversion != Conversion::TryFrom(None);
+                assert_eq!(unchanged, succeeded, "{} as {}", cell.input, row.to);
+            }
+        }
+    }
+}
+
+#[test]
+fn renders_terminal_and_markdown_tables() {
+    let table = casts::table(Type::U8);
+    let rendered = table.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 17);
+    assert!(lines[0].starts_with("u8 →"));
+    assert!(rendered.contains("i8     as     TryFrom  0     65    -1 ✗\n"));
+    assert!(rendered.contains("char   as     From     '\\0'  'A'   'ÿ'\n"));
+    assert!(rendered.contains("bool   E0054  —        —     —     —\n"));
+
+    let markdown = table.markdown();
+    assert!(markdown.starts_with("| 目标 | as | trait | `0` | `65` | `255` |\n"));
+    assert!(markdown.contains("| `i8` | as | TryFrom | 0 | 65 | -1 ✗ |\n"));
+    assert_eq!(markdown.lines().count(), 18);
+
+    let page = casts::markdown();
+    assert!(page.starts_with("# 附录：as 类型转换\n"));
+    assert_eq!(page.matches("\n## 从 `").count(), Type::ALL.len());
+    assert!(page.contains("| `char` | E0604 | TryFrom | '\\0' | ✗ | ✗ |\n"));
+}
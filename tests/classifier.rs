@@ -0,0 +1,243 @@
+// ============================================
+// 范围规则分类器测试
+// ============================================
+
+use rust_tutorial::classifier::{Diagnostic, IntervalSet, RuleSet};
+use rust_tutorial::integer::IntType;
+
+fn rules(text: &str) -> RuleSet {
+    RuleSet::parse(text).unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn label(rules: &RuleSet, value: i128) -> Option<&str> {
+    rules.classify(value).map(|rule| rule.label.as_str())
+}
+
+fn diagnostics(text: &str) -> Vec<String> {
+    rules(text).check().iter().map(|d| d.to_string()).collect()
+}
+
+#[test]
+fn reproduces_chapters_04_and_08() {
+    let ranges = rules(include_str!("../rules/ranges.txt"));
+    let sizes = rules(include_str!("../rules/sizes.txt"));
+    assert!(ranges.check().is_empty());
+    assert!(sizes.check().is_empty());
+
+    // 两章中 number 分别是 5 和 42
+    for snapshot in [
+        include_str!("snapshots/04_control_flow.txt"),
+        include_str!("snapshots/08_pattern_matching.txt"),
+    ] {
+        let expected = format!(
+            "{}\n{}\n",
+            label(&sizes, 5).unwrap(),
+            label(&ranges, 42).unwrap()
+        );
+        assert!(snapshot.contains(&expected), "{expected}");
+    }
+
+    assert_eq!(label(&ranges, 1), Some("1 到 10"));
+    assert_eq!(label(&ranges, 10), Some("1 到 10"));
+    assert_eq!(label(&ranges, 11), Some("11 到 20"));
+    assert_eq!(label(&ranges, 50), Some("21 到 50"));
+    assert_eq!(label(&ranges, 0), Some("其他"));
+    assert_eq!(label(&ranges, i32::MIN.into()), Some("其他"));
+    assert_eq!(label(&sizes, 9), Some("大"));
+    assert_eq!(label(&sizes, 10), Some("其他"));
+}
+
+#[test]
+fn patterns_bindings_and_guards() {
+    let rules = rules(
+        "\
+type i8
+# 注释和空行会被跳过
+
+..=-100 => 很小
+n @ -99..0 if n % 2 == 0 => 负偶数
+-99..0 => 负奇数
+0 | 0x7f => 边界
+n @ (1 | 3 | 5) => 奇数
+n if n >= 10 && n % 10 == 0 => 整十
+100.. => 很大
+_ => 其他
+",
+    );
+    assert_eq!(rules.ty, IntType::I8);
+    assert_eq!(rules.rules.len(), 8);
+    assert_eq!(rules.rules[0].line, 4);
+    assert_eq!(rules.rules[1].binding.as_deref(), Some("n"));
+    assert_eq!(rules.rules[1].guard.as_ref().unwrap().text, "n % 2 == 0");
+
+    assert_eq!(label(&rules, -128), Some("很小"));
+    assert_eq!(label(&rules, -100), Some("很小"));
+    assert_eq!(label(&rules, -98), Some("负偶数"));
+    // 和 Rust 一样，-3 % 2 == -1
+    assert_eq!(label(&rules, -3), Some("负奇数"));
+    assert_eq!(label(&rules, 127), Some("边界"));
+    assert_eq!(label(&rules, 3), Some("奇数"));
+    assert_eq!(label(&rules, 90), Some("整十"));
+    assert_eq!(label(&rules, 101), Some("很大"));
+    assert_eq!(label(&rules, 4), Some("其他"));
+    // 超出 i8 的范围
+    assert_eq!(label(&rules, 128), None);
+    assert!(rules.classify_text("128").is_err());
+    assert_eq!(
+        rules.classify_text("-0x80").unwrap().map(|r| r.line),
+        Some(4)
+    );
+}
+
+#[test]
+fn reports_unreachable_and_overlapping_rules() {
+    assert_eq!(
+        diagnostics(
+            "\
+1..=10 => a
+5..=15 => b
+3 | 12 => c
+n @ 20..=30 if n > 25 => d
+25 => e
+_ => f
+_ => g
+"
+        ),
+        [
+            "第 2 行: warning: `5..=15` 与第 1 行在 5..=10 上重叠",
+            "第 3 行: warning: unreachable pattern `3`（已被第 1 行覆盖）",
+            "第 3 行: warning: unreachable pattern `12`（已被第 2 行覆盖）",
+            "第 7 行: warning: unreachable pattern `_`（已被第 1、2、5、6 行覆盖）",
+        ]
+    );
+
+    // 先写特例再写一般情况不算重叠；被几条规则合起来覆盖也是不可达
+    assert_eq!(
+        diagnostics(include_str!("../rules/http_status.txt")),
+        Vec::<String>::new()
+    );
+    let covered = rules("1..=5 => a\n6..=9 => b\n3..=8 => c\n_ => d\n").check();
+    assert_eq!(
+        covered,
+        [Diagnostic::Unreachable {
+            line: 3,
+            pattern: String::from("3..=8"),
+            covered_by: vec![1, 2],
+        }]
+    );
+}
+
+#[test]
+fn reports_gaps_like_rustc() {
+    // 没有 _ 时第 04 章的 match 不能通过编译
+    assert_eq!(
+        diagnostics("1..=10 => a\n11..=20 => b\n21..=50 => c\n"),
+        [
+            "error[E0004]: non-exhaustive patterns: `i32::MIN..=0_i32` and `51_i32..=i32::MAX` not covered"
+        ]
+    );
+    // 带 guard 的规则不算覆盖
+    assert_eq!(
+        diagnostics("type u8\nn if n < 128 => a\n128.. => b\n"),
+        ["error[E0004]: non-exhaustive patterns: `0_u8..=127_u8` not covered"]
+    );
+    assert_eq!(
+        diagnostics("type u8\n1 | 3 | 5 | 7 => odd\n9.. => big\n"),
+        ["error[E0004]: non-exhaustive patterns: `0_u8`, `2_u8`, `4_u8` and 2 more not covered"]
+    );
+    assert_eq!(
+        diagnostics("type i8\n..0 => a\n1.. => b\n..=-1 => c\n"),
+        [
+            "第 4 行: warning: unreachable pattern `..=-1`（已被第 2 行覆盖）",
+            "error[E0004]: non-exhaustive patterns: `0_i8` not covered",
+        ]
+    );
+    assert!(diagnostics("type u8\n0..=255 => all\n").is_empty());
+}
+
+#[test]
+fn rejects_malformed_rules() {
+    let error = |text: &str| RuleSet::parse(text).unwrap_err().to_string();
+    assert_eq!(error("1..=10 => a\n\n5 a\n"), "第 3 行: 缺少 `=>`");
+    assert_eq!(
+        error("10..=1 => a"),
+        "第 1 行: `10..=1`: lower range bound must be less than or equal to upper (E0030)"
+    );
+    assert_eq!(
+        error("5..5 => a"),
+        "第 1 行: `5..5`: lower range bound must be less than upper (E0579)"
+    );
+    assert_eq!(
+        error("type u8\n256 => a"),
+        "第 2 行: `256`: error: literal out of range for `u8`"
+    );
+    assert_eq!(
+        error("n @ 1 | 2 => a"),
+        "第 1 行: 绑定多个模式时要加括号，例如 n @ (1 | 2)（否则 n 只绑定第一个模式，E0408）"
+    );
+    assert_eq!(
+        error("1 if x > 0 => a"),
+        "第 1 行: cannot find value `x` in this scope (E0425)：条件中只能使用模式绑定的变量"
+    );
+    assert_eq!(
+        error("n if n % 0 == 1 => a"),
+        "第 1 行: `n % 0 == 1`: 除数为零"
+    );
+    assert_eq!(
+        error("1 => a\ntype u8"),
+        "第 2 行: type 必须写在所有规则之前"
+    );
+    assert_eq!(error("type u128"), "第 1 行: 不支持 u128");
+}
+
+#[test]
+fn tallies_input_streams() {
+    let rules = rules(include_str!("../rules/http_status.txt"));
+    let input = "200 201 304\n301 404\n\n410 418 503 abc\n700 70000\n";
+    let tally = rules.tally(input.as_bytes()).unwrap();
+    let counts: Vec<(&str, usize)> = tally
+        .counts
+        .iter()
+        .map(|(label, count)| (label.as_str(), *count))
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            ("信息", 0),
+            ("成功", 2),
+            ("未修改", 1),
+            ("重定向", 1),
+            ("资源不存在", 2),
+            ("客户端错误", 1),
+            ("服务器错误", 1),
+            ("无效状态码", 1),
+        ]
+    );
+    assert_eq!(tally.unmatched, 0);
+    assert_eq!(
+        tally.invalid,
+        [(4, String::from("abc")), (5, String::from("70000"))]
+    );
+    assert!(tally.render().starts_with("信息        0\n成功        2\n"));
+
+    // 没有 _ 时落在空隙中的值不属于任何分组
+    let partial = RuleSet::parse("1..=3 => a\n").unwrap();
+    let tally = partial.tally("1 2 9".as_bytes()).unwrap();
+    assert_eq!((tally.counts[0].1, tally.unmatched), (2, 1));
+    assert!(tally.render().ends_with("没有匹配的规则: 1\n"));
+}
+
+#[test]
+fn interval_sets_merge_and_find_gaps() {
+    let mut set = IntervalSet::new();
+    set.insert(5, 9);
+    set.insert(20, 30);
+    set.insert(10, 12);
+    assert_eq!(set.intervals(), [(5, 12), (20, 30)]);
+    assert!(set.covers(6, 12));
+    assert!(!set.covers(12, 20));
+    assert_eq!(set.gaps(0, 40), [(0, 4), (13, 19), (31, 40)]);
+    set.insert(i128::MIN, 0);
+    set.insert(25, i128::MAX);
+    assert_eq!(set.gaps(i128::MIN, i128::MAX), [(1, 4), (13, 19)]);
+}
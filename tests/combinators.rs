@@ -0,0 +1,171 @@
+// ============================================
+// 函数组合子测试
+// ============================================
+
+use std::cell::Cell;
+
+use rust_tutorial::combinators::{
+    apply_n, apply_twice, compose, compose_mut, compose_once, curry, curry3, flip, identity,
+    memoize, once, pipe, uncurry, uncurry3,
+};
+
+fn add_one(x: i32) -> i32 {
+    x + 1
+}
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[test]
+fn generalizes_chapter_03() {
+    // 第 03 章的版本只接受函数指针和 i32
+    let chapter = include_str!("../src/03_functions.rs");
+    assert!(chapter.contains("fn apply_twice(f: fn(i32) -> i32, x: i32) -> i32 {"));
+    assert_eq!(apply_twice(add_one, 5), 7);
+    assert_eq!(identity(42), 42);
+    assert_eq!(identity("hello"), "hello");
+
+    // 不再限于 i32 和函数指针
+    assert_eq!(apply_twice(|s: String| s + "!", String::from("hi")), "hi!!");
+    assert_eq!(apply_n(double, 10, 1), 1024);
+    assert_eq!(apply_n(add_one, 0, 5), 5);
+}
+
+#[test]
+fn compose_and_pipe_order() {
+    let double_then_add = compose(add_one, double);
+    assert_eq!(double_then_add(5), 11);
+    let add_then_double = pipe(add_one, double);
+    assert_eq!(add_then_double(5), 12);
+
+    // 参数和结果的类型可以不同
+    let length_squared = pipe(str::len, |n: usize| n * n);
+    assert_eq!(length_squared("hello"), 25);
+    let describe = compose(
+        |n: usize| format!("{n} 个字符"),
+        |s: &str| s.chars().count(),
+    );
+    assert_eq!(describe("你好"), "2 个字符");
+
+    let minus = |a: i32, b: i32| a - b;
+    assert_eq!(flip(minus)(1, 10), 9);
+}
+
+#[test]
+fn fn_closures_borrow_their_environment() {
+    // 只读取捕获的变量：闭包是 Fn，可以通过 & 多次调用，原变量仍可使用
+    let offset = 100;
+    let names = ["a", "b", "c"];
+    let shifted = compose(move |x: i32| x + offset, |i: usize| i as i32);
+    let lookup = |i: usize| names[i];
+    let lookup_then_len = pipe(&lookup, str::len);
+    assert_eq!(shifted(2), 102);
+    assert_eq!(lookup_then_len(1), 1);
+    assert_eq!(lookup(2), "c");
+    assert_eq!(names.len(), 3);
+    assert_eq!(offset, 100);
+}
+
+#[test]
+fn fn_mut_closures_mutate_their_environment() {
+    // 修改捕获的变量：闭包是 FnMut，apply_n 和 compose_mut 接受它
+    let mut calls = 0;
+    let result = apply_n(
+        |x: i32| {
+            calls += 1;
+            x * 3
+        },
+        4,
+        1,
+    );
+    assert_eq!((result, calls), (81, 4));
+
+    let mut log = Vec::new();
+    let mut total = 0;
+    {
+        let mut record = compose_mut(
+            |x: i32| {
+                total += x;
+                total
+            },
+            |x: i32| {
+                log.push(x);
+                x * 10
+            },
+        );
+        assert_eq!(record(1), 10);
+        assert_eq!(record(2), 30);
+    }
+    // record 离开作用域后可变借用结束
+    assert_eq!(log, [1, 2]);
+    assert_eq!(total, 30);
+}
+
+#[test]
+fn fn_once_closures_move_out_of_their_environment() {
+    // 把捕获的 String 移动出去：闭包只实现 FnOnce
+    let greeting = String::from("hello");
+    let consume = move |suffix: &str| greeting + suffix;
+    let shout = compose_once(|s: String| s.to_uppercase(), consume);
+    assert_eq!(shout(", world"), "HELLO, WORLD");
+
+    let items = vec![1, 2, 3];
+    let mut take_items = once(move |extra: i32| {
+        let mut items = items;
+        items.push(extra);
+        items
+    });
+    assert_eq!(take_items(4), Some(vec![1, 2, 3, 4]));
+    assert_eq!(take_items(5), None);
+    assert_eq!(take_items(6), None);
+}
+
+#[test]
+fn curry_and_uncurry_round_trip() {
+    let add = |a: i32, b: i32| a + b;
+    let curried = curry(add);
+    let add_ten = curried(10);
+    assert_eq!(add_ten(5), 15);
+    assert_eq!(add_ten(-10), 0);
+    assert_eq!(curried(1)(2), 3);
+    assert_eq!(uncurry(curry(add))(20, 22), 42);
+
+    // 捕获的参数在内层闭包每次调用时克隆
+    let join = |a: String, b: String, c: &str| format!("{a}{b}{c}");
+    let hello = curry3(join)(String::from("hello"));
+    let hello_world = hello(String::from(", world"));
+    assert_eq!(hello_world("!"), "hello, world!");
+    assert_eq!(hello_world("?"), "hello, world?");
+
+    let volume = |x: i32, y: i32, z: i32| x * y * z;
+    assert_eq!(uncurry3(curry3(volume))(2, 3, 4), 24);
+    let by_hand = |a: i32| move |b: i32| move |c: i32| a - b - c;
+    assert_eq!(uncurry3(by_hand)(10, 3, 2), 5);
+}
+
+#[test]
+fn memoize_calls_each_key_once() {
+    let calls = Cell::new(0);
+    let slow_square = |n: u64| {
+        calls.set(calls.get() + 1);
+        n * n
+    };
+    let square = memoize(slow_square);
+    assert_eq!(square(12), 144);
+    assert_eq!(square(12), 144);
+    assert_eq!(square(3), 9);
+    assert_eq!(calls.get(), 2);
+
+    // 键可以是任何 Hash + Eq + Clone 的类型，返回值可以是 String
+    let lengths = Cell::new(0);
+    let describe = memoize(|word: String| {
+        lengths.set(lengths.get() + 1);
+        format!("{word}: {}", word.len())
+    });
+    for word in ["rust", "crab", "rust", "rust"] {
+        describe(word.to_string());
+    }
+    assert_eq!(describe(String::from("crab")), "crab: 4");
+    assert_eq!(lengths.get(), 2);
+}
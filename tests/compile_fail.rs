@@ -0,0 +1,110 @@
+// ============================================
+// 编译失败示例测试
+// ============================================
+// 对 rust_tutorial::compile_fail::CASES 中的每个用例调用本地 rustc，
+// 确认章节注释中“这样写会编译错误”的说法在当前工具链下依然成立
+
+use std::thread;
+
+use rust_tutorial::chapters;
+use rust_tutorial::compile_fail::{self, CASES};
+
+/// 形如 `// 代码 // 错误！…` 的行：被注释掉的代码，后面跟着说它会出错的注释
+fn is_annotated(line: &str) -> bool {
+    let Some(rest) = line.trim().strip_prefix("//") else {
+        return false;
+    };
+    match rest.rsplit_once("//") {
+        Some((code, note)) => {
+            !code.trim().is_empty() && (note.contains("错误") || note.contains("error"))
+        }
+        None => false,
+    }
+}
+
+#[test]
+fn commented_out_errors_still_fail_to_compile() {
+    // 每个用例都要调用两次 rustc，并行执行以节省时间
+    let handles: Vec<_> = CASES
+        .iter()
+        .map(|case| thread::spawn(move || (case, case.check())))
+        .collect();
+
+    let failures: Vec<String> = handles
+        .into_iter()
+        .map(|handle| handle.join().expect("检查线程 panic"))
+        .filter_map(|(case, result)| {
+            result
+                .err()
+                .map(|e| format!("{} `{}`: {}", case.chapter, case.anchor, e))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn every_case_uncomments_exactly_its_lines() {
+    for case in CASES {
+        let chapter = case.chapter().unwrap();
+        let (source, lines) = case.snippet().unwrap();
+
+        let original: Vec<&str> = chapter.source.lines().collect();
+        let changed: Vec<&str> = source.lines().collect();
+        assert_eq!(original.len(), changed.len());
+
+        for (index, (before, after)) in original.iter().zip(&changed).enumerate() {
+            if lines.contains(&(index + 1)) {
+                assert_ne!(before, after, "{} 第 {} 行", chapter.file_name(), index + 1);
+                assert!(!after.trim_start().starts_with("//"));
+            } else {
+                assert_eq!(before, after);
+            }
+        }
+    }
+}
+
+#[test]
+fn every_annotated_line_has_a_case() {
+    let mut missing = Vec::new();
+    for chapter in chapters::all() {
+        let covered: Vec<usize> = CASES
+            .iter()
+            .filter(|case| case.chapter().unwrap().slug == chapter.slug)
+            .flat_map(|case| case.snippet().unwrap().1)
+            .collect();
+        for (index, line) in chapter.source.lines().enumerate() {
+            if is_annotated(line) && !covered.contains(&(index + 1)) {
+                missing.push(format!(
+                    "{}:{} {}",
+                    chapter.file_name(),
+                    index + 1,
+                    line.trim()
+                ));
+            }
+        }
+    }
+    assert!(
+        missing.is_empty(),
+        "这些行说会编译错误，但 CASES 中没有对应的用例:\n{}",
+        missing.join("\n")
+    );
+
+    // 扫描规则本身：说明文字和没有错误说明的注释不算
+    assert!(is_annotated("    // x = 6; // error: cannot assign twice"));
+    assert!(is_annotated("//     &s // 错误！s 在这里离开作用域"));
+    assert!(!is_annotated(
+        "    // 下面的代码会编译错误，因为 x 是不可变的"
+    ));
+    assert!(!is_annotated("    let x = y?; // 如果错误，自动返回"));
+    assert!(!is_annotated("    // let r = &s; // 没问题"));
+}
+
+#[test]
+fn unmodified_chapters_compile_in_isolation() {
+    let chapter = CASES[0].chapter().unwrap();
+    assert_eq!(
+        compile_fail::compile(chapter, chapter.source).unwrap(),
+        vec![]
+    );
+}
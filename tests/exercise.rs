@@ -0,0 +1,158 @@
+// ============================================
+// 练习模式测试
+// ============================================
+
+use std::fs;
+
+use rust_tutorial::chapters;
+use rust_tutorial::exercise::{self, Breakage, Outcome, Workspace};
+use rust_tutorial::toolchain;
+
+#[test]
+fn every_chapter_with_code_sections_has_exercises() {
+    for chapter in chapters::all() {
+        let exercises = exercise::for_chapter(chapter);
+        assert!(!exercises.is_empty(), "{} 没有练习", chapter.file_name());
+        for exercise in &exercises {
+            assert_eq!(exercise::find(&exercise.id()).unwrap().line, exercise.line);
+        }
+    }
+}
+
+#[test]
+fn ids_follow_section_numbers() {
+    let exercises = exercise::all();
+    let mut ids: Vec<String> = exercises.iter().map(|e| e.id()).collect();
+    for exercise in &exercises {
+        let prefix = format!("{:02}-{}", exercise.chapter.number, exercise.section.number);
+        assert!(exercise.id().starts_with(&prefix), "{}", exercise.id());
+    }
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), exercises.len());
+
+    // 第 05 章开头的所有权规则也编了 1. 2. 3.，之后的第二个 "2." 是 05-2b
+    let moved = exercise::find("5-2b").unwrap();
+    assert_eq!(moved.section.title, "对于堆上分配的数据，赋值会移动所有权");
+    assert_eq!(exercise::find("05-4").unwrap().section.number, 4);
+    // 第 14 章模块顶层的段落重新从 1 编号
+    let elision = exercise::find("14-4B").unwrap();
+    assert_eq!(
+        (elision.id(), elision.section.indent),
+        (String::from("14-4b"), 0)
+    );
+    assert_eq!(exercise::find("14-4").unwrap().section.indent, 4);
+    assert!(exercise::find("14-4c").is_none());
+    assert!(exercise::find("05-2").is_none());
+}
+
+#[test]
+fn breakages_change_exactly_one_line() {
+    for exercise in exercise::all() {
+        let original = exercise.chapter.source.lines().nth(exercise.line).unwrap();
+        assert_ne!(original, exercise.broken, "{}", exercise.id());
+
+        let broken = exercise.broken_source();
+        assert!(broken.contains(exercise::MARKER));
+        assert_eq!(
+            broken.lines().count(),
+            exercise.chapter.source.lines().count() + 5,
+            "{}",
+            exercise.id()
+        );
+    }
+}
+
+#[test]
+fn breakage_kinds() {
+    let blank = exercise::find("01-1").unwrap();
+    assert_eq!(blank.breakage, Breakage::BlankedExpression);
+    assert_eq!(blank.broken, "    let x = ___;");
+
+    let mutability = exercise::find("01-2").unwrap();
+    assert_eq!(
+        mutability.breakage,
+        Breakage::RemovedMut {
+            name: String::from("y")
+        }
+    );
+    assert_eq!(mutability.broken, "    let y = 10;");
+
+    let lifetimes = exercise::all()
+        .into_iter()
+        .find(|e| e.breakage == Breakage::RemovedLifetimes && e.chapter.slug == "lifetimes")
+        .unwrap();
+    assert_eq!(lifetimes.broken, "fn longest(x: &str, y: &str) -> &str {");
+}
+
+#[test]
+fn check_reports_unfinished_compile_error_wrong_output_and_pass() {
+    let dir = toolchain::scratch_dir("exercise-test").unwrap();
+    let workspace = Workspace::open(&dir).unwrap();
+
+    let blank = exercise::find("01-1").unwrap();
+    let path = workspace.prepare(&blank).unwrap();
+    assert!(matches!(
+        exercise::check(&blank, &path).unwrap(),
+        Outcome::Unfinished
+    ));
+
+    let source = fs::read_to_string(&path).unwrap();
+    fs::write(&path, source.replace("let x = ___;", "let x = 6;")).unwrap();
+    match exercise::check(&blank, &path).unwrap() {
+        Outcome::WrongOutput { expected, actual } => {
+            // 只比较练习所在段落的输出
+            assert_eq!(expected, "x = 5\n");
+            assert_eq!(actual, "x = 6\n");
+        }
+        _ => panic!("应该是输出不一致"),
+    }
+
+    // 其他段落的输出变了不影响这道练习
+    let fixed = source.replace("let x = ___;", "let x = 5;").replace(
+        "println!(\"spaces = {}\", spaces);",
+        "println!(\"spaces: {}\", spaces);",
+    );
+    fs::write(&path, fixed).unwrap();
+    assert!(matches!(
+        exercise::check(&blank, &path).unwrap(),
+        Outcome::Passed
+    ));
+
+    let mutability = exercise::find("01-2").unwrap();
+    let path = workspace.prepare(&mutability).unwrap();
+    match exercise::check(&mutability, &path).unwrap() {
+        Outcome::CompileError(stderr) => assert!(stderr.contains("E0384"), "{}", stderr),
+        _ => panic!("应该编译失败"),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn progress_is_persisted() {
+    let dir = toolchain::scratch_dir("exercise-progress").unwrap();
+    let exercises = exercise::for_chapter(chapters::find("variables").unwrap());
+
+    let mut workspace = Workspace::open(&dir).unwrap();
+    assert_eq!(workspace.next(&exercises).unwrap().id(), "01-1");
+    workspace.mark_done(&exercises[0]).unwrap();
+
+    let workspace = Workspace::open(&dir).unwrap();
+    assert!(workspace.is_done(&exercises[0]));
+    assert_eq!(workspace.next(&exercises).unwrap().id(), exercises[1].id());
+
+    // 已有的练习文件不会被 prepare 覆盖，只有 reset 会
+    let path = workspace.prepare(&exercises[1]).unwrap();
+    fs::write(&path, "// 学习者的修改").unwrap();
+    workspace.prepare(&exercises[1]).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "// 学习者的修改");
+    workspace.reset(&exercises[1]).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        exercises[1].broken_source()
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
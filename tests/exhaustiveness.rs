@@ -0,0 +1,303 @@
+// ============================================
+// 穷尽性和可达性检查测试
+// ============================================
+
+use rust_tutorial::exhaustiveness::{self, Arm, Report, Ty, Variant};
+use rust_tutorial::integer::IntType;
+
+const CONTROL_FLOW: &str = include_str!("../src/04_control_flow.rs");
+const PATTERN_MATCHING: &str = include_str!("../src/08_pattern_matching.rs");
+
+/// 从章节源码中取出第 nth 个 `header` 开头的 match 的分支；
+/// 跨多行的模式合并成一行并去掉结尾的逗号，`if` 之后的 guard 只记录有没有
+fn arms(source: &str, header: &str, nth: usize) -> Vec<Arm> {
+    let lines: Vec<&str> = source.lines().collect();
+    let start = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim().starts_with(header))
+        .nth(nth)
+        .map(|(i, _)| i)
+        .expect(header);
+    let first = lines[start];
+    let indent = first.len() - first.trim_start().len();
+    let mut arms = Vec::new();
+    let mut pattern: Option<String> = None;
+    for line in &lines[start + 1..] {
+        let depth = line.len() - line.trim_start().len();
+        if depth == indent {
+            break;
+        }
+        let text = line.trim();
+        match pattern.as_mut() {
+            None if depth == indent + 4 && !text.starts_with('}') => {
+                pattern = Some(text.to_string())
+            }
+            Some(pattern) => {
+                pattern.push(' ');
+                pattern.push_str(text);
+            }
+            None => continue,
+        }
+        let Some((head, _)) = pattern.as_ref().unwrap().split_once("=>") else {
+            continue;
+        };
+        let head = head.replace(", }", " }");
+        let head = head.trim().trim_end_matches(',');
+        arms.push(match head.split_once(" if ") {
+            Some((pattern, _)) => Arm::guarded(pattern),
+            None => Arm::new(head),
+        });
+        pattern = None;
+    }
+    arms
+}
+
+fn check(ty: &Ty, arms: &[Arm]) -> Report {
+    exhaustiveness::check(ty, arms).unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn without_last(arms: &[Arm]) -> Vec<Arm> {
+    arms[..arms.len() - 1].to_vec()
+}
+
+fn message() -> Ty {
+    Ty::enumeration(
+        "Message",
+        vec![
+            Variant::unit("Quit"),
+            Variant::named("Move", vec![("x", Ty::i32()), ("y", Ty::i32())]),
+            Variant::tuple("Write", vec![Ty::Opaque("String")]),
+            Variant::tuple("ChangeColor", vec![Ty::i32(), Ty::i32(), Ty::i32()]),
+        ],
+    )
+}
+
+#[test]
+fn literal_and_range_matches_need_a_catch_all() {
+    for source in [CONTROL_FLOW, PATTERN_MATCHING] {
+        let ones = arms(source, "match number {", 0);
+        assert_eq!(ones.len(), 4);
+        assert_eq!(ones[0], Arm::new("1"));
+        let report = check(&Ty::i32(), &ones);
+        assert!(report.is_exhaustive() && report.unreachable.is_empty());
+        assert_eq!(
+            check(&Ty::i32(), &without_last(&ones)).render(),
+            "error[E0004]: non-exhaustive patterns: `i32::MIN..=0_i32` and `4_i32..=i32::MAX` not covered\n"
+        );
+
+        let sizes = arms(source, "match number {", 1);
+        assert_eq!(sizes[0], Arm::new("1 | 2 | 3"));
+        assert!(check(&Ty::i32(), &sizes).is_exhaustive());
+        assert_eq!(
+            check(&Ty::i32(), &without_last(&sizes)).missing,
+            ["i32::MIN..=0_i32", "10_i32..=i32::MAX"]
+        );
+
+        let ranges = arms(source, "match number {", 2);
+        assert_eq!(ranges[2], Arm::new("21..=50"));
+        assert!(check(&Ty::i32(), &ranges).is_exhaustive());
+        assert_eq!(
+            check(&Ty::i32(), &without_last(&ranges)).missing,
+            ["i32::MIN..=0_i32", "51_i32..=i32::MAX"]
+        );
+    }
+
+    // 换成 u8 后，从 0 开始的范围不再需要 `_`
+    let u8 = Ty::Int(IntType::U8);
+    let report = check(
+        &u8,
+        &[Arm::new("0..=9"), Arm::new("10..=99"), Arm::new("100..")],
+    );
+    assert!(report.is_exhaustive());
+}
+
+#[test]
+fn tuples_and_structs_from_chapters_04_and_08() {
+    let point = Ty::Tuple(vec![Ty::i32(), Ty::i32()]);
+    for source in [CONTROL_FLOW, PATTERN_MATCHING] {
+        let arms = arms(source, "match point {", 0);
+        assert_eq!(
+            arms,
+            [
+                Arm::new("(0, 0)"),
+                Arm::new("(0, y)"),
+                Arm::new("(x, 0)"),
+                Arm::new("(x, y)")
+            ]
+        );
+        assert!(check(&point, &arms).is_exhaustive());
+        // 去掉最后一个分支：x 和 y 都不为 0 的点没有被覆盖
+        assert_eq!(
+            check(&point, &without_last(&arms)).render(),
+            "error[E0004]: non-exhaustive patterns: `(i32::MIN..=-1_i32, i32::MIN..=-1_i32)`, \
+             `(i32::MIN..=-1_i32, 1_i32..=i32::MAX)`, `(1_i32..=i32::MAX, i32::MIN..=-1_i32)` \
+             and 1 more not covered\n"
+        );
+    }
+
+    // 第 08 章的多个守卫：只有 guard 的分支不算覆盖
+    let guards = arms(PATTERN_MATCHING, "match point {", 1);
+    assert_eq!(guards.iter().filter(|arm| arm.guard).count(), 3);
+    assert!(check(&point, &guards).is_exhaustive());
+    assert_eq!(check(&point, &without_last(&guards)).missing, ["(_, _)"]);
+
+    let p = Ty::structure("Point", vec![("x", Ty::i32()), ("y", Ty::i32())]);
+    let struct_arms = arms(PATTERN_MATCHING, "match p {", 0);
+    assert_eq!(struct_arms[0], Arm::new("Point { x, y: 0 }"));
+    assert!(check(&p, &struct_arms).is_exhaustive());
+    assert_eq!(
+        check(&p, &without_last(&struct_arms)).missing[0],
+        "Point { x: i32::MIN..=-1_i32, y: i32::MIN..=-1_i32 }"
+    );
+}
+
+#[test]
+fn nested_options_and_guards() {
+    let nested = Ty::option(Ty::option(Ty::i32()));
+    let arms08 = arms(PATTERN_MATCHING, "match some_value {", 0);
+    assert_eq!(arms08[0], Arm::new("Some(Some(v))"));
+    assert!(check(&nested, &arms08).is_exhaustive());
+    let missing_inner = [arms08[0].clone(), arms08[2].clone()];
+    assert_eq!(check(&nested, &missing_inner).missing, ["Some(None)"]);
+    assert_eq!(check(&nested, &arms08[1..]).missing, ["Some(Some(_))"]);
+
+    // Some(x) if x < 5 之后仍然需要 Some(x)
+    let option = Ty::option(Ty::i32());
+    for source in [CONTROL_FLOW, PATTERN_MATCHING] {
+        let arms = arms(source, "match num {", 0);
+        assert_eq!(arms[0], Arm::guarded("Some(x)"));
+        assert!(check(&option, &arms).is_exhaustive());
+        let guarded_only = [arms[0].clone(), arms[2].clone()];
+        assert_eq!(check(&option, &guarded_only).missing, ["Some(_)"]);
+    }
+
+    // if let Some(3) 等价的 match
+    let arms = arms(CONTROL_FLOW, "match some_value {", 0);
+    assert_eq!(arms, [Arm::new("Some(3)"), Arm::new("_")]);
+    assert_eq!(
+        check(&option, &without_last(&arms)).missing,
+        ["None", "Some(i32::MIN..=2_i32)", "Some(4_i32..=i32::MAX)"]
+    );
+}
+
+#[test]
+fn enums_and_at_bindings() {
+    let arms08 = arms(PATTERN_MATCHING, "match msg {", 0);
+    assert_eq!(arms08[1], Arm::new("Message::Move { x, y }"));
+    let report = check(&message(), &arms08);
+    assert!(report.is_exhaustive() && report.unreachable.is_empty());
+    assert_eq!(check(&message(), &arms08[1..]).missing, ["Message::Quit"]);
+    let partial = [
+        Arm::new("Message::Quit"),
+        Arm::new("Message::Move { x: 0, .. }"),
+        Arm::new("Message::ChangeColor(0, ..)"),
+    ];
+    assert_eq!(
+        check(&message(), &partial).render(),
+        "error[E0004]: non-exhaustive patterns: `Message::Move { x: i32::MIN..=-1_i32, .. }`, \
+         `Message::Move { x: 1_i32..=i32::MAX, .. }`, `Message::Write(_)` and 2 more not covered\n"
+    );
+
+    let hello = Ty::enumeration(
+        "Message",
+        vec![Variant::named("Hello", vec![("id", Ty::i32())])],
+    );
+    let arms04 = arms(CONTROL_FLOW, "match msg {", 0);
+    assert_eq!(
+        arms04[0],
+        Arm::new("Message::Hello { id: id_variable @ 3..=7 }")
+    );
+    assert!(check(&hello, &arms04).is_exhaustive());
+    assert_eq!(
+        check(&hello, &without_last(&arms04)).missing,
+        [
+            "Message::Hello { id: i32::MIN..=2_i32 }",
+            "Message::Hello { id: 8_i32..=9_i32 }",
+            "Message::Hello { id: 13_i32..=i32::MAX }",
+        ]
+    );
+}
+
+#[test]
+fn reports_unreachable_arms_and_alternatives() {
+    let report = check(
+        &Ty::i32(),
+        &[
+            Arm::new("1..=10"),
+            Arm::new("5 | 11"),
+            Arm::guarded("n @ 12..=20"),
+            Arm::new("15"),
+            Arm::new("_"),
+            Arm::new("x"),
+        ],
+    );
+    assert_eq!(
+        report.render(),
+        "第 2 个分支: warning: unreachable pattern `5`\n\
+         第 6 个分支: warning: unreachable pattern `x`\n"
+    );
+    assert_eq!(report.unreachable[0].alternative, Some(0));
+    assert_eq!(report.unreachable[1].alternative, None);
+
+    // 两个 bool 的元组：各种写法合起来覆盖了所有情况
+    let pair = Ty::Tuple(vec![Ty::Bool, Ty::Bool]);
+    let report = check(
+        &pair,
+        &[
+            Arm::new("(true, _)"),
+            Arm::new("(_, true)"),
+            Arm::new("(false, false)"),
+            Arm::new("(true, true) | (false, true)"),
+        ],
+    );
+    assert!(report.is_exhaustive());
+    assert_eq!(report.unreachable.len(), 1);
+    assert_eq!(report.unreachable[0].alternative, None);
+
+    // Result 的两个变体各有一个分支，后面的 `_` 不可达
+    let result = Ty::result(Ty::i32(), Ty::Opaque("&str"));
+    let report = check(
+        &result,
+        &[Arm::new("Ok(value)"), Arm::new("Err(error)"), Arm::new("_")],
+    );
+    assert_eq!(report.unreachable.len(), 1);
+    assert!(report.is_exhaustive());
+}
+
+#[test]
+fn rejects_patterns_that_do_not_fit_the_type() {
+    let error = |ty: &Ty, pattern: &str| {
+        exhaustiveness::check(ty, &[Arm::new("_"), Arm::new(pattern)])
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        error(&Ty::i32(), "Some(1)"),
+        "第 2 个分支: `Some` 不是 `i32` 的变体"
+    );
+    assert_eq!(
+        error(&message(), "Message::Move { z }"),
+        "第 2 个分支: variant does not have a field named `z` (E0026)"
+    );
+    assert_eq!(
+        error(&message(), "Message::Move { x }"),
+        "第 2 个分支: pattern does not mention field `y` (E0027)"
+    );
+    assert_eq!(
+        error(&message(), "Message::ChangeColor(1, 2)"),
+        "第 2 个分支: this pattern has 2 fields, but the corresponding tuple variant has 3 fields (E0023)"
+    );
+    assert_eq!(
+        error(&Ty::i32(), "5..=1"),
+        "第 2 个分支: lower range bound must be less than or equal to upper (E0030)"
+    );
+    assert_eq!(
+        error(&Ty::Int(IntType::U8), "256"),
+        "第 2 个分支: error: literal out of range for `u8`"
+    );
+    assert_eq!(
+        error(&Ty::Bool, "1"),
+        "第 2 个分支: mismatched types: expected `bool`, found integer"
+    );
+}
@@ -0,0 +1,211 @@
+// ============================================
+// 二维几何测试
+// ============================================
+
+use rust_tutorial::chapters::{generics, structs, traits};
+use rust_tutorial::geometry::{GeometryError, Orientation, Point, Rectangle};
+use rust_tutorial::grid::Rng;
+
+fn random_rectangle(rng: &mut Rng) -> Rectangle {
+    Rectangle::new(
+        rng.below(40) as i32 - 20,
+        rng.below(40) as i32 - 20,
+        rng.below(15) as u32,
+        rng.below(15) as u32,
+    )
+}
+
+#[test]
+fn point_behaves_like_chapter_13() {
+    // 第 13 章：Point { x: 1, y: 0 } + Point { x: 2, y: 3 }
+    let sum = Point { x: 1, y: 0 } + Point { x: 2, y: 3 };
+    assert_eq!(sum, Point { x: 3, y: 3 });
+    assert_eq!(sum.to_string(), "(3, 3)");
+    assert_eq!(sum - Point::new(3, 3), Point::ORIGIN);
+
+    // 和元组互相转换
+    let point: Point = (5, -7).into();
+    let (x, y) = point.into();
+    assert_eq!((x, y), (5, -7));
+
+    // 第 12 章 Point<f32>::distance_from_origin 的 3-4-5
+    assert_eq!(Point::new(3, 4).distance_from_origin(), 5.0);
+    assert_eq!(
+        Point::new(i32::MIN, i32::MIN).distance_squared(Point::new(i32::MAX, i32::MAX)),
+        2 * (u32::MAX as u128).pow(2)
+    );
+    assert_eq!(Point::new(i32::MAX, 0).checked_add(Point::new(1, 0)), None);
+}
+
+#[test]
+fn contains_points_in_a_half_open_range() {
+    let rect = Rectangle::new(10, 20, 30, 50);
+    assert!(rect.contains_point(Point::new(10, 20)));
+    assert!(rect.contains_point(Point::new(39, 69)));
+    // 右边和下边不包含
+    assert!(!rect.contains_point(Point::new(40, 20)));
+    assert!(!rect.contains_point(Point::new(10, 70)));
+    assert!(!rect.contains_point(Point::new(9, 20)));
+    // 空矩形不包含任何点
+    assert!(!Rectangle::new(0, 0, 0, 10).contains_point(Point::ORIGIN));
+    // 靠近 i32 边界也不会溢出
+    let edge = Rectangle::new(i32::MAX, i32::MAX, u32::MAX, u32::MAX);
+    assert!(edge.contains_point(Point::new(i32::MAX, i32::MAX)));
+    assert_eq!(edge.right(), i32::MAX as i64 + u32::MAX as i64);
+    assert_eq!(rect.to_string(), "(10, 20) 30×50");
+}
+
+#[test]
+fn intersects_and_unions() {
+    let a = Rectangle::new(0, 0, 10, 10);
+    let b = Rectangle::new(5, -5, 10, 10);
+    assert!(a.intersects(&b));
+    assert_eq!(a.intersection(&b), Some(Rectangle::new(5, 0, 5, 5)));
+    assert_eq!(a.union(&b), Ok(Rectangle::new(0, -5, 15, 15)));
+
+    // 只是边挨着：不相交，但可以合并
+    let right = Rectangle::new(10, 0, 10, 10);
+    assert!(!a.intersects(&right));
+    assert_eq!(a.intersection(&right), None);
+    assert_eq!(a.union(&right), Ok(Rectangle::new(0, 0, 20, 10)));
+    assert!(a.contains(&Rectangle::new(2, 2, 8, 8)));
+    assert!(!a.contains(&b));
+
+    assert_eq!(
+        Rectangle::bounding_box([a, b, Rectangle::new(-3, 4, 1, 1)]),
+        Ok(Rectangle::new(-3, -5, 18, 15))
+    );
+    assert_eq!(Rectangle::bounding_box([]), Err(GeometryError::Empty));
+
+    // 从 i32::MIN 到很远的右边，宽度放不进 u32
+    let far_left = Rectangle::new(i32::MIN, 0, 1, 1);
+    let far_right = Rectangle::new(i32::MAX, 0, 10, 1);
+    let error = far_left.union(&far_right).unwrap_err();
+    assert_eq!(error, GeometryError::Overflow { op: "union" });
+    assert_eq!(
+        error.to_string(),
+        "union 溢出: 结果超出 i32 坐标或 u32 尺寸的范围"
+    );
+}
+
+#[test]
+fn random_rectangles_obey_set_laws() {
+    let mut rng = Rng::new(23);
+    for _ in 0..2000 {
+        let a = random_rectangle(&mut rng);
+        let b = random_rectangle(&mut rng);
+        assert_eq!(a.intersects(&b), b.intersects(&a));
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert_eq!(a.intersects(&b), a.intersection(&b).is_some());
+        if let Some(overlap) = a.intersection(&b) {
+            assert!(!overlap.is_empty());
+            assert!(a.contains(&overlap) && b.contains(&overlap));
+        }
+        let union = a.union(&b).unwrap();
+        assert!(union.contains(&a) && union.contains(&b));
+
+        // 一个点在交集里，当且仅当它同时在两个矩形里
+        let point = Point::new(rng.below(40) as i32 - 20, rng.below(40) as i32 - 20);
+        let in_both = a.contains_point(point) && b.contains_point(point);
+        let in_overlap = a
+            .intersection(&b)
+            .is_some_and(|overlap| overlap.contains_point(point));
+        assert_eq!(in_both, in_overlap);
+    }
+}
+
+#[test]
+fn translates_and_scales_with_checked_arithmetic() {
+    let rect = Rectangle::new(1, 2, 3, 4);
+    assert_eq!(rect.translate(-5, 10), Ok(Rectangle::new(-4, 12, 3, 4)));
+    assert_eq!(
+        rect.translate(i32::MAX, 0),
+        Err(GeometryError::Overflow { op: "translate" })
+    );
+    assert_eq!(rect.scale(3), Ok(Rectangle::new(3, 6, 9, 12)));
+    assert_eq!(rect.scale(0), Ok(Rectangle::new(0, 0, 0, 0)));
+    assert_eq!(
+        rect.scale(u32::MAX),
+        Err(GeometryError::Overflow { op: "scale" })
+    );
+    // 原点处的单位正方形放大到 u32::MAX 也放得下
+    let unit = Rectangle::square(Point::ORIGIN, 1);
+    assert_eq!(
+        unit.scale(u32::MAX),
+        Ok(Rectangle::square(Point::ORIGIN, u32::MAX))
+    );
+
+    // 第 06 章的 width * height 在这里会溢出
+    let huge = Rectangle::new(0, 0, 100_000, 100_000);
+    assert_eq!(huge.checked_area(), None);
+    assert_eq!(huge.area(), 10_000_000_000);
+    assert_eq!(Rectangle::new(0, 0, 30, 50).checked_area(), Some(1500));
+    assert_eq!(
+        Rectangle::new(0, 0, u32::MAX, u32::MAX).perimeter(),
+        4 * u32::MAX as u64
+    );
+}
+
+#[test]
+fn fits_inside_allows_equal_sizes_and_rotation() {
+    // 第 06 章：rect1 30×50 能容纳 10×40，不能容纳 60×45
+    let rect1 = Rectangle::new(0, 0, 30, 50);
+    let rect2 = Rectangle::new(0, 0, 10, 40);
+    let rect3 = Rectangle::new(0, 0, 60, 45);
+    assert_eq!(rect2.fits_inside(&rect1), Some(Orientation::Upright));
+    assert_eq!(rect3.fits_inside(&rect1), None);
+    // 一样大的矩形：can_hold 用 > 放不进去，这里可以
+    assert_eq!(rect1.fits_inside(&rect1), Some(Orientation::Upright));
+    // 横着放不进，转 90 度可以
+    let wide = Rectangle::new(7, 7, 50, 30);
+    assert_eq!(wide.fits_inside(&rect1), Some(Orientation::Rotated));
+    assert_eq!(
+        wide.rotated().fits_inside(&rect1),
+        Some(Orientation::Upright)
+    );
+    assert_eq!(wide.rotated().origin, wide.origin);
+    // 位置不影响判断
+    assert_eq!(
+        Rectangle::new(-100, 900, 10, 40).fits_inside(&rect1),
+        Some(Orientation::Upright)
+    );
+    assert_eq!(
+        Rectangle::from_corners(Point::new(30, 50), Point::ORIGIN),
+        rect1
+    );
+}
+
+#[test]
+fn converts_to_and_from_chapter_types() {
+    // 第 13 章的 Point：字段一样，来回转换不丢信息
+    let point: Point = traits::Point { x: 3, y: -4 }.into();
+    assert_eq!(point, Point::new(3, -4));
+    assert_eq!(point.distance_from_origin(), 5.0);
+    let back: traits::Point = (point + Point::new(1, 1)).into();
+    assert_eq!(back, traits::Point { x: 4, y: -3 });
+    assert_eq!(
+        back.to_string(),
+        point.checked_add(Point::new(1, 1)).unwrap().to_string()
+    );
+
+    // 第 12 章的 Point<i32>
+    let point = Point::from(generics::Point {
+        x: i32::MIN,
+        y: i32::MAX,
+    });
+    assert_eq!(point, Point::new(i32::MIN, i32::MAX));
+    let back = generics::Point::<i32>::from(point);
+    assert_eq!((back.x, back.y), (i32::MIN, i32::MAX));
+
+    // 第 06 章的 Rectangle 没有位置：转过来放在原点，转回去丢掉位置
+    let rect: Rectangle = structs::Rectangle {
+        width: 30,
+        height: 50,
+    }
+    .into();
+    assert_eq!(rect, Rectangle::new(0, 0, 30, 50));
+    assert_eq!(rect.area(), 1500);
+    let moved = rect.translate(-5, 7).unwrap();
+    let back = structs::Rectangle::from(moved);
+    assert_eq!((back.width, back.height), (30, 50));
+}
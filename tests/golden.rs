@@ -0,0 +1,124 @@
+// ============================================
+// 章节输出快照测试 (Golden Tests)
+// ============================================
+// 运行每一章的 main，把输出与 tests/snapshots/NN_name.txt 比较
+//
+//   cargo test --test golden                 比较所有章节
+//   cargo test --test golden -- enums        只比较名称或编号匹配的章节
+//   cargo test --test golden -- --bless      用当前输出更新快照文件
+//
+// 输出不确定的章节（指针地址、HashMap 迭代顺序）会先按
+// rust_tutorial::snapshot::rules 规范化再比较
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use rust_tutorial::chapters::{self, Chapter};
+use rust_tutorial::snapshot;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let bless = args.iter().any(|arg| arg == "--bless");
+    let filters: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+
+    let runner = Path::new(env!("CARGO_BIN_EXE_rust-tutorial"));
+    let snapshot_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+
+    let mut failed = 0;
+    let mut checked = 0;
+
+    for chapter in chapters::all() {
+        if !filters.is_empty() && !filters.iter().any(|filter| matches(chapter, filter)) {
+            continue;
+        }
+        checked += 1;
+
+        let path = snapshot_path(&snapshot_dir, chapter);
+        let actual = match snapshot::capture_normalized(runner, chapter) {
+            Ok(output) => output,
+            Err(e) => {
+                println!("{} ... 运行失败\n{}", chapter.file_name(), e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        if bless {
+            fs::create_dir_all(&snapshot_dir).expect("无法创建 tests/snapshots");
+            fs::write(&path, &actual).expect("无法写入快照文件");
+            println!("{} ... 已更新", chapter.file_name());
+            continue;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => println!("{} ... ok", chapter.file_name()),
+            Ok(expected) => {
+                println!("{} ... 输出与快照不一致", chapter.file_name());
+                print_diff(&expected, &actual);
+                failed += 1;
+            }
+            Err(_) => {
+                println!("{} ... 缺少快照 {}", chapter.file_name(), path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    if failed > 0 {
+        println!("快照测试失败: {failed}/{checked} 个章节");
+        println!("如果输出的变化是预期的，运行 cargo test --test golden -- --bless 更新快照");
+        ExitCode::FAILURE
+    } else {
+        println!("快照测试通过: {checked} 个章节");
+        ExitCode::SUCCESS
+    }
+}
+
+fn matches(chapter: &Chapter, filter: &str) -> bool {
+    chapters::find(filter).is_some_and(|found| found.number == chapter.number)
+}
+
+fn snapshot_path(dir: &Path, chapter: &Chapter) -> PathBuf {
+    dir.join(chapter.file_name().replace(".rs", ".txt"))
+}
+
+// ========== 行级 diff ==========
+
+/// 基于最长公共子序列打印 diff：`-` 为快照中的行，`+` 为实际输出中的行
+fn print_diff(expected: &str, actual: &str) {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] 表示 old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            println!("    -{}", old[i]);
+            i += 1;
+        } else {
+            println!("    +{}", new[j]);
+            j += 1;
+        }
+    }
+}
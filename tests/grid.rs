@@ -0,0 +1,163 @@
+// ============================================
+// 迷宫和网格测试
+// ============================================
+
+use rust_tutorial::grid::{Cell, Grid, Pos, Rng};
+
+const ROOMS: &str = include_str!("../mazes/rooms.txt");
+
+fn grid(text: &str) -> Grid {
+    Grid::parse(text).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// 路径从起点走到终点，每一步只走一格，不穿墙，也不重复
+fn assert_walkable(grid: &Grid, path: &[Pos]) {
+    assert_eq!(path.first(), Some(&grid.start));
+    assert_eq!(path.last(), Some(&grid.goal));
+    for pair in path.windows(2) {
+        assert!(
+            grid.neighbors(pair[0]).any(|next| next == pair[1]),
+            "{pair:?}"
+        );
+    }
+    let mut sorted = path.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), path.len());
+}
+
+#[test]
+fn parses_and_renders_ascii_mazes() {
+    let rooms = grid(ROOMS);
+    assert_eq!((rooms.width, rooms.height), (15, 13));
+    assert_eq!(rooms.start, Pos::new(1, 1));
+    assert_eq!(rooms.goal, Pos::new(11, 13));
+    assert_eq!(rooms.cell(Pos::new(0, 0)), Cell::Wall);
+    assert_eq!(rooms.to_string(), ROOMS);
+
+    // 空格也是通路，末尾的空行被忽略
+    let small = grid("#####\n#S  #\n#  G#\n#####\n\n");
+    assert_eq!(small.to_string(), "#####\n#S..#\n#..G#\n#####\n");
+    let neighbors: Vec<Pos> = small.neighbors(Pos::new(1, 2)).collect();
+    assert_eq!(neighbors, [Pos::new(1, 3), Pos::new(2, 2), Pos::new(1, 1)]);
+    assert_eq!(
+        rooms.dead_ends(),
+        [Pos::new(7, 13), Pos::new(11, 1), Pos::new(11, 11)]
+    );
+}
+
+#[test]
+fn bfs_finds_the_shortest_path_and_dfs_stops_early() {
+    let rooms = grid(ROOMS);
+    let bfs = rooms.bfs();
+    let dfs = rooms.dfs();
+    assert_walkable(&rooms, bfs.path.as_ref().unwrap());
+    assert_walkable(&rooms, dfs.path.as_ref().unwrap());
+    // 迷宫中有环：广度优先走了近路，深度优先一条路走到底，展开的格子少得多
+    assert_eq!((bfs.steps(), bfs.explored), (Some(26), 84));
+    assert_eq!((dfs.steps(), dfs.explored), (Some(30), 34));
+    assert_eq!(
+        rooms.render(bfs.path.as_ref().unwrap()),
+        "\
+###############
+#S....#.......#
+#*###.#.#####.#
+#*#...#.#...#.#
+#*#.###.#.#.#.#
+#***#.....#...#
+###*#.#####.###
+#..***#*****#.#
+#.###*#*###*#.#
+#...#***#..***#
+#.#.#####.###*#
+#.#.........#G#
+###############
+"
+    );
+}
+
+#[test]
+fn reports_when_the_goal_is_walled_off() {
+    let closed = grid("#######\n#S..#G#\n#...#.#\n#######\n");
+    for search in [closed.bfs(), closed.dfs()] {
+        assert_eq!(search.path, None);
+        assert_eq!(search.steps(), None);
+        // 起点所在的区域全部展开过一次
+        assert_eq!(search.explored, 6);
+    }
+
+    // 相邻的起点和终点只需要一步
+    let next_door = grid("####\n#SG#\n####\n");
+    assert_eq!(
+        next_door.bfs().path,
+        Some(vec![Pos::new(1, 1), Pos::new(1, 2)])
+    );
+    assert_eq!(next_door.dfs().steps(), Some(1));
+}
+
+#[test]
+fn rejects_malformed_mazes() {
+    let error = |text: &str| Grid::parse(text).unwrap_err().to_string();
+    assert_eq!(error(""), "第 1 行: 迷宫是空的");
+    assert_eq!(error("#####\n#S.G\n"), "第 2 行: 有 4 列，第 1 行有 5 列");
+    assert_eq!(error("#S.x#G#"), "第 1 行: 第 4 列: 未知的格子 `x`");
+    assert_eq!(
+        error("#S.#\n#.S#\n#G.#"),
+        "第 2 行: 起点 S 出现了两次（另一个在第 1 行第 2 列）"
+    );
+    assert_eq!(error("#S.#\n#..#"), "第 2 行: 没有终点 G");
+}
+
+#[test]
+fn generated_mazes_are_deterministic_and_perfect() {
+    let maze = Grid::generate(3, 4, 7);
+    assert_eq!(
+        maze.to_string(),
+        "\
+#########
+#S#.....#
+#.#.###.#
+#...#.#.#
+#####.#.#
+#......G#
+#########
+"
+    );
+    assert_eq!(maze, Grid::generate(3, 4, 7));
+    assert_ne!(maze, Grid::generate(3, 4, 8));
+
+    for seed in 0..20 {
+        let maze = Grid::generate(6, 9, seed);
+        assert_eq!((maze.height, maze.width), (13, 19));
+        // 54 个房间连成一棵树：房间之间打通了 53 堵墙
+        let open = (0..maze.height)
+            .flat_map(|row| (0..maze.width).map(move |col| Pos::new(row, col)))
+            .filter(|&pos| maze.is_open(pos))
+            .count();
+        assert_eq!(open, 54 + 53);
+        // 树上两点之间只有一条路，两种搜索找到的路径相同
+        let bfs = maze.bfs();
+        assert_walkable(&maze, bfs.path.as_ref().unwrap());
+        assert_eq!(bfs.path, maze.dfs().path, "seed {seed}");
+        // 生成的迷宫可以原样解析回来
+        assert_eq!(grid(&maze.to_string()), maze);
+    }
+}
+
+#[test]
+fn seeded_rng_is_reproducible() {
+    // SplitMix64 以 0 为种子的第一个输出
+    assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    let rolls: Vec<usize> = (0..1000).map(|_| a.below(6)).collect();
+    assert!(rolls.iter().all(|&roll| roll < 6));
+    assert_eq!(rolls, (0..1000).map(|_| b.below(6)).collect::<Vec<_>>());
+    // 每个点数都出现过，且不会太偏
+    for face in 0..6 {
+        let count = rolls.iter().filter(|&&roll| roll == face).count();
+        assert!((120..=220).contains(&count), "{face}: {count}");
+    }
+    assert_eq!(Rng::new(7).below(1), 0);
+}
@@ -0,0 +1,158 @@
+// ============================================
+// 多语言消息测试
+// ============================================
+
+use std::path::Path;
+
+use rust_tutorial::chapters;
+use rust_tutorial::i18n::{self, Lang};
+use rust_tutorial::snapshot;
+
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}')
+}
+
+#[test]
+fn catalogs_match_the_chapter_sources() {
+    let sources: Vec<(String, &str)> = chapters::all()
+        .iter()
+        .map(|chapter| (chapter.file_name(), chapter.source))
+        .collect();
+    let sources: Vec<(&str, &str)> = sources
+        .iter()
+        .map(|(file_name, source)| (file_name.as_str(), *source))
+        .collect();
+
+    for lang in Lang::ALL {
+        let report = i18n::check(lang, lang.catalog(), &sources);
+        assert!(report.is_ok(), "{:#?}", report);
+    }
+}
+
+#[test]
+fn english_output_has_no_chinese_text() {
+    let runner = Path::new(env!("CARGO_BIN_EXE_rust-tutorial"));
+
+    let handles: Vec<_> = chapters::all()
+        .iter()
+        .map(|chapter| {
+            std::thread::spawn(move || {
+                let output = snapshot::capture_in(runner, chapter, Lang::En).unwrap();
+                let lines: Vec<String> = output
+                    .lines()
+                    .filter(|line| !line.starts_with("=========="))
+                    .filter(|line| line.chars().any(is_cjk))
+                    .map(String::from)
+                    .collect();
+                (chapter.file_name(), lines)
+            })
+        })
+        .collect();
+
+    let mut failures = Vec::new();
+    for handle in handles {
+        let (file_name, lines) = handle.join().unwrap();
+        for line in lines {
+            failures.push(format!("{}: {}", file_name, line));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn render_and_arity() {
+    assert_eq!(i18n::render("x = {}, y = {}", &[&5, &6]), "x = 5, y = 6");
+    assert_eq!(
+        i18n::render("{1} 在 {0} 之后", &[&"a", &"b"]),
+        "b 在 a 之后"
+    );
+    assert_eq!(i18n::render("{{}} = {}", &[&"集合"]), "{} = 集合");
+    // 缺少参数的占位符输出为带位置的 {n}
+    assert_eq!(i18n::render("{} 和 {}", &[&1]), "1 和 {1}");
+
+    assert_eq!(i18n::arity("没有参数"), Ok(0));
+    assert_eq!(i18n::arity("{} + {} = {}"), Ok(3));
+    assert_eq!(i18n::arity("{2} {0}"), Ok(3));
+    assert!(i18n::arity("{:?}").is_err());
+    assert!(i18n::arity("{").is_err());
+}
+
+#[test]
+fn parse_reports_bad_lines() {
+    let text = "\
+# 注释
+
+a.one = 一
+a.two = {} 和 {}
+没有等号
+a.one = 重复
+bad key = x
+a.three = {:?}
+";
+    let (entries, errors) = i18n::parse(text);
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key).collect();
+    assert_eq!(keys, ["a.one", "a.two"]);
+    assert_eq!(entries[1].line, 4);
+    assert_eq!(entries[1].template, "{} 和 {}");
+
+    let lines: Vec<usize> = errors.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, [5, 6, 7, 8]);
+}
+
+#[test]
+fn check_finds_missing_unused_and_mismatched_messages() {
+    let source = "\
+fn main() {
+    println!(\"{}\", t!(\"demo.hello\", name));
+    // println!(\"{}\", t!(\"demo.commented\"));
+    let s = t!(\"demo.missing\");
+    let n = t!(\"demo.count\", f(a, b), c);
+}
+";
+    let usages = i18n::usages(source);
+    let found: Vec<(&str, usize, usize)> = usages
+        .iter()
+        .map(|usage| (usage.key, usage.args, usage.line))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("demo.hello", 1, 2),
+            ("demo.missing", 0, 4),
+            ("demo.count", 2, 5)
+        ]
+    );
+
+    let catalog = "demo.hello = 你好, {}\ndemo.count = {}\ndemo.unused = 没用\n";
+    let report = i18n::check(Lang::Zh, catalog, &[("demo.rs", source)]);
+    assert!(report.errors.is_empty());
+    assert_eq!(report.missing.len(), 1);
+    assert!(report.missing[0].starts_with("demo.missing"));
+    assert_eq!(report.unused.len(), 1);
+    assert!(report.unused[0].starts_with("demo.unused"));
+    assert_eq!(report.mismatched.len(), 1);
+    assert!(report.mismatched[0].starts_with("demo.count"));
+}
+
+#[test]
+fn languages_and_annotations() {
+    assert_eq!(Lang::parse("zh_CN.UTF-8"), Some(Lang::Zh));
+    assert_eq!(Lang::parse("en_US"), Some(Lang::En));
+    assert_eq!(Lang::parse("EN"), Some(Lang::En));
+    assert_eq!(Lang::parse("C"), None);
+    assert_eq!(Lang::parse("fr_FR"), None);
+
+    assert_eq!(
+        i18n::template_in(Lang::En, "generics.largest_char"),
+        "The largest char: {}"
+    );
+    assert_eq!(i18n::template_in(Lang::En, "no.such.key"), "no.such.key");
+    assert_eq!(
+        i18n::annotate(
+            Lang::Zh,
+            "println!(\"{}\", t!(\"generics.largest_char\", result));"
+        ),
+        "println!(\"{}\", t!(\"generics.largest_char\", result)); // \"最大字符: {}\""
+    );
+    assert_eq!(i18n::annotate(Lang::Zh, "let x = 5;"), "let x = 5;");
+}
@@ -0,0 +1,144 @@
+// ============================================
+// 关键字索引测试
+// ============================================
+
+use rust_tutorial::chapters;
+use rust_tutorial::index::{self, KeywordKind};
+use rust_tutorial::scan;
+
+#[test]
+fn generated_index_matches_the_chapter_sources() {
+    let keywords: Vec<&str> = index::keywords().iter().map(|k| k.name).collect();
+    let mut expected: Vec<(String, u8, usize)> = Vec::new();
+    for chapter in chapters::all() {
+        for token in scan::tokens(chapter.source) {
+            if scan::is_indexed(&token.text, &keywords) {
+                expected.push((token.text, chapter.number, token.line));
+            }
+        }
+    }
+    expected.sort();
+    expected.dedup();
+
+    let mut actual: Vec<(String, u8, usize)> = index::terms()
+        .flat_map(|term| {
+            index::occurrences(term)
+                .iter()
+                .map(move |o| (term.to_string(), o.chapter, o.line))
+        })
+        .collect();
+    actual.sort();
+    assert_eq!(actual, expected);
+
+    let catalogue = scan::catalogue(chapters::find("keywords").unwrap().source);
+    assert_eq!(catalogue.len(), index::keywords().len());
+}
+
+#[test]
+fn catalogue_from_chapter_16() {
+    let count = |kind| {
+        index::keywords()
+            .iter()
+            .filter(|keyword| keyword.kind == kind)
+            .count()
+    };
+    // 严格关键字中 false/true 拆成了两条
+    assert_eq!(count(KeywordKind::Strict), 38);
+    assert_eq!(count(KeywordKind::Reserved), 13);
+    assert_eq!(count(KeywordKind::Weak), 3);
+
+    let entry = index::lookup("move").unwrap();
+    let keyword = entry.keyword.unwrap();
+    assert_eq!(keyword.kind, KeywordKind::Strict);
+    assert_eq!(keyword.category, "所有权和借用");
+    assert_eq!(keyword.description, "强制闭包获取所有权");
+
+    let occurrence = entry.occurrences.iter().find(|o| o.chapter == 16).unwrap();
+    assert_eq!(occurrence.line, 83);
+    assert_eq!(
+        occurrence.section,
+        "所有权和借用关键字示例 / 20. move - 强制闭包获取所有权"
+    );
+    assert_eq!(occurrence.code(), "let closure = move || {");
+    assert_eq!(occurrence.chapter().slug, "keywords");
+}
+
+#[test]
+fn lookup_types_macros_lifetimes_and_reserved_words() {
+    let entry = index::lookup("HashMap").unwrap();
+    assert!(entry.keyword.is_none());
+    let chapters: Vec<u8> = entry.occurrences.iter().map(|o| o.chapter).collect();
+    assert_eq!(chapters, [9, 9, 9, 9, 10, 10, 10, 15, 15, 16]);
+    assert!(
+        entry
+            .occurrences
+            .iter()
+            .any(|o| o.section == "HashMap / 14. 创建 HashMap")
+    );
+
+    let entry = index::lookup("'static").unwrap();
+    assert_eq!(entry.keyword.unwrap().description, "表示静态生命周期");
+    assert!(entry.occurrences.iter().any(|o| o.chapter == 14));
+
+    // 宏名写不写 ! 都可以；弱关键字 macro_rules 对应代码中的 macro_rules!
+    assert_eq!(index::lookup("println"), index::lookup("println!"));
+    assert_eq!(index::lookup("vec").unwrap().term, "vec!");
+    let entry = index::lookup("macro_rules").unwrap();
+    assert_eq!(entry.keyword.unwrap().kind, KeywordKind::Weak);
+    assert_eq!(entry.occurrences.len(), 1);
+
+    let entry = index::lookup("yield").unwrap();
+    assert_eq!(entry.keyword.unwrap().kind, KeywordKind::Reserved);
+    assert!(entry.occurrences.is_empty());
+
+    assert!(index::lookup("hashm").is_none());
+    assert_eq!(index::suggestions("hashm", 10), ["HashMap"]);
+    assert!(index::suggestions("", 10).is_empty());
+}
+
+#[test]
+fn tokens_skip_comments_strings_and_chars() {
+    let source = "\
+// fn 注释中的 move 不算
+let s: &'static str = \"Vec 和 move 在字符串中\"; /* Option
+   HashMap */ let c = 'x';
+let n = '\\n'; 'outer: loop { break 'outer; }
+let r = r#\"raw \"Box\" text\"#; if a != b { println!(\"{}\", Some(1u8)); }
+";
+    let tokens: Vec<(String, usize)> = scan::tokens(source)
+        .into_iter()
+        .map(|token| (token.text, token.line))
+        .collect();
+    let expect = |text: &str, line| (text.to_string(), line);
+    assert_eq!(
+        tokens,
+        [
+            expect("let", 2),
+            expect("s", 2),
+            expect("'static", 2),
+            expect("str", 2),
+            expect("let", 3),
+            expect("c", 3),
+            expect("let", 4),
+            expect("n", 4),
+            expect("'outer", 4),
+            expect("loop", 4),
+            expect("break", 4),
+            expect("'outer", 4),
+            expect("let", 5),
+            expect("r", 5),
+            expect("if", 5),
+            expect("a", 5),
+            expect("b", 5),
+            expect("println!", 5),
+            expect("Some", 5),
+        ]
+    );
+
+    let keywords = ["let", "loop"];
+    assert!(scan::is_indexed("let", &keywords));
+    assert!(scan::is_indexed("'outer", &keywords));
+    assert!(scan::is_indexed("println!", &keywords));
+    assert!(scan::is_indexed("Some", &keywords));
+    assert!(!scan::is_indexed("str", &keywords));
+}
@@ -0,0 +1,280 @@
+// ============================================
+// 整数字面量和溢出计算测试
+// ============================================
+
+use rust_tutorial::chapters;
+use rust_tutorial::integer::{self, Error, IntType, Integer, Op, Outcome, Radix, Semantics};
+
+fn value(text: &str) -> String {
+    integer::parse(text).unwrap().value().unwrap().to_string()
+}
+
+fn out_of_range(text: &str) -> Vec<String> {
+    let error = integer::parse(text).unwrap().value().unwrap_err();
+    assert!(matches!(error, Error::OutOfRange(_)), "{text}: {error}");
+    let mut lines = vec![error.to_string()];
+    lines.extend(error.notes());
+    lines
+}
+
+#[test]
+fn literals_from_chapter_02() {
+    let source = chapters::find("data_types").unwrap().source;
+    let snapshot = include_str!("snapshots/02_data_types.txt");
+
+    let literals = [
+        ("98_222", "98222", Radix::Decimal),
+        ("0xff", "255", Radix::Hex),
+        ("0o77", "63", Radix::Octal),
+        ("0b1111_0000", "240", Radix::Binary),
+        ("b'A'", "65", Radix::Byte),
+    ];
+    for (text, expected, radix) in literals {
+        assert!(source.contains(&format!(" = {text};")), "{text}");
+        let literal = integer::parse(text).unwrap();
+        assert_eq!(literal.radix, radix);
+        assert_eq!(literal.value().unwrap().to_string(), expected);
+    }
+    assert!(snapshot.contains("decimal: 98222, hex: 255, octal: 63, binary: 240, byte: 65"));
+
+    // 章节开头的 i8 最小值和 u64 最大值
+    assert_eq!(
+        integer::operand("-128", IntType::I8).unwrap(),
+        IntType::I8.min()
+    );
+    assert_eq!(
+        integer::operand("18_446_744_073_709_551_615", IntType::U64).unwrap(),
+        IntType::U64.max()
+    );
+}
+
+#[test]
+fn suffixes_and_default_types() {
+    let literal = integer::parse("255u8").unwrap();
+    assert_eq!(literal.suffix, Some(IntType::U8));
+    assert_eq!(literal.ty(), IntType::U8);
+    assert_eq!(integer::parse("42").unwrap().ty(), IntType::I32);
+    assert_eq!(integer::parse("b'\\n'").unwrap().ty(), IntType::U8);
+    assert_eq!(value("b'\\x7f'"), "127");
+    assert_eq!(value("1_u16"), "1");
+    assert_eq!(value("0x_ff_u8"), "255");
+    assert_eq!(value("-128i8"), "-128");
+    assert_eq!(value("-0x80i8"), "-128");
+    assert_eq!(value("0xFFFF_FFFFu32"), "4294967295");
+    assert_eq!(
+        value("340282366920938463463374607431768211455u128"),
+        u128::MAX.to_string()
+    );
+    assert_eq!(
+        value("-170141183460469231731687303715884105728i128"),
+        i128::MIN.to_string()
+    );
+
+    // 十六进制中的 f 是数字，所以 0x1f32 不是带 f32 后缀的 0x1
+    let literal = integer::parse("0x1f32").unwrap();
+    assert_eq!((literal.magnitude, literal.suffix), (0x1f32, None));
+
+    // 没有后缀的字面量按上下文取类型，有后缀的必须一致
+    assert_eq!(
+        integer::operand("200", IntType::U8).unwrap().to_string(),
+        "200"
+    );
+    assert_eq!(
+        integer::operand("200i32", IntType::U8),
+        Err(Error::Mismatched {
+            expected: IntType::U8,
+            found: IntType::I32
+        })
+    );
+}
+
+#[test]
+fn invalid_literals() {
+    let error = |text: &str| integer::parse(text).unwrap_err().to_string();
+    assert_eq!(
+        error("0b102"),
+        "error: invalid digit for a base 2 literal: `2`"
+    );
+    assert_eq!(
+        error("0o8"),
+        "error: invalid digit for a base 8 literal: `8`"
+    );
+    assert_eq!(error("0x"), "error: no valid digits found for number");
+    assert_eq!(error("0x_"), "error: no valid digits found for number");
+    assert_eq!(
+        error("5u7"),
+        "error: invalid suffix `u7` for number literal"
+    );
+    assert_eq!(
+        error("2.5"),
+        "error: `2.5` is a floating-point literal, not an integer literal"
+    );
+    assert_eq!(error("_1"), "error: `_1` is not an integer literal");
+    assert_eq!(error("b'AB'"), "error: invalid byte literal `b'AB'`");
+    assert_eq!(error("b'中'"), "error: invalid byte literal `b'中'`");
+    assert_eq!(
+        error("-b'A'"),
+        "error[E0600]: cannot apply unary operator `-` to type `u8`"
+    );
+
+    let too_large = integer::parse("340282366920938463463374607431768211456").unwrap_err();
+    assert_eq!(too_large, Error::TooLarge);
+    assert_eq!(
+        too_large.notes(),
+        ["note: value exceeds limit of `340282366920938463463374607431768211455`"]
+    );
+
+    let negated = integer::parse("-1u32").unwrap().value().unwrap_err();
+    assert_eq!(negated, Error::NegatedUnsigned(IntType::U32));
+    assert_eq!(negated.notes(), ["note: unsigned values cannot be negated"]);
+}
+
+#[test]
+fn out_of_range_literals_match_rustc() {
+    assert_eq!(
+        out_of_range("256u8"),
+        [
+            "error: literal out of range for `u8`",
+            "note: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`",
+            "help: consider using the type `u16` instead",
+        ]
+    );
+    // 有符号类型的正数能放进同样位数的无符号类型时，建议无符号类型
+    assert_eq!(
+        out_of_range("128i8")[2],
+        "help: consider using the type `u8` instead"
+    );
+    assert_eq!(
+        out_of_range("3_000_000_000")[2],
+        "help: consider using the type `u32` instead"
+    );
+    assert_eq!(
+        out_of_range("300i8")[2],
+        "help: consider using the type `i16` instead"
+    );
+    assert_eq!(
+        out_of_range("-129i8")[2],
+        "help: consider using the type `i16` instead"
+    );
+    // 非十进制的字面量说明截断后会变成什么
+    assert_eq!(
+        out_of_range("0xffi8"),
+        [
+            "error: literal out of range for `i8`",
+            "note: the literal `0xffi8` (decimal `255`) does not fit into the type `i8` and will become `-1i8`",
+            "help: consider using the type `u8` instead",
+        ]
+    );
+    assert_eq!(
+        out_of_range("0x1_0000u16")[1],
+        "note: the literal `0x1_0000u16` (decimal `65536`) does not fit into the type `u16` and will become `0u16`"
+    );
+    // isize 和 usize 没有建议
+    assert_eq!(out_of_range("99_999_999_999_999_999_999usize").len(), 2);
+}
+
+#[test]
+fn types_and_values() {
+    assert_eq!(IntType::ALL.len(), 12);
+    for ty in IntType::ALL {
+        assert_eq!(IntType::parse(ty.name()), Some(ty));
+        assert_eq!(ty.signed(), ty.min().to_i128() != Some(0));
+    }
+    assert_eq!(IntType::I128.bits(), 128);
+    assert_eq!(IntType::U128.max().to_i128(), None);
+    assert_eq!(IntType::I16.min().to_i128(), Some(-32768));
+
+    let minus_one = Integer::new(IntType::I8, -1).unwrap();
+    assert_eq!(minus_one.binary(), "1111_1111");
+    assert_eq!(
+        Integer::new(IntType::U16, 5).unwrap().binary(),
+        "0000_0000_0000_0101"
+    );
+    assert_eq!(Integer::new(IntType::U8, 256), None);
+    assert_eq!(Integer::new(IntType::U8, -1), None);
+}
+
+#[test]
+fn calculator_compares_overflow_semantics() {
+    let u8 = |n| Integer::new(IntType::U8, n).unwrap();
+    let rows = integer::evaluate(Op::Add, u8(200), Some(u8(100))).unwrap();
+    let rendered: Vec<String> = rows
+        .iter()
+        .map(|(semantics, outcome)| format!("{} {}", semantics.method(Op::Add), outcome))
+        .collect();
+    assert_eq!(
+        rendered,
+        [
+            "checked_add None",
+            "wrapping_add 44",
+            "saturating_add 255",
+            "overflowing_add (44, true)",
+        ]
+    );
+    assert_eq!(
+        integer::plain_operator(Op::Add, IntType::U8, &rows),
+        "debug 构建 panic: attempt to add with overflow；release 构建得到 44"
+    );
+
+    let rows = integer::evaluate(Op::Add, u8(1), Some(u8(2))).unwrap();
+    assert_eq!(rows[0].1, Outcome::Checked(Some(u8(3))));
+    assert_eq!(integer::plain_operator(Op::Add, IntType::U8, &rows), "3");
+
+    let i32 = |n| Integer::new(IntType::I32, n).unwrap();
+    let rows = integer::evaluate(Op::Div, i32(7), Some(i32(0))).unwrap();
+    assert_eq!(rows[0].1, Outcome::Checked(None));
+    assert_eq!(rows[1].1, Outcome::Panic("attempt to divide by zero"));
+    let rows = integer::evaluate(Op::Rem, i32(7), Some(i32(0))).unwrap();
+    assert_eq!(rows[2], (Semantics::Saturating, Outcome::Missing));
+
+    // MIN / -1 和 MIN % -1 在 release 构建中也会 panic，不会回绕
+    let minus_one = i32(-1);
+    let rows = integer::evaluate(Op::Div, IntType::I32.min(), Some(minus_one)).unwrap();
+    assert_eq!(rows[1].1, Outcome::Value(IntType::I32.min()));
+    assert_eq!(
+        integer::plain_operator(Op::Div, IntType::I32, &rows),
+        "panic: attempt to divide with overflow（debug 和 release 构建都会 panic，不受 overflow-checks 影响）"
+    );
+    let rows = integer::evaluate(Op::Rem, IntType::I32.min(), Some(minus_one)).unwrap();
+    assert_eq!(
+        integer::plain_operator(Op::Rem, IntType::I32, &rows),
+        "panic: attempt to calculate the remainder with overflow（debug 和 release 构建都会 panic，不受 overflow-checks 影响）"
+    );
+
+    // i8::MIN 取负溢出；无符号类型没有 saturating_neg，也不能用 - 运算符
+    let rows = integer::evaluate(Op::Neg, IntType::I8.min(), None).unwrap();
+    assert_eq!(rows[2].1, Outcome::Value(IntType::I8.max()));
+    assert_eq!(rows[3].1, Outcome::Overflowing(IntType::I8.min(), true));
+    let rows = integer::evaluate(Op::Neg, u8(5), None).unwrap();
+    assert_eq!(rows[2].1, Outcome::Missing);
+    assert!(integer::plain_operator(Op::Neg, IntType::U8, &rows).contains("E0600"));
+
+    // pow 和 << 的右操作数是 u32
+    let exponent = integer::operand("31", IntType::U32).unwrap();
+    let rows = integer::evaluate(Op::Pow, i32(2), Some(exponent)).unwrap();
+    assert_eq!(rows[2].1, Outcome::Value(IntType::I32.max()));
+    assert_eq!(
+        integer::evaluate(Op::Pow, i32(2), Some(i32(3))),
+        Err(Error::Mismatched {
+            expected: IntType::U32,
+            found: IntType::I32
+        })
+    );
+    assert_eq!(Op::parse("<<"), Some(Op::Shl));
+    assert_eq!(Op::parse("mul"), Some(Op::Mul));
+}
+
+#[test]
+fn calculator_agrees_with_native_arithmetic() {
+    for a in i8::MIN..=i8::MAX {
+        for b in i8::MIN..=i8::MAX {
+            let x = Integer::new(IntType::I8, a.into()).unwrap();
+            let y = Integer::new(IntType::I8, b.into()).unwrap();
+            let rows = integer::evaluate(Op::Mul, x, Some(y)).unwrap();
+            let (product, overflowed) = a.overflowing_mul(b);
+            let product = Integer::new(IntType::I8, product.into()).unwrap();
+            assert_eq!(rows[1].1, Outcome::Value(product));
+            assert_eq!(rows[3].1, Outcome::Overflowing(product, overflowed));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// ============================================
+// 字符串驻留测试
+// ============================================
+
+use std::collections::HashMap;
+
+use rust_tutorial::interner::{Interner, Symbol};
+
+#[test]
+fn same_string_gets_the_same_symbol() {
+    let mut interner = Interner::new();
+    let hello = interner.get_or_intern("hello");
+    let world = interner.get_or_intern("world");
+    assert_ne!(hello, world);
+    // 从别的 String 来的同样内容，得到同一个 Symbol
+    let owned = String::from("hel") + "lo";
+    assert_eq!(interner.get_or_intern(&owned), hello);
+    assert_eq!(interner.len(), 2);
+    assert_eq!((hello.index(), world.index()), (0, 1));
+
+    assert_eq!(interner.get("world"), Some(world));
+    assert_eq!(interner.get("missing"), None);
+    assert_eq!(Interner::new().get("hello"), None);
+
+    // 空字符串和 Unicode 也可以驻留
+    let empty = interner.get_or_intern("");
+    let cat = interner.get_or_intern("😻");
+    assert_eq!(interner.resolve(empty), "");
+    assert_eq!(interner.resolve(cat), "😻");
+    assert!(!interner.is_empty());
+}
+
+#[test]
+fn symbols_are_copy_and_outlive_borrows_of_the_source() {
+    let mut interner = Interner::new();
+    let symbol = {
+        // 原来的 String 在块结束时被释放，Symbol 不借用它
+        let temporary = format!("{}-{}", "word", 42);
+        interner.get_or_intern(&temporary)
+    };
+    let copy = symbol;
+    assert_eq!(interner.resolve(symbol), "word-42");
+    assert_eq!(interner.resolve(copy), "word-42");
+    assert_eq!(size_of::<Symbol>(), 4);
+    assert_eq!(size_of::<Option<Symbol>>(), 8);
+
+    // resolve 返回的 &str 和 Interner 活得一样久，可以同时持有多个
+    let a = interner.get_or_intern("a");
+    let b = interner.get_or_intern("b");
+    let (ra, rb) = (interner.resolve(a), interner.resolve(b));
+    assert_eq!([ra, rb], ["a", "b"]);
+}
+
+#[test]
+fn strings_never_move_once_stored() {
+    let mut interner = Interner::with_chunk_size(16);
+    let first = interner.get_or_intern("0123456789");
+    let before = interner.resolve(first).as_ptr();
+    for i in 0..1000 {
+        interner.get_or_intern(&format!("word{i}"));
+    }
+    // 块满了就分配新块，不会扩容已有的块
+    assert_eq!(interner.resolve(first).as_ptr(), before);
+    // 比块还长的字符串单独占一块
+    let long = "x".repeat(100);
+    let symbol = interner.get_or_intern(&long);
+    assert_eq!(interner.resolve(symbol), long);
+
+    // 哈希表扩容之后所有的字符串都还找得到
+    for i in 0..1000 {
+        let word = format!("word{i}");
+        assert_eq!(interner.resolve(interner.get(&word).unwrap()), word);
+    }
+    assert_eq!(interner.len(), 1002);
+}
+
+#[test]
+fn iterates_in_interning_order() {
+    let mut interner = Interner::new();
+    for word in "the quick brown fox jumps over the lazy dog the end".split(' ') {
+        interner.get_or_intern(word);
+    }
+    let words: Vec<&str> = interner.iter().map(|(_, word)| word).collect();
+    assert_eq!(
+        words,
+        [
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog", "end"
+        ]
+    );
+    for (symbol, word) in &interner {
+        assert_eq!(interner.get(word), Some(symbol));
+    }
+}
+
+#[test]
+fn word_count_with_symbol_keys_saves_allocations() {
+    // 第 09 章的单词计数，重复 100 遍
+    let text = vec!["hello world wonderful world"; 100].join(" ");
+
+    let mut owned: HashMap<String, i32> = HashMap::new();
+    for word in text.split_whitespace() {
+        *owned.entry(word.to_string()).or_insert(0) += 1;
+    }
+
+    let mut interner = Interner::new();
+    let mut counts: HashMap<Symbol, i32> = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(interner.get_or_intern(word)).or_insert(0) += 1;
+    }
+    for (symbol, word) in &interner {
+        assert_eq!(counts[&symbol], owned[word]);
+    }
+
+    let stats = interner.stats();
+    assert_eq!((stats.strings, stats.bytes, stats.chunks), (3, 19, 1));
+    // 一个块、一次位置表分配、一次哈希表分配；to_string 的做法每个单词分配一次
+    assert_eq!(stats.allocations, 3);
+    assert_eq!(stats.arena_capacity, 4096);
+    assert!(stats.total_bytes() > stats.arena_capacity);
+
+    // 第 09 章的输出
+    let snapshot = include_str!("snapshots/09_collections.txt");
+    assert!(snapshot.contains("Symbol(1) world: 200\n"));
+    assert!(snapshot.contains("复制成 String 键: 400 次分配，2400 字节\n"));
+    assert!(snapshot.contains("驻留成 Symbol 键: 3 个不同的单词，3 次分配，19 字节\n"));
+}
@@ -0,0 +1,250 @@
+// ============================================
+// 变量绑定解释器测试
+// ============================================
+
+use rust_tutorial::chapters;
+use rust_tutorial::interpreter::{self, Error, Interpreter, Value};
+
+fn run(source: &str) -> Result<String, Error> {
+    Interpreter::new().run(source)
+}
+
+fn error(source: &str) -> Error {
+    run(source).expect_err(source)
+}
+
+#[test]
+fn runs_the_body_of_chapter_01() {
+    let source = chapters::find("variables").unwrap().source;
+    let start = source.find("pub fn main() {").unwrap() + "pub fn main() {".len();
+    let end = source.rfind('}').unwrap();
+
+    let output = run(&source[start..end]).unwrap();
+    let snapshot = include_str!("snapshots/01_variables.txt");
+    // 快照的第一行是章节标题
+    let expected = snapshot.split_once('\n').unwrap().1;
+    assert_eq!(output.trim_end(), expected.trim_end());
+}
+
+#[test]
+fn immutable_bindings_and_mut() {
+    let e = error("let x = 5;\nx = 6;");
+    assert_eq!(e.code, Some("E0384"));
+    assert_eq!(e.message, "cannot assign twice to immutable variable `x`");
+    assert_eq!((e.line, e.column), (2, 1));
+    assert_eq!(
+        e.help.as_deref(),
+        Some("consider making this binding mutable: `mut x`")
+    );
+    assert_eq!(
+        e.render("let x = 5;\nx = 6;", "main.rs"),
+        "\
+error[E0384]: cannot assign twice to immutable variable `x`
+ --> main.rs:2:1
+  |
+2 | x = 6;
+  | ^
+  = help: consider making this binding mutable: `mut x`
+"
+    );
+
+    assert_eq!(error("let x = 1; x += 1;").code, Some("E0384"));
+    assert_eq!(
+        run("let mut y = 10; y = 20; y *= 2; println!(\"{y}\");").unwrap(),
+        "40\n"
+    );
+
+    // 先声明后赋值：只能赋值一次，赋值前不能使用
+    assert_eq!(run("let x; x = 5; println!(\"{}\", x);").unwrap(), "5\n");
+    assert_eq!(error("let x; x = 5; x = 6;").code, Some("E0384"));
+    let e = error("let x: i32; println!(\"{}\", x);");
+    assert_eq!(e.code, Some("E0381"));
+    assert_eq!(e.message, "used binding `x` isn't initialized");
+}
+
+#[test]
+fn shadowing_may_change_the_type_but_assignment_may_not() {
+    let output = run("\
+let spaces = \"   \";
+let spaces = spaces.len();
+let spaces = spaces + 1;
+println!(\"{} {:?}\", spaces, (spaces, 'R'));
+")
+    .unwrap();
+    assert_eq!(output, "4 (4, 'R')\n");
+
+    let e = error("let mut spaces = \"   \";\nspaces = spaces.len();");
+    assert_eq!(e.code, Some("E0308"));
+    assert_eq!(
+        e.message,
+        "mismatched types: expected `&str`, found `usize`"
+    );
+
+    let e = error("let x: u8 = 1; let y: i32 = 2; let z = x + y;");
+    assert_eq!(e.message, "mismatched types: expected `u8`, found `i32`");
+    let e = error("let s = \"a\" + \"b\";");
+    assert_eq!(e.code, Some("E0369"));
+    assert_eq!(e.message, "cannot add `&str` to `&str`");
+    assert_eq!(error("let n = 5; n.len();").code, Some("E0599"));
+}
+
+#[test]
+fn blocks_scope_their_bindings() {
+    let output = run("\
+let x = 1;
+let y = {
+    let x = x * 10;
+    println!(\"inner x = {}\", x);
+    x + 1
+};
+println!(\"outer x = {x}, y = {y}\");
+")
+    .unwrap();
+    assert_eq!(output, "inner x = 10\nouter x = 1, y = 11\n");
+
+    let e = error("{\n    let scoped_var = 50;\n}\nprintln!(\"{}\", scoped_var);");
+    assert_eq!(e.code, Some("E0425"));
+    assert_eq!(e.message, "cannot find value `scoped_var` in this scope");
+    assert_eq!((e.line, e.column), (4, 16));
+
+    // 代码块中修改外层的 mut 变量
+    let output = run("let mut n = 1; { n += 1; } println!(\"{n}\");").unwrap();
+    assert_eq!(output, "2\n");
+}
+
+#[test]
+fn destructuring_literals_and_panics() {
+    let output = run(
+        "let (a, (b, _), mut c) = (1, (true, 'x'), 2.5); c = c * 2.0; println!(\"{a} {b} {c}\");",
+    )
+    .unwrap();
+    assert_eq!(output, "1 true 5\n");
+    let e = error("let (a, b) = (1, 2, 3);");
+    assert_eq!(e.code, Some("E0308"));
+
+    assert_eq!(
+        run("let x: u8 = 200; let y = x + 55; println!(\"{}\", y);").unwrap(),
+        "255\n"
+    );
+    let e = error("let x: u8 = 200; let y = x + 56;");
+    assert!(e.panic);
+    assert_eq!(e.to_string(), "panicked: attempt to add with overflow");
+    assert_eq!(
+        error("let z = 0; let q = 10 / z;").message,
+        "attempt to divide by zero"
+    );
+    assert_eq!(
+        run("let big = 1_000_000i64 * 1_000_000; println!(\"{}\", big);").unwrap(),
+        "1000000000000\n"
+    );
+    assert_eq!(
+        run("let v = if 3 > 2 && !false { \"yes\" } else { \"no\" }; println!(\"{v}\");").unwrap(),
+        "yes\n"
+    );
+
+    let e = error("let x = 5\nlet y = 6;");
+    assert_eq!(e.code, None);
+    assert_eq!(e.message, "expected `;`, found `let`");
+    assert_eq!((e.line, e.column), (2, 1));
+}
+
+#[test]
+fn i128_overflow_is_reported_not_a_crash() {
+    let max = "170141183460469231731687303715884105727";
+    for (source, verb) in [
+        (format!("let q = {max}i128; let r = q + 1;"), "add"),
+        (
+            format!("let q: i128 = -{max} - 1; let r = q - 1;"),
+            "subtract",
+        ),
+        (format!("let q = {max}i128; let r = q * 2;"), "multiply"),
+        (
+            format!("let q: i128 = -{max} - 1; let r = q / -1;"),
+            "divide",
+        ),
+        (
+            format!("let q: i128 = -{max} - 1; let r = q % -1;"),
+            "calculate the remainder",
+        ),
+        (format!("let q: i128 = -{max} - 1; let r = -q;"), "negate"),
+    ] {
+        let e = error(&source);
+        assert!(e.panic, "{source}");
+        assert_eq!(
+            e.message,
+            format!("attempt to {verb} with overflow"),
+            "{source}"
+        );
+    }
+
+    // u128 用 i128 保存，只能到 i128::MAX
+    assert_eq!(
+        run(&format!("let u = {max}u128; println!(\"{{u}}\");")).unwrap(),
+        format!("{max}\n")
+    );
+    let e = error(&format!("let u = {max}u128 + 1;"));
+    assert_eq!(
+        e.message,
+        "attempt to add with overflow (this interpreter stores u128 as i128, so u128 stops at i128::MAX)"
+    );
+    let e = error("let u = 340282366920938463463374607431768211455u128;");
+    assert!(
+        e.message.ends_with("so u128 stops at i128::MAX)"),
+        "{}",
+        e.message
+    );
+}
+
+#[test]
+fn repl_shows_the_scope_stack() {
+    let input = "\
+let x = 5
+let x = x.len()
+let x = \"hi\"
+{
+    let y = 1;
+}
+x
+:trace
+{ let x = 'c'; }
+:quit
+";
+    let mut out = Vec::new();
+    interpreter::repl(&mut input.as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    // 出错的输入不改变绑定
+    assert!(out.contains("error[E0599]: no method named `len` found for type `i32`"));
+    assert!(out.contains(
+        "\
+rust> 作用域 #0
+  x: i32 = 5    (已被遮蔽)
+  x: &str = \"hi\"
+"
+    ));
+    // 代码块结束后作用域栈没有变化，不再打印
+    assert!(out.contains("rust>   ...   ... rust> \"hi\"\n"));
+    // 跟踪模式下可以看到代码块内部的作用域
+    assert!(out.contains(
+        "\
+  作用域 #1
+    x: char = 'c'
+"
+    ));
+    assert!(out.contains("  x: &str = \"hi\"    (已被遮蔽)\n"));
+
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .run("let mut n = 1; let t = (n, false);")
+        .unwrap();
+    let scopes = interpreter.scopes();
+    assert_eq!(scopes.len(), 1);
+    assert_eq!(
+        scopes[0].bindings[1].value,
+        Some(Value::Tuple(vec![Value::Int(1, "i32"), Value::Bool(false)]))
+    );
+    assert_eq!(
+        interpreter::render_scopes(scopes),
+        "作用域 #0\n  mut n: i32 = 1\n  t: (i32, bool) = (1, false)\n"
+    );
+}
@@ -0,0 +1,152 @@
+// ============================================
+// 章节测验测试
+// ============================================
+
+use std::path::Path;
+
+use rust_tutorial::chapters;
+use rust_tutorial::quiz::{self, Kind};
+
+const SAMPLE: &str = "\
+# 注释
+
+[output]
+question: largest(&char_list) 打印出什么？
+code: let result = largest(&char_list);
+code:     println!(\"最大字符: {}\", result);
+answer: 最大字符: y
+explain: char 按 Unicode 标量值比较
+
+[choice]
+question: 哪个分支？
+wrong: A 分支
+right: B 分支
+right: C 分支
+";
+
+#[test]
+fn every_chapter_has_a_bank_that_matches_its_code_and_output() {
+    let runner = Path::new(env!("CARGO_BIN_EXE_rust-tutorial"));
+    let banks = quiz::all();
+    assert_eq!(banks.len(), chapters::all().len());
+
+    let handles: Vec<_> = banks
+        .into_iter()
+        .map(|bank| {
+            std::thread::spawn(move || {
+                let questions = bank.questions().unwrap();
+                assert!(questions.len() >= 3, "{} 题目太少", bank.file_name);
+                let problems = quiz::check(runner, &bank).unwrap();
+                let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+                (bank.file_name, problems)
+            })
+        })
+        .collect();
+
+    let mut failures = Vec::new();
+    for handle in handles {
+        let (file_name, problems) = handle.join().unwrap();
+        for problem in problems {
+            failures.push(format!("{}: {}", file_name, problem));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn parses_the_documented_format() {
+    let questions = quiz::parse(SAMPLE).unwrap();
+    assert_eq!(questions.len(), 2);
+
+    assert_eq!(questions[0].line, 3);
+    assert_eq!(
+        questions[0].code,
+        [
+            "let result = largest(&char_list);",
+            "    println!(\"最大字符: {}\", result);"
+        ]
+    );
+    assert_eq!(
+        questions[0].kind,
+        Kind::Output {
+            answer: vec![String::from("最大字符: y")]
+        }
+    );
+    assert_eq!(
+        questions[0].explain.as_deref(),
+        Some("char 按 Unicode 标量值比较")
+    );
+
+    let Kind::Choice { options } = &questions[1].kind else {
+        panic!("应该是选择题");
+    };
+    let rights: Vec<bool> = options.iter().map(|(_, right)| *right).collect();
+    assert_eq!(rights, [false, true, true]);
+}
+
+#[test]
+fn reports_format_errors_with_line_numbers() {
+    let cases = [
+        ("question: 没有类型\n", 1, "缺少 [choice] 或 [output]"),
+        ("[quiz]\n", 1, "未知的题目类型"),
+        ("[output]\nquestion: q\n", 1, "至少需要一行 answer"),
+        ("[choice]\nquestion: q\nright: a\n", 1, "至少需要一个 wrong"),
+        ("[choice]\nquestion: q\nanswer: a\n", 3, "不能有 answer"),
+        (
+            "[output]\nquestion: q\nanswer: a\nhint: h\n",
+            4,
+            "未知的字段",
+        ),
+        ("[output]\nanswer: a\n\n[output]\n", 1, "缺少 question"),
+        ("[output]\nquestion: q\n这一行没有冒号\n", 3, "无法识别"),
+    ];
+
+    for (text, line, message) in cases {
+        let error = quiz::parse(text).unwrap_err();
+        assert_eq!(error.line, line, "{:?}", text);
+        assert!(error.message.contains(message), "{:?}: {}", text, error);
+    }
+}
+
+#[test]
+fn validate_detects_stale_code_and_answers() {
+    let questions = quiz::parse(SAMPLE).unwrap();
+    let source = "    let result = largest(&char_list);\n    println!(\"最大字符: {}\", result);\n";
+
+    assert!(quiz::validate(&questions, source, "最大数字: 100\n最大字符: y\n").is_empty());
+
+    let problems = quiz::validate(&questions, source, "最大字符: q\n");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].message.contains("最大字符: y"));
+
+    let problems = quiz::validate(&questions, "let result = 0;\n", "最大字符: y\n");
+    assert_eq!(problems.len(), 2);
+    assert!(problems.iter().all(|p| p.message.contains("找不到代码")));
+}
+
+#[test]
+fn run_scores_answers() {
+    let questions = quiz::parse(SAMPLE).unwrap();
+
+    // 答对输出题；选择题先输入无效字母，再选 "c, B"
+    let mut input = "  最大字符: y \nZ\nc, B\n".as_bytes();
+    let mut output = Vec::new();
+    let score = quiz::run("测试", &questions, &mut input, &mut output).unwrap();
+    assert_eq!((score.correct, score.total), (2, 2));
+    assert!(score.missed.is_empty());
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("你的答案（多选）: 请输入 A 到 C 之间的字母"));
+    assert!(output.ends_with("得分: 2/2\n"));
+
+    // 答错输出题，然后输入结束
+    let mut input = "最大字符: q\n".as_bytes();
+    let mut output = Vec::new();
+    let score = quiz::run("测试", &questions, &mut input, &mut output).unwrap();
+    assert_eq!(score.correct, 0);
+    assert_eq!(score.missed, [1, 2]);
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("✗ 错误，实际输出是:\n  最大字符: y\n"));
+    assert!(output.contains("输入结束，停止答题"));
+}
@@ -0,0 +1,252 @@
+// ============================================
+// 空间索引测试
+// ============================================
+
+use rust_tutorial::geometry::{Point, Rectangle};
+use rust_tutorial::grid::Rng;
+use rust_tutorial::spatial::{BruteForce, Id, QuadTree, SpatialIndex, UniformGrid};
+
+/// 要和暴力法比较的几种索引
+fn indexes() -> Vec<(&'static str, Box<dyn SpatialIndex>)> {
+    vec![
+        ("grid(1)", Box::new(UniformGrid::new(1))),
+        ("grid(8)", Box::new(UniformGrid::new(8))),
+        ("grid(100)", Box::new(UniformGrid::new(100))),
+        ("quadtree", Box::new(QuadTree::new())),
+    ]
+}
+
+fn random_point(rng: &mut Rng, world: usize) -> Point {
+    Point::new(rng.below(world) as i32 - 10, rng.below(world) as i32 - 10)
+}
+
+fn random_rectangle(rng: &mut Rng, world: usize) -> Rectangle {
+    // 偶尔出现空矩形和很大的矩形
+    let size = |rng: &mut Rng| match rng.below(20) {
+        0 => 0,
+        1 => 40 + rng.below(400) as u32,
+        _ => 1 + rng.below(12) as u32,
+    };
+    let origin = random_point(rng, world);
+    Rectangle::at(origin, size(rng), size(rng))
+}
+
+#[test]
+fn answers_point_region_and_pair_queries() {
+    for (name, mut index) in indexes() {
+        let a = index.insert(Rectangle::new(0, 0, 30, 50));
+        let b = index.insert(Rectangle::new(20, 40, 10, 10));
+        let c = index.insert(Rectangle::new(30, 0, 5, 5));
+        let empty = index.insert(Rectangle::new(5, 5, 0, 10));
+        assert_eq!(index.len(), 4, "{name}");
+
+        assert_eq!(index.query_point(Point::new(25, 45)), [a, b], "{name}");
+        // 右边界不包含：(30, 0) 属于 c，不属于 a
+        assert_eq!(index.query_point(Point::new(30, 0)), [c], "{name}");
+        assert_eq!(index.query_point(Point::new(5, 5)), [a], "{name}");
+        assert_eq!(
+            index.query_region(&Rectangle::new(28, -10, 4, 12)),
+            [a, c],
+            "{name}"
+        );
+        assert_eq!(
+            index.query_region(&Rectangle::new(0, 0, 0, 0)),
+            [],
+            "{name}"
+        );
+        // a 和 c 只是边挨着，空矩形和谁都不相交
+        assert_eq!(index.overlapping_pairs(), [(a, b)], "{name}");
+
+        assert_eq!(index.remove(a), Some(Rectangle::new(0, 0, 30, 50)));
+        assert_eq!(index.remove(a), None, "{name}");
+        assert_eq!(index.get(a), None, "{name}");
+        assert_eq!(index.get(empty), Some(Rectangle::new(5, 5, 0, 10)));
+        assert_eq!(index.query_point(Point::new(25, 45)), [b], "{name}");
+        assert_eq!(index.overlapping_pairs(), [], "{name}");
+        // Id 不会复用
+        let d = index.insert(Rectangle::new(0, 0, 1, 1));
+        assert_eq!(d.index(), 4, "{name}");
+        assert_eq!(index.len(), 4, "{name}");
+    }
+}
+
+#[test]
+fn finds_the_nearest_rectangle() {
+    for (name, mut index) in indexes() {
+        assert_eq!(index.nearest(Point::ORIGIN), None, "{name}");
+        index.insert(Rectangle::new(0, 0, 0, 0));
+        assert_eq!(index.nearest(Point::ORIGIN), None, "{name}");
+
+        let left = index.insert(Rectangle::new(-10, 0, 5, 5));
+        let right = index.insert(Rectangle::new(10, 0, 5, 5));
+        let far = index.insert(Rectangle::new(1000, 1000, 1, 1));
+        // 到 left 最近的整点是 (-6, 0)，到 right 是 (10, 0)
+        assert_eq!(index.nearest(Point::new(3, 0)), Some((right, 49)), "{name}");
+        // 一样近时取 Id 小的
+        assert_eq!(index.nearest(Point::new(2, 2)), Some((left, 64)), "{name}");
+        assert_eq!(index.nearest(Point::new(2, 0)), Some((left, 64)), "{name}");
+        assert_eq!(index.nearest(Point::new(12, 3)), Some((right, 0)), "{name}");
+        assert_eq!(
+            index.nearest(Point::new(990, 2000)),
+            Some((far, 10 * 10 + 1000 * 1000)),
+            "{name}"
+        );
+        index.remove(right);
+        assert_eq!(index.nearest(Point::new(3, 0)), Some((left, 81)), "{name}");
+    }
+}
+
+#[test]
+fn agrees_with_brute_force_on_random_operations() {
+    for seed in 0..8 {
+        let mut rng = Rng::new(seed);
+        let mut reference = BruteForce::new();
+        let mut indexes = indexes();
+        let mut ids: Vec<Id> = Vec::new();
+        for step in 0..600 {
+            match rng.below(10) {
+                0..=4 => {
+                    let rectangle = random_rectangle(&mut rng, 200);
+                    let id = reference.insert(rectangle);
+                    for (_, index) in &mut indexes {
+                        assert_eq!(index.insert(rectangle), id);
+                    }
+                    ids.push(id);
+                }
+                5 if !ids.is_empty() => {
+                    // 有时删除已经删掉的矩形
+                    let id = ids[rng.below(ids.len())];
+                    let removed = reference.remove(id);
+                    for (_, index) in &mut indexes {
+                        assert_eq!(index.remove(id), removed);
+                    }
+                }
+                _ => {
+                    let point = random_point(&mut rng, 220);
+                    let region = random_rectangle(&mut rng, 220);
+                    for (name, index) in &indexes {
+                        let context = format!("seed {seed} step {step} {name}");
+                        assert_eq!(index.len(), reference.len(), "{context}");
+                        assert_eq!(
+                            index.query_point(point),
+                            reference.query_point(point),
+                            "{context} {point}"
+                        );
+                        assert_eq!(
+                            index.query_region(&region),
+                            reference.query_region(&region),
+                            "{context} {region}"
+                        );
+                        assert_eq!(
+                            index.nearest(point),
+                            reference.nearest(point),
+                            "{context} {point}"
+                        );
+                    }
+                }
+            }
+            if step % 50 == 49 {
+                let pairs = reference.overlapping_pairs();
+                for (name, index) in &indexes {
+                    assert_eq!(index.overlapping_pairs(), pairs, "seed {seed} {name}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn handles_rectangles_at_the_ends_of_the_coordinate_range() {
+    let everything = Rectangle::new(i32::MIN, i32::MIN, u32::MAX, u32::MAX);
+    // 从 i32::MAX 伸出去，右边界超出 i32
+    let beyond = Rectangle::new(i32::MAX - 1, i32::MAX - 1, u32::MAX, 2);
+    let corner = Rectangle::new(i32::MIN, i32::MIN, 1, 1);
+    let small = Rectangle::new(0, 0, 10, 10);
+    for (name, mut index) in indexes() {
+        let mut reference = BruteForce::new();
+        let mut ids = Vec::new();
+        for rectangle in [everything, beyond, corner, small] {
+            ids.push(index.insert(rectangle));
+            assert_eq!(ids.last(), Some(&reference.insert(rectangle)));
+        }
+        for point in [
+            Point::new(i32::MAX, i32::MAX),
+            Point::new(i32::MIN, i32::MIN),
+            Point::new(5, 5),
+            Point::new(i32::MAX, 0),
+        ] {
+            assert_eq!(
+                index.query_point(point),
+                reference.query_point(point),
+                "{name} {point}"
+            );
+            assert_eq!(
+                index.nearest(point),
+                reference.nearest(point),
+                "{name} {point}"
+            );
+        }
+        for region in [everything, beyond, Rectangle::new(-5, -5, 10, 10)] {
+            assert_eq!(
+                index.query_region(&region),
+                reference.query_region(&region),
+                "{name} {region}"
+            );
+        }
+        assert_eq!(index.overlapping_pairs().len(), 3, "{name}");
+        index.remove(ids[0]);
+        reference.remove(ids[0]);
+        // 离所有矩形都很远的点
+        let point = Point::new(-2_000_000_000, 2_000_000_000);
+        assert_eq!(index.nearest(point), reference.nearest(point), "{name}");
+    }
+}
+
+#[test]
+fn quadtree_splits_crowded_nodes() {
+    let mut tree = QuadTree::new();
+    assert_eq!((tree.node_count(), tree.depth()), (1, 1));
+    let mut ids = Vec::new();
+    for i in 0..100 {
+        ids.push(tree.insert(Rectangle::new(i * 3, i * 3, 2, 2)));
+    }
+    // 根节点覆盖 2^32 的范围，挤在一起的小矩形要分很多层
+    assert!(tree.depth() > 20, "depth {}", tree.depth());
+    assert_eq!(tree.node_count() % 4, 1);
+    assert_eq!(tree.query_point(Point::new(31, 31)), [ids[10]]);
+    assert_eq!(
+        tree.query_region(&Rectangle::new(0, 0, 7, 7)),
+        [ids[0], ids[1], ids[2]]
+    );
+
+    // 一百个一样的矩形：分到最深也放不开，不会无限分裂
+    let mut same = QuadTree::new();
+    for _ in 0..100 {
+        same.insert(Rectangle::new(7, 7, 1, 1));
+    }
+    assert!(same.depth() <= 33);
+    assert_eq!(same.query_point(Point::new(7, 7)).len(), 100);
+    assert_eq!(same.overlapping_pairs().len(), 100 * 99 / 2);
+
+    // 伸出 i32 范围的矩形不会留在根节点上，而是按左上角一直分到右下角最深的一层
+    let mut edge = QuadTree::new();
+    let beyond = Rectangle::new(i32::MAX - 1, i32::MAX - 1, u32::MAX, 2);
+    let stuck_out: Vec<_> = (0..9).map(|_| edge.insert(beyond)).collect();
+    assert_eq!(edge.depth(), 33);
+    assert_eq!(edge.query_point(Point::new(i32::MAX, i32::MAX)), stuck_out);
+    assert_eq!(edge.query_region(&beyond), stuck_out);
+    assert_eq!(edge.query_region(&Rectangle::new(0, 0, 10, 10)), []);
+    assert_eq!(
+        edge.nearest(Point::ORIGIN).map(|(id, _)| id),
+        Some(stuck_out[0])
+    );
+
+    // 删除不合并节点
+    let nodes = tree.node_count();
+    for id in ids {
+        tree.remove(id);
+    }
+    assert!(tree.is_empty());
+    assert_eq!(tree.node_count(), nodes);
+    assert_eq!(tree.nearest(Point::ORIGIN), None);
+}
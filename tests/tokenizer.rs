@@ -0,0 +1,247 @@
+// ============================================
+// 零拷贝分词器测试
+// ============================================
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+use rust_tutorial::tokenizer::{self, Kind, LineMap, Location, Span, Token, TokenError, Tokenizer};
+
+// 统计当前线程分配内存的次数，用来确认分词不分配
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn kinds(text: &str) -> Vec<(Kind, &str)> {
+    tokenizer::tokenize(text)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .iter()
+        .map(|token| (token.kind, token.text))
+        .collect()
+}
+
+#[test]
+fn splits_words_numbers_strings_and_punctuation() {
+    use Kind::*;
+    assert_eq!(
+        kinds("let x_1 = 1_000 + 3.14; // \"hi\\n\""),
+        [
+            (Word, "let"),
+            (Word, "x_1"),
+            (Punct, "="),
+            (Number, "1_000"),
+            (Punct, "+"),
+            (Number, "3.14"),
+            (Punct, ";"),
+            (Punct, "/"),
+            (Punct, "/"),
+            (Str, "\"hi\\n\""),
+        ]
+    );
+    // 1..5 中的点不是小数点
+    assert_eq!(
+        kinds("1..5 x.0"),
+        [
+            (Number, "1"),
+            (Punct, "."),
+            (Punct, "."),
+            (Number, "5"),
+            (Word, "x"),
+            (Punct, "."),
+            (Number, "0")
+        ]
+    );
+    // 每个记号的 span 都能从原字符串切回同样的内容
+    let text = "hello world";
+    for token in Tokenizer::new(text).map(Result::unwrap) {
+        assert_eq!(token.span.slice(text), token.text);
+        assert_eq!(&text[std::ops::Range::from(token.span)], token.text);
+    }
+    assert_eq!(tokenizer::tokenize("   \n\t").unwrap(), []);
+}
+
+#[test]
+fn never_splits_inside_a_character() {
+    use Kind::*;
+    // 全角空格和不换行空格也是空白；中文字符是字母，全角逗号是标点
+    assert_eq!(
+        kinds("你好，世界\u{3000}héllo\u{a0}😻!"),
+        [
+            (Word, "你好"),
+            (Punct, "，"),
+            (Word, "世界"),
+            (Word, "héllo"),
+            (Punct, "😻"),
+            (Punct, "!")
+        ]
+    );
+    // e + 组合重音符是一个单词，单独的组合标记跟在标点后面
+    assert_eq!(
+        kinds("cafe\u{301} -\u{301}"),
+        [(Word, "cafe\u{301}"), (Punct, "-\u{301}")]
+    );
+
+    let text = "नमस्ते ℤ = \"😻\" 🦀🦀";
+    for token in Tokenizer::new(text).map(Result::unwrap) {
+        assert!(text.is_char_boundary(token.span.start));
+        assert!(text.is_char_boundary(token.span.end));
+    }
+    assert_eq!(tokenizer::words(text).collect::<Vec<_>>(), ["नमस्ते", "ℤ"]);
+}
+
+#[test]
+fn tokens_borrow_from_the_input_without_allocating() {
+    let text = "fn first_word(s: &str) -> &str { let bytes = s.as_bytes(); \"done\" }";
+    // 第一次调用会初始化 Unicode 类别表，先热身
+    Tokenizer::new("é").count();
+
+    let (count, allocated) = allocations(|| Tokenizer::new(text).filter(Result::is_ok).count());
+    assert_eq!((count, allocated), (24, 0));
+
+    let (words, allocated) = allocations(|| {
+        let mut longest = "";
+        for word in tokenizer::words(text) {
+            if word.len() > longest.len() {
+                longest = word;
+            }
+        }
+        longest
+    });
+    assert_eq!((words, allocated), ("first_word", 0));
+    // 记号就是原字符串的一部分，指针落在原字符串之内
+    let range = text.as_bytes().as_ptr_range();
+    assert!(range.contains(&words.as_ptr()));
+
+    // 没有转义的字符串直接借用，有转义时才分配
+    let plain = Tokenizer::new("\"plain\"").next().unwrap().unwrap();
+    assert!(matches!(plain.unescape(), Cow::Borrowed("plain")));
+    let escaped = Tokenizer::new(r#""a\tb\"c\\ \u{1F980}""#)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(escaped.unquoted(), r#"a\tb\"c\\ \u{1F980}"#);
+    assert!(matches!(escaped.unescape(), Cow::Owned(s) if s == "a\tb\"c\\ 🦀"));
+}
+
+#[test]
+fn generalizes_first_word_from_chapter_14() {
+    // 第 14 章按空格切：第一个单词带着逗号
+    fn chapter_first_word(s: &str) -> &str {
+        let bytes = s.as_bytes();
+        for (i, &item) in bytes.iter().enumerate() {
+            if item == b' ' {
+                return &s[0..i];
+            }
+        }
+        s
+    }
+    assert_eq!(chapter_first_word("hello, world"), "hello,");
+    assert_eq!(tokenizer::first_word("hello, world"), "hello");
+    assert_eq!(tokenizer::first_word("  ...  "), "");
+    // 第 05 章的 &s[0..5] 和 &s[6..11]，不用写死字节范围
+    let s = String::from("hello world");
+    let spans: Vec<Span> = Tokenizer::new(&s).map(|t| t.unwrap().span).collect();
+    assert_eq!(spans, [Span::new(0, 5), Span::new(6, 11)]);
+    // 全角空格隔开的单词：按 b' ' 找不到边界
+    assert_eq!(chapter_first_word("你好\u{3000}世界"), "你好\u{3000}世界");
+    assert_eq!(tokenizer::first_word("你好\u{3000}世界"), "你好");
+}
+
+#[test]
+fn reports_string_errors_and_keeps_going() {
+    let text = "a \"b\\q\" c \"\\u{110000}\" \"open";
+    let results: Vec<Result<Token, TokenError>> = Tokenizer::new(text).collect();
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[1], Err(TokenError::UnknownEscape(Span::new(4, 6))));
+    assert_eq!(results[2].as_ref().unwrap().text, "c");
+    assert_eq!(
+        results[3],
+        Err(TokenError::InvalidUnicodeEscape(Span::new(11, 21)))
+    );
+    assert_eq!(
+        results[4],
+        Err(TokenError::UnterminatedString(Span::new(23, 28)))
+    );
+    assert_eq!(
+        tokenizer::tokenize(text).unwrap_err().to_string(),
+        "未知的转义序列"
+    );
+
+    // 缺少 } 的 \u{ 不会吞掉右引号和后面的记号
+    let text = "a \"\\u{41\" b \"}\" c";
+    let results: Vec<Result<Token, TokenError>> = Tokenizer::new(text).collect();
+    assert_eq!(
+        results[1],
+        Err(TokenError::InvalidUnicodeEscape(Span::new(3, 8)))
+    );
+    let rest: Vec<&str> = results[2..]
+        .iter()
+        .map(|r| r.as_ref().unwrap().text)
+        .collect();
+    assert_eq!(rest, ["b", "\"}\"", "c"]);
+    for (text, span) in [
+        ("\"\\u41\"", 1..3),
+        ("\"\\u{}\"", 1..5),
+        ("\"\\u{1234567}\"", 1..10),
+        ("\"\\u{4g}\"", 1..5),
+    ] {
+        let error = Tokenizer::new(text).next().unwrap().unwrap_err();
+        assert_eq!(
+            error,
+            TokenError::InvalidUnicodeEscape(Span::new(span.start, span.end)),
+            "{text}"
+        );
+    }
+    assert_eq!(
+        kinds("\"\\u{10FFFF}\\u{0}\""),
+        [(Kind::Str, "\"\\u{10FFFF}\\u{0}\"")]
+    );
+}
+
+#[test]
+fn maps_offsets_to_lines_and_columns() {
+    let text = "let a = 1;\r\nlet 名字 = \"没有结束\n\nend";
+    let map = LineMap::new(text);
+    assert_eq!(map.line_count(), 4);
+    assert_eq!(map.locate(0), Location { line: 1, column: 1 });
+    assert_eq!(map.line(1), "let a = 1;");
+    // 列号按字符计，不是按字节
+    let name = text.find("名字").unwrap();
+    assert_eq!(map.locate(name).to_string(), "2:5");
+    assert_eq!(map.locate(text.len()), Location { line: 4, column: 4 });
+    assert_eq!(map.line(3), "");
+
+    let error = tokenizer::tokenize(text).unwrap_err();
+    assert_eq!(
+        error,
+        TokenError::UnterminatedString(Span::new(25, text.len()))
+    );
+    // 标记按终端宽度对齐：中文字符占两列
+    assert_eq!(
+        map.render(error.span(), &error.to_string()),
+        "第 2 行第 10 列: 未结束的字符串\n  let 名字 = \"没有结束\n             ^^^^^^^^^\n"
+    );
+}
@@ -0,0 +1,179 @@
+// ============================================
+// Unicode 检查测试
+// ============================================
+
+use std::panic;
+
+use rust_tutorial::unicode::{self, Category, SliceError};
+
+fn clusters(s: &str) -> Vec<&str> {
+    unicode::graphemes(s)
+        .into_iter()
+        .map(|(_, cluster)| cluster)
+        .collect()
+}
+
+#[test]
+fn chars_from_chapter_02() {
+    assert_eq!(std::mem::size_of::<char>(), 4);
+
+    let chars = unicode::inspect("zℤ😻");
+    let summary: Vec<(usize, String, usize, usize, Category)> = chars
+        .iter()
+        .map(|info| {
+            (
+                info.offset,
+                info.scalar(),
+                info.utf8.len(),
+                info.utf16.len(),
+                info.category,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (0, String::from("U+007A"), 1, 1, Category::Ll),
+            (1, String::from("U+2124"), 3, 1, Category::Lu),
+            (4, String::from("U+1F63B"), 4, 2, Category::So),
+        ]
+    );
+    assert_eq!(chars[2].utf8, [0xF0, 0x9F, 0x98, 0xBB]);
+    assert_eq!(chars[2].utf16, [0xD83D, 0xDE3B]);
+    assert_eq!(chars[2].bytes(), 4..8);
+    assert_eq!(unicode::boundaries("zℤ😻"), [0, 1, 4, 8]);
+}
+
+#[test]
+fn strings_from_chapter_09() {
+    let snapshot = include_str!("snapshots/09_collections.txt");
+
+    // &hello[0..4] 取出前两个西里尔字母
+    assert_eq!(unicode::slice("Здравствуйте", 0..4), Ok("Зд"));
+    assert!(snapshot.contains("切片: Зд\n"));
+
+    // chars() 和 bytes() 的输出与逐字符检查的结果一致
+    let chars = unicode::inspect("नमस्ते");
+    let by_char: Vec<String> = chars.iter().map(|info| info.char.to_string()).collect();
+    let by_byte: Vec<String> = chars
+        .iter()
+        .flat_map(|info| info.utf8.iter().map(u8::to_string))
+        .collect();
+    let expected = format!("{}\n{}\n", by_char.join("\n"), by_byte.join("\n"));
+    assert!(snapshot.contains(&expected));
+
+    // 6 个 char，4 个字形簇：virama 和元音符号都是组合标记，跟在前一个辅音上
+    // （Unicode 14.0 没有 GB9c，不会把 स् 和 ते 连起来）
+    assert_eq!(chars.len(), 6);
+    assert_eq!(clusters("नमस्ते"), ["न", "म", "स्", "ते"]);
+    let cluster_of: Vec<usize> = chars.iter().map(|info| info.cluster).collect();
+    assert_eq!(cluster_of, [0, 1, 2, 2, 3, 3]);
+    assert_eq!(chars[3].category, Category::Mn);
+    assert_eq!(chars[3].display(), "◌\u{94D}");
+}
+
+#[test]
+fn grapheme_clusters() {
+    // 组合标记、CRLF、控制字符
+    assert_eq!(clusters("e\u{301}x"), ["e\u{301}", "x"]);
+    assert_eq!(clusters("a\r\nb"), ["a", "\r\n", "b"]);
+    assert_eq!(clusters("\n\u{301}"), ["\n", "\u{301}"]);
+    // 间距组合标记和 Prepend
+    assert_eq!(clusters("कि"), ["कि"]);
+    assert_eq!(clusters("\u{600}1"), ["\u{600}1"]);
+    // 谚文字母组合成音节
+    assert_eq!(
+        clusters("\u{1100}\u{1161}\u{11A8}한"),
+        ["\u{1100}\u{1161}\u{11A8}", "한"]
+    );
+    // ZWJ 连接的表情、肤色修饰符
+    assert_eq!(clusters("👨‍👩‍👧!"), ["👨‍👩‍👧", "!"]);
+    assert_eq!(clusters("👍🏽"), ["👍🏽"]);
+    assert_eq!(clusters("a\u{200D}b"), ["a\u{200D}", "b"]);
+    // 区域指示符两两成对
+    assert_eq!(clusters("🇨🇳🇺🇸🇫"), ["🇨🇳", "🇺🇸", "🇫"]);
+    // virama 跟在前一个辅音上，但不把后一个辅音连进来
+    assert_eq!(clusters("सत"), ["स", "त"]);
+    assert_eq!(clusters("ক্ষ"), ["ক্", "ষ"]);
+    assert!(clusters("").is_empty());
+}
+
+#[test]
+fn categories_agree_with_std() {
+    assert_eq!(unicode::category(' '), Category::Zs);
+    assert_eq!(unicode::category('\u{378}'), Category::Cn);
+    assert_eq!(unicode::category('\u{E000}'), Category::Co);
+    assert_eq!(unicode::category('中'), Category::Lo);
+    assert_eq!(unicode::category('('), Category::Ps);
+
+    // 标准库的 is_numeric 和 is_control 就是按通用类别定义的。
+    // 类别表的 Unicode 版本可能比标准库旧，新分配的字符在表中是 Cn，跳过它们。
+    for c in (0..=0x10FFFF).filter_map(char::from_u32) {
+        let category = unicode::category(c);
+        if category == Category::Cn {
+            continue;
+        }
+        let numeric = matches!(category, Category::Nd | Category::Nl | Category::No);
+        assert_eq!(c.is_numeric(), numeric, "{:?} {}", c, category);
+        assert_eq!(c.is_control(), category == Category::Cc, "{:?}", c);
+    }
+}
+
+#[test]
+fn slice_errors_match_std_panics() {
+    let long = "é".repeat(200);
+    let samples = ["Здравствуйте", "नमस्ते", "a😻b", "", long.as_str()];
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut mismatches = Vec::new();
+    for s in samples {
+        let limit = s.len().min(20) + 2;
+        for start in 0..limit {
+            for end in 0..limit {
+                let std = panic::catch_unwind(|| s[start..end].len())
+                    .map_err(|payload| payload.downcast_ref::<String>().cloned());
+                let ours = unicode::slice(s, start..end);
+                let agrees = match (&ours, &std) {
+                    (Ok(slice), Ok(len)) => slice.len() == *len,
+                    (Err(error), Err(Some(message))) => error.panic_message(s) == *message,
+                    _ => false,
+                };
+                if !agrees {
+                    mismatches.push(format!("{s:?}[{start}..{end}]: {ours:?} vs {std:?}"));
+                }
+            }
+        }
+    }
+    panic::set_hook(hook);
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+
+    assert_eq!(
+        unicode::slice("Здравствуйте", 0..3),
+        Err(SliceError::NotCharBoundary {
+            start: false,
+            index: 3,
+            char: 'д',
+            bytes: 2..4
+        })
+    );
+    assert_eq!(
+        unicode::slice("Здравствуйте", 0..3)
+            .unwrap_err()
+            .to_string(),
+        "终点 3 落在字符 'д' 的中间（它占字节 2..4），应改为 2 或 4"
+    );
+    let (start, end) = (2, 1);
+    assert_eq!(
+        unicode::slice("abc", start..end).unwrap_err().to_string(),
+        "起点 2 大于终点 1"
+    );
+}
+
+#[test]
+fn display_width() {
+    assert_eq!(unicode::width("abc"), 3);
+    assert_eq!(unicode::width("中文"), 4);
+    assert_eq!(unicode::width("नमस्ते"), 4);
+    assert_eq!(unicode::width("😻"), 2);
+}